@@ -0,0 +1,78 @@
+//! Analyzing a few thousand files with one front end, which compiles its query once, against
+//! building a front end for every file, which recompiles the query each time.
+//!
+//! Run with `cargo bench --bench tree_sitter_front_ends`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use js_ast_analyzer::{DartParser, KotlinParser, PythonParser};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Copies of the samples written for each language
+const FILES_PER_LANGUAGE: usize = 1000;
+
+/// Fill a scratch directory with copies of the test samples of one language
+fn corpus(language: &str, extension: &str) -> (PathBuf, Vec<PathBuf>) {
+    let dir = std::env::temp_dir().join(format!("js-ast-analyzer-bench-{}-{}", language, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let samples: Vec<String> = fs::read_dir(Path::new("test-samples").join(language))
+        .unwrap()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == extension))
+        .map(|path| fs::read_to_string(path).unwrap())
+        .collect();
+
+    let files = (0..FILES_PER_LANGUAGE)
+        .map(|index| {
+            let path = dir.join(format!("file_{}.{}", index, extension));
+            fs::write(&path, &samples[index % samples.len()]).unwrap();
+            path
+        })
+        .collect();
+    (dir, files)
+}
+
+fn bench_language<P>(c: &mut Criterion, language: &str, extension: &str, build: fn() -> P, analyze: fn(&P, &Path))
+where
+    P: Sized,
+{
+    let (dir, files) = corpus(language, extension);
+    let mut group = c.benchmark_group(format!("{}/{}_files", language, files.len()));
+    group.sample_size(10);
+
+    group.bench_function("one_front_end", |b| {
+        b.iter(|| {
+            let parser = build();
+            for file in &files {
+                analyze(&parser, file);
+            }
+        })
+    });
+    group.bench_function("front_end_per_file", |b| {
+        b.iter(|| {
+            for file in &files {
+                analyze(&build(), file);
+            }
+        })
+    });
+
+    group.finish();
+    fs::remove_dir_all(dir).ok();
+}
+
+fn front_ends(c: &mut Criterion) {
+    bench_language(c, "kotlin", "kt", || KotlinParser::new().unwrap(), |parser, file| {
+        parser.analyze_file(file).unwrap();
+    });
+    bench_language(c, "dart", "dart", || DartParser::new().unwrap(), |parser, file| {
+        parser.analyze_file(file).unwrap();
+    });
+    bench_language(c, "python", "py", || PythonParser::new().unwrap(), |parser, file| {
+        parser.analyze_file(file).unwrap();
+    });
+}
+
+criterion_group!(benches, front_ends);
+criterion_main!(benches);
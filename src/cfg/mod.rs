@@ -0,0 +1,553 @@
+//! Control flow graphs of function bodies and the checks built on them: unreachable code,
+//! constant conditions, switch fallthrough, inconsistent returns and loops that never exit.
+//! The graph only models statement-level control flow; every front end drives the same builder.
+
+pub mod tree;
+
+use crate::types::{Category, CodeIssue, Severity};
+use std::collections::HashMap;
+use std::path::Path;
+
+pub type BlockId = usize;
+
+const ENTRY: BlockId = 0;
+const EXIT: BlockId = 1;
+/// Where uncaught exceptions go
+const THROW_EXIT: BlockId = 2;
+
+/// Source range of a statement or condition; positions are 1-indexed
+#[derive(Debug, Clone, Copy)]
+pub struct Site {
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+/// The function a graph was built for, when its return paths should be checked
+#[derive(Debug, Clone)]
+pub struct FunctionInfo {
+    pub name: String,
+    pub site: Site,
+}
+
+struct Statement {
+    site: Site,
+    block: BlockId,
+    parent: Option<usize>,
+    previous: Option<usize>,
+}
+
+/// A loop or switch that `break` (and for loops, `continue`) can target
+struct Target {
+    label: Option<String>,
+    is_loop: bool,
+    continue_to: BlockId,
+    break_to: BlockId,
+    header: BlockId,
+    endless: bool,
+    site: Site,
+}
+
+struct Return {
+    block: BlockId,
+    value: bool,
+    site: Site,
+}
+
+/// How far a jump goes, which decides the `finally` blocks it runs on the way
+#[derive(Debug, Clone, Copy)]
+enum Scope {
+    /// Out of the function (`return`, uncaught `throw`)
+    Function,
+    /// To the handler at this index of the handler stack
+    Handler(usize),
+    /// To the loop or switch at this index of the target stack
+    Target(usize),
+}
+
+/// A jump that entered a `finally` block and continues to `to` once it has run
+struct Exit {
+    /// The block the jump was made from; the finally only continues to `to` if it is reachable
+    guard: BlockId,
+    to: BlockId,
+    scope: Scope,
+}
+
+/// A `try` with a `finally` block, from the start of the `try` to the start of the `finally`
+struct Finally {
+    entry: BlockId,
+    exits: Vec<Exit>,
+    /// Sizes of the target and handler stacks when the `try` started
+    targets: usize,
+    handlers: usize,
+}
+
+impl Finally {
+    fn crossed_by(&self, scope: Scope) -> bool {
+        match scope {
+            Scope::Function => true,
+            Scope::Handler(index) => index < self.handlers,
+            Scope::Target(index) => index < self.targets,
+        }
+    }
+}
+
+/// Builds the graph while a front end walks a function body in source order.
+/// Statements after a jump land in a fresh block without predecessors, which is how dead code shows up.
+pub struct CfgBuilder {
+    successors: Vec<Vec<BlockId>>,
+    current: BlockId,
+    statements: Vec<Statement>,
+    parents: Vec<usize>,
+    last_child: HashMap<Option<usize>, usize>,
+    targets: Vec<Target>,
+    handlers: Vec<BlockId>,
+    /// `try` statements whose `finally` hasn't been reached yet, innermost last
+    finallies: Vec<Finally>,
+    /// `finally` blocks being built with the block where their `try` completes normally, innermost last
+    closing: Vec<(Finally, BlockId)>,
+    /// Edges `(from, to, guard)` that only exist when `guard` is reachable
+    guarded: Vec<(BlockId, BlockId, BlockId)>,
+    returns: Vec<Return>,
+    fallthroughs: Vec<(BlockId, Site)>,
+    endless_loops: Vec<(BlockId, Site)>,
+    constant_conditions: Vec<(BlockId, Site, bool, bool)>,
+}
+
+impl Default for CfgBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CfgBuilder {
+    pub fn new() -> Self {
+        Self {
+            successors: vec![Vec::new(); 3],
+            current: ENTRY,
+            statements: Vec::new(),
+            parents: Vec::new(),
+            last_child: HashMap::new(),
+            targets: Vec::new(),
+            handlers: Vec::new(),
+            finallies: Vec::new(),
+            closing: Vec::new(),
+            guarded: Vec::new(),
+            returns: Vec::new(),
+            fallthroughs: Vec::new(),
+            endless_loops: Vec::new(),
+            constant_conditions: Vec::new(),
+        }
+    }
+
+    pub fn current(&self) -> BlockId {
+        self.current
+    }
+
+    /// Continue building in `block`
+    pub fn start(&mut self, block: BlockId) {
+        self.current = block;
+    }
+
+    pub fn new_block(&mut self) -> BlockId {
+        self.successors.push(Vec::new());
+        self.successors.len() - 1
+    }
+
+    pub fn edge(&mut self, from: BlockId, to: BlockId) {
+        self.successors[from].push(to);
+    }
+
+    /// A new block entered from the current one
+    pub fn fork(&mut self) -> BlockId {
+        let block = self.new_block();
+        self.edge(self.current, block);
+        block
+    }
+
+    /// A new block entered from every block in `ends`; becomes the current block
+    pub fn join(&mut self, ends: &[BlockId]) {
+        let block = self.new_block();
+        for end in ends {
+            self.edge(*end, block);
+        }
+        self.current = block;
+    }
+
+    /// Record a statement in the current block; returns its id for `enter`
+    pub fn statement(&mut self, site: Site) -> usize {
+        let id = self.statements.len();
+        let parent = self.parents.last().copied();
+        let previous = self.last_child.insert(parent, id);
+        self.statements.push(Statement {
+            site,
+            block: self.current,
+            parent,
+            previous,
+        });
+        id
+    }
+
+    /// Statements recorded until `leave` are nested in statement `id`
+    pub fn enter(&mut self, id: usize) {
+        self.parents.push(id);
+    }
+
+    pub fn leave(&mut self) {
+        self.parents.pop();
+    }
+
+    /// The rest of the current path is dead
+    fn dead_end(&mut self) {
+        self.current = self.new_block();
+    }
+
+    /// Jump from the current block to `to`, through the innermost `finally` the jump leaves
+    fn jump(&mut self, to: BlockId, scope: Scope) {
+        let from = self.current;
+        match self.finallies.last_mut() {
+            Some(finally) if finally.crossed_by(scope) => {
+                let entry = finally.entry;
+                finally.exits.push(Exit { guard: from, to, scope });
+                self.edge(from, entry);
+            }
+            _ => self.edge(from, to),
+        }
+        self.dead_end();
+    }
+
+    pub fn jump_return(&mut self, value: bool, site: Site) {
+        self.returns.push(Return {
+            block: self.current,
+            value,
+            site,
+        });
+        self.jump(EXIT, Scope::Function);
+    }
+
+    pub fn jump_throw(&mut self) {
+        match self.handlers.last() {
+            Some(&handler) => self.jump(handler, Scope::Handler(self.handlers.len() - 1)),
+            None => self.jump(THROW_EXIT, Scope::Function),
+        }
+    }
+
+    /// An unlabeled `break` leaves the innermost loop or switch. A label that can't be resolved
+    /// leaves every enclosing loop, so such loops are never taken for endless ones.
+    pub fn jump_break(&mut self, label: Option<&str>) {
+        let index = match label {
+            Some(label) => self
+                .targets
+                .iter()
+                .rposition(|target| target.label.as_deref() == Some(label))
+                .or_else(|| self.targets.iter().position(|target| target.is_loop)),
+            None => self.targets.len().checked_sub(1),
+        };
+        match index {
+            Some(index) => self.jump(self.targets[index].break_to, Scope::Target(index)),
+            None => self.dead_end(),
+        }
+    }
+
+    pub fn jump_continue(&mut self, label: Option<&str>) {
+        let index = self.targets.iter().rposition(|target| {
+            target.is_loop && (label.is_none() || target.label.as_deref() == label)
+        });
+        match index {
+            Some(index) => self.jump(self.targets[index].continue_to, Scope::Target(index)),
+            None => self.dead_end(),
+        }
+    }
+
+    /// Start a loop whose condition has already been evaluated for constants; the current block becomes the body.
+    /// `test_first` is false for `do`/`while` loops. Call `end_loop` after the body.
+    pub fn begin_loop(&mut self, label: Option<String>, site: Site, endless: bool, test_first: bool) {
+        let header = self.new_block();
+        self.edge(self.current, header);
+        let after = self.new_block();
+
+        let (body, latch) = if test_first {
+            if !endless {
+                self.edge(header, after);
+            }
+            let body = self.new_block();
+            self.edge(header, body);
+            (body, header)
+        } else {
+            let latch = self.new_block();
+            self.edge(latch, header);
+            if !endless {
+                self.edge(latch, after);
+            }
+            (header, latch)
+        };
+
+        self.targets.push(Target {
+            label,
+            is_loop: true,
+            continue_to: latch,
+            break_to: after,
+            header,
+            endless,
+            site,
+        });
+        self.current = body;
+    }
+
+    pub fn end_loop(&mut self) {
+        if let Some(target) = self.targets.pop() {
+            self.edge(self.current, target.continue_to);
+            if target.endless {
+                self.endless_loops.push((target.header, target.site));
+            }
+            self.current = target.break_to;
+        }
+    }
+
+    /// Start a `switch` that `break` can leave; returns the block after it
+    pub fn begin_switch(&mut self, label: Option<String>, site: Site) -> BlockId {
+        let after = self.new_block();
+        self.targets.push(Target {
+            label,
+            is_loop: false,
+            continue_to: after,
+            break_to: after,
+            header: self.current,
+            endless: false,
+            site,
+        });
+        after
+    }
+
+    /// Close the switch, joining the end of the last case with the block after it
+    pub fn end_switch(&mut self) {
+        if let Some(target) = self.targets.pop() {
+            self.edge(self.current, target.break_to);
+            self.current = target.break_to;
+        }
+    }
+
+    /// Exceptions thrown until `end_try` are caught by `handler`
+    pub fn begin_try(&mut self, handler: BlockId) {
+        self.handlers.push(handler);
+    }
+
+    pub fn end_try(&mut self) {
+        self.handlers.pop();
+    }
+
+    /// Start a `try` that has a `finally` block: returns, throws, breaks and continues until
+    /// `begin_finally` run the `finally` first. Call before `begin_try` for its handlers.
+    pub fn try_finally(&mut self) {
+        let entry = self.new_block();
+        self.finallies.push(Finally {
+            entry,
+            exits: Vec::new(),
+            targets: self.targets.len(),
+            handlers: self.handlers.len(),
+        });
+    }
+
+    /// Enter the `finally` block from every block in `ends`, where the `try` and its handlers complete normally
+    pub fn begin_finally(&mut self, ends: &[BlockId]) {
+        self.join(ends);
+        let Some(finally) = self.finallies.pop() else {
+            return;
+        };
+        let normal = self.current;
+        self.edge(normal, finally.entry);
+        self.current = finally.entry;
+        self.closing.push((finally, normal));
+    }
+
+    /// Leave the `finally` block: each path continues where it was going when it entered.
+    /// The block after the `try` becomes the current one.
+    pub fn end_finally(&mut self) {
+        let Some((finally, normal)) = self.closing.pop() else {
+            return;
+        };
+        let end = self.current;
+        for exit in finally.exits {
+            match self.finallies.last_mut() {
+                Some(outer) if outer.crossed_by(exit.scope) => {
+                    self.guarded.push((end, outer.entry, exit.guard));
+                    outer.exits.push(exit);
+                }
+                _ => self.guarded.push((end, exit.to, exit.guard)),
+            }
+        }
+        let after = self.new_block();
+        self.guarded.push((end, after, normal));
+        self.current = after;
+    }
+
+    /// The end of a non-empty case at `from` runs straight into the next case
+    pub fn fallthrough(&mut self, from: BlockId, site: Site) {
+        self.fallthroughs.push((from, site));
+    }
+
+    /// A branch or loop condition that is a literal
+    pub fn constant_condition(&mut self, site: Site, value: bool, is_loop: bool) {
+        self.constant_conditions.push((self.current, site, value, is_loop));
+    }
+
+    /// Close the graph and report what it shows. Return paths are only checked when `function` is given.
+    pub fn finish(mut self, file_path: &Path, function: Option<&FunctionInfo>) -> Vec<CodeIssue> {
+        let falls_off_end = self.current;
+        self.edge(falls_off_end, EXIT);
+
+        let reachable = self.reachable();
+        let terminates = self.terminating();
+        let mut issues = Vec::new();
+
+        for statement in &self.statements {
+            let dead = !reachable[statement.block];
+            let parent_live = statement.parent.is_none_or(|parent| reachable[self.statements[parent].block]);
+            let previous_live = statement.previous.is_none_or(|previous| reachable[self.statements[previous].block]);
+            // Only the first dead statement of a run is reported, not the ones after or inside it
+            if dead && parent_live && previous_live {
+                issues.push(issue(
+                    file_path,
+                    statement.site,
+                    "Unreachable code: every path before this statement returns, throws, breaks or continues.".to_string(),
+                    "unreachable-code",
+                    Severity::Warning,
+                    Category::CodeQuality,
+                ));
+            }
+        }
+
+        for (block, site, value, is_loop) in &self.constant_conditions {
+            if !reachable[*block] {
+                continue;
+            }
+            let message = if *is_loop {
+                "Loop condition is always false, so the body never runs.".to_string()
+            } else {
+                format!(
+                    "Condition is always {}, so the {} branch never runs.",
+                    value,
+                    if *value { "else" } else { "then" }
+                )
+            };
+            issues.push(issue(file_path, *site, message, "constant-condition", Severity::Warning, Category::CodeQuality));
+        }
+
+        for (block, site) in &self.fallthroughs {
+            if reachable[*block] {
+                issues.push(issue(
+                    file_path,
+                    *site,
+                    "This case falls through into the next one. End it with break or return, or add a `// falls through` comment if that is intended.".to_string(),
+                    "switch-fallthrough",
+                    Severity::Warning,
+                    Category::BestPractice,
+                ));
+            }
+        }
+
+        for (header, site) in &self.endless_loops {
+            if reachable[*header] && !terminates[*header] {
+                issues.push(issue(
+                    file_path,
+                    *site,
+                    "Infinite loop: the condition is always true and nothing inside breaks, returns or throws.".to_string(),
+                    "infinite-loop",
+                    Severity::Warning,
+                    Category::CodeQuality,
+                ));
+            }
+        }
+
+        if let Some(function) = function {
+            let with_value = self.returns.iter().find(|ret| ret.value && reachable[ret.block]);
+            let without_value = self.returns.iter().find(|ret| !ret.value && reachable[ret.block]);
+            if let Some(with_value) = with_value {
+                let missing = match without_value {
+                    Some(ret) => Some(format!("line {} returns nothing", ret.site.line)),
+                    None if reachable[falls_off_end] => {
+                        Some(format!("the end of the function (line {}) is reached without a return", function.site.end_line))
+                    }
+                    None => None,
+                };
+                if let Some(missing) = missing {
+                    issues.push(issue(
+                        file_path,
+                        function.site,
+                        format!(
+                            "Function '{}' returns a value on some paths (line {}) but not on others: {}.",
+                            function.name, with_value.site.line, missing
+                        ),
+                        "inconsistent-return",
+                        Severity::Warning,
+                        Category::CodeQuality,
+                    ));
+                }
+            }
+        }
+
+        issues
+    }
+
+    fn reachable(&self) -> Vec<bool> {
+        let mut seen = vec![false; self.successors.len()];
+        let mut stack = vec![ENTRY];
+        loop {
+            while let Some(block) = stack.pop() {
+                if std::mem::replace(&mut seen[block], true) {
+                    continue;
+                }
+                stack.extend(self.successors[block].iter().copied());
+            }
+            // A `finally` only continues to where the paths that entered it were going
+            stack.extend(
+                self.guarded
+                    .iter()
+                    .filter(|&&(from, to, guard)| seen[from] && seen[guard] && !seen[to])
+                    .map(|&(_, to, _)| to),
+            );
+            if stack.is_empty() {
+                return seen;
+            }
+        }
+    }
+
+    /// Blocks from which the function can be left, normally or by an exception
+    fn terminating(&self) -> Vec<bool> {
+        let mut predecessors = vec![Vec::new(); self.successors.len()];
+        for (block, successors) in self.successors.iter().enumerate() {
+            for successor in successors {
+                predecessors[*successor].push(block);
+            }
+        }
+        for &(from, to, _) in &self.guarded {
+            predecessors[to].push(from);
+        }
+
+        let mut seen = vec![false; self.successors.len()];
+        let mut stack = vec![EXIT, THROW_EXIT];
+        while let Some(block) = stack.pop() {
+            if std::mem::replace(&mut seen[block], true) {
+                continue;
+            }
+            stack.extend(predecessors[block].iter().copied());
+        }
+        seen
+    }
+}
+
+fn issue(file_path: &Path, site: Site, message: String, rule: &str, severity: Severity, category: Category) -> CodeIssue {
+    CodeIssue {
+        file_path: file_path.display().to_string(),
+        line: site.line,
+        column: site.column,
+        end_line: Some(site.end_line),
+        end_column: Some(site.end_column),
+        message,
+        severity,
+        category,
+        rule: rule.to_string(),
+        code_snippet: None,
+    }
+}
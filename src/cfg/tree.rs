@@ -0,0 +1,329 @@
+//! Control flow graphs for the tree-sitter front ends. Each grammar maps its statement nodes to a
+//! `Flow`, and the driver turns those into graph blocks.
+
+use super::{CfgBuilder, FunctionInfo, Site};
+use crate::cognitive::CognitiveGrammar;
+use crate::types::CodeIssue;
+use std::path::Path;
+use tree_sitter::Node;
+
+/// What a statement node does to control flow
+pub enum Flow<'tree> {
+    /// Not a statement: comments, labels, punctuation
+    Ignore,
+    /// A block or statement list; its children are the statements
+    Sequence(Vec<Node<'tree>>),
+    /// A statement without nested statements
+    Statement,
+    /// A statement whose nested statements run once, in order (`with`, `synchronized`)
+    Compound(Vec<Node<'tree>>),
+    /// `alternatives` are `else`/`elif` parts in order; an `elif` classifies as an `If` without alternatives
+    If {
+        condition: Option<Node<'tree>>,
+        consequence: Option<Node<'tree>>,
+        alternatives: Vec<Node<'tree>>,
+    },
+    /// `endless` is for loops without a condition at all, like `for (;;)`; `test_first` is false for `do`/`while`
+    Loop {
+        condition: Option<Node<'tree>>,
+        body: Option<Node<'tree>>,
+        label: Option<String>,
+        test_first: bool,
+        endless: bool,
+    },
+    /// `switch`/`when`/`match`: each arm is the statements of one case. `breakable` if `break` leaves it.
+    Branches {
+        arms: Vec<Vec<Node<'tree>>>,
+        exhaustive: bool,
+        breakable: bool,
+    },
+    /// `otherwise` runs after the body when nothing was thrown (Python's `try`/`else`)
+    Try {
+        body: Option<Node<'tree>>,
+        handlers: Vec<Node<'tree>>,
+        otherwise: Option<Node<'tree>>,
+        finalizer: Option<Node<'tree>>,
+    },
+    Return { value: bool },
+    Throw,
+    Break(Option<String>),
+    Continue(Option<String>),
+}
+
+/// How a tree-sitter grammar's statements map to control flow
+pub struct FlowGrammar {
+    /// Function nodes and their names come from the cognitive complexity table
+    pub cognitive: &'static CognitiveGrammar,
+    /// The node holding a function's statements; `None` for expression bodies
+    pub body: fn(Node) -> Option<Node>,
+    /// Where a function's declaration starts, as in `MetricsGrammar::header`
+    pub header: fn(Node) -> Node,
+    pub classify: for<'tree> fn(Node<'tree>, &str) -> Flow<'tree>,
+    /// Value of a condition that is a literal
+    pub constant: fn(Node, &str) -> Option<bool>,
+    /// Generators may loop forever on purpose, yielding each time around
+    pub generator: fn(Node, &str) -> bool,
+    /// Off where the compiler already rejects functions that don't return on every path
+    pub check_returns: bool,
+}
+
+/// Build a graph for the top-level code and for every function, and report what they show
+pub fn analyze_tree(grammar: &FlowGrammar, root: Node, code: &str, file_path: &Path) -> Vec<CodeIssue> {
+    let mut top_level = TreeFlow::new(grammar, code, false);
+    top_level.sequence(root);
+    let mut issues = top_level.cfg.finish(file_path, None);
+
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if grammar.cognitive.is_function(node) {
+            if let Some(body) = (grammar.body)(node) {
+                let mut flow = TreeFlow::new(grammar, code, (grammar.generator)(node, code));
+                flow.sequence(body);
+
+                let function = grammar.check_returns.then(|| {
+                    let header = site((grammar.header)(node));
+                    let end = site(node);
+                    FunctionInfo {
+                        name: (grammar.cognitive.function_name)(node, code).unwrap_or_else(|| "<anonymous>".to_string()),
+                        site: Site {
+                            end_line: end.end_line,
+                            end_column: end.end_column,
+                            ..header
+                        },
+                    }
+                });
+                issues.extend(flow.cfg.finish(file_path, function.as_ref()));
+            }
+        }
+
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.named_children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+
+    issues.sort_by_key(|issue| (issue.line, issue.column));
+    issues
+}
+
+fn site(node: Node) -> Site {
+    let start = node.start_position();
+    let end = node.end_position();
+    Site {
+        line: start.row + 1,
+        column: start.column + 1,
+        end_line: end.row + 1,
+        end_column: end.column + 1,
+    }
+}
+
+struct TreeFlow<'g> {
+    grammar: &'g FlowGrammar,
+    code: &'g str,
+    generator: bool,
+    cfg: CfgBuilder,
+}
+
+impl<'g> TreeFlow<'g> {
+    fn new(grammar: &'g FlowGrammar, code: &'g str, generator: bool) -> Self {
+        Self {
+            grammar,
+            code,
+            generator,
+            cfg: CfgBuilder::new(),
+        }
+    }
+
+    fn sequence(&mut self, node: Node) {
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.named_children(&mut cursor).collect();
+        for child in children {
+            self.statement(child);
+        }
+    }
+
+    fn body(&mut self, node: Option<Node>) {
+        if let Some(node) = node {
+            self.statement(node);
+        }
+    }
+
+    fn statement(&mut self, node: Node) {
+        match (self.grammar.classify)(node, self.code) {
+            Flow::Ignore => {}
+            Flow::Sequence(children) => {
+                for child in children {
+                    self.statement(child);
+                }
+            }
+            flow => {
+                let id = self.cfg.statement(site(node));
+                self.cfg.enter(id);
+                self.flow(node, flow);
+                self.cfg.leave();
+            }
+        }
+    }
+
+    fn flow(&mut self, node: Node, flow: Flow) {
+        match flow {
+            Flow::Ignore | Flow::Sequence(_) | Flow::Statement => {}
+            Flow::Compound(children) => {
+                for child in children {
+                    self.statement(child);
+                }
+            }
+            Flow::If {
+                condition,
+                consequence,
+                alternatives,
+            } => self.branch(condition, consequence, &alternatives),
+            Flow::Loop {
+                condition,
+                body,
+                label,
+                test_first,
+                endless,
+            } => {
+                let value = condition.and_then(|condition| (self.grammar.constant)(condition, self.code));
+                // `do { ... } while (false)` is an idiom for a block that can be left with `break`
+                if let (Some(condition), Some(false), true) = (condition, value, test_first) {
+                    self.cfg.constant_condition(site(condition), false, true);
+                }
+                let endless = (endless || value == Some(true)) && !self.generator;
+                self.cfg.begin_loop(label, site(node), endless, test_first);
+                self.body(body);
+                self.cfg.end_loop();
+            }
+            Flow::Branches {
+                arms,
+                exhaustive,
+                breakable,
+            } => {
+                if breakable {
+                    self.cfg.begin_switch(None, site(node));
+                }
+                let start = self.cfg.current();
+                let mut ends = Vec::new();
+                for arm in arms {
+                    self.cfg.start(start);
+                    let block = self.cfg.fork();
+                    self.cfg.start(block);
+                    for statement in arm {
+                        self.statement(statement);
+                    }
+                    ends.push(self.cfg.current());
+                }
+                if !exhaustive {
+                    ends.push(start);
+                }
+                self.cfg.join(&ends);
+                if breakable {
+                    self.cfg.end_switch();
+                }
+            }
+            Flow::Try {
+                body,
+                handlers,
+                otherwise,
+                finalizer,
+            } => {
+                // Returns, throws and jumps out of the body or the handlers run the `finally` first
+                if finalizer.is_some() {
+                    self.cfg.try_finally();
+                }
+                // Anything in the body may throw, so the handlers are live whenever the try is
+                let handler = self.cfg.fork();
+                if !handlers.is_empty() {
+                    self.cfg.begin_try(handler);
+                }
+                self.body(body);
+                if !handlers.is_empty() {
+                    self.cfg.end_try();
+                }
+                self.body(otherwise);
+
+                let mut ends = vec![self.cfg.current()];
+                for node in handlers {
+                    self.cfg.start(handler);
+                    let block = self.cfg.fork();
+                    self.cfg.start(block);
+                    self.statement(node);
+                    ends.push(self.cfg.current());
+                }
+                if finalizer.is_some() {
+                    self.cfg.begin_finally(&ends);
+                    self.body(finalizer);
+                    self.cfg.end_finally();
+                } else {
+                    self.cfg.join(&ends);
+                }
+            }
+            Flow::Return { value } => self.cfg.jump_return(value, site(node)),
+            Flow::Throw => self.cfg.jump_throw(),
+            Flow::Break(label) => self.cfg.jump_break(label.as_deref()),
+            Flow::Continue(label) => self.cfg.jump_continue(label.as_deref()),
+        }
+    }
+
+    fn branch(&mut self, condition: Option<Node>, consequence: Option<Node>, alternatives: &[Node]) {
+        if let Some(condition) = condition {
+            if let Some(value) = (self.grammar.constant)(condition, self.code) {
+                self.cfg.constant_condition(site(condition), value, false);
+            }
+        }
+
+        let start = self.cfg.current();
+        let then_block = self.cfg.fork();
+        self.cfg.start(then_block);
+        self.body(consequence);
+        let then_end = self.cfg.current();
+
+        self.cfg.start(start);
+        let else_block = self.cfg.fork();
+        self.cfg.start(else_block);
+        if let Some((first, rest)) = alternatives.split_first() {
+            match (self.grammar.classify)(*first, self.code) {
+                Flow::If {
+                    condition,
+                    consequence,
+                    alternatives,
+                } if alternatives.is_empty() => self.branch(condition, consequence, rest),
+                _ => {
+                    self.statement(*first);
+                    for alternative in rest {
+                        self.statement(*alternative);
+                    }
+                }
+            }
+        }
+        let else_end = self.cfg.current();
+        self.cfg.join(&[then_end, else_end]);
+    }
+}
+
+/// Named children of `node`, for `Flow::Sequence` and friends
+pub fn named_children(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.named_children(&mut cursor).collect();
+    children
+}
+
+/// `node` without the parentheses around it
+pub fn unparenthesized(node: Node) -> Node {
+    let mut node = node;
+    while node.kind() == "parenthesized_expression" && node.named_child_count() == 1 {
+        match node.named_child(0) {
+            Some(inner) => node = inner,
+            None => break,
+        }
+    }
+    node
+}
+
+/// Whether `node` contains one of `kinds` outside the nested functions of `grammar`
+pub fn contains_kind(grammar: &CognitiveGrammar, node: Node, kinds: &[&str]) -> bool {
+    named_children(node).into_iter().any(|child| {
+        kinds.contains(&child.kind())
+            || (!grammar.is_function(child) && contains_kind(grammar, child, kinds))
+    })
+}
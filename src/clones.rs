@@ -0,0 +1,322 @@
+use crate::config::ClonesConfig;
+use crate::metrics::halstead::{self, Lexicon, TokenKind};
+use crate::metrics::round;
+use crate::types::{AnalysisResult, Category, CodeIssue, DuplicationSummary, Severity};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::hash::{Hash, Hasher};
+
+/// Statements starting with these are skipped; import lists repeat across files by nature
+const PREAMBLE_KEYWORDS: &[&str] = &["import", "package", "library", "part", "from"];
+
+/// Base of the rolling window hash
+const HASH_BASE: u64 = 1_000_003;
+
+/// Where one copy of a clone lives; lines are 1-indexed
+#[derive(Debug, Clone)]
+pub struct CloneLocation {
+    pub file_path: String,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+/// Code that appears at least twice, token for token once identifiers and literals are normalized
+#[derive(Debug, Clone)]
+pub struct CloneGroup {
+    /// Length of the shared token sequence
+    pub tokens: usize,
+    pub locations: Vec<CloneLocation>,
+}
+
+/// A file reduced to normalized tokens
+struct Unit {
+    file_path: String,
+    keys: Vec<u64>,
+    /// First and last line of each token
+    lines: Vec<(usize, usize)>,
+    code_lines: BTreeSet<usize>,
+}
+
+impl Unit {
+    fn new(file_path: &str, code: &str, lexicon: &Lexicon) -> Self {
+        let mut unit = Self {
+            file_path: file_path.to_string(),
+            keys: Vec::new(),
+            lines: Vec::new(),
+            code_lines: BTreeSet::new(),
+        };
+
+        let mut previous_line = None;
+        let mut preamble_line = None;
+        let mut depth = 0i32;
+
+        for token in halstead::tokenize(code, lexicon) {
+            if token.kind == TokenKind::Comment {
+                continue;
+            }
+            unit.code_lines.extend(token.line + 1..=token.end_line + 1);
+
+            let starts_line = previous_line != Some(token.line);
+            previous_line = Some(token.end_line);
+            if starts_line && token.kind == TokenKind::Keyword && PREAMBLE_KEYWORDS.contains(&token.text.as_str()) {
+                preamble_line = Some(token.line);
+                depth = 0;
+            }
+
+            // An import runs to the end of its line, or further while brackets are open
+            if let Some(line) = preamble_line {
+                if token.line == line || depth > 0 {
+                    match token.text.as_str() {
+                        "(" | "[" | "{" => depth += 1,
+                        ")" | "]" | "}" => depth -= 1,
+                        _ => {}
+                    }
+                    preamble_line = Some(token.end_line);
+                    continue;
+                }
+                preamble_line = None;
+            }
+
+            let normalized = match token.kind {
+                TokenKind::Identifier => "$id",
+                TokenKind::Literal => "$lit",
+                _ => token.text.as_str(),
+            };
+            let mut hasher = DefaultHasher::new();
+            normalized.hash(&mut hasher);
+            unit.keys.push(hasher.finish());
+            unit.lines.push((token.line + 1, token.end_line + 1));
+        }
+
+        unit
+    }
+
+    /// Rolling hash of every window of `length` tokens, by start position
+    fn window_hashes(&self, length: usize) -> Vec<u64> {
+        if self.keys.len() < length {
+            return Vec::new();
+        }
+
+        let power = (1..length).fold(1u64, |power, _| power.wrapping_mul(HASH_BASE));
+        let mut hash = self.keys[..length]
+            .iter()
+            .fold(0u64, |hash, key| hash.wrapping_mul(HASH_BASE).wrapping_add(*key));
+        let mut hashes = vec![hash];
+        for start in 1..=self.keys.len() - length {
+            hash = hash
+                .wrapping_sub(self.keys[start - 1].wrapping_mul(power))
+                .wrapping_mul(HASH_BASE)
+                .wrapping_add(self.keys[start + length - 1]);
+            hashes.push(hash);
+        }
+        hashes
+    }
+
+    fn location(&self, start: usize, tokens: usize) -> CloneLocation {
+        CloneLocation {
+            file_path: self.file_path.clone(),
+            start_line: self.lines[start].0,
+            end_line: self.lines[start + tokens - 1].1,
+        }
+    }
+}
+
+/// Finds copy-pasted code within and across the files of an analysis
+#[derive(Debug, Clone)]
+pub struct CloneDetector {
+    enabled: bool,
+    min_tokens: usize,
+}
+
+impl CloneDetector {
+    pub fn with_config(config: &ClonesConfig) -> Self {
+        Self {
+            enabled: config.enabled,
+            min_tokens: config.min_tokens.max(1),
+        }
+    }
+
+    /// Report clones between the analyzed files as issues and fill in the duplication summary
+    pub fn detect(&self, result: &mut AnalysisResult, lexicon: &Lexicon) {
+        if !self.enabled {
+            return;
+        }
+
+        let sources: Vec<(String, String)> = result
+            .files
+            .iter()
+            .filter_map(|file| {
+                fs::read_to_string(&file.file_path)
+                    .ok()
+                    .map(|code| (file.file_path.clone(), code))
+            })
+            .collect();
+        let (groups, summary) = self.find_clones(&sources, lexicon);
+
+        for group in &groups {
+            for (index, location) in group.locations.iter().enumerate() {
+                let issue = self.issue(group, index);
+                if let Some(file) = result.files.iter_mut().find(|file| file.file_path == location.file_path) {
+                    file.summary.add(issue.severity);
+                    result.summary.add(issue.severity);
+                    file.issues.push(issue);
+                }
+            }
+        }
+        result.duplication = Some(summary);
+    }
+
+    /// Clone groups among `(file path, source)` pairs, plus how much of the code they cover
+    pub fn find_clones(&self, sources: &[(String, String)], lexicon: &Lexicon) -> (Vec<CloneGroup>, DuplicationSummary) {
+        let units: Vec<Unit> = sources
+            .iter()
+            .map(|(file_path, code)| Unit::new(file_path, code, lexicon))
+            .collect();
+        let hashes: Vec<Vec<u64>> = units.iter().map(|unit| unit.window_hashes(self.min_tokens)).collect();
+
+        let mut windows: HashMap<u64, Vec<(usize, usize)>> = HashMap::new();
+        for (file, file_hashes) in hashes.iter().enumerate() {
+            for (start, hash) in file_hashes.iter().enumerate() {
+                windows.entry(*hash).or_default().push((file, start));
+            }
+        }
+
+        let mut covered: Vec<Vec<bool>> = hashes.iter().map(|file_hashes| vec![false; file_hashes.len()]).collect();
+        let mut groups = Vec::new();
+
+        for (file, file_hashes) in hashes.iter().enumerate() {
+            for (start, hash) in file_hashes.iter().enumerate() {
+                if covered[file][start] {
+                    continue;
+                }
+                let Some(bucket) = windows.get(hash).filter(|bucket| bucket.len() > 1) else {
+                    continue;
+                };
+
+                let (copies, tokens) = self.copies_of(&units, &covered, (file, start), bucket);
+                if copies.is_empty() {
+                    continue;
+                }
+
+                let mut locations = vec![units[file].location(start, tokens)];
+                for &(other, other_start) in &copies {
+                    locations.push(units[other].location(other_start, tokens));
+                }
+                // Windows inside a reported clone would only report parts of it again
+                for &(covered_file, covered_start) in copies.iter().chain([(file, start)].iter()) {
+                    covered[covered_file][covered_start..=covered_start + tokens - self.min_tokens].fill(true);
+                }
+                groups.push(CloneGroup { tokens, locations });
+            }
+        }
+
+        let summary = Self::summary(&units, &groups);
+        (groups, summary)
+    }
+
+    /// Later, uncovered copies of the window at `origin` and the number of tokens they all share with it
+    fn copies_of(
+        &self,
+        units: &[Unit],
+        covered: &[Vec<bool>],
+        origin: (usize, usize),
+        bucket: &[(usize, usize)],
+    ) -> (Vec<(usize, usize)>, usize) {
+        let (file, start) = origin;
+        let keys = &units[file].keys;
+        let window = &keys[start..start + self.min_tokens];
+        let mut copies: Vec<(usize, usize)> = Vec::new();
+        let mut tokens = usize::MAX;
+
+        for &(other, other_start) in bucket {
+            if (other, other_start) <= origin || covered[other][other_start] {
+                continue;
+            }
+            let other_keys = &units[other].keys;
+            let overlaps = |a: usize, b: usize| b < a + self.min_tokens;
+            if (other == file && overlaps(start, other_start))
+                || copies.iter().any(|&(copy, copy_start)| copy == other && overlaps(copy_start, other_start))
+                || other_keys[other_start..other_start + self.min_tokens] != *window
+            {
+                continue;
+            }
+
+            let mut length = self.min_tokens;
+            while start + length < keys.len()
+                && other_start + length < other_keys.len()
+                && keys[start + length] == other_keys[other_start + length]
+                && (other != file || start + length < other_start)
+            {
+                length += 1;
+            }
+            tokens = tokens.min(length);
+            copies.push((other, other_start));
+        }
+
+        (copies, tokens)
+    }
+
+    fn summary(units: &[Unit], groups: &[CloneGroup]) -> DuplicationSummary {
+        let mut duplicated: HashMap<&str, BTreeSet<usize>> = HashMap::new();
+        for location in groups.iter().flat_map(|group| &group.locations) {
+            duplicated
+                .entry(location.file_path.as_str())
+                .or_default()
+                .extend(location.start_line..=location.end_line);
+        }
+
+        let total_lines: usize = units.iter().map(|unit| unit.code_lines.len()).sum();
+        let duplicated_lines: usize = units
+            .iter()
+            .map(|unit| {
+                duplicated
+                    .get(unit.file_path.as_str())
+                    .map_or(0, |lines| lines.intersection(&unit.code_lines).count())
+            })
+            .sum();
+        let percentage = if total_lines == 0 {
+            0.0
+        } else {
+            round(duplicated_lines as f64 * 100.0 / total_lines as f64)
+        };
+
+        DuplicationSummary {
+            clone_groups: groups.len(),
+            duplicated_lines,
+            total_lines,
+            percentage,
+        }
+    }
+
+    /// The issue for copy `index` of a group, pointing at all other copies
+    fn issue(&self, group: &CloneGroup, index: usize) -> CodeIssue {
+        let location = &group.locations[index];
+        let others: Vec<String> = group
+            .locations
+            .iter()
+            .enumerate()
+            .filter(|(other, _)| *other != index)
+            .map(|(_, other)| format!("{}:{}-{}", other.file_path, other.start_line, other.end_line))
+            .collect();
+
+        CodeIssue {
+            file_path: location.file_path.clone(),
+            line: location.start_line,
+            column: 1,
+            end_line: Some(location.end_line),
+            end_column: None,
+            message: format!(
+                "Duplicated code: {} tokens (lines {}-{}) also appear at {}. Extract the shared logic into a function.",
+                group.tokens,
+                location.start_line,
+                location.end_line,
+                others.join(", ")
+            ),
+            severity: Severity::Warning,
+            category: Category::Maintainability,
+            rule: "duplicate-code".to_string(),
+            code_snippet: None,
+        }
+    }
+}
@@ -0,0 +1,330 @@
+use crate::types::{Category, CodeIssue, Severity};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tree_sitter::Node;
+
+/// How many constructs are listed in the issue message
+const TOP_CONTRIBUTORS: usize = 3;
+
+/// One construct that added to a function's cognitive complexity
+#[derive(Debug, Clone)]
+pub struct Increment {
+    /// Short label, e.g. "if", "&&", "recursion"
+    pub construct: String,
+    pub line: usize,
+    pub amount: usize,
+}
+
+/// Cognitive complexity of a single function, as the list of increments that make it up.
+///
+/// Structural constructs (branches, loops, catches, ternaries) cost `1 + nesting`,
+/// `else`/`else if`, sequences of like boolean operators, labeled jumps and recursion cost 1.
+#[derive(Debug, Clone, Default)]
+pub struct CognitiveScore {
+    increments: Vec<Increment>,
+}
+
+impl CognitiveScore {
+    /// A construct that breaks the linear flow and is penalized for its nesting
+    pub fn nested(&mut self, construct: impl Into<String>, line: usize, nesting: usize) {
+        self.increments.push(Increment {
+            construct: construct.into(),
+            line,
+            amount: 1 + nesting,
+        });
+    }
+
+    /// A construct that breaks the linear flow regardless of nesting
+    pub fn flat(&mut self, construct: impl Into<String>, line: usize) {
+        self.increments.push(Increment {
+            construct: construct.into(),
+            line,
+            amount: 1,
+        });
+    }
+
+    pub fn total(&self) -> usize {
+        self.increments.iter().map(|increment| increment.amount).sum()
+    }
+
+    /// Constructs grouped by label, biggest contribution first: `(label, total, lines)`
+    pub fn contributors(&self) -> Vec<(String, usize, Vec<usize>)> {
+        let mut grouped: BTreeMap<&str, (usize, Vec<usize>)> = BTreeMap::new();
+        for increment in &self.increments {
+            let entry = grouped.entry(increment.construct.as_str()).or_default();
+            entry.0 += increment.amount;
+            if !entry.1.contains(&increment.line) {
+                entry.1.push(increment.line);
+            }
+        }
+
+        let mut contributors: Vec<(String, usize, Vec<usize>)> = grouped
+            .into_iter()
+            .map(|(construct, (amount, lines))| (construct.to_string(), amount, lines))
+            .collect();
+        contributors.sort_by_key(|contributor| std::cmp::Reverse(contributor.1));
+        contributors
+    }
+
+    /// The biggest contributors as `if +3 (line 4), && +2 (lines 5, 7)`, using the given words for "line"
+    pub fn top_contributors(&self, line_word: &str, lines_word: &str) -> String {
+        let top: Vec<String> = self
+            .contributors()
+            .into_iter()
+            .take(TOP_CONTRIBUTORS)
+            .map(|(construct, amount, lines)| {
+                let lines: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+                let label = if lines.len() == 1 { line_word } else { lines_word };
+                format!("{} +{} ({} {})", construct, amount, label, lines.join(", "))
+            })
+            .collect();
+        top.join(", ")
+    }
+
+    /// Build the issue for a function over the threshold; positions are 1-indexed
+    pub fn issue(
+        &self,
+        file_path: &Path,
+        function_name: &str,
+        threshold: usize,
+        line: usize,
+        column: usize,
+        end: Option<(usize, usize)>,
+    ) -> CodeIssue {
+        CodeIssue {
+            file_path: file_path.display().to_string(),
+            line,
+            column,
+            end_line: end.map(|(line, _)| line),
+            end_column: end.map(|(_, column)| column),
+            message: format!(
+                "Function '{}' has a cognitive complexity of {} (max {}). Biggest contributors: {}.",
+                function_name,
+                self.total(),
+                threshold,
+                self.top_contributors("line", "lines")
+            ),
+            severity: Severity::Warning,
+            category: Category::Complexity,
+            rule: "cognitive-complexity".to_string(),
+            code_snippet: None,
+        }
+    }
+}
+
+/// Node kinds of a tree-sitter grammar that matter for cognitive complexity.
+/// Unknown kinds simply never match, so a grammar update can't break parsing.
+pub struct CognitiveGrammar {
+    /// Function-like nodes; each is scored on its own and nested ones are not counted in their parent
+    pub functions: &'static [&'static str],
+    /// Branches, loops, catches and ternaries: `1 + nesting`, and they nest their children
+    pub nesting: &'static [&'static str],
+    /// `if` nodes whose `else` branch follows an anonymous `else` child
+    pub ifs: &'static [&'static str],
+    /// `elif`/`else` clauses that are children of an `if` node
+    pub else_clauses: &'static [&'static str],
+    /// Binary boolean operators; the operator is the `operator` field or the node kind itself
+    pub boolean_operators: &'static [&'static str],
+    /// `break`/`continue`, counted when they jump to a label
+    pub jumps: &'static [&'static str],
+    /// Name of a function node, if it has one
+    pub function_name: fn(Node, &str) -> Option<String>,
+    /// Name of the function called by a node, if it is a call
+    pub callee: fn(Node, &str) -> Option<String>,
+}
+
+impl CognitiveGrammar {
+    /// Whether `node` is a function; keyword tokens such as Python's `lambda` share the kind of their node
+    pub fn is_function(&self, node: Node) -> bool {
+        node.is_named() && self.functions.contains(&node.kind())
+    }
+}
+
+/// Score every function in a tree-sitter tree and report those over `threshold`
+pub fn analyze_tree(
+    grammar: &CognitiveGrammar,
+    root: Node,
+    code: &str,
+    file_path: &Path,
+    threshold: usize,
+) -> Vec<CodeIssue> {
+    let mut issues = Vec::new();
+    let mut stack = vec![root];
+
+    while let Some(node) = stack.pop() {
+        if grammar.is_function(node) {
+            let name = (grammar.function_name)(node, code);
+            let score = score_function(grammar, node, code);
+
+            if score.total() > threshold {
+                let start = node.start_position();
+                let end = node.end_position();
+                issues.push(score.issue(
+                    file_path,
+                    name.as_deref().unwrap_or("<anonymous>"),
+                    threshold,
+                    start.row + 1,
+                    start.column + 1,
+                    Some((end.row + 1, end.column + 1)),
+                ));
+            }
+        }
+
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+
+    issues.sort_by_key(|issue| issue.line);
+    issues
+}
+
+/// Cognitive complexity of one function node, not counting the functions nested in it
+pub fn score_function(grammar: &CognitiveGrammar, node: Node, code: &str) -> CognitiveScore {
+    let name = (grammar.function_name)(node, code);
+    let mut score = CognitiveScore::default();
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        score_node(grammar, child, code, name.as_deref(), 0, &mut score);
+    }
+    score
+}
+
+fn score_node(
+    grammar: &CognitiveGrammar,
+    node: Node,
+    code: &str,
+    function_name: Option<&str>,
+    nesting: usize,
+    score: &mut CognitiveScore,
+) {
+    let kind = node.kind();
+    let line = node.start_position().row + 1;
+
+    if grammar.is_function(node) {
+        return;
+    }
+
+    if grammar.ifs.contains(&kind) {
+        score.nested(label(kind), line, nesting);
+        score_if_branches(grammar, node, code, function_name, nesting, score);
+        return;
+    }
+
+    if grammar.else_clauses.contains(&kind)
+        && node.parent().is_some_and(|parent| grammar.ifs.contains(&parent.kind()))
+    {
+        score.flat(label(kind), line);
+    }
+
+    let mut child_nesting = nesting;
+    if grammar.nesting.contains(&kind) {
+        score.nested(label(kind), line, nesting);
+        child_nesting += 1;
+    }
+
+    if let Some(operator) = boolean_operator(grammar, node, code) {
+        let continues_sequence = node
+            .parent()
+            .and_then(|parent| boolean_operator(grammar, parent, code))
+            .is_some_and(|parent_operator| parent_operator == operator);
+        if !continues_sequence {
+            score.flat(operator, line);
+        }
+    }
+
+    if grammar.jumps.contains(&kind) {
+        let text = node.utf8_text(code.as_bytes()).unwrap_or("");
+        let mut cursor = node.walk();
+        let has_label = text.contains('@')
+            || node
+                .named_children(&mut cursor)
+                .any(|child| matches!(child.kind(), "identifier" | "label" | "simple_identifier"));
+        if has_label {
+            score.flat("labeled jump", line);
+        }
+    }
+
+    if let (Some(callee), Some(name)) = ((grammar.callee)(node, code), function_name) {
+        if callee.rsplit('.').next() == Some(name) {
+            score.flat("recursion", line);
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        score_node(grammar, child, code, function_name, child_nesting, score);
+    }
+}
+
+/// Children of an `if`: the condition and body nest one level deeper, `else if` stays at the same level
+fn score_if_branches(
+    grammar: &CognitiveGrammar,
+    node: Node,
+    code: &str,
+    function_name: Option<&str>,
+    nesting: usize,
+    score: &mut CognitiveScore,
+) {
+    let mut after_else = false;
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.kind() == "else" {
+            after_else = true;
+            continue;
+        }
+        if after_else && child.is_named() {
+            after_else = false;
+            let branch = unwrap_single_child(child);
+            let line = branch.start_position().row + 1;
+            if grammar.ifs.contains(&branch.kind()) {
+                score.flat(format!("else {}", label(branch.kind())), line);
+                score_if_branches(grammar, branch, code, function_name, nesting, score);
+            } else {
+                score.flat("else", line);
+                score_node(grammar, child, code, function_name, nesting + 1, score);
+            }
+            continue;
+        }
+        score_node(grammar, child, code, function_name, nesting + 1, score);
+    }
+}
+
+/// `else` bodies are often wrapped (e.g. Kotlin's `control_structure_body`); a braced block is never an `else if`
+fn unwrap_single_child(node: Node) -> Node {
+    let mut current = node;
+    while current.named_child_count() == 1 && current.kind() != "block" {
+        match current.named_child(0) {
+            Some(child) => current = child,
+            None => break,
+        }
+    }
+    current
+}
+
+fn boolean_operator(grammar: &CognitiveGrammar, node: Node, code: &str) -> Option<String> {
+    if !grammar.boolean_operators.contains(&node.kind()) {
+        return None;
+    }
+    match node.child_by_field_name("operator") {
+        Some(operator) => operator.utf8_text(code.as_bytes()).ok().map(String::from),
+        None => Some(label(node.kind())),
+    }
+}
+
+/// Human readable label for a node kind: `if_statement` -> `if`, `conjunction_expression` -> `&&`
+fn label(kind: &str) -> String {
+    match kind {
+        "conjunction_expression" | "logical_and_expression" => "&&".to_string(),
+        "disjunction_expression" | "logical_or_expression" => "||".to_string(),
+        "conditional_expression" => "ternary".to_string(),
+        "elif_clause" => "elif".to_string(),
+        _ => {
+            let mut label = kind;
+            for suffix in ["_statement", "_expression", "_clause", "_block"] {
+                label = label.strip_suffix(suffix).unwrap_or(label);
+            }
+            label.replace('_', "-")
+        }
+    }
+}
@@ -0,0 +1,316 @@
+use crate::error::{AnalyzerError, Result};
+use crate::languages::javascript::structural::StructuralRule;
+use crate::plugins::Plugin;
+use crate::types::{Category, Severity};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File name looked up in the working directory when `--config` is not given
+pub const DEFAULT_CONFIG_FILE: &str = ".js-ast-analyzer.json";
+
+/// Optional rule sets that can be listed under `profiles`
+pub const PROFILES: &[&str] = &["android"];
+
+/// Front ends that run the query rules listed under `rules`
+pub const RULE_LANGUAGES: &[&str] = &["kotlin", "dart", "python"];
+
+/// Languages a plugin can be limited to
+pub const PLUGIN_LANGUAGES: &[&str] = &["javascript", "typescript", "kotlin", "dart", "python"];
+
+/// Project configuration shared by all language front ends
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Optional rule sets to turn on, from [`PROFILES`]
+    pub profiles: Vec<String>,
+    /// Project rules written as tree-sitter queries
+    pub rules: Vec<RuleConfig>,
+    /// Rule plugins compiled to WebAssembly
+    pub plugins: Vec<PluginConfig>,
+    /// Plugins compiled from `plugins` when the config is loaded
+    #[serde(skip)]
+    pub loaded_plugins: Vec<Plugin>,
+    /// Settings specific to JavaScript and TypeScript
+    pub javascript: JavascriptConfig,
+    /// Settings for the JS/TS taint analysis
+    pub taint: TaintConfig,
+    /// Settings for hardcoded secret detection
+    pub secrets: SecretsConfig,
+    /// Thresholds for the complexity rules
+    pub complexity: ComplexityConfig,
+    /// Settings for duplicate code detection
+    pub clones: ClonesConfig,
+    /// Settings specific to Python
+    pub python: PythonConfig,
+    /// Settings specific to Kotlin
+    pub kotlin: KotlinConfig,
+    /// Settings specific to Dart and Flutter
+    pub dart: DartConfig,
+}
+
+/// A project rule written as a tree-sitter query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleConfig {
+    /// Rule identifier reported on each issue
+    pub id: String,
+    /// Front end that runs the query, from [`RULE_LANGUAGES`]
+    pub language: String,
+    /// S-expression query; `#eq?`, `#not-eq?`, `#match?` and `#any-of?` predicates are applied
+    pub query: String,
+    /// Capture whose node is reported, once per match
+    pub capture: String,
+    /// Issue message; `{name}` is replaced by the text captured as `@name`
+    pub message: String,
+    #[serde(default = "RuleConfig::default_severity")]
+    pub severity: Severity,
+    #[serde(default = "RuleConfig::default_category")]
+    pub category: Category,
+    /// Snippets the rule must report, checked by `--test-rule`
+    #[serde(default)]
+    pub invalid: Vec<String>,
+    /// Snippets the rule must not report, checked by `--test-rule`
+    #[serde(default)]
+    pub valid: Vec<String>,
+}
+
+impl RuleConfig {
+    fn default_severity() -> Severity {
+        Severity::Warning
+    }
+
+    fn default_category() -> Category {
+        Category::BestPractice
+    }
+}
+
+/// A rule plugin compiled to WebAssembly
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PluginConfig {
+    /// The `.wasm` module, relative to the config file
+    pub path: PathBuf,
+    /// Languages the plugin runs on, from [`PLUGIN_LANGUAGES`]; empty runs it on every file
+    pub languages: Vec<String>,
+    /// Fuel (roughly, wasm instructions) the plugin may spend on one file
+    pub fuel: u64,
+    /// Largest linear memory the plugin may grow to
+    pub max_memory_mb: usize,
+    /// Whether the request includes the file's syntax tree
+    pub ast: bool,
+}
+
+impl Default for PluginConfig {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::new(),
+            languages: Vec::new(),
+            fuel: 100_000_000,
+            max_memory_mb: 64,
+            ast: true,
+        }
+    }
+}
+
+/// Settings specific to JavaScript and TypeScript
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JavascriptConfig {
+    /// YAML files of structural pattern rules, or directories of them, relative to the config file
+    pub rule_files: Vec<PathBuf>,
+    /// Rules compiled from `rule_files` when the config is loaded
+    #[serde(skip)]
+    pub rules: Vec<StructuralRule>,
+}
+
+/// Settings for the JS/TS taint analysis
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaintConfig {
+    /// Extra functions that clean tainted data, e.g. `"DOMPurify.sanitize"` or `"escapeHtml"`.
+    /// A name without a dot matches the last segment of any callee.
+    pub sanitizers: Vec<String>,
+}
+
+/// Settings for hardcoded secret detection
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SecretsConfig {
+    /// File with one allowlist entry per line (`#` starts a comment), relative to the config file
+    pub allowlist_file: Option<PathBuf>,
+    /// Secret values or file path patterns (`*` wildcards) that are never reported.
+    /// Entries from `allowlist_file` are appended when the config is loaded.
+    pub allowlist: Vec<String>,
+}
+
+/// Thresholds for the complexity rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ComplexityConfig {
+    /// Functions with a cognitive complexity above this are reported
+    pub cognitive_threshold: usize,
+}
+
+impl Default for ComplexityConfig {
+    fn default() -> Self {
+        Self {
+            cognitive_threshold: 15,
+        }
+    }
+}
+
+/// Settings for duplicate code detection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClonesConfig {
+    pub enabled: bool,
+    /// Shortest token sequence reported as a clone
+    pub min_tokens: usize,
+}
+
+impl Default for ClonesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_tokens: 50,
+        }
+    }
+}
+
+/// Settings specific to Python
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PythonConfig {
+    /// Python runs and `type-coverage` fail when fewer than this percentage of functions are fully annotated
+    pub min_type_coverage: Option<f64>,
+}
+
+/// Settings specific to Kotlin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KotlinConfig {
+    /// `?.let` calls may nest this deep before they are reported
+    pub max_let_depth: usize,
+}
+
+impl Default for KotlinConfig {
+    fn default() -> Self {
+        Self { max_let_depth: 2 }
+    }
+}
+
+/// Settings specific to Dart and Flutter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DartConfig {
+    /// `build()` methods longer than this are reported
+    pub max_build_lines: usize,
+    /// `build()` methods with a higher cognitive complexity are reported
+    pub max_build_complexity: usize,
+}
+
+impl Default for DartConfig {
+    fn default() -> Self {
+        Self {
+            max_build_lines: 60,
+            max_build_complexity: 8,
+        }
+    }
+}
+
+impl Config {
+    /// Load a config file from disk
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|_| AnalyzerError::FileReadError {
+            path: path.display().to_string(),
+        })?;
+
+        let mut config: Self = serde_json::from_str(&content).map_err(|e| AnalyzerError::ConfigError {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })?;
+
+        if let Some(unknown) = config.profiles.iter().find(|profile| !PROFILES.contains(&profile.as_str())) {
+            return Err(AnalyzerError::ConfigError {
+                path: path.display().to_string(),
+                message: format!("unknown profile '{}', expected one of: {}", unknown, PROFILES.join(", ")),
+            });
+        }
+
+        if let Some(rule) = config.rules.iter().find(|rule| !RULE_LANGUAGES.contains(&rule.language.as_str())) {
+            return Err(AnalyzerError::ConfigError {
+                path: path.display().to_string(),
+                message: format!(
+                    "rule '{}' has unknown language '{}', expected one of: {}",
+                    rule.id,
+                    rule.language,
+                    RULE_LANGUAGES.join(", ")
+                ),
+            });
+        }
+
+        if let Some(allowlist_file) = &config.secrets.allowlist_file {
+            let allowlist_path = path.parent().unwrap_or(Path::new(".")).join(allowlist_file);
+            let entries = fs::read_to_string(&allowlist_path).map_err(|e| AnalyzerError::ConfigError {
+                path: path.display().to_string(),
+                message: format!("cannot read allowlist {}: {}", allowlist_path.display(), e),
+            })?;
+            config.secrets.allowlist.extend(
+                entries
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map(String::from),
+            );
+        }
+
+        for rule_file in &config.javascript.rule_files {
+            let rule_path = path.parent().unwrap_or(Path::new(".")).join(rule_file);
+            if !rule_path.exists() {
+                return Err(AnalyzerError::ConfigError {
+                    path: path.display().to_string(),
+                    message: format!("rule file {} does not exist", rule_path.display()),
+                });
+            }
+            config.javascript.rules.extend(StructuralRule::load(&rule_path)?);
+        }
+
+        for plugin in &config.plugins {
+            if let Some(language) = plugin.languages.iter().find(|language| !PLUGIN_LANGUAGES.contains(&language.as_str())) {
+                return Err(AnalyzerError::ConfigError {
+                    path: path.display().to_string(),
+                    message: format!(
+                        "plugin {} has unknown language '{}', expected one of: {}",
+                        plugin.path.display(),
+                        language,
+                        PLUGIN_LANGUAGES.join(", ")
+                    ),
+                });
+            }
+            let plugin_path = path.parent().unwrap_or(Path::new(".")).join(&plugin.path);
+            config.loaded_plugins.push(Plugin::load(plugin, &plugin_path)?);
+        }
+
+        Ok(config)
+    }
+
+    /// Whether the named profile is turned on
+    pub fn has_profile(&self, name: &str) -> bool {
+        self.profiles.iter().any(|profile| profile == name)
+    }
+
+    /// Load the given config file, or the default one from the working directory if it exists
+    pub fn resolve(explicit: Option<&Path>) -> Result<Self> {
+        match explicit {
+            Some(path) => Self::load(path),
+            None => {
+                let default_path = PathBuf::from(DEFAULT_CONFIG_FILE);
+                if default_path.is_file() {
+                    Self::load(&default_path)
+                } else {
+                    Ok(Self::default())
+                }
+            }
+        }
+    }
+}
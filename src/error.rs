@@ -19,6 +19,18 @@ pub enum AnalyzerError {
 
     #[error("Invalid file path: {0}")]
     InvalidPath(String),
+
+    #[error("Invalid config file {path}: {message}")]
+    ConfigError { path: String, message: String },
+
+    #[error("Failed to load the {language} grammar: {message}")]
+    GrammarError { language: String, message: String },
+
+    #[error("Invalid rule '{rule}': {message}")]
+    RuleError { rule: String, message: String },
+
+    #[error("Invalid plugin {path}: {message}")]
+    PluginError { path: String, message: String },
 }
 
 pub type Result<T> = std::result::Result<T, AnalyzerError>;
@@ -0,0 +1,371 @@
+//! Flutter widget rules: `setState` and `BuildContext` after an `await` without a `mounted` check,
+//! constructor calls that could be `const`, oversized `build()` methods, controllers that are never
+//! disposed and printing inside `build`
+
+use crate::cfg::tree::named_children;
+use crate::cognitive::{self, CognitiveGrammar};
+use crate::types::{Category, CodeIssue, Severity};
+use std::path::Path;
+use tree_sitter::Node;
+
+/// Types a `State` has to release in `dispose()`
+const DISPOSABLES: &[&str] = &[
+    "TextEditingController",
+    "AnimationController",
+    "ScrollController",
+    "PageController",
+    "TabController",
+    "FocusNode",
+    "StreamSubscription",
+    "StreamController",
+    "Timer",
+];
+
+/// Calls that release a disposable
+const RELEASES: &[&str] = &["dispose", "cancel", "close"];
+
+const PRINTS: &[&str] = &["print", "debugPrint"];
+
+fn text<'a>(node: Node, code: &'a str) -> &'a str {
+    node.utf8_text(code.as_bytes()).unwrap_or("")
+}
+
+fn issue(file_path: &Path, node: Node, code: &str, message: String, rule: &str, severity: Severity, category: Category) -> CodeIssue {
+    let start = node.start_position();
+    let end = node.end_position();
+    CodeIssue {
+        file_path: file_path.display().to_string(),
+        line: start.row + 1,
+        column: start.column + 1,
+        end_line: Some(end.row + 1),
+        end_column: Some(end.column + 1),
+        message,
+        severity,
+        category,
+        rule: rule.to_string(),
+        code_snippet: Some(text(node, code).lines().next().unwrap_or("").to_string()),
+    }
+}
+
+/// Thresholds for `build()` methods
+pub struct BuildLimits {
+    pub max_lines: usize,
+    pub max_complexity: usize,
+}
+
+/// The name a call starts with and whether its first selector passes arguments: `setState(...)`, `Text(...)`
+fn called_name<'a>(node: Node, code: &'a str) -> Option<&'a str> {
+    if node.kind() != "member_access" {
+        return None;
+    }
+    let children = named_children(node);
+    match children.as_slice() {
+        [name, selector, ..] if name.kind() == "identifier" && selector.named_child(0).is_some_and(|part| part.kind() == "argument_part") => {
+            Some(text(*name, code))
+        }
+        _ => None,
+    }
+}
+
+/// The function body `node` runs in: a declaration's `function_body` or a closure's `function_expression_body`
+fn enclosing_body(node: Node) -> Option<Node> {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if matches!(parent.kind(), "function_body" | "function_expression_body") {
+            return Some(parent);
+        }
+        current = parent.parent();
+    }
+    None
+}
+
+/// Whether `node` runs after an `await` of its own function without a `mounted` check in between
+fn after_async_gap(node: Node, code: &str) -> bool {
+    let Some(body) = enclosing_body(node) else {
+        return false;
+    };
+    if !text(body, code).starts_with("async") {
+        return false;
+    }
+
+    let mut last_await = None;
+    let mut checks = Vec::new();
+    let mut stack = vec![body];
+    while let Some(candidate) = stack.pop() {
+        match candidate.kind() {
+            "await_expression" if candidate.end_byte() <= node.start_byte() && enclosing_body(candidate) == Some(body) => {
+                last_await = last_await.max(Some(candidate.end_byte()));
+            }
+            "if_statement" => checks.push(candidate),
+            _ => {}
+        }
+        stack.extend(named_children(candidate));
+    }
+    let Some(last_await) = last_await else {
+        return false;
+    };
+
+    !checks.into_iter().filter(|check| check.start_byte() >= last_await).any(|check| {
+        let condition = check.child_by_field_name("condition").map(|condition| text(condition, code)).unwrap_or("");
+        if !condition.contains("mounted") {
+            return false;
+        }
+        let negated = condition.contains('!');
+        let exits = check
+            .child_by_field_name("consequence")
+            .is_some_and(|consequence| text(consequence, code).trim_start_matches('{').trim_start().starts_with("return"));
+        let inside = check.start_byte() <= node.start_byte() && node.end_byte() <= check.end_byte();
+        (negated && exits && check.end_byte() <= node.start_byte()) || (!negated && inside)
+    })
+}
+
+/// Whether an argument is a compile-time constant
+fn is_constant(node: Node, code: &str) -> bool {
+    let value = match node.kind() {
+        "argument" | "named_argument" => match node.named_child(node.named_child_count().saturating_sub(1)) {
+            Some(value) => value,
+            None => return false,
+        },
+        _ => node,
+    };
+    match value.kind() {
+        "string_literal" => !text(value, code).contains('$'),
+        "decimal_integer_literal" | "decimal_floating_point_literal" | "hex_integer_literal" | "true" | "false" | "null_literal" => true,
+        "const_object_expression" => true,
+        "member_access" => constructor_arguments(value, code).is_some_and(|arguments| arguments.iter().all(|argument| is_constant(*argument, code))) || is_static_constant(value, code),
+        _ => false,
+    }
+}
+
+/// `Colors.red`, `EdgeInsets.zero`: a class followed by one field
+fn is_static_constant(node: Node, code: &str) -> bool {
+    let children = named_children(node);
+    matches!(children.as_slice(), [class, field]
+        if class.kind() == "identifier"
+            && text(*class, code).starts_with(char::is_uppercase)
+            && field.named_child(0).is_some_and(|field| field.kind() == "unconditional_assignable_selector"))
+}
+
+/// The arguments of an unnamed constructor call such as `Text('a')`, which has at least one
+fn constructor_arguments<'tree>(node: Node<'tree>, code: &str) -> Option<Vec<Node<'tree>>> {
+    let name = called_name(node, code)?;
+    if !name.starts_with(char::is_uppercase) || node.named_child_count() != 2 {
+        return None;
+    }
+    let arguments: Vec<Node> = named_children(node.named_child(1)?.named_child(0)?)
+        .into_iter()
+        .filter(|child| child.kind() == "arguments")
+        .flat_map(named_children)
+        .collect();
+    (!arguments.is_empty()).then_some(arguments)
+}
+
+/// Whether `node` is already in a constant context such as `const Text(...)`
+fn in_const(node: Node) -> bool {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if parent.kind() == "const_object_expression" {
+            return true;
+        }
+        if matches!(parent.kind(), "function_body" | "function_expression_body") {
+            return false;
+        }
+        current = parent.parent();
+    }
+    false
+}
+
+/// Whether `node` is an argument of a constructor call that would be reported itself
+fn in_const_candidate(node: Node, code: &str) -> bool {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if constructor_arguments(parent, code).is_some() {
+            return is_constant(parent, code);
+        }
+        if !matches!(parent.kind(), "argument" | "named_argument" | "arguments" | "argument_part" | "selector") {
+            return false;
+        }
+        current = parent.parent();
+    }
+    false
+}
+
+/// Whether `node` is part of the widget tree returned by a `build()` method
+fn in_build(node: Node, code: &str) -> bool {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if parent.kind() == "class_member_definition" {
+            return method_name(parent, code) == Some("build");
+        }
+        current = parent.parent();
+    }
+    false
+}
+
+/// The name of the method a `class_member_definition` declares
+fn method_name<'a>(member: Node, code: &'a str) -> Option<&'a str> {
+    let signature = named_children(member).into_iter().find(|child| child.kind() == "method_signature")?;
+    let function = named_children(signature).into_iter().find(|child| child.kind() == "function_signature")?;
+    function.child_by_field_name("name").map(|name| text(name, code))
+}
+
+fn method_body(member: Node) -> Option<Node> {
+    named_children(member).into_iter().find(|child| child.kind() == "function_body")
+}
+
+/// Fields of a `State` that hold one of the [`DISPOSABLES`], with their declarations
+fn disposables<'tree, 'a>(class_body: Node<'tree>, code: &'a str) -> Vec<(&'a str, Node<'tree>)> {
+    let mut fields = Vec::new();
+    for member in named_children(class_body).into_iter().filter(|member| member.kind() == "class_member_definition") {
+        for declaration in named_children(member).into_iter().filter(|child| child.kind() == "declaration") {
+            let children = named_children(declaration);
+            let declared = children
+                .iter()
+                .find(|child| child.kind() == "type_identifier")
+                .is_some_and(|declared| DISPOSABLES.contains(&text(*declared, code)));
+            for identifiers in children.iter().filter(|child| child.kind() == "initialized_identifier_list") {
+                for field in named_children(*identifiers) {
+                    let Some(name) = field.named_child(0) else {
+                        continue;
+                    };
+                    let created = field
+                        .named_child(1)
+                        .and_then(|value| called_name(value, code))
+                        .is_some_and(|created| DISPOSABLES.contains(&created));
+                    if declared || created {
+                        fields.push((text(name, code), declaration));
+                    }
+                }
+            }
+        }
+    }
+    fields
+}
+
+/// Whether `dispose()` releases the field `name`
+fn releases(dispose: Option<Node>, name: &str, code: &str) -> bool {
+    let Some(body) = dispose else {
+        return false;
+    };
+    let body = text(body, code);
+    RELEASES.iter().any(|release| {
+        body.contains(&format!("{}.{}(", name, release)) || body.contains(&format!("{}?.{}(", name, release)) || body.contains(&format!("{}!.{}(", name, release))
+    })
+}
+
+/// Run the Flutter rules over a parsed Dart file
+pub fn analyze_tree(grammar: &CognitiveGrammar, root: Node, code: &str, file_path: &Path, limits: &BuildLimits) -> Vec<CodeIssue> {
+    let mut issues = Vec::new();
+
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        match node.kind() {
+            "member_access" => {
+                if called_name(node, code) == Some("setState") && after_async_gap(node, code) {
+                    issues.push(issue(
+                        file_path,
+                        node,
+                        code,
+                        "setState is called after an await without checking mounted. Return early with `if (!mounted) return;`.".to_string(),
+                        "flutter-setstate-after-await",
+                        Severity::Warning,
+                        Category::CodeQuality,
+                    ));
+                }
+                let constant = constructor_arguments(node, code).is_some() && is_constant(node, code);
+                if constant && !in_const(node) && !in_const_candidate(node, code) && in_build(node, code) {
+                    issues.push(issue(
+                        file_path,
+                        node,
+                        code,
+                        format!("All arguments of {} are constant. Add const so the widget is built once.", called_name(node, code).unwrap_or("")),
+                        "flutter-prefer-const",
+                        Severity::Suggestion,
+                        Category::Performance,
+                    ));
+                }
+            }
+            "identifier" if text(node, code) == "context" => {
+                let checks_mounted = node.next_named_sibling().is_some_and(|next| text(next, code) == ".mounted");
+                let declares = node.parent().is_some_and(|parent| matches!(parent.kind(), "formal_parameter" | "label"));
+                if !checks_mounted && !declares && after_async_gap(node, code) {
+                    issues.push(issue(
+                        file_path,
+                        node,
+                        code,
+                        "BuildContext is used after an await. The widget may be unmounted by then; check `context.mounted` first.".to_string(),
+                        "flutter-context-across-async-gap",
+                        Severity::Warning,
+                        Category::CodeQuality,
+                    ));
+                }
+            }
+            "class_member_definition" if method_name(node, code) == Some("build") => {
+                if let Some(body) = method_body(node) {
+                    let lines = body.end_position().row - body.start_position().row + 1;
+                    let complexity = cognitive::score_function(grammar, body, code).total();
+                    if lines > limits.max_lines || complexity > limits.max_complexity {
+                        issues.push(issue(
+                            file_path,
+                            node,
+                            code,
+                            format!(
+                                "build() has {} lines and a cognitive complexity of {} (limits {} and {}). Extract parts of the tree into widgets.",
+                                lines, complexity, limits.max_lines, limits.max_complexity
+                            ),
+                            "flutter-large-build",
+                            Severity::Warning,
+                            Category::Complexity,
+                        ));
+                    }
+
+                    let mut calls = vec![body];
+                    while let Some(call) = calls.pop() {
+                        if let Some(name) = called_name(call, code).filter(|name| PRINTS.contains(name)) {
+                            issues.push(issue(
+                                file_path,
+                                call,
+                                code,
+                                format!("{} in build() runs on every rebuild. Remove it.", name),
+                                "flutter-print-in-build",
+                                Severity::Warning,
+                                Category::Performance,
+                            ));
+                        }
+                        calls.extend(named_children(call));
+                    }
+                }
+            }
+            "class_definition" => {
+                let is_state = node
+                    .child_by_field_name("superclass")
+                    .and_then(|superclass| named_children(superclass).into_iter().find(|child| child.kind() == "type_identifier"))
+                    .is_some_and(|superclass| text(superclass, code) == "State");
+                if let Some(body) = node.child_by_field_name("body").filter(|_| is_state) {
+                    let dispose = named_children(body)
+                        .into_iter()
+                        .find(|member| method_name(*member, code) == Some("dispose"))
+                        .and_then(method_body);
+                    for (name, declaration) in disposables(body, code) {
+                        if !releases(dispose, name, code) {
+                            issues.push(issue(
+                                file_path,
+                                declaration,
+                                code,
+                                format!("'{}' is never disposed. Release it in dispose() to avoid leaking it.", name),
+                                "flutter-undisposed-controller",
+                                Severity::Warning,
+                                Category::Performance,
+                            ));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        stack.extend(named_children(node));
+    }
+
+    issues.sort_by_key(|issue| (issue.line, issue.column));
+    issues
+}
@@ -1,3 +1,5 @@
+pub mod flutter;
+pub mod null_safety;
 pub mod parser;
 
 pub use parser::DartParser;
@@ -0,0 +1,405 @@
+//! Dart null safety rules built on declared types: member access on nullable variables, the `!`
+//! operator, `late` fields nothing initializes, `dynamic` and `as` casts of nullable values.
+//!
+//! Names are resolved through the file's scopes, so a nullable variable only affects the code that
+//! can see it, and a `?` inside type arguments such as `Map<String, int?>` does not make it nullable.
+
+use crate::cfg::tree::named_children;
+use crate::types::{Category, CodeIssue, Severity};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use tree_sitter::Node;
+
+/// Members every object has, so they are safe to use on a nullable value
+const OBJECT_MEMBERS: &[&str] = &["toString", "hashCode", "runtimeType", "noSuchMethod"];
+
+/// Methods that run before a `late` field of a `State` is first read
+const INIT_METHODS: &[&str] = &["initState", "didChangeDependencies"];
+
+fn text<'a>(node: Node, code: &'a str) -> &'a str {
+    node.utf8_text(code.as_bytes()).unwrap_or("")
+}
+
+fn issue(file_path: &Path, node: Node, snippet: &str, message: String, rule: &str, severity: Severity, category: Category) -> CodeIssue {
+    let start = node.start_position();
+    let end = node.end_position();
+    CodeIssue {
+        file_path: file_path.display().to_string(),
+        line: start.row + 1,
+        column: start.column + 1,
+        end_line: Some(end.row + 1),
+        end_column: Some(end.column + 1),
+        message,
+        severity,
+        category,
+        rule: rule.to_string(),
+        code_snippet: Some(snippet.to_string()),
+    }
+}
+
+/// Whether a declaration's own type ends in `?`. A `?` inside its type arguments belongs to them.
+fn declares_nullable(declaration: Node) -> bool {
+    let mut cursor = declaration.walk();
+    let nullable = declaration.children(&mut cursor).any(|child| child.kind() == "?");
+    nullable
+}
+
+/// The `formal_parameter`s of a signature, including optional and named ones
+fn parameters(signature: Node) -> Vec<Node> {
+    let mut found = Vec::new();
+    let mut stack = vec![signature];
+    while let Some(node) = stack.pop() {
+        match node.kind() {
+            "formal_parameter" => found.push(node),
+            "function_body" | "function_expression_body" | "initializers" => {}
+            _ => stack.extend(named_children(node)),
+        }
+    }
+    found
+}
+
+/// `name == null` or `name != null` somewhere in `condition`: whether `name` is non-null when it holds
+fn null_check(condition: Node, name: &str, code: &str) -> Option<bool> {
+    let mut stack = vec![condition];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "equality_expression" {
+            let children = named_children(node);
+            if let [left, operator, right] = children.as_slice() {
+                let tested = match (left.kind(), right.kind()) {
+                    ("identifier", "null_literal") => Some(*left),
+                    ("null_literal", "identifier") => Some(*right),
+                    _ => None,
+                };
+                if tested.is_some_and(|tested| text(tested, code) == name) {
+                    return Some(text(*operator, code) == "!=");
+                }
+            }
+        }
+        if !matches!(node.kind(), "function_expression" | "lambda_expression") {
+            stack.extend(named_children(node));
+        }
+    }
+    None
+}
+
+fn contains(outer: Node, inner: Node) -> bool {
+    outer.start_byte() <= inner.start_byte() && inner.end_byte() <= outer.end_byte()
+}
+
+/// Whether a null check around or before `node` promotes `name` to non-null
+fn promoted(node: Node, name: &str, code: &str) -> bool {
+    let mut child = node;
+    while let Some(parent) = child.parent() {
+        match parent.kind() {
+            "if_statement" => {
+                let condition = parent.child_by_field_name("condition");
+                let in_consequence = parent.child_by_field_name("consequence").is_some_and(|consequence| contains(consequence, node));
+                if in_consequence && condition.and_then(|condition| null_check(condition, name, code)) == Some(true) {
+                    return true;
+                }
+            }
+            "conditional_expression" | "logical_and_expression" => {
+                let guard = parent.named_child(0).filter(|guard| !contains(*guard, node));
+                if guard.and_then(|guard| null_check(guard, name, code)) == Some(true) {
+                    return true;
+                }
+            }
+            "block" => {
+                // `if (name == null) return;` earlier in the block
+                let exits_early = named_children(parent)
+                    .into_iter()
+                    .take_while(|statement| statement.end_byte() <= child.start_byte())
+                    .filter(|statement| statement.kind() == "if_statement")
+                    .any(|statement| {
+                        let exits = statement.child_by_field_name("consequence").is_some_and(|consequence| {
+                            let exit = text(consequence, code).trim_start_matches('{').trim_start();
+                            exit.starts_with("return") || exit.starts_with("throw")
+                        });
+                        exits && statement.child_by_field_name("condition").and_then(|condition| null_check(condition, name, code)) == Some(false)
+                    });
+                if exits_early {
+                    return true;
+                }
+            }
+            "function_body" | "function_expression_body" => return false,
+            _ => {}
+        }
+        child = parent;
+    }
+    false
+}
+
+/// `Map<String, dynamic>`: the usual type of decoded JSON
+fn is_json_map(node: Node, code: &str) -> bool {
+    node.parent()
+        .filter(|arguments| arguments.kind() == "type_arguments")
+        .and_then(|arguments| arguments.prev_named_sibling())
+        .is_some_and(|map| text(map, code) == "Map")
+        && node.prev_named_sibling().is_some_and(|key| text(key, code) == "String")
+}
+
+/// Whether a `late` field is assigned by every constructor or an init method of the class
+fn initialized(class_body: Node, name: &str, code: &str) -> bool {
+    let members: Vec<Node> = named_children(class_body).into_iter().filter(|member| member.kind() == "class_member_definition").collect();
+    let constructors: Vec<Node> = members
+        .iter()
+        .copied()
+        .filter(|member| {
+            named_children(*member)
+                .into_iter()
+                .filter(|child| child.kind() == "method_signature")
+                .flat_map(named_children)
+                .any(|signature| matches!(signature.kind(), "constructor_signature" | "factory_constructor_signature"))
+        })
+        .collect();
+
+    let assigns = |root: Node| {
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            let target = match node.kind() {
+                "assignment_expression" => node.child_by_field_name("left"),
+                "field_initializer" | "constructor_param" => named_children(node).into_iter().find(|child| child.kind() == "identifier"),
+                _ => None,
+            };
+            if target.is_some_and(|target| matches!(text(target, code), t if t == name || t.strip_prefix("this.") == Some(name))) {
+                return true;
+            }
+            stack.extend(named_children(node));
+        }
+        false
+    };
+
+    let in_init_method = members.iter().any(|member| {
+        let name = named_children(*member)
+            .into_iter()
+            .filter(|child| child.kind() == "method_signature")
+            .flat_map(named_children)
+            .find_map(|signature| signature.child_by_field_name("name"))
+            .map(|name| text(name, code));
+        name.is_some_and(|name| INIT_METHODS.contains(&name)) && assigns(*member)
+    });
+    in_init_method || (!constructors.is_empty() && constructors.iter().all(|constructor| assigns(*constructor)))
+}
+
+#[derive(Clone, Copy)]
+struct Declared {
+    nullable: bool,
+}
+
+struct Checker<'a> {
+    code: &'a str,
+    file_path: &'a Path,
+    scopes: Vec<HashMap<&'a str, Declared>>,
+    issues: Vec<CodeIssue>,
+}
+
+impl<'a> Checker<'a> {
+    fn declare(&mut self, name: Node, nullable: bool) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(text(name, self.code), Declared { nullable });
+        }
+    }
+
+    fn resolve(&self, name: &str) -> Option<Declared> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name).copied())
+    }
+
+    /// Declare the variables of a `declaration` or `initialized_variable_definition`
+    fn declare_variables(&mut self, declaration: Node) {
+        let nullable = declares_nullable(declaration);
+        if let Some(name) = declaration.child_by_field_name("name") {
+            self.declare(name, nullable);
+        }
+        for list in named_children(declaration).into_iter().filter(|child| child.kind() == "initialized_identifier_list") {
+            for variable in named_children(list) {
+                if let Some(name) = variable.named_child(0) {
+                    self.declare(name, nullable);
+                }
+            }
+        }
+    }
+
+    fn declare_parameters(&mut self, signature: Node) {
+        for parameter in parameters(signature) {
+            let name = parameter.child_by_field_name("name").or_else(|| {
+                named_children(parameter)
+                    .into_iter()
+                    .filter(|child| child.kind() == "constructor_param")
+                    .find_map(|param| named_children(param).into_iter().find(|child| child.kind() == "identifier"))
+            });
+            if let Some(name) = name {
+                self.declare(name, declares_nullable(parameter));
+            }
+        }
+    }
+
+    fn visit(&mut self, node: Node<'a>) {
+        let scoped = matches!(
+            node.kind(),
+            "program" | "class_body" | "block" | "for_statement" | "function_body" | "function_expression" | "lambda_expression"
+        );
+        if scoped {
+            self.scopes.push(HashMap::new());
+        }
+
+        match node.kind() {
+            "class_body" => self.check_class(node),
+            "function_body" => {
+                if let Some(signature) = node.prev_named_sibling().filter(|signature| matches!(signature.kind(), "function_signature" | "method_signature")) {
+                    self.declare_parameters(signature);
+                }
+            }
+            "function_expression" => {
+                if let Some(signature) = node.child_by_field_name("parameters") {
+                    self.declare_parameters(signature);
+                }
+            }
+            "declaration" | "initialized_variable_definition" => self.declare_variables(node),
+            "for_loop_parts" => {
+                if let Some(name) = node.child_by_field_name("name") {
+                    self.declare(name, declares_nullable(node));
+                }
+            }
+            "member_access" => self.check_access(node),
+            "type_cast_expression" => self.check_cast(node),
+            "selector" if text(node, self.code) == "!" => {
+                let start = node.parent().map_or(node.start_byte(), |access| access.start_byte());
+                let asserted = &self.code[start..node.end_byte()];
+                let target = asserted.trim_end_matches('!');
+                self.issues.push(issue(
+                    self.file_path,
+                    node,
+                    asserted,
+                    format!("`!` throws if '{}' is null. Check for null first or use `?.` and `??`.", target),
+                    "dart-bang-operator",
+                    Severity::Warning,
+                    Category::CodeQuality,
+                ));
+            }
+            "type_identifier" if text(node, self.code) == "dynamic" && !is_json_map(node, self.code) => {
+                self.issues.push(issue(
+                    self.file_path,
+                    node,
+                    "dynamic",
+                    "`dynamic` turns off type checking. Use a specific type, or Object? if any value is allowed.".to_string(),
+                    "dart-dynamic",
+                    Severity::Suggestion,
+                    Category::BestPractice,
+                ));
+            }
+            _ => {}
+        }
+
+        for child in named_children(node) {
+            self.visit(child);
+        }
+        if scoped {
+            self.scopes.pop();
+        }
+    }
+
+    /// Declare the fields up front, since methods may use fields declared below them, and check `late` fields
+    fn check_class(&mut self, class_body: Node<'a>) {
+        let declarations: Vec<Node> = named_children(class_body)
+            .into_iter()
+            .filter(|member| member.kind() == "class_member_definition")
+            .flat_map(named_children)
+            .filter(|child| child.kind() == "declaration")
+            .collect();
+
+        let mut reported = HashSet::new();
+        for declaration in declarations {
+            self.declare_variables(declaration);
+
+            let mut cursor = declaration.walk();
+            let late = declaration.children(&mut cursor).any(|child| child.kind() == "late");
+            if !late {
+                continue;
+            }
+            for list in named_children(declaration).into_iter().filter(|child| child.kind() == "initialized_identifier_list") {
+                for variable in named_children(list).into_iter().filter(|variable| variable.named_child_count() == 1) {
+                    let Some(name) = variable.named_child(0).map(|name| text(name, self.code)) else {
+                        continue;
+                    };
+                    if !initialized(class_body, name, self.code) && reported.insert(name) {
+                        self.issues.push(issue(
+                            self.file_path,
+                            declaration,
+                            text(declaration, self.code),
+                            format!("late field '{}' is not set by every constructor or in initState, so reading it first throws. Initialize it or make it nullable.", name),
+                            "dart-late-uninitialized",
+                            Severity::Warning,
+                            Category::CodeQuality,
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_access(&mut self, node: Node) {
+        let children = named_children(node);
+        let [target, selector, ..] = children.as_slice() else {
+            return;
+        };
+        if target.kind() != "identifier" {
+            return;
+        }
+        let name = text(*target, self.code);
+        let selector_text = text(*selector, self.code);
+        let member = selector_text.trim_start_matches(['.', '?']);
+        let safe = selector_text.starts_with("?.")
+            || selector_text.starts_with("?[")
+            || selector_text == "!"
+            || OBJECT_MEMBERS.iter().any(|object_member| member.starts_with(object_member));
+        if safe || !self.resolve(name).is_some_and(|declared| declared.nullable) || promoted(node, name, self.code) {
+            return;
+        }
+        self.issues.push(issue(
+            self.file_path,
+            *target,
+            &format!("{}{}", name, selector_text),
+            format!("Unsafe property access on nullable variable '{}'. Use '?.' or check for null.", name),
+            "null-safety",
+            Severity::Error,
+            Category::CodeQuality,
+        ));
+    }
+
+    fn check_cast(&mut self, node: Node) {
+        let children = named_children(node);
+        let [value, cast] = children.as_slice() else {
+            return;
+        };
+        let name = text(*value, self.code);
+        let nullable = value.kind() == "identifier" && self.resolve(name).is_some_and(|declared| declared.nullable);
+        if !nullable || declares_nullable(*cast) || promoted(node, name, self.code) {
+            return;
+        }
+        let target = text(*cast, self.code).trim_start_matches("as").trim();
+        self.issues.push(issue(
+            self.file_path,
+            node,
+            text(node, self.code),
+            format!("'{}' may be null, and `as {}` throws on null. Check for null before casting or cast to {}?.", name, target, target),
+            "dart-nullable-cast",
+            Severity::Warning,
+            Category::CodeQuality,
+        ));
+    }
+}
+
+/// Run the null safety rules over a parsed Dart file
+pub fn analyze_tree(root: Node, code: &str, file_path: &Path) -> Vec<CodeIssue> {
+    let mut checker = Checker {
+        code,
+        file_path,
+        scopes: Vec::new(),
+        issues: Vec::new(),
+    };
+    checker.visit(root);
+
+    let mut issues = checker.issues;
+    issues.sort_by_key(|issue| (issue.line, issue.column));
+    issues
+}
@@ -1,14 +1,291 @@
+use super::flutter::{self, BuildLimits};
+use super::null_safety;
+use crate::cfg::{self, tree::{named_children, unparenthesized, Flow, FlowGrammar}};
+use crate::clones::CloneDetector;
+use crate::cognitive::{self, CognitiveGrammar};
+use crate::config::Config;
 use crate::error::{AnalyzerError, Result};
+use crate::languages::grammar::Grammar;
+use crate::languages::query_rules::QueryRules;
+use crate::metrics::halstead;
+use crate::metrics::tree::{self, MetricsGrammar};
+use crate::metrics::FileMetrics;
+use crate::plugins::{self, Plugin};
+use crate::secrets::{self, SecretScanner};
 use crate::types::{AnalysisResult, Category, CodeIssue, FileAnalysis, Severity, SeveritySummary};
 use std::fs;
 use std::path::{Path, PathBuf};
-use tree_sitter::{Parser, Query, QueryCursor};
+use tree_sitter::{Node, QueryCursor};
+
+/// Dart node kinds scored by the cognitive complexity rule.
+/// Declarations are a signature followed by a sibling `function_body`, so the body is what gets scored.
+const COGNITIVE_GRAMMAR: CognitiveGrammar = CognitiveGrammar {
+    functions: &["function_body", "function_expression"],
+    nesting: &[
+        "for_statement",
+        "while_statement",
+        "do_statement",
+        "switch_statement",
+        "switch_expression",
+        "catch_clause",
+        "conditional_expression",
+    ],
+    ifs: &["if_statement"],
+    else_clauses: &[],
+    boolean_operators: &["logical_and_expression", "logical_or_expression"],
+    jumps: &["break_statement", "continue_statement"],
+    function_name,
+    callee,
+};
+
+fn function_name(node: Node, code: &str) -> Option<String> {
+    if node.kind() != "function_body" {
+        return None;
+    }
+    let signature = node.prev_named_sibling()?;
+    let mut stack = vec![signature];
+    while let Some(current) = stack.pop() {
+        if current.kind() == "identifier" {
+            return current.utf8_text(code.as_bytes()).ok().map(String::from);
+        }
+        if current.kind() == "formal_parameter_list" {
+            continue;
+        }
+        let mut cursor = current.walk();
+        let children: Vec<Node> = current.named_children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+    None
+}
+
+/// Calls are a target followed by a `selector` holding the arguments
+fn callee(node: Node, code: &str) -> Option<String> {
+    if node.kind() != "selector" || node.named_child(0)?.kind() != "argument_part" {
+        return None;
+    }
+    let target = node.prev_named_sibling()?;
+    target.utf8_text(code.as_bytes()).ok().map(String::from)
+}
+
+/// Dart node kinds used by the `metrics` subcommand
+const METRICS_GRAMMAR: MetricsGrammar = MetricsGrammar {
+    cognitive: &COGNITIVE_GRAMMAR,
+    lexicon: &halstead::DART,
+    decisions: &[
+        "if_statement",
+        "for_statement",
+        "while_statement",
+        "do_statement",
+        "switch_statement_case",
+        "switch_expression_case",
+        "catch_clause",
+        "conditional_expression",
+        "logical_and_expression",
+        "logical_or_expression",
+        "if_null_expression",
+    ],
+    statement_lists: &["block"],
+    parameter_lists: &["formal_parameter_list"],
+    parameters: &["formal_parameter"],
+    header: signature,
+};
+
+/// A declaration's `function_body` follows its signature
+fn signature(node: Node) -> Node {
+    match node.kind() {
+        "function_body" => node.prev_named_sibling().unwrap_or(node),
+        _ => node,
+    }
+}
+
+/// Dart statements mapped to control flow
+const FLOW_GRAMMAR: FlowGrammar = FlowGrammar {
+    cognitive: &COGNITIVE_GRAMMAR,
+    body: flow_body,
+    header: signature,
+    classify,
+    constant: |node, _| match unparenthesized(node).kind() {
+        "true" => Some(true),
+        "false" | "null_literal" => Some(false),
+        _ => None,
+    },
+    generator: |node, code| {
+        let body = match node.kind() {
+            "function_expression" => node.child_by_field_name("body"),
+            _ => Some(node),
+        };
+        body.and_then(|body| body.utf8_text(code.as_bytes()).ok())
+            .is_some_and(|text| text.starts_with("async*") || text.starts_with("sync*"))
+    },
+    check_returns: true,
+};
+
+/// The `block` of a function; `=>` bodies have no statements
+fn flow_body(node: Node) -> Option<Node> {
+    let body = match node.kind() {
+        "function_expression" => node.child_by_field_name("body")?,
+        _ => node,
+    };
+    named_children(body).into_iter().find(|child| child.kind() == "block")
+}
+
+fn classify<'tree>(node: Node<'tree>, code: &str) -> Flow<'tree> {
+    let field = |name| node.child_by_field_name(name);
+    let children_of = |kinds: &[&str]| -> Vec<Node<'tree>> {
+        named_children(node)
+            .into_iter()
+            .filter(|child| kinds.contains(&child.kind()))
+            .collect()
+    };
+    let jump_label = || {
+        children_of(&["identifier"])
+            .first()
+            .and_then(|label| label.utf8_text(code.as_bytes()).ok())
+            .map(String::from)
+    };
+
+    match node.kind() {
+        "comment" | "documentation_comment" | "label" | "ERROR" => Flow::Ignore,
+        "block" => Flow::Sequence(named_children(node)),
+        "finally_clause" => Flow::Sequence(children_of(&["block"])),
+        "if_statement" => Flow::If {
+            condition: field("condition"),
+            consequence: field("consequence"),
+            alternatives: field("alternative").into_iter().collect(),
+        },
+        "while_statement" | "do_statement" => Flow::Loop {
+            condition: field("condition"),
+            body: field("body"),
+            label: None,
+            test_first: node.kind() == "while_statement",
+            endless: false,
+        },
+        "for_statement" => {
+            let parts = children_of(&["for_loop_parts"]).pop();
+            let for_in = parts.is_some_and(|parts| parts.child_by_field_name("value").is_some());
+            let condition = parts.and_then(|parts| parts.child_by_field_name("condition"));
+            Flow::Loop {
+                condition,
+                body: field("body"),
+                label: None,
+                test_first: true,
+                endless: !for_in && condition.is_none(),
+            }
+        }
+        "switch_statement" => {
+            // Labels and statements are flat in the block; consecutive labels share their statements
+            let mut arms: Vec<Vec<Node>> = Vec::new();
+            let mut exhaustive = false;
+            let mut open = false;
+            for child in field("body").map(named_children).unwrap_or_default() {
+                if child.kind() == "switch_label" {
+                    exhaustive |= child.utf8_text(code.as_bytes()).is_ok_and(|text| text.trim_start().starts_with("default"));
+                    if !open {
+                        arms.push(Vec::new());
+                        open = true;
+                    }
+                } else if let Some(arm) = arms.last_mut() {
+                    arm.push(child);
+                    open = false;
+                }
+            }
+            Flow::Branches {
+                arms,
+                exhaustive,
+                breakable: true,
+            }
+        }
+        "try_statement" => {
+            let body = field("body");
+            Flow::Try {
+                body,
+                handlers: children_of(&["block"])
+                    .into_iter()
+                    .filter(|block| Some(*block) != body)
+                    .collect(),
+                otherwise: None,
+                finalizer: children_of(&["finally_clause"]).pop(),
+            }
+        }
+        "return_statement" => Flow::Return {
+            value: node.named_child_count() > 0,
+        },
+        "throw_statement" => Flow::Throw,
+        "expression_statement" => match node.named_child(0) {
+            Some(expression) if expression.kind() == "throw_expression" => Flow::Throw,
+            Some(expression) if expression.utf8_text(code.as_bytes()) == Ok("rethrow") => Flow::Throw,
+            _ => Flow::Statement,
+        },
+        "break_statement" => Flow::Break(jump_label()),
+        "continue_statement" => Flow::Continue(jump_label()),
+        _ => Flow::Statement,
+    }
+}
 
-pub struct DartParser {}
+/// Rule patterns matched against every Dart file, compiled once per parser
+const QUERY: &str = "
+    (member_access
+        (identifier) @func_name
+        (selector (argument_part))
+        (#match? @func_name \"^print$\")
+    ) @print_call
+
+    (decimal_integer_literal) @magic_number
+    (decimal_floating_point_literal) @magic_number
+    (hex_integer_literal) @magic_number
+
+    (string_literal) @string_literal
+
+    (class_definition
+        name: (identifier) @class_name
+    )
+
+    (initialized_variable_definition
+        name: (identifier) @variable_name
+    )
+
+    (if_statement) @if_stmt
+
+    ;; Index access
+    (member_access
+        (identifier)
+        (selector) @selector_node
+    )
+";
+
+pub struct DartParser {
+    grammar: Grammar,
+    rules: QueryRules,
+    plugins: Vec<Plugin>,
+    secrets: SecretScanner,
+    clones: CloneDetector,
+    cognitive_threshold: usize,
+    build_limits: BuildLimits,
+}
 
 impl DartParser {
-    pub fn new() -> Self {
-        Self {}
+    /// Library entry point; the binary always builds parsers from the resolved config
+    #[allow(dead_code)]
+    pub fn new() -> Result<Self> {
+        Self::with_config(&Config::default())
+    }
+
+    /// Build the front end, compiling its grammar query and the project rules once. Fails if a query doesn't compile.
+    pub fn with_config(config: &Config) -> Result<Self> {
+        let grammar = Grammar::new("Dart", tree_sitter_dart::language(), QUERY)?;
+        let rules = QueryRules::compile(&grammar, &config.rules)?;
+        Ok(Self {
+            grammar,
+            rules,
+            plugins: config.loaded_plugins.clone(),
+            secrets: SecretScanner::with_config(&config.secrets),
+            clones: CloneDetector::with_config(&config.clones),
+            cognitive_threshold: config.complexity.cognitive_threshold,
+            build_limits: BuildLimits {
+                max_lines: config.dart.max_build_lines,
+                max_complexity: config.dart.max_build_complexity,
+            },
+        })
     }
 
     pub fn analyze_file(&self, file_path: &Path) -> Result<FileAnalysis> {
@@ -16,22 +293,7 @@ impl DartParser {
             path: file_path.display().to_string(),
         })?;
 
-        let mut parser = Parser::new();
-        // Since we don't have tree_sitter_dart trait directly available as language()
-        // We will assume tree_sitter_dart::language() is available
-        // If compilation fails, we might need a different import.
-        parser
-            .set_language(&tree_sitter_dart::language())
-            .expect("Error loading Dart grammar");
-
-        let tree = parser
-            .parse(&code, None)
-            .ok_or_else(|| AnalyzerError::ParseError {
-                file: file_path.display().to_string(),
-                line: 0,
-                column: 0,
-                message: "Failed to parse Dart file".to_string(),
-            })?;
+        let tree = self.grammar.parse(&code, file_path)?;
 
         let mut issues = Vec::new();
         let root_node = tree.root_node();
@@ -72,356 +334,242 @@ impl DartParser {
 
         // To be safe, I will use a very permissive query or catch the error if I could, but standard unwrap is fine for 'dev'.
         // query_source
-        let query_source = "
-            (member_access
-                (identifier) @func_name
-                (selector (argument_part))
-                (#match? @func_name \"^print$\")
-            ) @print_call
-            
-            (decimal_integer_literal) @magic_number
-            (decimal_floating_point_literal) @magic_number
-            (hex_integer_literal) @magic_number
-            
-            (string_literal) @string_literal
-            
-            (class_definition 
-                name: (identifier) @class_name
-            )
-
-            (initialized_variable_definition 
-                name: (identifier) @variable_name
-            )
-
-            (if_statement) @if_stmt
-
-            ;; Null safety - Definitions
-            (initialized_variable_definition 
-                name: (identifier) @def_name
-            ) @var_def
-
-            ;; Null safety - Access
-            (member_access 
-                (identifier) @access_target 
-                (selector) @selector_node
-            )
-        ";
 
         // NOTE: If the above query fails at runtime, I might need to adjust node names.
-        let mut nullable_vars = std::collections::HashSet::new();
-
-        if let Ok(query) = Query::new(&tree_sitter_dart::language(), query_source) {
-            let mut query_cursor = QueryCursor::new();
-            let matches = query_cursor.matches(&query, root_node, code.as_bytes());
-
-            for m in matches {
-                // Pre-process match for definitions
-                let capture_map: std::collections::HashMap<_, _> = m
-                    .captures
-                    .iter()
-                    .map(|c| (query.capture_names()[c.index as usize], c.node))
-                    .collect();
-                if let (Some(var_def_node), Some(name_node)) =
-                    (capture_map.get("var_def"), capture_map.get("def_name"))
-                {
-                    let start_byte = var_def_node.start_byte();
-                    let name_start_byte = name_node.start_byte();
-                    if name_start_byte > start_byte {
-                        let type_section = &code[start_byte..name_start_byte];
-                        if type_section.contains('?') {
-                            let name_text = name_node.utf8_text(code.as_bytes()).unwrap_or("");
-                            nullable_vars.insert(name_text.to_string());
-                        }
-                    }
-                }
-
-                for capture in m.captures {
-                    let node = capture.node;
-                    let start = node.start_position();
-                    let end = node.end_position();
-                    let capture_name = query.capture_names()[capture.index as usize];
-
-                    if capture_name == "print_call" {
-                        issues.push(CodeIssue {
-                            file_path: file_path.display().to_string(),
-                            line: start.row + 1,
-                            column: start.column + 1,
-                            end_line: Some(end.row + 1),
-                            end_column: Some(end.column + 1),
-                            message: "Avoid using print() in production. Use a logger.".to_string(),
-                            severity: Severity::Warning,
-                            category: Category::BestPractice,
-                            rule: "no-print".to_string(),
-                            code_snippet: Some(
-                                node.utf8_text(code.as_bytes()).unwrap_or("").to_string(),
-                            ),
-                        });
-                    } else if capture_name == "magic_number" {
-                        let text = node.utf8_text(code.as_bytes()).unwrap_or("");
-                        // Ignore common small numbers
-                        if text != "0"
-                            && text != "1"
-                            && text != "-1"
-                            && text != "2"
-                            && text != "10"
-                            && text != "100"
-                        {
-                            // Ignore if it's in a const declaration
-                            let mut is_const = false;
-                            let mut parent = node.parent();
-                            let mut depth = 0;
-                            while let Some(p) = parent {
-                                depth += 1;
-                                if p.kind() == "initialized_variable_definition"
-                                    || p.kind() == "declaration"
-                                {
-                                    // Check children for const_builtin
-                                    let mut cursor = p.walk();
-                                    for child in p.children(&mut cursor) {
-                                        if child.kind() == "const_builtin" {
-                                            is_const = true;
-                                            break;
-                                        }
+        let query = self.grammar.query();
+        let mut query_cursor = QueryCursor::new();
+        let matches = query_cursor.matches(query, root_node, code.as_bytes());
+
+        for m in matches {
+            for capture in m.captures {
+                let node = capture.node;
+                let start = node.start_position();
+                let end = node.end_position();
+                let capture_name = query.capture_names()[capture.index as usize];
+
+                if capture_name == "print_call" {
+                    issues.push(CodeIssue {
+                        file_path: file_path.display().to_string(),
+                        line: start.row + 1,
+                        column: start.column + 1,
+                        end_line: Some(end.row + 1),
+                        end_column: Some(end.column + 1),
+                        message: "Avoid using print() in production. Use a logger.".to_string(),
+                        severity: Severity::Warning,
+                        category: Category::BestPractice,
+                        rule: "no-print".to_string(),
+                        code_snippet: Some(
+                            node.utf8_text(code.as_bytes()).unwrap_or("").to_string(),
+                        ),
+                    });
+                } else if capture_name == "magic_number" {
+                    let text = node.utf8_text(code.as_bytes()).unwrap_or("");
+                    // Ignore common small numbers
+                    if text != "0"
+                        && text != "1"
+                        && text != "-1"
+                        && text != "2"
+                        && text != "10"
+                        && text != "100"
+                    {
+                        // Ignore if it's in a const declaration
+                        let mut is_const = false;
+                        let mut parent = node.parent();
+                        let mut depth = 0;
+                        while let Some(p) = parent {
+                            depth += 1;
+                            if p.kind() == "initialized_variable_definition"
+                                || p.kind() == "declaration"
+                            {
+                                // Check children for const_builtin
+                                let mut cursor = p.walk();
+                                for child in p.children(&mut cursor) {
+                                    if child.kind() == "const_builtin" {
+                                        is_const = true;
+                                        break;
                                     }
                                 }
-                                if is_const {
-                                    break;
-                                }
-                                parent = p.parent();
-                                if parent.is_none()
-                                    || p.kind() == "class_body"
-                                    || p.kind() == "block"
-                                    || depth > 5
-                                {
-                                    break;
-                                }
                             }
-
-                            if !is_const {
-                                issues.push(CodeIssue {
-                                    file_path: file_path.display().to_string(),
-                                    line: start.row + 1,
-                                    column: start.column + 1,
-                                    end_line: Some(end.row + 1),
-                                    end_column: Some(end.column + 1),
-                                    message: format!(
-                                        "magic number detected: {}. Define a constant.",
-                                        text
-                                    ),
-                                    severity: Severity::Suggestion,
-                                    category: Category::BestPractice,
-                                    rule: "no-magic-numbers".to_string(),
-                                    code_snippet: Some(text.to_string()),
-                                });
+                            if is_const {
+                                break;
                             }
-                        }
-                    } else if capture_name == "string_literal" {
-                        let text = node.utf8_text(code.as_bytes()).unwrap_or("");
-                        // Heuristic: Check if string is long and not in a const declaration
-                        if text.len() > 20 && !text.contains("${") {
-                            let mut is_const = false;
-                            let mut parent = node.parent();
-                            while let Some(p) = parent {
-                                if p.kind() == "initialized_variable_definition"
-                                    || p.kind() == "declaration"
-                                {
-                                    let mut cursor = p.walk();
-                                    for child in p.children(&mut cursor) {
-                                        if child.kind() == "const_builtin" {
-                                            is_const = true;
-                                            break;
-                                        }
-                                    }
-                                }
-                                if is_const {
-                                    break;
-                                }
-                                parent = p.parent();
-                                if parent.is_none()
-                                    || p.kind() == "class_body"
-                                    || p.kind() == "block"
-                                {
-                                    break;
-                                }
-                            }
-
-                            if !is_const {
-                                issues.push(CodeIssue {
-                                    file_path: file_path.display().to_string(),
-                                    line: start.row + 1,
-                                    column: start.column + 1,
-                                    end_line: Some(end.row + 1),
-                                    end_column: Some(end.column + 1),
-                                    message: format!("hardcoded string detected: \"{}...\". Consider extracting to a constant.", &text.chars().take(20).collect::<String>()),
-                                    severity: Severity::Suggestion,
-                                    category: Category::BestPractice,
-                                    rule: "no-hardcoded-strings".to_string(),
-                                    code_snippet: Some(text.to_string()),
-                                });
+                            parent = p.parent();
+                            if parent.is_none()
+                                || p.kind() == "class_body"
+                                || p.kind() == "block"
+                                || depth > 5
+                            {
+                                break;
                             }
                         }
-                    } else if capture_name == "class_name" {
-                        let text = node.utf8_text(code.as_bytes()).unwrap_or("");
-                        if !text.chars().next().map_or(false, |c| c.is_uppercase()) {
-                            issues.push(CodeIssue {
-                                file_path: file_path.display().to_string(),
-                                line: start.row + 1,
-                                column: start.column + 1,
-                                end_line: Some(end.row + 1),
-                                end_column: Some(end.column + 1),
-                                message: format!("Class name '{}' should be PascalCase.", text),
-                                severity: Severity::Warning,
-                                category: Category::CodeQuality,
-                                rule: "class-naming".to_string(),
-                                code_snippet: Some(text.to_string()),
-                            });
-                        }
-                    } else if capture_name == "variable_name" {
-                        let text = node.utf8_text(code.as_bytes()).unwrap_or("");
-
-                        // Check for camelCase
-                        if !text.chars().next().map_or(false, |c| c.is_lowercase()) {
-                            issues.push(CodeIssue {
-                                file_path: file_path.display().to_string(),
-                                line: start.row + 1,
-                                column: start.column + 1,
-                                end_line: Some(end.row + 1),
-                                end_column: Some(end.column + 1),
-                                message: format!("Variable name '{}' should be camelCase.", text),
-                                severity: Severity::Warning,
-                                category: Category::CodeQuality,
-                                rule: "variable-naming".to_string(),
-                                code_snippet: Some(text.to_string()),
-                            });
-                        }
 
-                        // Check for generic names
-                        let generic_names = [
-                            "data", "result", "info", "value", "temp", "tmp", "obj", "item",
-                        ];
-                        if generic_names.contains(&text) {
+                        if !is_const {
                             issues.push(CodeIssue {
                                 file_path: file_path.display().to_string(),
                                 line: start.row + 1,
                                 column: start.column + 1,
                                 end_line: Some(end.row + 1),
                                 end_column: Some(end.column + 1),
-                                message: format!("Avoid using generic variable name '{}'. Use a more descriptive name.", text),
+                                message: format!(
+                                    "magic number detected: {}. Define a constant.",
+                                    text
+                                ),
                                 severity: Severity::Suggestion,
                                 category: Category::BestPractice,
-                                rule: "variable-naming".to_string(),
+                                rule: "no-magic-numbers".to_string(),
                                 code_snippet: Some(text.to_string()),
                             });
                         }
-
-                        // Check for short names
-                        if text.len() < 3 {
-                            let mut is_loop_var = false;
-                            let mut parent = node.parent();
-                            while let Some(p) = parent {
-                                if p.kind() == "for_statement" || p.kind() == "for_in_statement" {
-                                    is_loop_var = true;
-                                    break;
+                    }
+                } else if capture_name == "string_literal" {
+                    let text = node.utf8_text(code.as_bytes()).unwrap_or("");
+                    let secret = self.secrets.scan(file_path, secrets::unquote(text));
+                    if let Some(finding) = &secret {
+                        issues.push(self.secrets.issue(
+                            file_path,
+                            finding,
+                            start.row + 1,
+                            start.column + 1,
+                            Some((end.row + 1, end.column + 1)),
+                        ));
+                    }
+                    // Heuristic: Check if string is long and not in a const declaration
+                    if secret.is_none() && text.len() > 20 && !text.contains("${") {
+                        let mut is_const = false;
+                        let mut parent = node.parent();
+                        while let Some(p) = parent {
+                            if p.kind() == "initialized_variable_definition"
+                                || p.kind() == "declaration"
+                            {
+                                let mut cursor = p.walk();
+                                for child in p.children(&mut cursor) {
+                                    if child.kind() == "const_builtin" {
+                                        is_const = true;
+                                        break;
+                                    }
                                 }
-                                parent = p.parent();
                             }
-
-                            if !is_loop_var {
-                                issues.push(CodeIssue {
-                                    file_path: file_path.display().to_string(),
-                                    line: start.row + 1,
-                                    column: start.column + 1,
-                                    end_line: Some(end.row + 1),
-                                    end_column: Some(end.column + 1),
-                                    message: format!("Penamaan variable '{}' cukup pendek. Kamu bisa menggunakan penamaan yang lebih deskriptif untuk penulisan yang lebih baik.", text),
-                                    severity: Severity::Suggestion,
-                                    category: Category::CodeQuality,
-                                    rule: "variable-naming".to_string(),
-                                    code_snippet: Some(text.to_string()),
-                                });
+                            if is_const {
+                                break;
                             }
-                        }
-
-                        // Check for boolean naming
-                        if let Some(parent) = node.parent() {
-                            let mut cursor = parent.walk();
-                            for child in parent.children(&mut cursor) {
-                                if (child.kind() == "type_identifier"
-                                    || child.kind() == "boolean_type")
-                                    && child.utf8_text(code.as_bytes()).unwrap_or("") == "bool"
-                                {
-                                    if !text.starts_with("is")
-                                        && !text.starts_with("has")
-                                        && !text.starts_with("can")
-                                        && !text.starts_with("should")
-                                    {
-                                        issues.push(CodeIssue {
-                                            file_path: file_path.display().to_string(),
-                                            line: start.row + 1,
-                                            column: start.column + 1,
-                                            end_line: Some(end.row + 1),
-                                            end_column: Some(end.column + 1),
-                                            message: format!("Dalam menuliskan sebuah penamaan variable '{}' kamu bisa memulainya dengan keyword seperti 'is', 'has', 'can', or 'should'. Contohnya: isOddNumber", text),
-                                            severity: Severity::Warning,
-                                            category: Category::CodeQuality,
-                                            rule: "variable-naming".to_string(),
-                                            code_snippet: Some(text.to_string()),
-                                        });
-                                    }
-                                }
+                            parent = p.parent();
+                            if parent.is_none()
+                                || p.kind() == "class_body"
+                                || p.kind() == "block"
+                            {
+                                break;
                             }
                         }
 
-                        // Heuristic unused check
-                        let count = code.matches(text).count();
-                        if count <= 1 {
+                        if !is_const {
                             issues.push(CodeIssue {
                                 file_path: file_path.display().to_string(),
                                 line: start.row + 1,
                                 column: start.column + 1,
                                 end_line: Some(end.row + 1),
                                 end_column: Some(end.column + 1),
-                                message: format!("Sepertinya variabel '{}' ini tidak kamu gunakan, kamu bisa melakukan penghapusan pada variabel yang tidak digunakan seperti ini ya!", text),
-                                severity: Severity::Warning,
-                                category: Category::Maintainability,
-                                rule: "unused-variable".to_string(),
+                                message: format!("hardcoded string detected: \"{}...\". Consider extracting to a constant.", &text.chars().take(20).collect::<String>()),
+                                severity: Severity::Suggestion,
+                                category: Category::BestPractice,
+                                rule: "no-hardcoded-strings".to_string(),
                                 code_snippet: Some(text.to_string()),
                             });
                         }
-                    } else if capture_name == "if_stmt" {
-                        // Check nesting
-                        let mut depth = 0;
+                    }
+                } else if capture_name == "class_name" {
+                    let text = node.utf8_text(code.as_bytes()).unwrap_or("");
+                    if !text.chars().next().map_or(false, |c| c.is_uppercase()) {
+                        issues.push(CodeIssue {
+                            file_path: file_path.display().to_string(),
+                            line: start.row + 1,
+                            column: start.column + 1,
+                            end_line: Some(end.row + 1),
+                            end_column: Some(end.column + 1),
+                            message: format!("Class name '{}' should be PascalCase.", text),
+                            severity: Severity::Warning,
+                            category: Category::CodeQuality,
+                            rule: "class-naming".to_string(),
+                            code_snippet: Some(text.to_string()),
+                        });
+                    }
+                } else if capture_name == "variable_name" {
+                    let text = node.utf8_text(code.as_bytes()).unwrap_or("");
+
+                    // Check for camelCase
+                    if !text.chars().next().map_or(false, |c| c.is_lowercase()) {
+                        issues.push(CodeIssue {
+                            file_path: file_path.display().to_string(),
+                            line: start.row + 1,
+                            column: start.column + 1,
+                            end_line: Some(end.row + 1),
+                            end_column: Some(end.column + 1),
+                            message: format!("Variable name '{}' should be camelCase.", text),
+                            severity: Severity::Warning,
+                            category: Category::CodeQuality,
+                            rule: "variable-naming".to_string(),
+                            code_snippet: Some(text.to_string()),
+                        });
+                    }
+
+                    // Check for generic names
+                    let generic_names = [
+                        "data", "result", "info", "value", "temp", "tmp", "obj", "item",
+                    ];
+                    if generic_names.contains(&text) {
+                        issues.push(CodeIssue {
+                            file_path: file_path.display().to_string(),
+                            line: start.row + 1,
+                            column: start.column + 1,
+                            end_line: Some(end.row + 1),
+                            end_column: Some(end.column + 1),
+                            message: format!("Avoid using generic variable name '{}'. Use a more descriptive name.", text),
+                            severity: Severity::Suggestion,
+                            category: Category::BestPractice,
+                            rule: "variable-naming".to_string(),
+                            code_snippet: Some(text.to_string()),
+                        });
+                    }
+
+                    // Check for short names
+                    if text.len() < 3 {
+                        let mut is_loop_var = false;
                         let mut parent = node.parent();
                         while let Some(p) = parent {
-                            if p.kind() == "if_statement" {
-                                depth += 1;
+                            if p.kind() == "for_statement" || p.kind() == "for_in_statement" {
+                                is_loop_var = true;
+                                break;
                             }
                             parent = p.parent();
                         }
-                        if depth >= 2 {
+
+                        if !is_loop_var {
                             issues.push(CodeIssue {
                                 file_path: file_path.display().to_string(),
                                 line: start.row + 1,
                                 column: start.column + 1,
                                 end_line: Some(end.row + 1),
                                 end_column: Some(end.column + 1),
-                                message: "Hindari penggunaan kondisi bersarang seperti ini ya, agar lebih baik kamu bisa melakukan refactor terlebih dahulu untuk memudahkan kamu dalam proses memahami kode berikutnya.".to_string(),
-                                severity: Severity::Warning,
-                                category: Category::Complexity,
-                                rule: "nested-if".to_string(),
-                                code_snippet: Some("if (...)".to_string()),
+                                message: format!("Penamaan variable '{}' cukup pendek. Kamu bisa menggunakan penamaan yang lebih deskriptif untuk penulisan yang lebih baik.", text),
+                                severity: Severity::Suggestion,
+                                category: Category::CodeQuality,
+                                rule: "variable-naming".to_string(),
+                                code_snippet: Some(text.to_string()),
                             });
                         }
-                    } else if capture_name == "access_target" {
-                        // Check for unsafe access on nullable vars
-                        let text = node.utf8_text(code.as_bytes()).unwrap_or("");
-                        if nullable_vars.contains(text) {
-                            if let Some(selector) = capture_map.get("selector_node") {
-                                // selector should contain '?' if safe
-                                let selector_text =
-                                    selector.utf8_text(code.as_bytes()).unwrap_or("");
-                                if !selector_text.starts_with("?.")
-                                    && !selector_text.starts_with("?[")
+                    }
+
+                    // Check for boolean naming
+                    if let Some(parent) = node.parent() {
+                        let mut cursor = parent.walk();
+                        for child in parent.children(&mut cursor) {
+                            if (child.kind() == "type_identifier"
+                                || child.kind() == "boolean_type")
+                                && child.utf8_text(code.as_bytes()).unwrap_or("") == "bool"
+                            {
+                                if !text.starts_with("is")
+                                    && !text.starts_with("has")
+                                    && !text.starts_with("can")
+                                    && !text.starts_with("should")
                                 {
                                     issues.push(CodeIssue {
                                         file_path: file_path.display().to_string(),
@@ -429,62 +577,102 @@ impl DartParser {
                                         column: start.column + 1,
                                         end_line: Some(end.row + 1),
                                         end_column: Some(end.column + 1),
-                                        message: format!("Unsafe property access on nullable variable '{}'. Use '?.' or check for null.", text),
-                                        severity: Severity::Error,
+                                        message: format!("Dalam menuliskan sebuah penamaan variable '{}' kamu bisa memulainya dengan keyword seperti 'is', 'has', 'can', or 'should'. Contohnya: isOddNumber", text),
+                                        severity: Severity::Warning,
                                         category: Category::CodeQuality,
-                                        rule: "null-safety".to_string(),
-                                        code_snippet: Some(format!("{}{}", text, selector_text)),
+                                        rule: "variable-naming".to_string(),
+                                        code_snippet: Some(text.to_string()),
                                     });
                                 }
                             }
                         }
-                    } else if capture_name == "selector_node" {
-                        // Check for array access with literal index
-                        let text = node.utf8_text(code.as_bytes()).unwrap_or("");
-                        // S-expression: (selector (unconditional_assignable_selector (index_selector (decimal_integer_literal))))
-                        // We can just check text? selector text would be "[10]"
-                        if text.starts_with('[') {
-                            // It is an index selector
-                            // Check for high index? Or just warn about bounds?
-                            // Test calls it "Unsafe array access".
-                            // Let's check for literal integers inside.
-                            if text.contains(|c: char| c.is_digit(10)) {
-                                // Very heuristic
-                                // Check if it's a large number?
-                                // Let's just flag it as potentially unsafe if it's a literal index > 0?
-                                issues.push(CodeIssue {
-                                    file_path: file_path.display().to_string(),
-                                    line: start.row + 1,
-                                    column: start.column + 1,
-                                    end_line: Some(end.row + 1),
-                                    end_column: Some(end.column + 1),
-                                    message: "Potensial issue dapat terjadi dengan pendekatan seperti ini, pastikan kamu selalu melakukan pengecekan untuk index-nya ya.".to_string(),
-                                    severity: Severity::Warning,
-                                    category: Category::CodeQuality,
-                                    rule: "null-safety".to_string(),
-                                    code_snippet: Some(text.to_string()),
-                                });
-                            }
+                    }
+
+                    // Heuristic unused check
+                    let count = code.matches(text).count();
+                    if count <= 1 {
+                        issues.push(CodeIssue {
+                            file_path: file_path.display().to_string(),
+                            line: start.row + 1,
+                            column: start.column + 1,
+                            end_line: Some(end.row + 1),
+                            end_column: Some(end.column + 1),
+                            message: format!("Sepertinya variabel '{}' ini tidak kamu gunakan, kamu bisa melakukan penghapusan pada variabel yang tidak digunakan seperti ini ya!", text),
+                            severity: Severity::Warning,
+                            category: Category::Maintainability,
+                            rule: "unused-variable".to_string(),
+                            code_snippet: Some(text.to_string()),
+                        });
+                    }
+                } else if capture_name == "if_stmt" {
+                    // Check nesting
+                    let mut depth = 0;
+                    let mut parent = node.parent();
+                    while let Some(p) = parent {
+                        if p.kind() == "if_statement" {
+                            depth += 1;
+                        }
+                        parent = p.parent();
+                    }
+                    if depth >= 2 {
+                        issues.push(CodeIssue {
+                            file_path: file_path.display().to_string(),
+                            line: start.row + 1,
+                            column: start.column + 1,
+                            end_line: Some(end.row + 1),
+                            end_column: Some(end.column + 1),
+                            message: "Hindari penggunaan kondisi bersarang seperti ini ya, agar lebih baik kamu bisa melakukan refactor terlebih dahulu untuk memudahkan kamu dalam proses memahami kode berikutnya.".to_string(),
+                            severity: Severity::Warning,
+                            category: Category::Complexity,
+                            rule: "nested-if".to_string(),
+                            code_snippet: Some("if (...)".to_string()),
+                        });
+                    }
+                } else if capture_name == "selector_node" {
+                    // Check for array access with literal index
+                    let text = node.utf8_text(code.as_bytes()).unwrap_or("");
+                    // S-expression: (selector (unconditional_assignable_selector (index_selector (decimal_integer_literal))))
+                    // We can just check text? selector text would be "[10]"
+                    if text.starts_with('[') {
+                        // It is an index selector
+                        // Check for high index? Or just warn about bounds?
+                        // Test calls it "Unsafe array access".
+                        // Let's check for literal integers inside.
+                        if text.contains(|c: char| c.is_digit(10)) {
+                            // Very heuristic
+                            // Check if it's a large number?
+                            // Let's just flag it as potentially unsafe if it's a literal index > 0?
+                            issues.push(CodeIssue {
+                                file_path: file_path.display().to_string(),
+                                line: start.row + 1,
+                                column: start.column + 1,
+                                end_line: Some(end.row + 1),
+                                end_column: Some(end.column + 1),
+                                message: "Potensial issue dapat terjadi dengan pendekatan seperti ini, pastikan kamu selalu melakukan pengecekan untuk index-nya ya.".to_string(),
+                                severity: Severity::Warning,
+                                category: Category::CodeQuality,
+                                rule: "null-safety".to_string(),
+                                code_snippet: Some(text.to_string()),
+                            });
                         }
                     }
                 }
             }
-        } else if let Err(e) = Query::new(&tree_sitter_dart::language(), query_source) {
-            // Fallback if query fails compilation (due to wrong node names)
-            issues.push(CodeIssue {
-                file_path: file_path.display().to_string(),
-                line: 1,
-                column: 1,
-                end_line: None,
-                end_column: None,
-                message: format!("Internal Error: Failed to compile Dart AST Query: {}", e),
-                severity: Severity::Error,
-                category: Category::CodeQuality,
-                rule: "internal-error".to_string(),
-                code_snippet: None,
-            });
         }
 
+        issues.extend(cognitive::analyze_tree(
+            &COGNITIVE_GRAMMAR,
+            root_node,
+            &code,
+            file_path,
+            self.cognitive_threshold,
+        ));
+        issues.extend(cfg::tree::analyze_tree(&FLOW_GRAMMAR, root_node, &code, file_path));
+        issues.extend(null_safety::analyze_tree(root_node, &code, file_path));
+        issues.extend(flutter::analyze_tree(&COGNITIVE_GRAMMAR, root_node, &code, file_path, &self.build_limits));
+        issues.extend(self.rules.check(root_node, &code, file_path));
+        issues.extend(plugins::run_all(&self.plugins, file_path, "dart", &code, || plugins::tree_sitter_ast(root_node, &code)));
+
         let mut summary = SeveritySummary::new();
         for issue in &issues {
             summary.add(issue.severity);
@@ -497,6 +685,20 @@ impl DartParser {
         })
     }
 
+    /// Code metrics for a file and each function in it
+    pub fn metrics_file(&self, file_path: &Path) -> Result<FileMetrics> {
+        let code = fs::read_to_string(file_path).map_err(|_| AnalyzerError::FileReadError {
+            path: file_path.display().to_string(),
+        })?;
+        let tree = self.grammar.parse(&code, file_path)?;
+        Ok(tree::file_metrics(&METRICS_GRAMMAR, tree.root_node(), &code, file_path))
+    }
+
+    pub fn metrics_directory(&self, dir_path: &Path) -> Result<Vec<FileMetrics>> {
+        let files = self.find_dart_files(dir_path)?;
+        Ok(files.iter().filter_map(|file_path| self.metrics_file(file_path).ok()).collect())
+    }
+
     pub fn analyze_directory(&self, dir_path: &Path) -> Result<AnalysisResult> {
         let mut result = AnalysisResult::new();
         let dart_files = self.find_dart_files(dir_path)?;
@@ -506,9 +708,15 @@ impl DartParser {
                 result.add_file(analysis);
             }
         }
+        self.detect_clones(&mut result);
         Ok(result)
     }
 
+    /// Report duplicated code within and across the files of `result`
+    pub fn detect_clones(&self, result: &mut AnalysisResult) {
+        self.clones.detect(result, &halstead::DART);
+    }
+
     fn find_dart_files(&self, dir_path: &Path) -> Result<Vec<PathBuf>> {
         let mut files = Vec::new();
         for entry in walkdir::WalkDir::new(dir_path)
@@ -527,9 +735,3 @@ impl DartParser {
         Ok(files)
     }
 }
-
-impl Default for DartParser {
-    fn default() -> Self {
-        Self::new()
-    }
-}
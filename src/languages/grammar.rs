@@ -0,0 +1,74 @@
+//! Tree-sitter grammars of the Kotlin, Dart and Python front ends. The rule query is compiled once
+//! when the front end is built and shared by every file; each thread keeps its own `Parser`.
+
+use crate::error::{AnalyzerError, Result};
+use std::cell::RefCell;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::path::Path;
+use tree_sitter::{Language, Parser, Query, Tree};
+
+thread_local! {
+    /// One parser per grammar and thread, since a `Parser` can't be shared between threads
+    static PARSERS: RefCell<HashMap<&'static str, Parser>> = RefCell::new(HashMap::new());
+}
+
+/// A language with its compiled rule query
+pub struct Grammar {
+    name: &'static str,
+    language: Language,
+    query: Query,
+}
+
+impl Grammar {
+    /// Load `language` and compile `query_source` for it
+    pub fn new(name: &'static str, language: Language, query_source: &str) -> Result<Self> {
+        let error = |message: String| AnalyzerError::GrammarError {
+            language: name.to_string(),
+            message,
+        };
+        Parser::new().set_language(&language).map_err(|e| error(e.to_string()))?;
+        let query = Query::new(&language, query_source).map_err(|e| error(e.to_string()))?;
+        Ok(Self { name, language, query })
+    }
+
+    /// Display name, e.g. `Kotlin`
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The tree-sitter language, for compiling further queries
+    pub fn language(&self) -> &Language {
+        &self.language
+    }
+
+    /// The rule query, compiled once
+    pub fn query(&self) -> &Query {
+        &self.query
+    }
+
+    /// Parse a file with this thread's parser for the grammar
+    pub fn parse(&self, code: &str, file_path: &Path) -> Result<Tree> {
+        PARSERS.with(|parsers| {
+            let mut parsers = parsers.borrow_mut();
+            let parser = match parsers.entry(self.name) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let mut parser = Parser::new();
+                    parser.set_language(&self.language).map_err(|e| AnalyzerError::GrammarError {
+                        language: self.name.to_string(),
+                        message: e.to_string(),
+                    })?;
+                    entry.insert(parser)
+                }
+            };
+
+            parser.parse(code, None).ok_or_else(|| AnalyzerError::ParseError {
+                file: file_path.display().to_string(),
+                line: 0,
+                column: 0,
+                message: format!("Failed to parse {} file", self.name),
+            })
+        })
+    }
+}
@@ -0,0 +1,188 @@
+use oxc_ast::ast::*;
+use std::collections::HashMap;
+
+/// A local name that refers to a Node.js module, or to one export of it
+#[derive(Debug, Clone)]
+pub struct ModuleBinding {
+    /// Normalized module name (`node:fs` becomes `fs`, `fs/promises` becomes `fs.promises`)
+    pub module: String,
+    /// Imported member, `None` when the whole module is bound
+    pub imported: Option<String>,
+}
+
+/// Module bindings created by top-level `import` declarations and `require()` calls
+#[derive(Debug, Default)]
+pub struct ModuleBindings {
+    bindings: HashMap<String, ModuleBinding>,
+}
+
+impl ModuleBindings {
+    pub fn collect(program: &Program) -> Self {
+        let mut bindings = HashMap::new();
+
+        for stmt in &program.body {
+            match stmt {
+                Statement::ImportDeclaration(import) => {
+                    let module = normalize_module(import.source.value.as_str());
+                    if let Some(specifiers) = &import.specifiers {
+                        for specifier in specifiers {
+                            match specifier {
+                                ImportDeclarationSpecifier::ImportSpecifier(spec) => {
+                                    bindings.insert(
+                                        spec.local.name.to_string(),
+                                        ModuleBinding {
+                                            module: module.clone(),
+                                            imported: Some(spec.imported.name().to_string()),
+                                        },
+                                    );
+                                }
+                                ImportDeclarationSpecifier::ImportDefaultSpecifier(spec) => {
+                                    bindings.insert(
+                                        spec.local.name.to_string(),
+                                        ModuleBinding { module: module.clone(), imported: None },
+                                    );
+                                }
+                                ImportDeclarationSpecifier::ImportNamespaceSpecifier(spec) => {
+                                    bindings.insert(
+                                        spec.local.name.to_string(),
+                                        ModuleBinding { module: module.clone(), imported: None },
+                                    );
+                                }
+                            }
+                        }
+                    }
+                }
+                Statement::VariableDeclaration(var_decl) => {
+                    for var in &var_decl.declarations {
+                        let Some(module) = var.init.as_ref().and_then(required_module) else {
+                            continue;
+                        };
+                        match &var.id.kind {
+                            BindingPatternKind::BindingIdentifier(ident) => {
+                                bindings.insert(
+                                    ident.name.to_string(),
+                                    ModuleBinding { module: module.clone(), imported: None },
+                                );
+                            }
+                            BindingPatternKind::ObjectPattern(obj_pattern) => {
+                                for prop in &obj_pattern.properties {
+                                    let Some(imported) = prop.key.static_name() else {
+                                        continue;
+                                    };
+                                    if let BindingPatternKind::BindingIdentifier(local) = &prop.value.kind {
+                                        bindings.insert(
+                                            local.name.to_string(),
+                                            ModuleBinding {
+                                                module: module.clone(),
+                                                imported: Some(imported.to_string()),
+                                            },
+                                        );
+                                    }
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Self { bindings }
+    }
+
+    /// Rewrite a dotted path so that its first segment names the module it was loaded from,
+    /// e.g. `cp.exec` becomes `child_process.exec` after `const cp = require('child_process')`
+    pub fn resolve(&self, path: &str) -> String {
+        let (root, rest) = match path.split_once('.') {
+            Some((root, rest)) => (root, Some(rest)),
+            None => (path, None),
+        };
+
+        let Some(binding) = self.bindings.get(root) else {
+            return path.to_string();
+        };
+
+        let mut resolved = binding.module.clone();
+        if let Some(imported) = &binding.imported {
+            resolved.push('.');
+            resolved.push_str(imported);
+        }
+        if let Some(rest) = rest {
+            resolved.push('.');
+            resolved.push_str(rest);
+        }
+        resolved
+    }
+}
+
+/// Module name passed to `require('...')`, if the expression is such a call
+pub fn required_module(expr: &Expression) -> Option<String> {
+    if let Expression::CallExpression(call) = expr {
+        if let Expression::Identifier(ident) = &call.callee {
+            if ident.name == "require" {
+                if let Some(Argument::StringLiteral(lit)) = call.arguments.first() {
+                    return Some(normalize_module(lit.value.as_str()));
+                }
+            }
+        }
+    }
+    None
+}
+
+fn normalize_module(name: &str) -> String {
+    let name = name.strip_prefix("node:").unwrap_or(name);
+    match name.split_once('/') {
+        Some((base, "promises")) => format!("{}.promises", base),
+        _ => name.to_string(),
+    }
+}
+
+/// Dotted text of a plain member chain such as `req.query.id` or `this.el.innerHTML`.
+/// Returns `None` when the chain contains calls or computed keys that are not string literals.
+pub fn member_path(expr: &Expression) -> Option<String> {
+    match expr {
+        Expression::Identifier(ident) => Some(ident.name.to_string()),
+        Expression::ThisExpression(_) => Some("this".to_string()),
+        Expression::StaticMemberExpression(member) => {
+            member_path(&member.object).map(|object| format!("{}.{}", object, member.property.name))
+        }
+        Expression::ComputedMemberExpression(member) => match &member.expression {
+            Expression::StringLiteral(lit) => {
+                member_path(&member.object).map(|object| format!("{}.{}", object, lit.value))
+            }
+            _ => None,
+        },
+        Expression::ParenthesizedExpression(paren) => member_path(&paren.expression),
+        _ => None,
+    }
+}
+
+/// Last segment of a dotted path
+pub fn last_segment(path: &str) -> &str {
+    path.rsplit('.').next().unwrap_or(path)
+}
+
+/// Every local name introduced by a binding pattern, including destructured and defaulted ones
+pub fn binding_names(pattern: &BindingPattern, names: &mut Vec<String>) {
+    match &pattern.kind {
+        BindingPatternKind::BindingIdentifier(ident) => names.push(ident.name.to_string()),
+        BindingPatternKind::ObjectPattern(obj_pattern) => {
+            for prop in &obj_pattern.properties {
+                binding_names(&prop.value, names);
+            }
+            if let Some(rest) = &obj_pattern.rest {
+                binding_names(&rest.argument, names);
+            }
+        }
+        BindingPatternKind::ArrayPattern(arr_pattern) => {
+            for elem in arr_pattern.elements.iter().flatten() {
+                binding_names(elem, names);
+            }
+            if let Some(rest) = &arr_pattern.rest {
+                binding_names(&rest.argument, names);
+            }
+        }
+        BindingPatternKind::AssignmentPattern(assign) => binding_names(&assign.left, names),
+    }
+}
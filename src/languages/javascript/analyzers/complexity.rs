@@ -1,14 +1,47 @@
 use super::Analyzer;
+use crate::cognitive::CognitiveScore;
+use crate::config::ComplexityConfig;
 use crate::types::{CodeIssue, Category, Severity};
 use oxc_ast::ast::*;
-use oxc_span::Span;
+use oxc_span::{GetSpan, Span};
 use std::path::Path;
 
-pub struct ComplexityAnalyzer;
+pub struct ComplexityAnalyzer {
+    cognitive_threshold: usize,
+}
+
+/// The function being scored for cognitive complexity
+struct CognitiveContext<'a> {
+    name: &'a str,
+    source_code: &'a str,
+    score: CognitiveScore,
+}
+
+impl CognitiveContext<'_> {
+    fn line(&self, span: Span) -> usize {
+        ComplexityAnalyzer::get_line_column(self.source_code, span).0
+    }
+
+    fn nested(&mut self, construct: &str, span: Span, nesting: usize) {
+        let line = self.line(span);
+        self.score.nested(construct, line, nesting);
+    }
+
+    fn flat(&mut self, construct: &str, span: Span) {
+        let line = self.line(span);
+        self.score.flat(construct, line);
+    }
+}
 
 impl ComplexityAnalyzer {
     pub fn new() -> Self {
-        Self
+        Self::with_config(&ComplexityConfig::default())
+    }
+
+    pub fn with_config(config: &ComplexityConfig) -> Self {
+        Self {
+            cognitive_threshold: config.cognitive_threshold,
+        }
     }
 
     fn get_line_column(source_code: &str, span: Span) -> (usize, usize) {
@@ -117,42 +150,46 @@ impl ComplexityAnalyzer {
             }
             Statement::FunctionDeclaration(func) => {
                 let func_name = func.id.as_ref().map_or("<anonymous>", |id| id.name.as_str());
-
-                if let Some(body) = &func.body {
-                    // Count complexity of the function
-                    let complexity = self.calculate_complexity(&body.statements);
-
-                    if complexity > 10 {
-                        self.add_issue(
-                            issues,
-                            file_path,
-                            source_code,
-                            func.span,
-                            format!("Fungsi '{}' memiliki cyclomatic complexity tinggi ({}). Pertimbangkan refactoring", func_name, complexity),
-                            "complexity".to_string(),
-                            Severity::Warning,
-                        );
-                    }
-
-                    // Count parameters
-                    if func.params.items.len() > 5 {
-                        self.add_issue(
-                            issues,
-                            file_path,
-                            source_code,
-                            func.span,
-                            format!("Fungsi '{}' memiliki terlalu banyak parameter ({}). Pertimbangkan menggunakan parameter objek", func_name, func.params.items.len()),
-                            "max-params".to_string(),
-                            Severity::Suggestion,
-                        );
-                    }
-
-                    // Analyze function body
-                    for stmt in &body.statements {
-                        self.analyze_statement(issues, stmt, file_path, source_code, 0);
-                    }
+                self.analyze_function(issues, func_name, func, file_path, source_code);
+            }
+            Statement::VariableDeclaration(var_decl) => {
+                self.analyze_variable_declaration(issues, var_decl, file_path, source_code);
+            }
+            Statement::ClassDeclaration(class) => {
+                self.analyze_class(issues, class, file_path, source_code);
+            }
+            Statement::ReturnStatement(ret_stmt) => {
+                if let Some(argument) = &ret_stmt.argument {
+                    self.analyze_expression(issues, argument, file_path, source_code);
                 }
             }
+            Statement::ExportNamedDeclaration(export) => match &export.declaration {
+                Some(Declaration::FunctionDeclaration(func)) => {
+                    let func_name = func.id.as_ref().map_or("<anonymous>", |id| id.name.as_str());
+                    self.analyze_function(issues, func_name, func, file_path, source_code);
+                }
+                Some(Declaration::VariableDeclaration(var_decl)) => {
+                    self.analyze_variable_declaration(issues, var_decl, file_path, source_code);
+                }
+                Some(Declaration::ClassDeclaration(class)) => {
+                    self.analyze_class(issues, class, file_path, source_code);
+                }
+                _ => {}
+            },
+            Statement::ExportDefaultDeclaration(export) => match &export.declaration {
+                ExportDefaultDeclarationKind::FunctionDeclaration(func) => {
+                    let func_name = func.id.as_ref().map_or("default", |id| id.name.as_str());
+                    self.analyze_function(issues, func_name, func, file_path, source_code);
+                }
+                ExportDefaultDeclarationKind::ClassDeclaration(class) => {
+                    self.analyze_class(issues, class, file_path, source_code);
+                }
+                declaration => {
+                    if let Some(expr) = declaration.as_expression() {
+                        self.analyze_expression(issues, expr, file_path, source_code);
+                    }
+                }
+            },
             Statement::ExpressionStatement(expr_stmt) => {
                 self.analyze_expression(issues, &expr_stmt.expression, file_path, source_code);
             }
@@ -226,6 +263,17 @@ impl ComplexityAnalyzer {
                 self.analyze_statement(issues, &do_while_stmt.body, file_path, source_code, new_depth);
                 self.analyze_expression(issues, &do_while_stmt.test, file_path, source_code);
             }
+            Statement::ForOfStatement(for_of) => {
+                self.analyze_expression(issues, &for_of.right, file_path, source_code);
+                self.analyze_statement(issues, &for_of.body, file_path, source_code, depth + 1);
+            }
+            Statement::ForInStatement(for_in) => {
+                self.analyze_expression(issues, &for_in.right, file_path, source_code);
+                self.analyze_statement(issues, &for_in.body, file_path, source_code, depth + 1);
+            }
+            Statement::LabeledStatement(labeled) => {
+                self.analyze_statement(issues, &labeled.body, file_path, source_code, depth);
+            }
             Statement::TryStatement(try_stmt) => {
                 for stmt in &try_stmt.block.body {
                     self.analyze_statement(issues, stmt, file_path, source_code, depth);
@@ -303,30 +351,340 @@ impl ComplexityAnalyzer {
         }
     }
 
+    fn analyze_variable_declaration(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        var_decl: &VariableDeclaration,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        for var in &var_decl.declarations {
+            let Some(init) = &var.init else {
+                continue;
+            };
+            // `const handler = () => {}` takes the variable's name
+            match (&var.id.kind, init) {
+                (BindingPatternKind::BindingIdentifier(id), Expression::ArrowFunctionExpression(arrow)) => {
+                    self.analyze_arrow(issues, id.name.as_str(), arrow, file_path, source_code);
+                }
+                (BindingPatternKind::BindingIdentifier(id), Expression::FunctionExpression(func)) => {
+                    let func_name = func.id.as_ref().map_or(id.name.as_str(), |id| id.name.as_str());
+                    self.analyze_function(issues, func_name, func, file_path, source_code);
+                }
+                _ => self.analyze_expression(issues, init, file_path, source_code),
+            }
+        }
+    }
+
+    fn analyze_class(&self, issues: &mut Vec<CodeIssue>, class: &Class, file_path: &Path, source_code: &str) {
+        for element in &class.body.body {
+            match element {
+                ClassElement::MethodDefinition(method) => {
+                    let name = method.key.static_name();
+                    let func_name = name.as_deref().unwrap_or("<computed>");
+                    self.analyze_function(issues, func_name, &method.value, file_path, source_code);
+                }
+                ClassElement::PropertyDefinition(prop) => {
+                    if let Some(value) = &prop.value {
+                        self.analyze_property_value(issues, &prop.key, value, file_path, source_code);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Object and class property values that are functions take the property's name
+    fn analyze_property_value(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        key: &PropertyKey,
+        value: &Expression,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        let name = key.static_name();
+        let func_name = name.as_deref().unwrap_or("<computed>");
+        match value {
+            Expression::ArrowFunctionExpression(arrow) => {
+                self.analyze_arrow(issues, func_name, arrow, file_path, source_code);
+            }
+            Expression::FunctionExpression(func) => {
+                self.analyze_function(issues, func_name, func, file_path, source_code);
+            }
+            _ => self.analyze_expression(issues, value, file_path, source_code),
+        }
+    }
+
+    fn analyze_function(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        func_name: &str,
+        func: &Function,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        if let Some(body) = &func.body {
+            self.check_function(issues, func_name, func.span, &func.params, body, file_path, source_code);
+        }
+    }
+
+    fn analyze_arrow(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        func_name: &str,
+        arrow: &ArrowFunctionExpression,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        self.check_function(issues, func_name, arrow.span, &arrow.params, &arrow.body, file_path, source_code);
+    }
+
+    /// Size and complexity checks shared by every function-like node
+    #[allow(clippy::too_many_arguments)]
+    fn check_function(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        func_name: &str,
+        span: Span,
+        params: &FormalParameters,
+        body: &FunctionBody,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        // Count complexity of the function
+        let complexity = self.calculate_complexity(&body.statements);
+
+        if complexity > 10 {
+            self.add_issue(
+                issues,
+                file_path,
+                source_code,
+                span,
+                format!("Fungsi '{}' memiliki cyclomatic complexity tinggi ({}). Pertimbangkan refactoring", func_name, complexity),
+                "complexity".to_string(),
+                Severity::Warning,
+            );
+        }
+
+        // Count parameters
+        if params.items.len() > 5 {
+            self.add_issue(
+                issues,
+                file_path,
+                source_code,
+                span,
+                format!("Fungsi '{}' memiliki terlalu banyak parameter ({}). Pertimbangkan menggunakan parameter objek", func_name, params.items.len()),
+                "max-params".to_string(),
+                Severity::Suggestion,
+            );
+        }
+
+        let score = self.cognitive_score(func_name, body, source_code);
+        if score.total() > self.cognitive_threshold {
+            let (line, column) = Self::get_line_column(source_code, span);
+            let mut issue = score.issue(file_path, func_name, self.cognitive_threshold, line, column, None);
+            issue.message = format!(
+                "Fungsi '{}' memiliki cognitive complexity {} (maksimal {}). Kontributor terbesar: {}. Pertimbangkan memecah fungsi",
+                func_name,
+                score.total(),
+                self.cognitive_threshold,
+                score.top_contributors("baris", "baris")
+            );
+            issues.push(issue);
+        }
+
+        // Analyze function body
+        for stmt in &body.statements {
+            self.analyze_statement(issues, stmt, file_path, source_code, 0);
+        }
+    }
+
     fn analyze_expression(
         &self,
-        _issues: &mut Vec<CodeIssue>,
+        issues: &mut Vec<CodeIssue>,
         expr: &Expression,
         file_path: &Path,
         source_code: &str,
     ) {
-        // Traverse expressions but don't add issues here
         match expr {
             Expression::BinaryExpression(bin_expr) => {
-                // Check for complex conditions
-                self.check_complex_condition(bin_expr, file_path, source_code);
-                self.analyze_expression(_issues, &bin_expr.left, file_path, source_code);
-                self.analyze_expression(_issues, &bin_expr.right, file_path, source_code);
+                self.analyze_expression(issues, &bin_expr.left, file_path, source_code);
+                self.analyze_expression(issues, &bin_expr.right, file_path, source_code);
             }
             Expression::LogicalExpression(logical_expr) => {
-                self.analyze_expression(_issues, &logical_expr.left, file_path, source_code);
-                self.analyze_expression(_issues, &logical_expr.right, file_path, source_code);
+                self.analyze_expression(issues, &logical_expr.left, file_path, source_code);
+                self.analyze_expression(issues, &logical_expr.right, file_path, source_code);
             }
             Expression::CallExpression(call_expr) => {
-                self.analyze_expression(_issues, &call_expr.callee, file_path, source_code);
+                self.analyze_expression(issues, &call_expr.callee, file_path, source_code);
                 for arg in &call_expr.arguments {
                     if let Some(expr) = arg.as_expression() {
-                        self.analyze_expression(_issues, expr, file_path, source_code);
+                        self.analyze_expression(issues, expr, file_path, source_code);
+                    }
+                }
+            }
+            Expression::NewExpression(new_expr) => {
+                for arg in &new_expr.arguments {
+                    if let Some(expr) = arg.as_expression() {
+                        self.analyze_expression(issues, expr, file_path, source_code);
+                    }
+                }
+            }
+            Expression::ArrowFunctionExpression(arrow) => {
+                self.analyze_arrow(issues, "<anonymous>", arrow, file_path, source_code);
+            }
+            Expression::FunctionExpression(func) => {
+                let func_name = func.id.as_ref().map_or("<anonymous>", |id| id.name.as_str());
+                self.analyze_function(issues, func_name, func, file_path, source_code);
+            }
+            Expression::ClassExpression(class) => {
+                self.analyze_class(issues, class, file_path, source_code);
+            }
+            Expression::ObjectExpression(obj_expr) => {
+                for prop in &obj_expr.properties {
+                    if let ObjectPropertyKind::ObjectProperty(prop) = prop {
+                        self.analyze_property_value(issues, &prop.key, &prop.value, file_path, source_code);
+                    }
+                }
+            }
+            Expression::ArrayExpression(array) => {
+                for elem in &array.elements {
+                    if let Some(expr) = elem.as_expression() {
+                        self.analyze_expression(issues, expr, file_path, source_code);
+                    }
+                }
+            }
+            Expression::AssignmentExpression(assign_expr) => {
+                self.analyze_expression(issues, &assign_expr.right, file_path, source_code);
+            }
+            Expression::ConditionalExpression(cond) => {
+                self.analyze_expression(issues, &cond.test, file_path, source_code);
+                self.analyze_expression(issues, &cond.consequent, file_path, source_code);
+                self.analyze_expression(issues, &cond.alternate, file_path, source_code);
+            }
+            Expression::ParenthesizedExpression(paren) => {
+                self.analyze_expression(issues, &paren.expression, file_path, source_code);
+            }
+            Expression::AwaitExpression(await_expr) => {
+                self.analyze_expression(issues, &await_expr.argument, file_path, source_code);
+            }
+            Expression::SequenceExpression(seq) => {
+                for expr in &seq.expressions {
+                    self.analyze_expression(issues, expr, file_path, source_code);
+                }
+            }
+            Expression::StaticMemberExpression(member) => {
+                self.analyze_expression(issues, &member.object, file_path, source_code);
+            }
+            Expression::TSAsExpression(as_expr) => {
+                self.analyze_expression(issues, &as_expr.expression, file_path, source_code);
+            }
+            _ => {}
+        }
+    }
+
+    /// Cognitive complexity of a function body, not counting the functions nested in it
+    pub fn cognitive_score(&self, func_name: &str, body: &FunctionBody, source_code: &str) -> CognitiveScore {
+        let mut context = CognitiveContext {
+            name: func_name,
+            source_code,
+            score: CognitiveScore::default(),
+        };
+        for stmt in &body.statements {
+            self.cognitive_statement(&mut context, stmt, 0);
+        }
+        context.score
+    }
+
+    /// Cognitive complexity of a statement; nested functions are scored on their own
+    fn cognitive_statement(&self, context: &mut CognitiveContext, stmt: &Statement, nesting: usize) {
+        match stmt {
+            Statement::IfStatement(if_stmt) => {
+                context.nested("if", if_stmt.span, nesting);
+                self.cognitive_if(context, if_stmt, nesting);
+            }
+            Statement::ForStatement(for_stmt) => {
+                context.nested("for", for_stmt.span, nesting);
+                if let Some(test) = &for_stmt.test {
+                    self.cognitive_expression(context, test, nesting + 1, None);
+                }
+                self.cognitive_statement(context, &for_stmt.body, nesting + 1);
+            }
+            Statement::ForOfStatement(for_of) => {
+                context.nested("for", for_of.span, nesting);
+                self.cognitive_expression(context, &for_of.right, nesting, None);
+                self.cognitive_statement(context, &for_of.body, nesting + 1);
+            }
+            Statement::ForInStatement(for_in) => {
+                context.nested("for", for_in.span, nesting);
+                self.cognitive_expression(context, &for_in.right, nesting, None);
+                self.cognitive_statement(context, &for_in.body, nesting + 1);
+            }
+            Statement::WhileStatement(while_stmt) => {
+                context.nested("while", while_stmt.span, nesting);
+                self.cognitive_expression(context, &while_stmt.test, nesting + 1, None);
+                self.cognitive_statement(context, &while_stmt.body, nesting + 1);
+            }
+            Statement::DoWhileStatement(do_while) => {
+                context.nested("do-while", do_while.span, nesting);
+                self.cognitive_statement(context, &do_while.body, nesting + 1);
+                self.cognitive_expression(context, &do_while.test, nesting + 1, None);
+            }
+            Statement::SwitchStatement(switch_stmt) => {
+                context.nested("switch", switch_stmt.span, nesting);
+                self.cognitive_expression(context, &switch_stmt.discriminant, nesting, None);
+                for case in &switch_stmt.cases {
+                    for stmt in &case.consequent {
+                        self.cognitive_statement(context, stmt, nesting + 1);
+                    }
+                }
+            }
+            Statement::TryStatement(try_stmt) => {
+                for stmt in &try_stmt.block.body {
+                    self.cognitive_statement(context, stmt, nesting);
+                }
+                if let Some(handler) = &try_stmt.handler {
+                    context.nested("catch", handler.span, nesting);
+                    for stmt in &handler.body.body {
+                        self.cognitive_statement(context, stmt, nesting + 1);
+                    }
+                }
+                if let Some(finalizer) = &try_stmt.finalizer {
+                    for stmt in &finalizer.body {
+                        self.cognitive_statement(context, stmt, nesting);
+                    }
+                }
+            }
+            Statement::BreakStatement(break_stmt) if break_stmt.label.is_some() => {
+                context.flat("lompatan berlabel", break_stmt.span);
+            }
+            Statement::ContinueStatement(continue_stmt) if continue_stmt.label.is_some() => {
+                context.flat("lompatan berlabel", continue_stmt.span);
+            }
+            Statement::LabeledStatement(labeled) => {
+                self.cognitive_statement(context, &labeled.body, nesting);
+            }
+            Statement::BlockStatement(block) => {
+                for stmt in &block.body {
+                    self.cognitive_statement(context, stmt, nesting);
+                }
+            }
+            Statement::ExpressionStatement(expr_stmt) => {
+                self.cognitive_expression(context, &expr_stmt.expression, nesting, None);
+            }
+            Statement::ReturnStatement(ret_stmt) => {
+                if let Some(argument) = &ret_stmt.argument {
+                    self.cognitive_expression(context, argument, nesting, None);
+                }
+            }
+            Statement::ThrowStatement(throw_stmt) => {
+                self.cognitive_expression(context, &throw_stmt.argument, nesting, None);
+            }
+            Statement::VariableDeclaration(var_decl) => {
+                for var in &var_decl.declarations {
+                    if let Some(init) = &var.init {
+                        self.cognitive_expression(context, init, nesting, None);
                     }
                 }
             }
@@ -334,13 +692,125 @@ impl ComplexityAnalyzer {
         }
     }
 
-    fn check_complex_condition(
+    /// `else if` chains stay at the nesting of the first `if`, a plain `else` adds one
+    fn cognitive_if(&self, context: &mut CognitiveContext, if_stmt: &IfStatement, nesting: usize) {
+        self.cognitive_expression(context, &if_stmt.test, nesting + 1, None);
+        self.cognitive_statement(context, &if_stmt.consequent, nesting + 1);
+
+        match &if_stmt.alternate {
+            Some(Statement::IfStatement(else_if)) => {
+                context.flat("else if", else_if.span);
+                self.cognitive_if(context, else_if, nesting);
+            }
+            Some(alternate) => {
+                context.flat("else", alternate.span());
+                self.cognitive_statement(context, alternate, nesting + 1);
+            }
+            None => {}
+        }
+    }
+
+    /// `parent_operator` is the operator of the enclosing logical expression, so `a && b && c` counts once
+    fn cognitive_expression(
         &self,
-        _bin_expr: &BinaryExpression,
-        _file_path: &Path,
-        _source_code: &str,
+        context: &mut CognitiveContext,
+        expr: &Expression,
+        nesting: usize,
+        parent_operator: Option<LogicalOperator>,
     ) {
-        // This is a placeholder - we could add more sophisticated condition analysis here
-        // For now, we just track depth through the recursion
+        match expr {
+            Expression::LogicalExpression(logical) => {
+                if parent_operator != Some(logical.operator) {
+                    context.flat(logical.operator.as_str(), logical.span);
+                }
+                self.cognitive_expression(context, &logical.left, nesting, Some(logical.operator));
+                self.cognitive_expression(context, &logical.right, nesting, Some(logical.operator));
+            }
+            Expression::ConditionalExpression(cond) => {
+                context.nested("ternary", cond.span, nesting);
+                self.cognitive_expression(context, &cond.test, nesting + 1, None);
+                self.cognitive_expression(context, &cond.consequent, nesting + 1, None);
+                self.cognitive_expression(context, &cond.alternate, nesting + 1, None);
+            }
+            Expression::CallExpression(call_expr) => {
+                let recursive = match &call_expr.callee {
+                    Expression::Identifier(ident) => ident.name == context.name,
+                    Expression::StaticMemberExpression(member) => {
+                        matches!(member.object, Expression::ThisExpression(_)) && member.property.name == context.name
+                    }
+                    _ => false,
+                };
+                if recursive {
+                    context.flat("rekursi", call_expr.span);
+                }
+                self.cognitive_expression(context, &call_expr.callee, nesting, None);
+                for arg in &call_expr.arguments {
+                    if let Some(expr) = arg.as_expression() {
+                        self.cognitive_expression(context, expr, nesting, None);
+                    }
+                }
+            }
+            Expression::NewExpression(new_expr) => {
+                for arg in &new_expr.arguments {
+                    if let Some(expr) = arg.as_expression() {
+                        self.cognitive_expression(context, expr, nesting, None);
+                    }
+                }
+            }
+            Expression::BinaryExpression(bin_expr) => {
+                self.cognitive_expression(context, &bin_expr.left, nesting, None);
+                self.cognitive_expression(context, &bin_expr.right, nesting, None);
+            }
+            Expression::AssignmentExpression(assign_expr) => {
+                self.cognitive_expression(context, &assign_expr.right, nesting, None);
+            }
+            Expression::UnaryExpression(unary) => {
+                self.cognitive_expression(context, &unary.argument, nesting, None);
+            }
+            Expression::AwaitExpression(await_expr) => {
+                self.cognitive_expression(context, &await_expr.argument, nesting, None);
+            }
+            Expression::ParenthesizedExpression(paren) => {
+                self.cognitive_expression(context, &paren.expression, nesting, parent_operator);
+            }
+            Expression::SequenceExpression(seq) => {
+                for expr in &seq.expressions {
+                    self.cognitive_expression(context, expr, nesting, None);
+                }
+            }
+            Expression::StaticMemberExpression(member) => {
+                self.cognitive_expression(context, &member.object, nesting, None);
+            }
+            Expression::ComputedMemberExpression(member) => {
+                self.cognitive_expression(context, &member.object, nesting, None);
+                self.cognitive_expression(context, &member.expression, nesting, None);
+            }
+            Expression::ArrayExpression(array) => {
+                for elem in &array.elements {
+                    if let Some(expr) = elem.as_expression() {
+                        self.cognitive_expression(context, expr, nesting, None);
+                    }
+                }
+            }
+            Expression::ObjectExpression(obj_expr) => {
+                for prop in &obj_expr.properties {
+                    if let ObjectPropertyKind::ObjectProperty(prop) = prop {
+                        self.cognitive_expression(context, &prop.value, nesting, None);
+                    }
+                }
+            }
+            Expression::TemplateLiteral(template) => {
+                for expr in &template.expressions {
+                    self.cognitive_expression(context, expr, nesting, None);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Default for ComplexityAnalyzer {
+    fn default() -> Self {
+        Self::new()
     }
 }
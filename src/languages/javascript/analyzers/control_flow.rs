@@ -0,0 +1,484 @@
+use super::Analyzer;
+use crate::cfg::{CfgBuilder, FunctionInfo, Site};
+use crate::types::CodeIssue;
+use oxc_ast::ast::*;
+use oxc_span::{GetSpan, Span};
+use std::path::Path;
+
+/// Builds a control flow graph for the top level and for every function, and reports unreachable code,
+/// constant conditions, switch fallthrough, inconsistent returns and loops that never exit
+#[derive(Default)]
+pub struct ControlFlowAnalyzer;
+
+impl ControlFlowAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Analyzer for ControlFlowAnalyzer {
+    fn analyze(&self, program: &Program, file_path: &Path, source_code: &str) -> Vec<CodeIssue> {
+        let mut walker = FlowWalker {
+            source_code,
+            file_path,
+            issues: Vec::new(),
+        };
+
+        let mut cfg = CfgBuilder::new();
+        let mut flow = FunctionFlow { cfg: &mut cfg, generator: false };
+        for stmt in &program.body {
+            walker.statement(&mut flow, stmt, None);
+        }
+        let mut issues = cfg.finish(file_path, None);
+
+        issues.append(&mut walker.issues);
+        issues.sort_by_key(|issue| (issue.line, issue.column));
+        issues
+    }
+}
+
+/// The graph of the function being walked
+struct FunctionFlow<'c> {
+    cfg: &'c mut CfgBuilder,
+    /// Generators may loop forever on purpose, yielding each time around
+    generator: bool,
+}
+
+struct FlowWalker<'s> {
+    source_code: &'s str,
+    file_path: &'s Path,
+    issues: Vec<CodeIssue>,
+}
+
+impl<'s> FlowWalker<'s> {
+    fn site(&self, span: Span) -> Site {
+        let position = |offset: u32| {
+            let before = &self.source_code[..offset as usize];
+            let line = before.matches('\n').count() + 1;
+            let column = offset as usize - before.rfind('\n').map_or(0, |newline| newline + 1) + 1;
+            (line, column)
+        };
+        let (line, column) = position(span.start);
+        let (end_line, end_column) = position(span.end);
+        Site {
+            line,
+            column,
+            end_line,
+            end_column,
+        }
+    }
+
+    fn function(&mut self, name: &str, span: Span, body: &FunctionBody, generator: bool, check_returns: bool) {
+        let mut cfg = CfgBuilder::new();
+        let mut flow = FunctionFlow { cfg: &mut cfg, generator };
+        for stmt in &body.statements {
+            self.statement(&mut flow, stmt, None);
+        }
+
+        let function = check_returns.then(|| FunctionInfo {
+            name: name.to_string(),
+            site: self.site(span),
+        });
+        let issues = cfg.finish(self.file_path, function.as_ref());
+        self.issues.extend(issues);
+    }
+
+    fn function_declaration(&mut self, fallback_name: &str, func: &Function) {
+        let name = func.id.as_ref().map_or(fallback_name, |id| id.name.as_str());
+        if let Some(body) = &func.body {
+            self.function(name, func.span, body, func.generator, true);
+        }
+    }
+
+    fn arrow(&mut self, name: &str, arrow: &ArrowFunctionExpression) {
+        // `x => x * 2` has no statements that could disagree about returning
+        self.function(name, arrow.span, &arrow.body, false, !arrow.expression);
+    }
+
+    /// Functions assigned to a variable or property take its name
+    fn named_expression(&mut self, name: &str, expr: &Expression) {
+        match expr {
+            Expression::ArrowFunctionExpression(arrow) => self.arrow(name, arrow),
+            Expression::FunctionExpression(func) => self.function_declaration(name, func),
+            _ => self.expression(expr),
+        }
+    }
+
+    fn class(&mut self, class: &Class) {
+        for element in &class.body.body {
+            match element {
+                ClassElement::MethodDefinition(method) => {
+                    let name = method.key.static_name();
+                    let name = name.as_deref().unwrap_or("<computed>");
+                    // Constructors and setters have no result to be consistent about
+                    if matches!(method.kind, MethodDefinitionKind::Constructor | MethodDefinitionKind::Set) {
+                        if let Some(body) = &method.value.body {
+                            self.function(name, method.value.span, body, false, false);
+                        }
+                    } else {
+                        self.function_declaration(name, &method.value);
+                    }
+                }
+                ClassElement::PropertyDefinition(prop) => {
+                    if let Some(value) = &prop.value {
+                        let name = prop.key.static_name();
+                        self.named_expression(name.as_deref().unwrap_or("<computed>"), value);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Walk a statement into the graph. `label` is set for the body of a labeled statement.
+    fn statement(&mut self, flow: &mut FunctionFlow, stmt: &Statement, label: Option<&str>) {
+        match stmt {
+            Statement::BlockStatement(block) if label.is_none() => {
+                for stmt in &block.body {
+                    self.statement(flow, stmt, None);
+                }
+                return;
+            }
+            Statement::LabeledStatement(labeled) => {
+                self.statement(flow, &labeled.body, Some(labeled.label.name.as_str()));
+                return;
+            }
+            Statement::EmptyStatement(_) => return,
+            // Hoisted, so never unreachable
+            Statement::FunctionDeclaration(func) => {
+                self.function_declaration("<anonymous>", func);
+                return;
+            }
+            _ => {}
+        }
+
+        let span = stmt.span();
+        let id = flow.cfg.statement(self.site(span));
+        flow.cfg.enter(id);
+
+        match stmt {
+            Statement::BlockStatement(block) => {
+                // A labeled block that `break label` can leave
+                flow.cfg.begin_switch(label.map(String::from), self.site(span));
+                for stmt in &block.body {
+                    self.statement(flow, stmt, None);
+                }
+                flow.cfg.end_switch();
+            }
+            Statement::VariableDeclaration(var_decl) => self.variable_declaration(var_decl),
+            Statement::ClassDeclaration(class) => self.class(class),
+            Statement::ExpressionStatement(expr_stmt) => self.expression(&expr_stmt.expression),
+            Statement::ReturnStatement(ret_stmt) => {
+                if let Some(argument) = &ret_stmt.argument {
+                    self.expression(argument);
+                }
+                flow.cfg.jump_return(ret_stmt.argument.is_some(), self.site(span));
+            }
+            Statement::ThrowStatement(throw_stmt) => {
+                self.expression(&throw_stmt.argument);
+                flow.cfg.jump_throw();
+            }
+            Statement::BreakStatement(break_stmt) => {
+                flow.cfg.jump_break(break_stmt.label.as_ref().map(|label| label.name.as_str()));
+            }
+            Statement::ContinueStatement(continue_stmt) => {
+                flow.cfg.jump_continue(continue_stmt.label.as_ref().map(|label| label.name.as_str()));
+            }
+            Statement::IfStatement(if_stmt) => {
+                self.expression(&if_stmt.test);
+                if let Some(value) = constant(&if_stmt.test) {
+                    flow.cfg.constant_condition(self.site(if_stmt.test.span()), value, false);
+                }
+
+                let start = flow.cfg.current();
+                let consequent = flow.cfg.fork();
+                flow.cfg.start(consequent);
+                self.statement(flow, &if_stmt.consequent, None);
+                let consequent_end = flow.cfg.current();
+
+                flow.cfg.start(start);
+                let alternate = flow.cfg.fork();
+                flow.cfg.start(alternate);
+                if let Some(alternate) = &if_stmt.alternate {
+                    self.statement(flow, alternate, None);
+                }
+                let alternate_end = flow.cfg.current();
+                flow.cfg.join(&[consequent_end, alternate_end]);
+            }
+            Statement::WhileStatement(while_stmt) => {
+                self.expression(&while_stmt.test);
+                self.test_first_loop(flow, Some(&while_stmt.test), &while_stmt.body, label, span);
+            }
+            Statement::ForStatement(for_stmt) => {
+                if let Some(ForStatementInit::VariableDeclaration(var_decl)) = &for_stmt.init {
+                    self.variable_declaration(var_decl);
+                }
+                self.test_first_loop(flow, for_stmt.test.as_ref(), &for_stmt.body, label, span);
+            }
+            Statement::ForOfStatement(for_of) => {
+                self.expression(&for_of.right);
+                flow.cfg.begin_loop(label.map(String::from), self.site(span), false, true);
+                self.statement(flow, &for_of.body, None);
+                flow.cfg.end_loop();
+            }
+            Statement::ForInStatement(for_in) => {
+                self.expression(&for_in.right);
+                flow.cfg.begin_loop(label.map(String::from), self.site(span), false, true);
+                self.statement(flow, &for_in.body, None);
+                flow.cfg.end_loop();
+            }
+            Statement::DoWhileStatement(do_while) => {
+                // `do { ... } while (false)` is an idiom for a block that can be left with `break`
+                let endless = constant(&do_while.test) == Some(true) && !flow.generator;
+                flow.cfg.begin_loop(label.map(String::from), self.site(span), endless, false);
+                self.statement(flow, &do_while.body, None);
+                self.expression(&do_while.test);
+                flow.cfg.end_loop();
+            }
+            Statement::SwitchStatement(switch_stmt) => self.switch(flow, switch_stmt, label),
+            Statement::TryStatement(try_stmt) => {
+                // Returns, throws and jumps out of the block or the handler run the `finally` first
+                if try_stmt.finalizer.is_some() {
+                    flow.cfg.try_finally();
+                }
+                // Anything in the block may throw, so the handler is live whenever the try is
+                let handler = flow.cfg.fork();
+                if try_stmt.handler.is_some() {
+                    flow.cfg.begin_try(handler);
+                }
+                for stmt in &try_stmt.block.body {
+                    self.statement(flow, stmt, None);
+                }
+                if try_stmt.handler.is_some() {
+                    flow.cfg.end_try();
+                }
+
+                let mut ends = vec![flow.cfg.current()];
+                if let Some(catch) = &try_stmt.handler {
+                    flow.cfg.start(handler);
+                    for stmt in &catch.body.body {
+                        self.statement(flow, stmt, None);
+                    }
+                    ends.push(flow.cfg.current());
+                }
+                if let Some(finalizer) = &try_stmt.finalizer {
+                    flow.cfg.begin_finally(&ends);
+                    for stmt in &finalizer.body {
+                        self.statement(flow, stmt, None);
+                    }
+                    flow.cfg.end_finally();
+                } else {
+                    flow.cfg.join(&ends);
+                }
+            }
+            Statement::ExportNamedDeclaration(export) => match &export.declaration {
+                Some(Declaration::VariableDeclaration(var_decl)) => self.variable_declaration(var_decl),
+                Some(Declaration::FunctionDeclaration(func)) => self.function_declaration("<anonymous>", func),
+                Some(Declaration::ClassDeclaration(class)) => self.class(class),
+                _ => {}
+            },
+            Statement::ExportDefaultDeclaration(export) => match &export.declaration {
+                ExportDefaultDeclarationKind::FunctionDeclaration(func) => self.function_declaration("default", func),
+                ExportDefaultDeclarationKind::ClassDeclaration(class) => self.class(class),
+                declaration => {
+                    if let Some(expr) = declaration.as_expression() {
+                        self.named_expression("default", expr);
+                    }
+                }
+            },
+            _ => {}
+        }
+
+        flow.cfg.leave();
+    }
+
+    fn test_first_loop(
+        &mut self,
+        flow: &mut FunctionFlow,
+        test: Option<&Expression>,
+        body: &Statement,
+        label: Option<&str>,
+        span: Span,
+    ) {
+        let value = match test {
+            Some(test) => constant(test),
+            None => Some(true),
+        };
+        if let (Some(test), Some(false)) = (test, value) {
+            flow.cfg.constant_condition(self.site(test.span()), false, true);
+        }
+
+        let endless = value == Some(true) && !flow.generator;
+        flow.cfg.begin_loop(label.map(String::from), self.site(span), endless, true);
+        self.statement(flow, body, None);
+        flow.cfg.end_loop();
+    }
+
+    /// Each case is entered from the discriminant and from the end of the case before it
+    fn switch(&mut self, flow: &mut FunctionFlow, switch_stmt: &SwitchStatement, label: Option<&str>) {
+        self.expression(&switch_stmt.discriminant);
+        let start = flow.cfg.current();
+        let after = flow.cfg.begin_switch(label.map(String::from), self.site(switch_stmt.span));
+        if !switch_stmt.cases.iter().any(|case| case.test.is_none()) {
+            flow.cfg.edge(start, after);
+        }
+
+        let mut previous: Option<&SwitchCase> = None;
+        for case in &switch_stmt.cases {
+            if let Some(test) = &case.test {
+                self.expression(test);
+            }
+            let block = flow.cfg.new_block();
+            flow.cfg.edge(start, block);
+            if let Some(previous) = previous {
+                let end = flow.cfg.current();
+                flow.cfg.edge(end, block);
+                if !previous.consequent.is_empty() && !self.marked_fallthrough(previous, case) {
+                    flow.cfg.fallthrough(end, self.site(previous.span));
+                }
+            }
+
+            flow.cfg.start(block);
+            for stmt in &case.consequent {
+                self.statement(flow, stmt, None);
+            }
+            previous = Some(case);
+        }
+
+        flow.cfg.end_switch();
+    }
+
+    /// A `// falls through` comment between two cases makes the fallthrough intended
+    fn marked_fallthrough(&self, case: &SwitchCase, next: &SwitchCase) -> bool {
+        let start = case.consequent.last().map_or(case.span.start, |stmt| stmt.span().end) as usize;
+        let between = self.source_code[start..next.span.start as usize].to_lowercase();
+        between.contains("falls through") || between.contains("fallthrough") || between.contains("fall through")
+    }
+
+    fn variable_declaration(&mut self, var_decl: &VariableDeclaration) {
+        for var in &var_decl.declarations {
+            let Some(init) = &var.init else {
+                continue;
+            };
+            match &var.id.kind {
+                BindingPatternKind::BindingIdentifier(id) => self.named_expression(id.name.as_str(), init),
+                _ => self.expression(init),
+            }
+        }
+    }
+
+    /// Only looks for the functions nested in an expression
+    fn expression(&mut self, expr: &Expression) {
+        match expr {
+            Expression::ArrowFunctionExpression(arrow) => self.arrow("<anonymous>", arrow),
+            Expression::FunctionExpression(func) => self.function_declaration("<anonymous>", func),
+            Expression::ClassExpression(class) => self.class(class),
+            Expression::LogicalExpression(logical) => {
+                self.expression(&logical.left);
+                self.expression(&logical.right);
+            }
+            Expression::ConditionalExpression(cond) => {
+                self.expression(&cond.test);
+                self.expression(&cond.consequent);
+                self.expression(&cond.alternate);
+            }
+            Expression::CallExpression(call_expr) => {
+                self.expression(&call_expr.callee);
+                for arg in &call_expr.arguments {
+                    if let Some(expr) = arg.as_expression() {
+                        self.expression(expr);
+                    }
+                }
+            }
+            Expression::NewExpression(new_expr) => {
+                for arg in &new_expr.arguments {
+                    if let Some(expr) = arg.as_expression() {
+                        self.expression(expr);
+                    }
+                }
+            }
+            Expression::BinaryExpression(bin_expr) => {
+                self.expression(&bin_expr.left);
+                self.expression(&bin_expr.right);
+            }
+            Expression::AssignmentExpression(assign_expr) => self.expression(&assign_expr.right),
+            Expression::UnaryExpression(unary) => self.expression(&unary.argument),
+            Expression::AwaitExpression(await_expr) => self.expression(&await_expr.argument),
+            Expression::ParenthesizedExpression(paren) => self.expression(&paren.expression),
+            Expression::SequenceExpression(seq) => {
+                for expr in &seq.expressions {
+                    self.expression(expr);
+                }
+            }
+            Expression::StaticMemberExpression(member) => self.expression(&member.object),
+            Expression::ComputedMemberExpression(member) => {
+                self.expression(&member.object);
+                self.expression(&member.expression);
+            }
+            Expression::ArrayExpression(array) => {
+                for elem in &array.elements {
+                    if let Some(expr) = elem.as_expression() {
+                        self.expression(expr);
+                    }
+                }
+            }
+            Expression::ObjectExpression(obj_expr) => {
+                for prop in &obj_expr.properties {
+                    if let ObjectPropertyKind::ObjectProperty(prop) = prop {
+                        let name = prop.key.static_name();
+                        self.named_expression(name.as_deref().unwrap_or("<computed>"), &prop.value);
+                    }
+                }
+            }
+            Expression::TemplateLiteral(template) => {
+                for expr in &template.expressions {
+                    self.expression(expr);
+                }
+            }
+            Expression::TSAsExpression(as_expr) => self.expression(&as_expr.expression),
+            Expression::TSNonNullExpression(non_null) => self.expression(&non_null.expression),
+            Expression::JSXElement(element) => self.jsx_element(element),
+            Expression::JSXFragment(fragment) => self.jsx_children(&fragment.children),
+            _ => {}
+        }
+    }
+
+    fn jsx_element(&mut self, element: &JSXElement) {
+        for attr in &element.opening_element.attributes {
+            if let JSXAttributeItem::Attribute(attr) = attr {
+                if let Some(JSXAttributeValue::ExpressionContainer(container)) = &attr.value {
+                    if let Some(expr) = container.expression.as_expression() {
+                        self.expression(expr);
+                    }
+                }
+            }
+        }
+        self.jsx_children(&element.children);
+    }
+
+    fn jsx_children(&mut self, children: &[JSXChild]) {
+        for child in children {
+            match child {
+                JSXChild::Element(element) => self.jsx_element(element),
+                JSXChild::Fragment(fragment) => self.jsx_children(&fragment.children),
+                JSXChild::ExpressionContainer(container) => {
+                    if let Some(expr) = container.expression.as_expression() {
+                        self.expression(expr);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Value of a condition that is a literal
+fn constant(expr: &Expression) -> Option<bool> {
+    match expr {
+        Expression::BooleanLiteral(literal) => Some(literal.value),
+        Expression::NumericLiteral(literal) => Some(literal.value != 0.0),
+        Expression::NullLiteral(_) => Some(false),
+        Expression::ParenthesizedExpression(paren) => constant(&paren.expression),
+        _ => None,
+    }
+}
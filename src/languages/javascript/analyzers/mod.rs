@@ -7,11 +7,34 @@ pub mod complexity;
 pub mod magic_numbers;
 pub mod naming;
 pub mod null_safety;
+pub mod bindings;
+pub mod taint;
+pub mod node_security;
+pub mod secrets;
+pub mod react;
+pub mod promises;
+pub mod control_flow;
+pub mod structural;
+pub mod plugins;
 
+use crate::config::Config;
 use crate::types::CodeIssue;
 use oxc_ast::ast::Program;
+use std::collections::HashSet;
 use std::path::Path;
 
+/// Single-node rules and the rule that replaces them when it reports the same line with more detail
+const SUPERSEDED_RULES: &[(&str, &str)] = &[
+    ("no-eval", "taint-code-injection"),
+    ("no-inner-html", "taint-xss"),
+    ("no-outer-html", "taint-xss"),
+    ("no-exec-non-literal", "taint-command-injection"),
+    ("no-sql-concat", "taint-sql-injection"),
+    ("no-fs-request-path", "taint-path-traversal"),
+    ("no-hardcoded-secrets", "hardcoded-credential"),
+    ("no-hardcoded-secrets", "high-entropy-secret"),
+];
+
 /// Trait for AST analyzers
 pub trait Analyzer {
     /// Analyze a module and return any issues found
@@ -29,20 +52,40 @@ pub struct Analyzers {
     pub magic_numbers: magic_numbers::MagicNumberAnalyzer,
     pub naming: naming::NamingAnalyzer,
     pub null_safety: null_safety::NullSafetyAnalyzer,
+    pub taint: taint::TaintAnalyzer,
+    pub node_security: node_security::NodeSecurityAnalyzer,
+    pub secrets: secrets::SecretsAnalyzer,
+    pub react: react::ReactAnalyzer,
+    pub promises: promises::PromiseAnalyzer,
+    pub control_flow: control_flow::ControlFlowAnalyzer,
+    pub structural: structural::StructuralAnalyzer,
+    pub plugins: plugins::PluginAnalyzer,
 }
 
 impl Analyzers {
     pub fn new() -> Self {
+        Self::with_config(&Config::default())
+    }
+
+    pub fn with_config(config: &Config) -> Self {
         Self {
             patterns: patterns::PatternAnalyzer::new(),
             typescript: typescript::TypeScriptAnalyzer::new(),
             security: security::SecurityAnalyzer::new(),
             best_practices: best_practices::BestPracticeAnalyzer::new(),
             unused: unused::UnusedAnalyzer::new(),
-            complexity: complexity::ComplexityAnalyzer::new(),
+            complexity: complexity::ComplexityAnalyzer::with_config(&config.complexity),
             magic_numbers: magic_numbers::MagicNumberAnalyzer::new(),
             naming: naming::NamingAnalyzer::new(),
             null_safety: null_safety::NullSafetyAnalyzer::new(),
+            taint: taint::TaintAnalyzer::with_config(&config.taint),
+            node_security: node_security::NodeSecurityAnalyzer::new(),
+            secrets: secrets::SecretsAnalyzer::with_config(&config.secrets),
+            react: react::ReactAnalyzer::new(),
+            promises: promises::PromiseAnalyzer::new(),
+            control_flow: control_flow::ControlFlowAnalyzer::new(),
+            structural: structural::StructuralAnalyzer::with_config(&config.javascript),
+            plugins: plugins::PluginAnalyzer::with_config(config),
         }
     }
 
@@ -58,11 +101,36 @@ impl Analyzers {
         issues.extend(self.magic_numbers.analyze(program, file_path, source_code));
         issues.extend(self.naming.analyze(program, file_path, source_code));
         issues.extend(self.null_safety.analyze(program, file_path, source_code));
+        issues.extend(self.taint.analyze(program, file_path, source_code));
+        issues.extend(self.node_security.analyze(program, file_path, source_code));
+        issues.extend(self.secrets.analyze(program, file_path, source_code));
+        issues.extend(self.react.analyze(program, file_path, source_code));
+        issues.extend(self.promises.analyze(program, file_path, source_code));
+        issues.extend(self.control_flow.analyze(program, file_path, source_code));
+        issues.extend(self.structural.analyze(program, file_path, source_code));
+        issues.extend(self.plugins.analyze(program, file_path, source_code));
 
+        drop_superseded(&mut issues);
         issues
     }
 }
 
+/// Drop issues that repeat a finding a taint flow or the secret scanner already reports
+fn drop_superseded(issues: &mut Vec<CodeIssue>) {
+    let replacements: HashSet<(String, usize, String)> = issues
+        .iter()
+        .filter(|issue| SUPERSEDED_RULES.iter().any(|(_, replacement)| *replacement == issue.rule))
+        .map(|issue| (issue.file_path.clone(), issue.line, issue.rule.clone()))
+        .collect();
+
+    issues.retain(|issue| {
+        !SUPERSEDED_RULES
+            .iter()
+            .filter(|(rule, _)| *rule == issue.rule)
+            .any(|(_, replacement)| replacements.contains(&(issue.file_path.clone(), issue.line, replacement.to_string())))
+    });
+}
+
 impl Default for Analyzers {
     fn default() -> Self {
         Self::new()
@@ -0,0 +1,700 @@
+use super::bindings::{last_segment, member_path, ModuleBindings};
+use super::taint::FS_PATH_FUNCTIONS;
+use super::Analyzer;
+use crate::types::{CodeIssue, Category, Severity};
+use oxc_ast::ast::*;
+use oxc_span::Span;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Request properties that carry user input in Express-style handlers
+const REQUEST_INPUTS: &[&str] = &[
+    "req.query", "req.body", "req.params", "req.headers", "req.cookies",
+    "request.query", "request.body", "request.params", "request.headers", "request.cookies",
+];
+
+const SQL_KEYWORDS: &[&str] = &["select ", "insert ", "update ", "delete ", "replace ", "merge "];
+
+const TOKEN_WORDS: &[&str] = &[
+    "token", "secret", "password", "nonce", "salt", "session", "otp", "apikey", "api_key", "csrf",
+];
+
+/// Names in the current function that hold request input or SQL built from strings
+#[derive(Debug, Clone, Default)]
+struct Scope {
+    request_vars: HashSet<String>,
+    sql_vars: HashSet<String>,
+}
+
+pub struct NodeSecurityAnalyzer;
+
+impl NodeSecurityAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn get_line_column(source_code: &str, span: Span) -> (usize, usize) {
+        let start = span.start as usize;
+        let before = &source_code[..start];
+        let line = before.lines().count();
+        let last_newline = before.rfind('\n').unwrap_or(0);
+        let column = start - last_newline;
+        (line, column)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn add_issue(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        file_path: &Path,
+        source_code: &str,
+        span: Span,
+        message: String,
+        rule: String,
+        severity: Severity,
+    ) {
+        let (line, column) = Self::get_line_column(source_code, span);
+        let start = span.start as usize;
+        let end = span.end as usize;
+        let code_snippet = source_code.get(start..end).map(|s| s.to_string());
+
+        issues.push(CodeIssue {
+            file_path: file_path.display().to_string(),
+            line,
+            column,
+            end_line: None,
+            end_column: None,
+            message,
+            severity,
+            category: Category::Security,
+            rule,
+            code_snippet,
+        });
+    }
+
+    fn is_token_name(name: &str) -> bool {
+        let name_lower = name.to_lowercase();
+        TOKEN_WORDS.iter().any(|word| name_lower.contains(word))
+    }
+
+    fn is_request_path(path: &str) -> bool {
+        REQUEST_INPUTS.iter().any(|input| {
+            path == *input || (path.starts_with(input) && path[input.len()..].starts_with('.'))
+        })
+    }
+
+    /// Whether the expression reads from the incoming request, directly or through a local variable
+    fn references_request(expr: &Expression, scope: &Scope) -> bool {
+        if let Some(path) = member_path(expr) {
+            let root = path.split('.').next().unwrap_or(&path);
+            return Self::is_request_path(&path) || scope.request_vars.contains(root);
+        }
+
+        match expr {
+            Expression::StaticMemberExpression(member) => Self::references_request(&member.object, scope),
+            Expression::ComputedMemberExpression(member) => {
+                Self::references_request(&member.object, scope)
+                    || Self::references_request(&member.expression, scope)
+            }
+            Expression::BinaryExpression(bin_expr) => {
+                Self::references_request(&bin_expr.left, scope) || Self::references_request(&bin_expr.right, scope)
+            }
+            Expression::LogicalExpression(logical_expr) => {
+                Self::references_request(&logical_expr.left, scope)
+                    || Self::references_request(&logical_expr.right, scope)
+            }
+            Expression::ConditionalExpression(cond_expr) => {
+                Self::references_request(&cond_expr.consequent, scope)
+                    || Self::references_request(&cond_expr.alternate, scope)
+            }
+            Expression::TemplateLiteral(template) => {
+                template.expressions.iter().any(|expr| Self::references_request(expr, scope))
+            }
+            Expression::CallExpression(call) => {
+                Self::references_request(&call.callee, scope)
+                    || call.arguments.iter().any(|arg| {
+                        arg.as_expression().is_some_and(|expr| Self::references_request(expr, scope))
+                    })
+            }
+            Expression::ParenthesizedExpression(paren) => Self::references_request(&paren.expression, scope),
+            Expression::AwaitExpression(await_expr) => Self::references_request(&await_expr.argument, scope),
+            Expression::TSAsExpression(as_expr) => Self::references_request(&as_expr.expression, scope),
+            _ => false,
+        }
+    }
+
+    fn is_literal_string(expr: &Expression) -> bool {
+        match expr {
+            Expression::StringLiteral(_) => true,
+            Expression::TemplateLiteral(template) => template.expressions.is_empty(),
+            Expression::ParenthesizedExpression(paren) => Self::is_literal_string(&paren.expression),
+            _ => false,
+        }
+    }
+
+    /// Leading string text of a concatenation or template, used to recognise SQL
+    fn leading_text(expr: &Expression) -> Option<String> {
+        match expr {
+            Expression::StringLiteral(lit) => Some(lit.value.to_string()),
+            Expression::TemplateLiteral(template) => {
+                template.quasis.first().map(|quasi| quasi.value.raw.to_string())
+            }
+            Expression::BinaryExpression(bin_expr) => Self::leading_text(&bin_expr.left),
+            Expression::ParenthesizedExpression(paren) => Self::leading_text(&paren.expression),
+            _ => None,
+        }
+    }
+
+    /// A string built from dynamic parts (concatenation or template with expressions) that looks like SQL
+    fn is_dynamic_sql(expr: &Expression) -> bool {
+        let is_dynamic = match expr {
+            Expression::BinaryExpression(bin_expr) => {
+                bin_expr.operator == BinaryOperator::Addition
+                    && !(Self::is_literal_string(&bin_expr.left) && Self::is_literal_string(&bin_expr.right))
+            }
+            Expression::TemplateLiteral(template) => !template.expressions.is_empty(),
+            _ => false,
+        };
+
+        is_dynamic
+            && Self::leading_text(expr).is_some_and(|text| {
+                let text = text.trim_start().to_lowercase();
+                SQL_KEYWORDS.iter().any(|keyword| text.starts_with(keyword))
+            })
+    }
+
+    fn find_math_random(expr: &Expression) -> Option<Span> {
+        match expr {
+            Expression::CallExpression(call) => {
+                if member_path(&call.callee).as_deref() == Some("Math.random") {
+                    return Some(call.span);
+                }
+                Self::find_math_random(&call.callee).or_else(|| {
+                    call.arguments
+                        .iter()
+                        .filter_map(|arg| arg.as_expression())
+                        .find_map(Self::find_math_random)
+                })
+            }
+            Expression::StaticMemberExpression(member) => Self::find_math_random(&member.object),
+            Expression::ComputedMemberExpression(member) => {
+                Self::find_math_random(&member.object).or_else(|| Self::find_math_random(&member.expression))
+            }
+            Expression::BinaryExpression(bin_expr) => {
+                Self::find_math_random(&bin_expr.left).or_else(|| Self::find_math_random(&bin_expr.right))
+            }
+            Expression::TemplateLiteral(template) => template.expressions.iter().find_map(Self::find_math_random),
+            Expression::ParenthesizedExpression(paren) => Self::find_math_random(&paren.expression),
+            _ => None,
+        }
+    }
+
+    fn check_token_random(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        name: &str,
+        value: &Expression,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        if !Self::is_token_name(name) {
+            return;
+        }
+        if let Some(span) = Self::find_math_random(value) {
+            self.add_issue(
+                issues,
+                file_path,
+                source_code,
+                span,
+                format!("Math.random() tidak aman secara kriptografis untuk '{}'. Gunakan crypto.randomBytes() atau crypto.randomUUID()", name),
+                "no-insecure-random".to_string(),
+                Severity::Warning,
+            );
+        }
+    }
+}
+
+impl Analyzer for NodeSecurityAnalyzer {
+    fn analyze(&self, program: &Program, file_path: &Path, source_code: &str) -> Vec<CodeIssue> {
+        let mut issues = Vec::new();
+        let bindings = ModuleBindings::collect(program);
+        let mut scope = Scope::default();
+
+        for stmt in &program.body {
+            self.analyze_statement(&mut issues, stmt, file_path, source_code, &bindings, &mut scope);
+        }
+
+        issues
+    }
+}
+
+impl NodeSecurityAnalyzer {
+    fn analyze_statement(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        stmt: &Statement,
+        file_path: &Path,
+        source_code: &str,
+        bindings: &ModuleBindings,
+        scope: &mut Scope,
+    ) {
+        match stmt {
+            Statement::VariableDeclaration(var_decl) => {
+                self.analyze_variable_declaration(issues, var_decl, file_path, source_code, bindings, scope);
+            }
+            Statement::ExpressionStatement(expr_stmt) => {
+                self.analyze_expression(issues, &expr_stmt.expression, file_path, source_code, bindings, scope);
+            }
+            Statement::ReturnStatement(ret_stmt) => {
+                if let Some(argument) = &ret_stmt.argument {
+                    self.analyze_expression(issues, argument, file_path, source_code, bindings, scope);
+                }
+            }
+            Statement::BlockStatement(block) => {
+                for stmt in &block.body {
+                    self.analyze_statement(issues, stmt, file_path, source_code, bindings, scope);
+                }
+            }
+            Statement::IfStatement(if_stmt) => {
+                self.analyze_expression(issues, &if_stmt.test, file_path, source_code, bindings, scope);
+                self.analyze_statement(issues, &if_stmt.consequent, file_path, source_code, bindings, scope);
+                if let Some(alternate) = &if_stmt.alternate {
+                    self.analyze_statement(issues, alternate, file_path, source_code, bindings, scope);
+                }
+            }
+            Statement::ForStatement(for_stmt) => {
+                match &for_stmt.init {
+                    Some(ForStatementInit::VariableDeclaration(var_decl)) => {
+                        self.analyze_variable_declaration(issues, var_decl, file_path, source_code, bindings, scope);
+                    }
+                    Some(init) => {
+                        if let Some(expr) = init.as_expression() {
+                            self.analyze_expression(issues, expr, file_path, source_code, bindings, scope);
+                        }
+                    }
+                    None => {}
+                }
+                if let Some(test) = &for_stmt.test {
+                    self.analyze_expression(issues, test, file_path, source_code, bindings, scope);
+                }
+                if let Some(update) = &for_stmt.update {
+                    self.analyze_expression(issues, update, file_path, source_code, bindings, scope);
+                }
+                self.analyze_statement(issues, &for_stmt.body, file_path, source_code, bindings, scope);
+            }
+            Statement::ForOfStatement(for_of) => {
+                self.analyze_expression(issues, &for_of.right, file_path, source_code, bindings, scope);
+                self.analyze_statement(issues, &for_of.body, file_path, source_code, bindings, scope);
+            }
+            Statement::ForInStatement(for_in) => {
+                self.analyze_expression(issues, &for_in.right, file_path, source_code, bindings, scope);
+                self.analyze_statement(issues, &for_in.body, file_path, source_code, bindings, scope);
+            }
+            Statement::WhileStatement(while_stmt) => {
+                self.analyze_expression(issues, &while_stmt.test, file_path, source_code, bindings, scope);
+                self.analyze_statement(issues, &while_stmt.body, file_path, source_code, bindings, scope);
+            }
+            Statement::DoWhileStatement(do_while) => {
+                self.analyze_statement(issues, &do_while.body, file_path, source_code, bindings, scope);
+                self.analyze_expression(issues, &do_while.test, file_path, source_code, bindings, scope);
+            }
+            Statement::TryStatement(try_stmt) => {
+                for stmt in &try_stmt.block.body {
+                    self.analyze_statement(issues, stmt, file_path, source_code, bindings, scope);
+                }
+                if let Some(handler) = &try_stmt.handler {
+                    for stmt in &handler.body.body {
+                        self.analyze_statement(issues, stmt, file_path, source_code, bindings, scope);
+                    }
+                }
+                if let Some(finalizer) = &try_stmt.finalizer {
+                    for stmt in &finalizer.body {
+                        self.analyze_statement(issues, stmt, file_path, source_code, bindings, scope);
+                    }
+                }
+            }
+            Statement::SwitchStatement(switch_stmt) => {
+                for case in &switch_stmt.cases {
+                    for stmt in &case.consequent {
+                        self.analyze_statement(issues, stmt, file_path, source_code, bindings, scope);
+                    }
+                }
+            }
+            Statement::FunctionDeclaration(func) => {
+                if let Some(body) = &func.body {
+                    self.analyze_function_body(issues, body, file_path, source_code, bindings, scope);
+                }
+            }
+            Statement::ClassDeclaration(class) => {
+                for element in &class.body.body {
+                    if let ClassElement::MethodDefinition(method) = element {
+                        if let Some(body) = &method.value.body {
+                            self.analyze_function_body(issues, body, file_path, source_code, bindings, scope);
+                        }
+                    }
+                }
+            }
+            Statement::ExportNamedDeclaration(export) => match &export.declaration {
+                Some(Declaration::VariableDeclaration(var_decl)) => {
+                    self.analyze_variable_declaration(issues, var_decl, file_path, source_code, bindings, scope);
+                }
+                Some(Declaration::FunctionDeclaration(func)) => {
+                    if let Some(body) = &func.body {
+                        self.analyze_function_body(issues, body, file_path, source_code, bindings, scope);
+                    }
+                }
+                _ => {}
+            },
+            Statement::ExportDefaultDeclaration(export) => {
+                if let ExportDefaultDeclarationKind::FunctionDeclaration(func) = &export.declaration {
+                    if let Some(body) = &func.body {
+                        self.analyze_function_body(issues, body, file_path, source_code, bindings, scope);
+                    }
+                } else if let Some(expr) = export.declaration.as_expression() {
+                    self.analyze_expression(issues, expr, file_path, source_code, bindings, scope);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Function bodies get their own scope so request variables don't leak between handlers
+    fn analyze_function_body(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        body: &FunctionBody,
+        file_path: &Path,
+        source_code: &str,
+        bindings: &ModuleBindings,
+        scope: &Scope,
+    ) {
+        let mut inner = scope.clone();
+        for stmt in &body.statements {
+            self.analyze_statement(issues, stmt, file_path, source_code, bindings, &mut inner);
+        }
+    }
+
+    fn analyze_variable_declaration(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        var_decl: &VariableDeclaration,
+        file_path: &Path,
+        source_code: &str,
+        bindings: &ModuleBindings,
+        scope: &mut Scope,
+    ) {
+        for var in &var_decl.declarations {
+            let Some(init) = &var.init else {
+                continue;
+            };
+
+            self.analyze_expression(issues, init, file_path, source_code, bindings, scope);
+
+            let is_request = Self::references_request(init, scope);
+            match &var.id.kind {
+                BindingPatternKind::BindingIdentifier(ident) => {
+                    if is_request {
+                        scope.request_vars.insert(ident.name.to_string());
+                    }
+                    if Self::is_dynamic_sql(init) {
+                        scope.sql_vars.insert(ident.name.to_string());
+                    }
+                    self.check_token_random(issues, &ident.name, init, file_path, source_code);
+                }
+                BindingPatternKind::ObjectPattern(obj_pattern) if is_request => {
+                    for prop in &obj_pattern.properties {
+                        if let BindingPatternKind::BindingIdentifier(ident) = &prop.value.kind {
+                            scope.request_vars.insert(ident.name.to_string());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn analyze_expression(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        expr: &Expression,
+        file_path: &Path,
+        source_code: &str,
+        bindings: &ModuleBindings,
+        scope: &mut Scope,
+    ) {
+        match expr {
+            Expression::CallExpression(call_expr) => {
+                self.check_call(issues, call_expr, file_path, source_code, bindings, scope);
+
+                self.analyze_expression(issues, &call_expr.callee, file_path, source_code, bindings, scope);
+                for arg in &call_expr.arguments {
+                    if let Some(expr) = arg.as_expression() {
+                        self.analyze_expression(issues, expr, file_path, source_code, bindings, scope);
+                    }
+                }
+            }
+            Expression::NewExpression(new_expr) => {
+                if member_path(&new_expr.callee).as_deref() == Some("RegExp") {
+                    self.check_regexp(issues, new_expr.span, new_expr.arguments.first(), file_path, source_code, scope);
+                }
+                for arg in &new_expr.arguments {
+                    if let Some(expr) = arg.as_expression() {
+                        self.analyze_expression(issues, expr, file_path, source_code, bindings, scope);
+                    }
+                }
+            }
+            Expression::AssignmentExpression(assign_expr) => {
+                let target = match &assign_expr.left {
+                    AssignmentTarget::AssignmentTargetIdentifier(ident) => Some(ident.name.to_string()),
+                    AssignmentTarget::StaticMemberExpression(member) => member_path(&member.object)
+                        .map(|object| format!("{}.{}", object, member.property.name)),
+                    _ => None,
+                };
+
+                if let Some(target) = &target {
+                    // process.env.NODE_TLS_REJECT_UNAUTHORIZED = '0'
+                    if target.ends_with("NODE_TLS_REJECT_UNAUTHORIZED") {
+                        if let Expression::StringLiteral(lit) = &assign_expr.right {
+                            if lit.value == "0" {
+                                self.add_issue(
+                                    issues,
+                                    file_path,
+                                    source_code,
+                                    assign_expr.span,
+                                    "Verifikasi sertifikat TLS dinonaktifkan untuk seluruh proses. Hapus NODE_TLS_REJECT_UNAUTHORIZED=0".to_string(),
+                                    "no-tls-verify-disabled".to_string(),
+                                    Severity::Error,
+                                );
+                            }
+                        }
+                    }
+
+                    self.check_token_random(issues, last_segment(target), &assign_expr.right, file_path, source_code);
+
+                    if !target.contains('.') {
+                        if Self::references_request(&assign_expr.right, scope) {
+                            scope.request_vars.insert(target.clone());
+                        }
+                        if Self::is_dynamic_sql(&assign_expr.right) {
+                            scope.sql_vars.insert(target.clone());
+                        }
+                    }
+                }
+
+                self.analyze_expression(issues, &assign_expr.right, file_path, source_code, bindings, scope);
+            }
+            Expression::ObjectExpression(obj_expr) => {
+                for prop in &obj_expr.properties {
+                    if let ObjectPropertyKind::ObjectProperty(prop) = prop {
+                        if let Some(key) = prop.key.static_name() {
+                            if key == "rejectUnauthorized" {
+                                if let Expression::BooleanLiteral(lit) = &prop.value {
+                                    if !lit.value {
+                                        self.add_issue(
+                                            issues,
+                                            file_path,
+                                            source_code,
+                                            prop.span,
+                                            "rejectUnauthorized: false menonaktifkan verifikasi sertifikat TLS dan membuka celah man-in-the-middle".to_string(),
+                                            "no-tls-verify-disabled".to_string(),
+                                            Severity::Error,
+                                        );
+                                    }
+                                }
+                            }
+                            self.check_token_random(issues, &key, &prop.value, file_path, source_code);
+                        }
+                        self.analyze_expression(issues, &prop.value, file_path, source_code, bindings, scope);
+                    }
+                }
+            }
+            Expression::ArrowFunctionExpression(arrow) => {
+                self.analyze_function_body(issues, &arrow.body, file_path, source_code, bindings, scope);
+            }
+            Expression::FunctionExpression(func) => {
+                if let Some(body) = &func.body {
+                    self.analyze_function_body(issues, body, file_path, source_code, bindings, scope);
+                }
+            }
+            Expression::AwaitExpression(await_expr) => {
+                self.analyze_expression(issues, &await_expr.argument, file_path, source_code, bindings, scope);
+            }
+            Expression::ParenthesizedExpression(paren) => {
+                self.analyze_expression(issues, &paren.expression, file_path, source_code, bindings, scope);
+            }
+            Expression::StaticMemberExpression(member) => {
+                self.analyze_expression(issues, &member.object, file_path, source_code, bindings, scope);
+            }
+            Expression::BinaryExpression(bin_expr) => {
+                self.analyze_expression(issues, &bin_expr.left, file_path, source_code, bindings, scope);
+                self.analyze_expression(issues, &bin_expr.right, file_path, source_code, bindings, scope);
+            }
+            Expression::LogicalExpression(logical_expr) => {
+                self.analyze_expression(issues, &logical_expr.left, file_path, source_code, bindings, scope);
+                self.analyze_expression(issues, &logical_expr.right, file_path, source_code, bindings, scope);
+            }
+            Expression::ConditionalExpression(cond_expr) => {
+                self.analyze_expression(issues, &cond_expr.test, file_path, source_code, bindings, scope);
+                self.analyze_expression(issues, &cond_expr.consequent, file_path, source_code, bindings, scope);
+                self.analyze_expression(issues, &cond_expr.alternate, file_path, source_code, bindings, scope);
+            }
+            Expression::TemplateLiteral(template) => {
+                for expr in &template.expressions {
+                    self.analyze_expression(issues, expr, file_path, source_code, bindings, scope);
+                }
+            }
+            Expression::ArrayExpression(arr_expr) => {
+                for elem in &arr_expr.elements {
+                    match elem {
+                        ArrayExpressionElement::SpreadElement(spread) => {
+                            self.analyze_expression(issues, &spread.argument, file_path, source_code, bindings, scope);
+                        }
+                        _ => {
+                            if let Some(expr) = elem.as_expression() {
+                                self.analyze_expression(issues, expr, file_path, source_code, bindings, scope);
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_call(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        call_expr: &CallExpression,
+        file_path: &Path,
+        source_code: &str,
+        bindings: &ModuleBindings,
+        scope: &Scope,
+    ) {
+        let Some(callee) = member_path(&call_expr.callee) else {
+            return;
+        };
+        let resolved = bindings.resolve(&callee);
+        let (object, method) = match resolved.rsplit_once('.') {
+            Some((object, method)) => (object, method),
+            None => ("", resolved.as_str()),
+        };
+        let root = callee.split('.').next().unwrap_or(&callee);
+        let first_arg = call_expr.arguments.first().and_then(|arg| arg.as_expression());
+
+        match (object, method) {
+            ("child_process", "exec" | "execSync") => {
+                if let Some(command) = first_arg {
+                    if !Self::is_literal_string(command) {
+                        self.add_issue(
+                            issues,
+                            file_path,
+                            source_code,
+                            call_expr.span,
+                            format!("{}() dijalankan melalui shell dengan perintah non-literal. Gunakan execFile()/spawn() dengan array argumen", method),
+                            "no-exec-non-literal".to_string(),
+                            Severity::Warning,
+                        );
+                    }
+                }
+            }
+            ("fs" | "fs.promises", method)
+                if FS_PATH_FUNCTIONS.contains(&method) && first_arg.is_some_and(|path| Self::references_request(path, scope)) =>
+            {
+                self.add_issue(
+                    issues,
+                    file_path,
+                    source_code,
+                    call_expr.span,
+                    format!("Path untuk fs.{}() berasal dari request. Validasi dengan path.resolve() dan pastikan tetap di dalam direktori yang diizinkan", method),
+                    "no-fs-request-path".to_string(),
+                    Severity::Error,
+                );
+            }
+            ("crypto", "createHash") => {
+                if let Some(Expression::StringLiteral(algorithm)) = first_arg {
+                    let algorithm = algorithm.value.to_lowercase();
+                    if algorithm == "md5" || algorithm == "sha1" {
+                        self.add_issue(
+                            issues,
+                            file_path,
+                            source_code,
+                            call_expr.span,
+                            format!("Algoritma hash '{}' sudah tidak aman. Gunakan sha256 atau lebih kuat (bcrypt/argon2 untuk password)", algorithm),
+                            "no-weak-hash".to_string(),
+                            Severity::Warning,
+                        );
+                    }
+                }
+            }
+            ("", "RegExp") => {
+                self.check_regexp(issues, call_expr.span, call_expr.arguments.first(), file_path, source_code, scope);
+            }
+            (_, "redirect") if matches!(root, "res" | "response") => {
+                let is_request = call_expr.arguments.iter().any(|arg| {
+                    arg.as_expression().is_some_and(|expr| Self::references_request(expr, scope))
+                });
+                if is_request {
+                    self.add_issue(
+                        issues,
+                        file_path,
+                        source_code,
+                        call_expr.span,
+                        "Redirect ke URL dari request tanpa validasi (open redirect). Cocokkan dengan daftar URL yang diizinkan".to_string(),
+                        "no-open-redirect".to_string(),
+                        Severity::Warning,
+                    );
+                }
+            }
+            (_, "query" | "execute" | "raw") if !matches!(root, "document" | "req" | "request") => {
+                let is_dynamic = match first_arg {
+                    Some(Expression::Identifier(ident)) => scope.sql_vars.contains(ident.name.as_str()),
+                    Some(sql) => Self::is_dynamic_sql(sql),
+                    None => false,
+                };
+                if is_dynamic {
+                    self.add_issue(
+                        issues,
+                        file_path,
+                        source_code,
+                        call_expr.span,
+                        format!("Query SQL dibangun dengan penggabungan string lalu dikirim ke {}(). Gunakan parameterized query (placeholder ? atau $1)", method),
+                        "no-sql-concat".to_string(),
+                        Severity::Error,
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_regexp(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        span: Span,
+        pattern: Option<&Argument>,
+        file_path: &Path,
+        source_code: &str,
+        scope: &Scope,
+    ) {
+        let is_request = pattern
+            .and_then(|arg| arg.as_expression())
+            .is_some_and(|expr| Self::references_request(expr, scope));
+        if is_request {
+            self.add_issue(
+                issues,
+                file_path,
+                source_code,
+                span,
+                "RegExp dibuat dari input pengguna - rentan ReDoS dan injeksi pola. Escape input atau gunakan pencarian string biasa".to_string(),
+                "no-regexp-user-input".to_string(),
+                Severity::Warning,
+            );
+        }
+    }
+}
+
+impl Default for NodeSecurityAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,29 @@
+use super::Analyzer;
+use crate::config::Config;
+use crate::plugins::{self, Plugin};
+use crate::types::CodeIssue;
+use oxc_ast::ast::Program;
+use std::path::Path;
+
+/// Runs the WebAssembly rule plugins configured for JavaScript and TypeScript
+pub struct PluginAnalyzer {
+    plugins: Vec<Plugin>,
+}
+
+impl PluginAnalyzer {
+    pub fn with_config(config: &Config) -> Self {
+        Self {
+            plugins: config.loaded_plugins.clone(),
+        }
+    }
+}
+
+impl Analyzer for PluginAnalyzer {
+    fn analyze(&self, program: &Program, file_path: &Path, source_code: &str) -> Vec<CodeIssue> {
+        let language = match file_path.extension().and_then(|ext| ext.to_str()) {
+            Some("ts") | Some("tsx") => "typescript",
+            _ => "javascript",
+        };
+        plugins::run_all(&self.plugins, file_path, language, source_code, || plugins::javascript_ast(program, source_code))
+    }
+}
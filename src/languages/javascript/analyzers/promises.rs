@@ -0,0 +1,784 @@
+use super::bindings::{last_segment, member_path, ModuleBindings};
+use super::Analyzer;
+use crate::types::{CodeIssue, Category, Severity};
+use oxc_ast::ast::*;
+use oxc_span::Span;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Static `Promise` helpers that always return a promise
+const PROMISE_STATICS: &[&str] = &["all", "allSettled", "any", "race", "resolve", "reject"];
+
+/// Modules whose functions return promises
+const PROMISE_MODULES: &[&str] = &["axios", "fs.promises", "node-fetch", "got", "ky"];
+
+/// Functions in this file whose return type is known, from `async` or a TS return annotation
+#[derive(Debug, Clone, Default)]
+struct PromiseFunctions {
+    returns_promise: HashSet<String>,
+    returns_other: HashSet<String>,
+}
+
+impl PromiseFunctions {
+    fn collect(program: &Program) -> Self {
+        let mut functions = Self::default();
+        for stmt in &program.body {
+            match stmt {
+                Statement::ExportNamedDeclaration(export) => match &export.declaration {
+                    Some(Declaration::FunctionDeclaration(func)) => functions.add_function(func),
+                    Some(Declaration::VariableDeclaration(var_decl)) => functions.add_variables(var_decl),
+                    Some(Declaration::ClassDeclaration(class)) => functions.add_class(class),
+                    _ => {}
+                },
+                Statement::FunctionDeclaration(func) => functions.add_function(func),
+                Statement::VariableDeclaration(var_decl) => functions.add_variables(var_decl),
+                Statement::ClassDeclaration(class) => functions.add_class(class),
+                _ => {}
+            }
+        }
+        functions
+    }
+
+    fn insert(&mut self, name: String, kind: Option<bool>) {
+        match kind {
+            Some(true) => {
+                self.returns_promise.insert(name);
+            }
+            Some(false) => {
+                self.returns_other.insert(name);
+            }
+            None => {}
+        }
+    }
+
+    fn add_function(&mut self, func: &Function) {
+        if let Some(id) = &func.id {
+            self.insert(id.name.to_string(), return_kind(func.r#async, func.return_type.as_deref()));
+        }
+    }
+
+    fn add_variables(&mut self, var_decl: &VariableDeclaration) {
+        for var in &var_decl.declarations {
+            let BindingPatternKind::BindingIdentifier(ident) = &var.id.kind else {
+                continue;
+            };
+
+            // const load: () => Promise<void> = ...
+            if let Some(TSType::TSFunctionType(func_type)) = var.id.type_annotation.as_ref().map(|t| &t.type_annotation) {
+                self.insert(ident.name.to_string(), Some(is_promise_type(&func_type.return_type.type_annotation)));
+                continue;
+            }
+
+            let kind = match &var.init {
+                Some(Expression::ArrowFunctionExpression(arrow)) => return_kind(arrow.r#async, arrow.return_type.as_deref()),
+                Some(Expression::FunctionExpression(func)) => return_kind(func.r#async, func.return_type.as_deref()),
+                _ => None,
+            };
+            self.insert(ident.name.to_string(), kind);
+        }
+    }
+
+    fn add_class(&mut self, class: &Class) {
+        for element in &class.body.body {
+            if let ClassElement::MethodDefinition(method) = element {
+                if let Some(name) = method.key.static_name() {
+                    let kind = return_kind(method.value.r#async, method.value.return_type.as_deref());
+                    self.insert(format!("this.{}", name), kind);
+                }
+            }
+        }
+    }
+
+    fn add_params(&mut self, params: &FormalParameters) {
+        for param in &params.items {
+            let BindingPatternKind::BindingIdentifier(ident) = &param.pattern.kind else {
+                continue;
+            };
+            if let Some(TSType::TSFunctionType(func_type)) = param.pattern.type_annotation.as_ref().map(|t| &t.type_annotation) {
+                self.insert(ident.name.to_string(), Some(is_promise_type(&func_type.return_type.type_annotation)));
+            }
+        }
+    }
+}
+
+/// `Some(true)` for async functions and `Promise<T>` annotations, `Some(false)` for any other annotation
+fn return_kind(is_async: bool, return_type: Option<&TSTypeAnnotation>) -> Option<bool> {
+    if is_async {
+        return Some(true);
+    }
+    return_type.map(|annotation| is_promise_type(&annotation.type_annotation))
+}
+
+fn is_promise_type(ts_type: &TSType) -> bool {
+    match ts_type {
+        TSType::TSTypeReference(reference) => match &reference.type_name {
+            TSTypeName::IdentifierReference(ident) => ident.name == "Promise" || ident.name == "PromiseLike",
+            _ => false,
+        },
+        TSType::TSUnionType(union_type) => union_type.types.iter().any(is_promise_type),
+        TSType::TSParenthesizedType(paren) => is_promise_type(&paren.type_annotation),
+        _ => false,
+    }
+}
+
+/// State of the function currently being visited
+#[derive(Debug, Default)]
+struct FunctionState {
+    is_async: bool,
+    saw_await: bool,
+    in_loop: bool,
+    /// Inside a `try` block, where a returned promise must be awaited for `catch` to see it
+    in_try: bool,
+}
+
+pub struct PromiseAnalyzer;
+
+impl PromiseAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn get_line_column(source_code: &str, span: Span) -> (usize, usize) {
+        let start = span.start as usize;
+        let before = &source_code[..start];
+        let line = before.lines().count();
+        let last_newline = before.rfind('\n').unwrap_or(0);
+        let column = start - last_newline;
+        (line, column)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn add_issue(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        file_path: &Path,
+        source_code: &str,
+        span: Span,
+        message: String,
+        rule: String,
+        severity: Severity,
+        category: Category,
+    ) {
+        let (line, column) = Self::get_line_column(source_code, span);
+        let start = span.start as usize;
+        let end = span.end as usize;
+        let code_snippet = source_code.get(start..end).map(|s| s.to_string());
+
+        issues.push(CodeIssue {
+            file_path: file_path.display().to_string(),
+            line,
+            column,
+            end_line: None,
+            end_column: None,
+            message,
+            severity,
+            category,
+            rule,
+            code_snippet,
+        });
+    }
+
+    /// Whether a call evaluates to a promise: TS annotations and `async` first, then well-known APIs
+    fn returns_promise(call_expr: &CallExpression, functions: &PromiseFunctions, bindings: &ModuleBindings) -> bool {
+        // fetch(url).then(...), promise.finally(...)
+        if let Expression::StaticMemberExpression(member) = &call_expr.callee {
+            if matches!(member.property.name.as_str(), "then" | "catch" | "finally") {
+                return true;
+            }
+        }
+
+        let Some(path) = member_path(&call_expr.callee) else {
+            return false;
+        };
+        if functions.returns_promise.contains(&path) {
+            return true;
+        }
+        if functions.returns_other.contains(&path) {
+            return false;
+        }
+
+        let resolved = bindings.resolve(&path);
+        let name = last_segment(&resolved);
+
+        if resolved == "fetch" {
+            return true;
+        }
+        if let Some(method) = resolved.strip_prefix("Promise.") {
+            return PROMISE_STATICS.contains(&method);
+        }
+        if PROMISE_MODULES.iter().any(|module| {
+            resolved == *module || (resolved.starts_with(module) && resolved[module.len()..].starts_with('.'))
+        }) {
+            return true;
+        }
+        name.len() > "Async".len() && name.ends_with("Async")
+    }
+
+    /// Whether a promise chain ends in a rejection handler
+    fn handles_rejection(expr: &Expression) -> bool {
+        let Expression::CallExpression(call_expr) = expr else {
+            return false;
+        };
+        let Expression::StaticMemberExpression(member) = &call_expr.callee else {
+            return false;
+        };
+        match member.property.name.as_str() {
+            "catch" => true,
+            "then" => call_expr.arguments.len() >= 2,
+            "finally" => Self::handles_rejection(&member.object),
+            _ => false,
+        }
+    }
+
+    fn is_then_call(expr: &Expression) -> bool {
+        match expr {
+            Expression::CallExpression(call_expr) => match &call_expr.callee {
+                Expression::StaticMemberExpression(member) => match member.property.name.as_str() {
+                    "then" => true,
+                    "finally" => Self::is_then_call(&member.object),
+                    _ => false,
+                },
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+impl Analyzer for PromiseAnalyzer {
+    fn analyze(&self, program: &Program, file_path: &Path, source_code: &str) -> Vec<CodeIssue> {
+        let mut issues = Vec::new();
+        let functions = PromiseFunctions::collect(program);
+        let bindings = ModuleBindings::collect(program);
+        // Top-level await in modules
+        let mut state = FunctionState { is_async: true, saw_await: true, ..FunctionState::default() };
+
+        for stmt in &program.body {
+            self.analyze_statement(&mut issues, stmt, file_path, source_code, &functions, &bindings, &mut state);
+        }
+
+        issues
+    }
+}
+
+impl PromiseAnalyzer {
+    #[allow(clippy::too_many_arguments)]
+    fn analyze_statement(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        stmt: &Statement,
+        file_path: &Path,
+        source_code: &str,
+        functions: &PromiseFunctions,
+        bindings: &ModuleBindings,
+        state: &mut FunctionState,
+    ) {
+        match stmt {
+            Statement::VariableDeclaration(var_decl) => {
+                for var in &var_decl.declarations {
+                    if let Some(init) = &var.init {
+                        self.analyze_expression(issues, init, file_path, source_code, functions, bindings, state);
+                    }
+                }
+            }
+            Statement::ExpressionStatement(expr_stmt) => {
+                self.check_floating(issues, &expr_stmt.expression, file_path, source_code, functions, bindings);
+                self.analyze_expression(issues, &expr_stmt.expression, file_path, source_code, functions, bindings, state);
+            }
+            Statement::ReturnStatement(ret_stmt) => {
+                if let Some(argument) = &ret_stmt.argument {
+                    self.check_return(issues, argument, file_path, source_code, functions, bindings, state);
+                    self.analyze_expression(issues, argument, file_path, source_code, functions, bindings, state);
+                }
+            }
+            Statement::ThrowStatement(throw_stmt) => {
+                self.analyze_expression(issues, &throw_stmt.argument, file_path, source_code, functions, bindings, state);
+            }
+            Statement::BlockStatement(block) => {
+                for stmt in &block.body {
+                    self.analyze_statement(issues, stmt, file_path, source_code, functions, bindings, state);
+                }
+            }
+            Statement::IfStatement(if_stmt) => {
+                self.analyze_expression(issues, &if_stmt.test, file_path, source_code, functions, bindings, state);
+                self.analyze_statement(issues, &if_stmt.consequent, file_path, source_code, functions, bindings, state);
+                if let Some(alternate) = &if_stmt.alternate {
+                    self.analyze_statement(issues, alternate, file_path, source_code, functions, bindings, state);
+                }
+            }
+            Statement::ForStatement(for_stmt) => {
+                self.analyze_loop_body(issues, &for_stmt.body, file_path, source_code, functions, bindings, state);
+            }
+            Statement::ForOfStatement(for_of) => {
+                self.analyze_expression(issues, &for_of.right, file_path, source_code, functions, bindings, state);
+                if for_of.r#await {
+                    // for await (...) is sequential on purpose
+                    state.saw_await = true;
+                    self.analyze_statement(issues, &for_of.body, file_path, source_code, functions, bindings, state);
+                } else {
+                    self.analyze_loop_body(issues, &for_of.body, file_path, source_code, functions, bindings, state);
+                }
+            }
+            Statement::ForInStatement(for_in) => {
+                self.analyze_loop_body(issues, &for_in.body, file_path, source_code, functions, bindings, state);
+            }
+            Statement::WhileStatement(while_stmt) => {
+                self.analyze_expression(issues, &while_stmt.test, file_path, source_code, functions, bindings, state);
+                self.analyze_loop_body(issues, &while_stmt.body, file_path, source_code, functions, bindings, state);
+            }
+            Statement::DoWhileStatement(do_while) => {
+                self.analyze_loop_body(issues, &do_while.body, file_path, source_code, functions, bindings, state);
+            }
+            Statement::TryStatement(try_stmt) => {
+                let in_try = state.in_try;
+                state.in_try = true;
+                for stmt in &try_stmt.block.body {
+                    self.analyze_statement(issues, stmt, file_path, source_code, functions, bindings, state);
+                }
+                // A catch block followed by finally still needs the await before finally runs
+                state.in_try = in_try || try_stmt.finalizer.is_some();
+                if let Some(handler) = &try_stmt.handler {
+                    for stmt in &handler.body.body {
+                        self.analyze_statement(issues, stmt, file_path, source_code, functions, bindings, state);
+                    }
+                }
+                state.in_try = in_try;
+                if let Some(finalizer) = &try_stmt.finalizer {
+                    for stmt in &finalizer.body {
+                        self.analyze_statement(issues, stmt, file_path, source_code, functions, bindings, state);
+                    }
+                }
+            }
+            Statement::SwitchStatement(switch_stmt) => {
+                self.analyze_expression(issues, &switch_stmt.discriminant, file_path, source_code, functions, bindings, state);
+                for case in &switch_stmt.cases {
+                    for stmt in &case.consequent {
+                        self.analyze_statement(issues, stmt, file_path, source_code, functions, bindings, state);
+                    }
+                }
+            }
+            Statement::LabeledStatement(labeled) => {
+                self.analyze_statement(issues, &labeled.body, file_path, source_code, functions, bindings, state);
+            }
+            Statement::FunctionDeclaration(func) => {
+                self.analyze_function(issues, func, file_path, source_code, functions, bindings);
+            }
+            Statement::ClassDeclaration(class) => {
+                self.analyze_class(issues, class, file_path, source_code, functions, bindings, state);
+            }
+            Statement::ExportNamedDeclaration(export) => match &export.declaration {
+                Some(Declaration::VariableDeclaration(var_decl)) => {
+                    for var in &var_decl.declarations {
+                        if let Some(init) = &var.init {
+                            self.analyze_expression(issues, init, file_path, source_code, functions, bindings, state);
+                        }
+                    }
+                }
+                Some(Declaration::FunctionDeclaration(func)) => {
+                    self.analyze_function(issues, func, file_path, source_code, functions, bindings);
+                }
+                Some(Declaration::ClassDeclaration(class)) => {
+                    self.analyze_class(issues, class, file_path, source_code, functions, bindings, state);
+                }
+                _ => {}
+            },
+            Statement::ExportDefaultDeclaration(export) => match &export.declaration {
+                ExportDefaultDeclarationKind::FunctionDeclaration(func) => {
+                    self.analyze_function(issues, func, file_path, source_code, functions, bindings);
+                }
+                ExportDefaultDeclarationKind::ClassDeclaration(class) => {
+                    self.analyze_class(issues, class, file_path, source_code, functions, bindings, state);
+                }
+                declaration => {
+                    if let Some(expr) = declaration.as_expression() {
+                        self.analyze_expression(issues, expr, file_path, source_code, functions, bindings, state);
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn analyze_loop_body(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        body: &Statement,
+        file_path: &Path,
+        source_code: &str,
+        functions: &PromiseFunctions,
+        bindings: &ModuleBindings,
+        state: &mut FunctionState,
+    ) {
+        let in_loop = state.in_loop;
+        state.in_loop = true;
+        self.analyze_statement(issues, body, file_path, source_code, functions, bindings, state);
+        state.in_loop = in_loop;
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn analyze_class(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        class: &Class,
+        file_path: &Path,
+        source_code: &str,
+        functions: &PromiseFunctions,
+        bindings: &ModuleBindings,
+        state: &mut FunctionState,
+    ) {
+        for element in &class.body.body {
+            match element {
+                ClassElement::MethodDefinition(method) => {
+                    self.analyze_function(issues, &method.value, file_path, source_code, functions, bindings);
+                }
+                ClassElement::PropertyDefinition(prop) => {
+                    if let Some(value) = &prop.value {
+                        self.analyze_expression(issues, value, file_path, source_code, functions, bindings, state);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn analyze_function(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        func: &Function,
+        file_path: &Path,
+        source_code: &str,
+        functions: &PromiseFunctions,
+        bindings: &ModuleBindings,
+    ) {
+        if let Some(body) = &func.body {
+            let span = func.id.as_ref().map_or(func.span, |id| id.span);
+            let is_async = func.r#async && !func.generator;
+            self.analyze_function_body(issues, span, is_async, &func.params, body, false, file_path, source_code, functions, bindings);
+        }
+    }
+
+    /// Visit a function body with fresh loop/try state and report `async` functions that never await
+    #[allow(clippy::too_many_arguments)]
+    fn analyze_function_body(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        span: Span,
+        is_async: bool,
+        params: &FormalParameters,
+        body: &FunctionBody,
+        expression_body: bool,
+        file_path: &Path,
+        source_code: &str,
+        functions: &PromiseFunctions,
+        bindings: &ModuleBindings,
+    ) {
+        let mut state = FunctionState { is_async, ..FunctionState::default() };
+
+        let mut scoped;
+        let functions = if params.items.iter().any(|param| param.pattern.type_annotation.is_some()) {
+            scoped = functions.clone();
+            scoped.add_params(params);
+            &scoped
+        } else {
+            functions
+        };
+
+        if expression_body {
+            // async () => value: the expression is the return value, not a statement
+            for stmt in &body.statements {
+                if let Statement::ExpressionStatement(expr_stmt) = stmt {
+                    self.analyze_expression(issues, &expr_stmt.expression, file_path, source_code, functions, bindings, &mut state);
+                }
+            }
+        } else {
+            for stmt in &body.statements {
+                self.analyze_statement(issues, stmt, file_path, source_code, functions, bindings, &mut state);
+            }
+        }
+
+        if is_async && !state.saw_await && !body.statements.is_empty() {
+            self.add_issue(
+                issues,
+                file_path,
+                source_code,
+                span,
+                "Fungsi async tidak pernah menggunakan await. Hapus 'async' atau tambahkan await yang hilang".to_string(),
+                "require-await".to_string(),
+                Severity::Suggestion,
+                Category::BestPractice,
+            );
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn analyze_expression(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        expr: &Expression,
+        file_path: &Path,
+        source_code: &str,
+        functions: &PromiseFunctions,
+        bindings: &ModuleBindings,
+        state: &mut FunctionState,
+    ) {
+        match expr {
+            Expression::AwaitExpression(await_expr) => {
+                state.saw_await = true;
+                if state.in_loop {
+                    self.add_issue(
+                        issues,
+                        file_path,
+                        source_code,
+                        await_expr.span,
+                        "await di dalam loop menjalankan operasi satu per satu. Jika iterasi tidak saling bergantung, kumpulkan promise-nya dan gunakan Promise.all()".to_string(),
+                        "no-await-in-loop".to_string(),
+                        Severity::Warning,
+                        Category::Performance,
+                    );
+                }
+                self.analyze_expression(issues, &await_expr.argument, file_path, source_code, functions, bindings, state);
+            }
+            Expression::CallExpression(call_expr) => {
+                self.analyze_expression(issues, &call_expr.callee, file_path, source_code, functions, bindings, state);
+                for arg in &call_expr.arguments {
+                    if let Some(expr) = arg.as_expression() {
+                        self.analyze_expression(issues, expr, file_path, source_code, functions, bindings, state);
+                    }
+                }
+            }
+            Expression::NewExpression(new_expr) => {
+                if member_path(&new_expr.callee).as_deref() == Some("Promise") {
+                    let executor_is_async = match new_expr.arguments.first().and_then(|arg| arg.as_expression()) {
+                        Some(Expression::ArrowFunctionExpression(arrow)) => arrow.r#async,
+                        Some(Expression::FunctionExpression(func)) => func.r#async,
+                        _ => false,
+                    };
+                    if executor_is_async {
+                        self.add_issue(
+                            issues,
+                            file_path,
+                            source_code,
+                            new_expr.span,
+                            "Executor async pada new Promise(): error yang dilempar tidak menolak (reject) promise dan hilang begitu saja. Gunakan fungsi async biasa tanpa new Promise()".to_string(),
+                            "no-async-promise-executor".to_string(),
+                            Severity::Error,
+                            Category::CodeQuality,
+                        );
+                    }
+                }
+                for arg in &new_expr.arguments {
+                    if let Some(expr) = arg.as_expression() {
+                        self.analyze_expression(issues, expr, file_path, source_code, functions, bindings, state);
+                    }
+                }
+            }
+            Expression::ArrowFunctionExpression(arrow) => {
+                self.analyze_function_body(
+                    issues, arrow.span, arrow.r#async, &arrow.params, &arrow.body, arrow.expression,
+                    file_path, source_code, functions, bindings,
+                );
+            }
+            Expression::FunctionExpression(func) => {
+                self.analyze_function(issues, func, file_path, source_code, functions, bindings);
+            }
+            Expression::ClassExpression(class) => {
+                self.analyze_class(issues, class, file_path, source_code, functions, bindings, state);
+            }
+            Expression::AssignmentExpression(assign_expr) => {
+                self.analyze_expression(issues, &assign_expr.right, file_path, source_code, functions, bindings, state);
+            }
+            Expression::BinaryExpression(bin_expr) => {
+                self.analyze_expression(issues, &bin_expr.left, file_path, source_code, functions, bindings, state);
+                self.analyze_expression(issues, &bin_expr.right, file_path, source_code, functions, bindings, state);
+            }
+            Expression::LogicalExpression(logical) => {
+                self.analyze_expression(issues, &logical.left, file_path, source_code, functions, bindings, state);
+                self.analyze_expression(issues, &logical.right, file_path, source_code, functions, bindings, state);
+            }
+            Expression::ConditionalExpression(cond) => {
+                self.analyze_expression(issues, &cond.test, file_path, source_code, functions, bindings, state);
+                self.analyze_expression(issues, &cond.consequent, file_path, source_code, functions, bindings, state);
+                self.analyze_expression(issues, &cond.alternate, file_path, source_code, functions, bindings, state);
+            }
+            Expression::UnaryExpression(unary) => {
+                self.analyze_expression(issues, &unary.argument, file_path, source_code, functions, bindings, state);
+            }
+            Expression::ArrayExpression(array) => {
+                for elem in &array.elements {
+                    if let Some(expr) = elem.as_expression() {
+                        self.analyze_expression(issues, expr, file_path, source_code, functions, bindings, state);
+                    }
+                }
+            }
+            Expression::ObjectExpression(obj_expr) => {
+                for prop in &obj_expr.properties {
+                    if let ObjectPropertyKind::ObjectProperty(prop) = prop {
+                        self.analyze_expression(issues, &prop.value, file_path, source_code, functions, bindings, state);
+                    }
+                }
+            }
+            Expression::StaticMemberExpression(member) => {
+                self.analyze_expression(issues, &member.object, file_path, source_code, functions, bindings, state);
+            }
+            Expression::ComputedMemberExpression(member) => {
+                self.analyze_expression(issues, &member.object, file_path, source_code, functions, bindings, state);
+                self.analyze_expression(issues, &member.expression, file_path, source_code, functions, bindings, state);
+            }
+            Expression::ParenthesizedExpression(paren) => {
+                self.analyze_expression(issues, &paren.expression, file_path, source_code, functions, bindings, state);
+            }
+            Expression::SequenceExpression(seq) => {
+                for expr in &seq.expressions {
+                    self.analyze_expression(issues, expr, file_path, source_code, functions, bindings, state);
+                }
+            }
+            Expression::TemplateLiteral(template) => {
+                for expr in &template.expressions {
+                    self.analyze_expression(issues, expr, file_path, source_code, functions, bindings, state);
+                }
+            }
+            Expression::TSAsExpression(as_expr) => {
+                self.analyze_expression(issues, &as_expr.expression, file_path, source_code, functions, bindings, state);
+            }
+            Expression::JSXElement(element) => {
+                self.analyze_jsx_element(issues, element, file_path, source_code, functions, bindings, state);
+            }
+            _ => {}
+        }
+    }
+
+    /// Event handlers like `onClick={async () => ...}` live in JSX attributes
+    #[allow(clippy::too_many_arguments)]
+    fn analyze_jsx_element(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        element: &JSXElement,
+        file_path: &Path,
+        source_code: &str,
+        functions: &PromiseFunctions,
+        bindings: &ModuleBindings,
+        state: &mut FunctionState,
+    ) {
+        for attr in &element.opening_element.attributes {
+            if let JSXAttributeItem::Attribute(attr) = attr {
+                if let Some(JSXAttributeValue::ExpressionContainer(container)) = &attr.value {
+                    if let Some(expr) = container.expression.as_expression() {
+                        self.analyze_expression(issues, expr, file_path, source_code, functions, bindings, state);
+                    }
+                }
+            }
+        }
+        for child in &element.children {
+            match child {
+                JSXChild::Element(child) => {
+                    self.analyze_jsx_element(issues, child, file_path, source_code, functions, bindings, state);
+                }
+                JSXChild::ExpressionContainer(container) => {
+                    if let Some(expr) = container.expression.as_expression() {
+                        self.analyze_expression(issues, expr, file_path, source_code, functions, bindings, state);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// A promise used as a statement: nobody awaits it, returns it, or handles its rejection
+    fn check_floating(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        expr: &Expression,
+        file_path: &Path,
+        source_code: &str,
+        functions: &PromiseFunctions,
+        bindings: &ModuleBindings,
+    ) {
+        // `void save()` is an explicit fire-and-forget and never reaches this as a call
+        let Expression::CallExpression(call_expr) = expr else {
+            return;
+        };
+        if Self::handles_rejection(expr) {
+            return;
+        }
+
+        if Self::is_then_call(expr) {
+            self.add_issue(
+                issues,
+                file_path,
+                source_code,
+                call_expr.span,
+                "Rantai .then() tanpa penanganan rejection. Tambahkan .catch() atau argumen kedua pada .then() agar error tidak hilang".to_string(),
+                "promise-then-without-catch".to_string(),
+                Severity::Warning,
+                Category::CodeQuality,
+            );
+        } else if Self::returns_promise(call_expr, functions, bindings) {
+            let name = member_path(&call_expr.callee).unwrap_or_else(|| "promise".to_string());
+            self.add_issue(
+                issues,
+                file_path,
+                source_code,
+                call_expr.span,
+                format!(
+                    "Promise dari '{}' tidak di-await, di-return, atau diberi .catch(). Rejection-nya tidak tertangani. Gunakan 'void' jika memang disengaja",
+                    name
+                ),
+                "no-floating-promises".to_string(),
+                Severity::Error,
+                Category::CodeQuality,
+            );
+        }
+    }
+
+    /// `return await` is only needed inside `try`; without it the `catch` never sees the rejection
+    #[allow(clippy::too_many_arguments)]
+    fn check_return(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        argument: &Expression,
+        file_path: &Path,
+        source_code: &str,
+        functions: &PromiseFunctions,
+        bindings: &ModuleBindings,
+        state: &FunctionState,
+    ) {
+        if !state.is_async {
+            return;
+        }
+
+        match argument {
+            Expression::AwaitExpression(await_expr) if !state.in_try => {
+                self.add_issue(
+                    issues,
+                    file_path,
+                    source_code,
+                    await_expr.span,
+                    "'return await' di luar blok try tidak diperlukan. Kembalikan promise-nya langsung".to_string(),
+                    "return-await".to_string(),
+                    Severity::Suggestion,
+                    Category::BestPractice,
+                );
+            }
+            Expression::CallExpression(call_expr) if state.in_try && Self::returns_promise(call_expr, functions, bindings) => {
+                self.add_issue(
+                    issues,
+                    file_path,
+                    source_code,
+                    call_expr.span,
+                    "Promise di-return tanpa await di dalam blok try, sehingga rejection-nya tidak ditangkap oleh catch. Gunakan 'return await'".to_string(),
+                    "return-await".to_string(),
+                    Severity::Warning,
+                    Category::CodeQuality,
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Default for PromiseAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,1177 @@
+use super::bindings::{binding_names, last_segment, member_path};
+use super::Analyzer;
+use crate::types::{CodeIssue, Category, Severity};
+use oxc_ast::ast::*;
+use oxc_span::{GetSpan, Span};
+use std::collections::HashSet;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Hooks whose first argument runs again only when the dependency array changes
+const DEPENDENCY_HOOKS: &[&str] = &["useEffect", "useLayoutEffect", "useInsertionEffect", "useCallback", "useMemo"];
+
+/// Hooks whose return value keeps the same identity across renders
+const STABLE_HOOKS: &[&str] = &["useRef", "useCallback", "useMemo"];
+
+/// Names declared directly in a component or custom hook body
+#[derive(Debug, Default)]
+struct ComponentScope {
+    /// Props, state, and local variables that can change between renders
+    locals: HashSet<String>,
+    /// State setters, dispatchers, and refs: safe to leave out of dependency arrays
+    stable: HashSet<String>,
+    /// Objects, arrays, and functions recreated on every render
+    unstable: HashSet<String>,
+}
+
+impl ComponentScope {
+    fn collect(params: &FormalParameters, body: &FunctionBody) -> Self {
+        let mut scope = Self::default();
+
+        for param in &params.items {
+            let mut names = Vec::new();
+            binding_names(&param.pattern, &mut names);
+            scope.locals.extend(names);
+        }
+
+        for stmt in &body.statements {
+            match stmt {
+                Statement::VariableDeclaration(var_decl) => {
+                    for var in &var_decl.declarations {
+                        let mut names = Vec::new();
+                        binding_names(&var.id, &mut names);
+                        scope.locals.extend(names.iter().cloned());
+
+                        let Some(init) = &var.init else {
+                            continue;
+                        };
+                        match init {
+                            Expression::CallExpression(call) => {
+                                let hook = member_path(&call.callee).unwrap_or_default();
+                                let hook = last_segment(&hook);
+                                if hook == "useState" || hook == "useReducer" {
+                                    // const [value, setValue] = useState()
+                                    if let BindingPatternKind::ArrayPattern(arr_pattern) = &var.id.kind {
+                                        if let Some(Some(setter)) = arr_pattern.elements.get(1) {
+                                            let mut setters = Vec::new();
+                                            binding_names(setter, &mut setters);
+                                            scope.stable.extend(setters);
+                                        }
+                                    }
+                                } else if STABLE_HOOKS.contains(&hook) {
+                                    scope.stable.extend(names);
+                                }
+                            }
+                            Expression::ObjectExpression(_)
+                            | Expression::ArrayExpression(_)
+                            | Expression::ArrowFunctionExpression(_)
+                            | Expression::FunctionExpression(_)
+                            | Expression::NewExpression(_) => {
+                                scope.unstable.extend(names);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                Statement::FunctionDeclaration(func) => {
+                    if let Some(id) = &func.id {
+                        scope.locals.insert(id.name.to_string());
+                        scope.unstable.insert(id.name.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        scope
+    }
+}
+
+/// Where the code being visited sits relative to the enclosing component or hook
+#[derive(Debug, Clone, Default)]
+struct Context {
+    /// Scope of the innermost component or custom hook, `None` outside of them
+    scope: Option<Rc<ComponentScope>>,
+    /// The innermost component or hook is a component
+    in_component: bool,
+    /// Inside a callback or helper defined in the component or hook body
+    nested_function: bool,
+    /// Only runs on some renders: inside a branch, after an early return, or in `&&`/`?:`
+    conditional: bool,
+    in_loop: bool,
+    /// Index parameter of the enclosing `.map()` callback
+    map_index: Option<String>,
+}
+
+pub struct ReactAnalyzer;
+
+impl ReactAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn get_line_column(source_code: &str, span: Span) -> (usize, usize) {
+        let start = span.start as usize;
+        let before = &source_code[..start];
+        let line = before.lines().count();
+        let last_newline = before.rfind('\n').unwrap_or(0);
+        let column = start - last_newline;
+        (line, column)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn add_issue(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        file_path: &Path,
+        source_code: &str,
+        span: Span,
+        message: String,
+        rule: String,
+        severity: Severity,
+        category: Category,
+    ) {
+        let (line, column) = Self::get_line_column(source_code, span);
+        let start = span.start as usize;
+        let end = span.end as usize;
+        let code_snippet = source_code.get(start..end).map(|s| s.to_string());
+
+        issues.push(CodeIssue {
+            file_path: file_path.display().to_string(),
+            line,
+            column,
+            end_line: None,
+            end_column: None,
+            message,
+            severity,
+            category,
+            rule,
+            code_snippet,
+        });
+    }
+
+    fn is_component_name(name: &str) -> bool {
+        name.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+    }
+
+    /// `useState`, `useFetch`, ... but not the conditional-safe `use()`
+    fn is_hook_name(name: &str) -> bool {
+        name.strip_prefix("use")
+            .and_then(|rest| rest.chars().next())
+            .is_some_and(|c| c.is_ascii_uppercase())
+    }
+
+    fn attribute_name<'a>(attr: &'a JSXAttribute) -> Option<&'a str> {
+        match &attr.name {
+            JSXAttributeName::Identifier(ident) => Some(ident.name.as_str()),
+            _ => None,
+        }
+    }
+
+    fn find_attribute<'a, 'b>(element: &'b JSXOpeningElement<'a>, name: &str) -> Option<&'b JSXAttribute<'a>> {
+        element.attributes.iter().find_map(|item| match item {
+            JSXAttributeItem::Attribute(attr) if Self::attribute_name(attr) == Some(name) => Some(&**attr),
+            _ => None,
+        })
+    }
+
+    /// Text of an attribute given as `"..."`, `{'...'}` or a template without expressions
+    fn static_attribute_value(attr: &JSXAttribute) -> Option<String> {
+        match &attr.value {
+            Some(JSXAttributeValue::StringLiteral(lit)) => Some(lit.value.to_string()),
+            Some(JSXAttributeValue::ExpressionContainer(container)) => match container.expression.as_expression() {
+                Some(Expression::StringLiteral(lit)) => Some(lit.value.to_string()),
+                Some(Expression::TemplateLiteral(template)) if template.expressions.is_empty() => {
+                    template.quasis.first().map(|quasi| quasi.value.raw.to_string())
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Whether a `key` expression is built from the `.map()` index
+    fn uses_index(expr: &Expression, index: &str) -> bool {
+        match expr {
+            Expression::Identifier(ident) => ident.name == index,
+            Expression::TemplateLiteral(template) => template.expressions.iter().any(|e| Self::uses_index(e, index)),
+            Expression::BinaryExpression(bin_expr) => {
+                Self::uses_index(&bin_expr.left, index) || Self::uses_index(&bin_expr.right, index)
+            }
+            Expression::CallExpression(call) => {
+                // i.toString(), String(i)
+                let callee_uses = match &call.callee {
+                    Expression::StaticMemberExpression(member) => Self::uses_index(&member.object, index),
+                    _ => false,
+                };
+                callee_uses
+                    || call.arguments.iter().any(|arg| arg.as_expression().is_some_and(|e| Self::uses_index(e, index)))
+            }
+            Expression::ParenthesizedExpression(paren) => Self::uses_index(&paren.expression, index),
+            _ => false,
+        }
+    }
+
+    fn unwrap_parens<'a, 'b>(expr: &'b Expression<'a>) -> &'b Expression<'a> {
+        match expr {
+            Expression::ParenthesizedExpression(paren) => Self::unwrap_parens(&paren.expression),
+            _ => expr,
+        }
+    }
+
+    /// Expressions a callback body returns at its top level
+    fn returned_expressions<'a, 'b>(body: &'b FunctionBody<'a>, expression_body: bool) -> Vec<&'b Expression<'a>> {
+        if expression_body {
+            return body.statements.iter().filter_map(|stmt| match stmt {
+                Statement::ExpressionStatement(expr_stmt) => Some(Self::unwrap_parens(&expr_stmt.expression)),
+                _ => None,
+            }).collect();
+        }
+
+        body.statements.iter().filter_map(|stmt| match stmt {
+            Statement::ReturnStatement(ret_stmt) => ret_stmt.argument.as_ref().map(Self::unwrap_parens),
+            _ => None,
+        }).collect()
+    }
+
+    fn has_early_return(stmt: &Statement) -> bool {
+        match stmt {
+            Statement::ReturnStatement(_) => true,
+            Statement::BlockStatement(block) => block.body.iter().any(|s| matches!(s, Statement::ReturnStatement(_))),
+            _ => false,
+        }
+    }
+}
+
+impl Analyzer for ReactAnalyzer {
+    fn analyze(&self, program: &Program, file_path: &Path, source_code: &str) -> Vec<CodeIssue> {
+        let mut issues = Vec::new();
+
+        self.analyze_statements(&mut issues, &program.body, &Context::default(), file_path, source_code);
+
+        issues
+    }
+}
+
+impl ReactAnalyzer {
+    fn analyze_statements(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        stmts: &[Statement],
+        ctx: &Context,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        let mut ctx = ctx.clone();
+        for stmt in stmts {
+            self.analyze_statement(issues, stmt, &ctx, file_path, source_code);
+
+            // Everything after `if (...) return` only runs on some renders
+            if ctx.scope.is_some() && !ctx.nested_function {
+                if let Statement::IfStatement(if_stmt) = stmt {
+                    if Self::has_early_return(&if_stmt.consequent) {
+                        ctx.conditional = true;
+                    }
+                }
+            }
+        }
+    }
+
+    fn analyze_statement(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        stmt: &Statement,
+        ctx: &Context,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        match stmt {
+            Statement::VariableDeclaration(var_decl) => {
+                self.analyze_variable_declaration(issues, var_decl, ctx, file_path, source_code);
+            }
+            Statement::ExpressionStatement(expr_stmt) => {
+                self.analyze_expression(issues, &expr_stmt.expression, ctx, file_path, source_code);
+            }
+            Statement::ReturnStatement(ret_stmt) => {
+                if let Some(argument) = &ret_stmt.argument {
+                    self.analyze_expression(issues, argument, ctx, file_path, source_code);
+                }
+            }
+            Statement::BlockStatement(block) => {
+                self.analyze_statements(issues, &block.body, ctx, file_path, source_code);
+            }
+            Statement::IfStatement(if_stmt) => {
+                self.analyze_expression(issues, &if_stmt.test, ctx, file_path, source_code);
+                let branch = Context { conditional: true, ..ctx.clone() };
+                self.analyze_statement(issues, &if_stmt.consequent, &branch, file_path, source_code);
+                if let Some(alternate) = &if_stmt.alternate {
+                    self.analyze_statement(issues, alternate, &branch, file_path, source_code);
+                }
+            }
+            Statement::ForStatement(for_stmt) => {
+                let body = Context { in_loop: true, ..ctx.clone() };
+                self.analyze_statement(issues, &for_stmt.body, &body, file_path, source_code);
+            }
+            Statement::ForOfStatement(for_of) => {
+                self.analyze_expression(issues, &for_of.right, ctx, file_path, source_code);
+                let body = Context { in_loop: true, ..ctx.clone() };
+                self.analyze_statement(issues, &for_of.body, &body, file_path, source_code);
+            }
+            Statement::ForInStatement(for_in) => {
+                let body = Context { in_loop: true, ..ctx.clone() };
+                self.analyze_statement(issues, &for_in.body, &body, file_path, source_code);
+            }
+            Statement::WhileStatement(while_stmt) => {
+                let body = Context { in_loop: true, ..ctx.clone() };
+                self.analyze_statement(issues, &while_stmt.body, &body, file_path, source_code);
+            }
+            Statement::DoWhileStatement(do_while) => {
+                let body = Context { in_loop: true, ..ctx.clone() };
+                self.analyze_statement(issues, &do_while.body, &body, file_path, source_code);
+            }
+            Statement::TryStatement(try_stmt) => {
+                self.analyze_statements(issues, &try_stmt.block.body, ctx, file_path, source_code);
+                if let Some(handler) = &try_stmt.handler {
+                    let branch = Context { conditional: true, ..ctx.clone() };
+                    self.analyze_statements(issues, &handler.body.body, &branch, file_path, source_code);
+                }
+                if let Some(finalizer) = &try_stmt.finalizer {
+                    self.analyze_statements(issues, &finalizer.body, ctx, file_path, source_code);
+                }
+            }
+            Statement::SwitchStatement(switch_stmt) => {
+                let branch = Context { conditional: true, ..ctx.clone() };
+                for case in &switch_stmt.cases {
+                    self.analyze_statements(issues, &case.consequent, &branch, file_path, source_code);
+                }
+            }
+            Statement::FunctionDeclaration(func) => {
+                let name = func.id.as_ref().map(|id| id.name.as_str());
+                self.analyze_function(issues, name, func, ctx, file_path, source_code);
+            }
+            Statement::ClassDeclaration(class) => self.analyze_class(issues, class, ctx, file_path, source_code),
+            Statement::ExportNamedDeclaration(export) => match &export.declaration {
+                Some(Declaration::VariableDeclaration(var_decl)) => {
+                    self.analyze_variable_declaration(issues, var_decl, ctx, file_path, source_code);
+                }
+                Some(Declaration::FunctionDeclaration(func)) => {
+                    let name = func.id.as_ref().map(|id| id.name.as_str());
+                    self.analyze_function(issues, name, func, ctx, file_path, source_code);
+                }
+                Some(Declaration::ClassDeclaration(class)) => {
+                    self.analyze_class(issues, class, ctx, file_path, source_code);
+                }
+                _ => {}
+            },
+            Statement::ExportDefaultDeclaration(export) => match &export.declaration {
+                ExportDefaultDeclarationKind::FunctionDeclaration(func) => {
+                    // An anonymous default export is still treated as a component
+                    let name = func.id.as_ref().map_or("Default", |id| id.name.as_str());
+                    self.analyze_function(issues, Some(name), func, ctx, file_path, source_code);
+                }
+                ExportDefaultDeclarationKind::ClassDeclaration(class) => {
+                    self.analyze_class(issues, class, ctx, file_path, source_code);
+                }
+                declaration => {
+                    if let Some(expr) = declaration.as_expression() {
+                        self.analyze_expression(issues, expr, ctx, file_path, source_code);
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    fn analyze_variable_declaration(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        var_decl: &VariableDeclaration,
+        ctx: &Context,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        for var in &var_decl.declarations {
+            let Some(init) = &var.init else {
+                continue;
+            };
+
+            let name = match &var.id.kind {
+                BindingPatternKind::BindingIdentifier(ident) => Some(ident.name.as_str()),
+                _ => None,
+            };
+
+            // const Item = () => ..., const Item = memo(function () { ... })
+            let function = match init {
+                Expression::CallExpression(call)
+                    if matches!(
+                        member_path(&call.callee).as_deref().map(last_segment),
+                        Some("memo") | Some("forwardRef")
+                    ) =>
+                {
+                    call.arguments.first().and_then(|arg| arg.as_expression())
+                }
+                _ => Some(init),
+            };
+
+            match function {
+                Some(Expression::ArrowFunctionExpression(arrow)) => {
+                    self.analyze_function_like(
+                        issues, name, var.span, &arrow.params, &arrow.body, ctx, file_path, source_code,
+                    );
+                }
+                Some(Expression::FunctionExpression(func)) => {
+                    if let Some(body) = &func.body {
+                        self.analyze_function_like(
+                            issues, name, var.span, &func.params, body, ctx, file_path, source_code,
+                        );
+                    }
+                }
+                _ => self.analyze_expression(issues, init, ctx, file_path, source_code),
+            }
+        }
+    }
+
+    fn analyze_function(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        name: Option<&str>,
+        func: &Function,
+        ctx: &Context,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        if let Some(body) = &func.body {
+            self.analyze_function_like(issues, name, func.span, &func.params, body, ctx, file_path, source_code);
+        }
+    }
+
+    /// Class components: `render()` and the other methods are walked like plain functions,
+    /// since hooks don't apply to them
+    fn analyze_class(&self, issues: &mut Vec<CodeIssue>, class: &Class, ctx: &Context, file_path: &Path, source_code: &str) {
+        for element in &class.body.body {
+            match element {
+                ClassElement::MethodDefinition(method) => {
+                    self.analyze_function(issues, None, &method.value, ctx, file_path, source_code);
+                }
+                ClassElement::PropertyDefinition(prop) => {
+                    if let Some(value) = &prop.value {
+                        self.analyze_expression(issues, value, ctx, file_path, source_code);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Enter a function: components and custom hooks start a new scope, anything else nested
+    /// in one is a callback where hooks must not be called
+    #[allow(clippy::too_many_arguments)]
+    fn analyze_function_like(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        name: Option<&str>,
+        span: Span,
+        params: &FormalParameters,
+        body: &FunctionBody,
+        ctx: &Context,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        let is_component = name.is_some_and(Self::is_component_name);
+        let is_hook = name.is_some_and(Self::is_hook_name);
+
+        if is_component && ctx.in_component {
+            self.add_issue(
+                issues,
+                file_path,
+                source_code,
+                span,
+                format!(
+                    "Komponen '{}' didefinisikan di dalam komponen lain. Komponen ini dibuat ulang di setiap render sehingga state-nya selalu hilang. Pindahkan ke level modul",
+                    name.unwrap_or_default()
+                ),
+                "react-no-nested-components".to_string(),
+                Severity::Warning,
+                Category::React,
+            );
+        }
+
+        let inner = if is_component || is_hook {
+            Context {
+                scope: Some(Rc::new(ComponentScope::collect(params, body))),
+                in_component: is_component,
+                ..Context::default()
+            }
+        } else if ctx.scope.is_some() {
+            Context {
+                nested_function: true,
+                conditional: false,
+                in_loop: false,
+                ..ctx.clone()
+            }
+        } else {
+            Context {
+                map_index: ctx.map_index.clone(),
+                ..Context::default()
+            }
+        };
+
+        self.analyze_statements(issues, &body.statements, &inner, file_path, source_code);
+    }
+
+    fn analyze_expression(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        expr: &Expression,
+        ctx: &Context,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        match expr {
+            Expression::CallExpression(call_expr) => {
+                self.check_call(issues, call_expr, ctx, file_path, source_code);
+
+                if let Some(callback) = Self::map_callback(call_expr) {
+                    self.analyze_map_callback(issues, callback, ctx, file_path, source_code);
+                    self.analyze_expression(issues, &call_expr.callee, ctx, file_path, source_code);
+                    return;
+                }
+
+                self.analyze_expression(issues, &call_expr.callee, ctx, file_path, source_code);
+                for arg in &call_expr.arguments {
+                    if let Some(expr) = arg.as_expression() {
+                        self.analyze_expression(issues, expr, ctx, file_path, source_code);
+                    }
+                }
+            }
+            Expression::NewExpression(new_expr) => {
+                for arg in &new_expr.arguments {
+                    if let Some(expr) = arg.as_expression() {
+                        self.analyze_expression(issues, expr, ctx, file_path, source_code);
+                    }
+                }
+            }
+            Expression::AssignmentExpression(assign_expr) => {
+                self.analyze_expression(issues, &assign_expr.right, ctx, file_path, source_code);
+            }
+            Expression::BinaryExpression(bin_expr) => {
+                self.analyze_expression(issues, &bin_expr.left, ctx, file_path, source_code);
+                self.analyze_expression(issues, &bin_expr.right, ctx, file_path, source_code);
+            }
+            Expression::LogicalExpression(logical) => {
+                self.analyze_expression(issues, &logical.left, ctx, file_path, source_code);
+                let branch = Context { conditional: true, ..ctx.clone() };
+                self.analyze_expression(issues, &logical.right, &branch, file_path, source_code);
+            }
+            Expression::ConditionalExpression(cond) => {
+                self.analyze_expression(issues, &cond.test, ctx, file_path, source_code);
+                let branch = Context { conditional: true, ..ctx.clone() };
+                self.analyze_expression(issues, &cond.consequent, &branch, file_path, source_code);
+                self.analyze_expression(issues, &cond.alternate, &branch, file_path, source_code);
+            }
+            Expression::ArrayExpression(array) => {
+                for elem in &array.elements {
+                    if let Some(expr) = elem.as_expression() {
+                        self.analyze_expression(issues, expr, ctx, file_path, source_code);
+                    }
+                }
+            }
+            Expression::ObjectExpression(obj_expr) => {
+                for prop in &obj_expr.properties {
+                    if let ObjectPropertyKind::ObjectProperty(prop) = prop {
+                        self.analyze_expression(issues, &prop.value, ctx, file_path, source_code);
+                    }
+                }
+            }
+            Expression::ArrowFunctionExpression(arrow) => {
+                self.analyze_function_like(issues, None, arrow.span, &arrow.params, &arrow.body, ctx, file_path, source_code);
+            }
+            Expression::FunctionExpression(func) => {
+                let name = func.id.as_ref().map(|id| id.name.as_str());
+                self.analyze_function(issues, name, func, ctx, file_path, source_code);
+            }
+            Expression::StaticMemberExpression(member) => {
+                self.analyze_expression(issues, &member.object, ctx, file_path, source_code);
+            }
+            Expression::ComputedMemberExpression(member) => {
+                self.analyze_expression(issues, &member.object, ctx, file_path, source_code);
+                self.analyze_expression(issues, &member.expression, ctx, file_path, source_code);
+            }
+            Expression::ParenthesizedExpression(paren) => {
+                self.analyze_expression(issues, &paren.expression, ctx, file_path, source_code);
+            }
+            Expression::AwaitExpression(await_expr) => {
+                self.analyze_expression(issues, &await_expr.argument, ctx, file_path, source_code);
+            }
+            Expression::SequenceExpression(seq) => {
+                for expr in &seq.expressions {
+                    self.analyze_expression(issues, expr, ctx, file_path, source_code);
+                }
+            }
+            Expression::TemplateLiteral(template) => {
+                for expr in &template.expressions {
+                    self.analyze_expression(issues, expr, ctx, file_path, source_code);
+                }
+            }
+            Expression::TSAsExpression(as_expr) => {
+                self.analyze_expression(issues, &as_expr.expression, ctx, file_path, source_code);
+            }
+            Expression::JSXElement(element) => {
+                self.analyze_jsx_element(issues, element, ctx, file_path, source_code);
+            }
+            Expression::JSXFragment(fragment) => {
+                self.analyze_jsx_children(issues, &fragment.children, ctx, file_path, source_code);
+            }
+            _ => {}
+        }
+    }
+
+    fn analyze_jsx_element(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        element: &JSXElement,
+        ctx: &Context,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        let opening = &element.opening_element;
+
+        for item in &opening.attributes {
+            let JSXAttributeItem::Attribute(attr) = item else {
+                continue;
+            };
+
+            match Self::attribute_name(attr) {
+                Some("dangerouslySetInnerHTML") => {
+                    self.add_issue(
+                        issues,
+                        file_path,
+                        source_code,
+                        attr.span,
+                        "dangerouslySetInnerHTML menyisipkan HTML mentah dan rawan XSS. Sanitasi HTML-nya (misalnya DOMPurify) atau render sebagai teks".to_string(),
+                        "react-no-danger".to_string(),
+                        Severity::Warning,
+                        Category::Security,
+                    );
+                }
+                Some("href") | Some("src") | Some("action") | Some("formAction") => {
+                    if let Some(url) = Self::static_attribute_value(attr) {
+                        let url: String = url.chars().filter(|c| !c.is_whitespace()).collect();
+                        if url.to_lowercase().starts_with("javascript:") {
+                            self.add_issue(
+                                issues,
+                                file_path,
+                                source_code,
+                                attr.span,
+                                "URL 'javascript:' adalah bentuk eval() dan diblokir oleh React versi mendatang. Gunakan event handler seperti onClick".to_string(),
+                                "react-no-script-url".to_string(),
+                                Severity::Error,
+                                Category::Security,
+                            );
+                        }
+                    }
+                }
+                Some("target") if Self::static_attribute_value(attr).as_deref() == Some("_blank") => {
+                    let rel = Self::find_attribute(opening, "rel").map(Self::static_attribute_value);
+                    let safe = match rel {
+                        Some(Some(rel)) => rel.split_whitespace().any(|r| r == "noopener" || r == "noreferrer"),
+                        // rel={someVariable}: can't tell
+                        Some(None) => true,
+                        None => false,
+                    };
+                    if !safe {
+                        self.add_issue(
+                            issues,
+                            file_path,
+                            source_code,
+                            attr.span,
+                            "target=\"_blank\" tanpa rel=\"noopener noreferrer\" memberi halaman tujuan akses ke window.opener (reverse tabnabbing)".to_string(),
+                            "react-no-target-blank".to_string(),
+                            Severity::Warning,
+                            Category::Security,
+                        );
+                    }
+                }
+                Some("key") => {
+                    if let Some(index) = &ctx.map_index {
+                        if let Some(JSXAttributeValue::ExpressionContainer(container)) = &attr.value {
+                            if let Some(expr) = container.expression.as_expression() {
+                                if Self::uses_index(expr, index) {
+                                    self.add_issue(
+                                        issues,
+                                        file_path,
+                                        source_code,
+                                        attr.span,
+                                        format!("Index array '{}' dipakai sebagai key. Key berubah saat item ditambah, dihapus, atau diurutkan ulang; gunakan ID yang stabil dari data", index),
+                                        "react-no-array-index-key".to_string(),
+                                        Severity::Warning,
+                                        Category::React,
+                                    );
+                                }
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+
+            if let Some(JSXAttributeValue::ExpressionContainer(container)) = &attr.value {
+                if let Some(expr) = container.expression.as_expression() {
+                    self.analyze_expression(issues, expr, ctx, file_path, source_code);
+                }
+            }
+        }
+
+        self.analyze_jsx_children(issues, &element.children, ctx, file_path, source_code);
+    }
+
+    fn analyze_jsx_children(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        children: &[JSXChild],
+        ctx: &Context,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        for child in children {
+            match child {
+                JSXChild::Element(element) => self.analyze_jsx_element(issues, element, ctx, file_path, source_code),
+                JSXChild::Fragment(fragment) => {
+                    self.analyze_jsx_children(issues, &fragment.children, ctx, file_path, source_code);
+                }
+                JSXChild::ExpressionContainer(container) => {
+                    if let Some(expr) = container.expression.as_expression() {
+                        self.analyze_expression(issues, expr, ctx, file_path, source_code);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// The callback of `items.map(cb)` when it is written inline
+    fn map_callback<'a, 'b>(call_expr: &'b CallExpression<'a>) -> Option<&'b Expression<'a>> {
+        let Expression::StaticMemberExpression(member) = &call_expr.callee else {
+            return None;
+        };
+        if member.property.name != "map" {
+            return None;
+        }
+        call_expr.arguments.first()
+            .and_then(|arg| arg.as_expression())
+            .filter(|expr| matches!(expr, Expression::ArrowFunctionExpression(_) | Expression::FunctionExpression(_)))
+    }
+
+    fn analyze_map_callback(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        callback: &Expression,
+        ctx: &Context,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        let (span, params, body, expression_body) = match callback {
+            Expression::ArrowFunctionExpression(arrow) => (arrow.span, &arrow.params, &*arrow.body, arrow.expression),
+            Expression::FunctionExpression(func) => match &func.body {
+                Some(body) => (func.span, &func.params, &**body, false),
+                None => return,
+            },
+            _ => return,
+        };
+
+        for returned in Self::returned_expressions(body, expression_body) {
+            match returned {
+                Expression::JSXElement(element) if Self::find_attribute(&element.opening_element, "key").is_none() => {
+                    self.add_issue(
+                        issues,
+                        file_path,
+                        source_code,
+                        element.opening_element.span,
+                        "Elemen yang dikembalikan dari .map() tidak memiliki prop 'key'. React membutuhkan key unik untuk setiap item list".to_string(),
+                        "react-jsx-key".to_string(),
+                        Severity::Warning,
+                        Category::React,
+                    );
+                }
+                Expression::JSXFragment(fragment) => {
+                    self.add_issue(
+                        issues,
+                        file_path,
+                        source_code,
+                        fragment.span,
+                        "Fragment singkat <>...</> di dalam .map() tidak bisa diberi key. Gunakan <Fragment key={...}>".to_string(),
+                        "react-jsx-key".to_string(),
+                        Severity::Warning,
+                        Category::React,
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        let index = params.items.get(1).and_then(|param| match &param.pattern.kind {
+            BindingPatternKind::BindingIdentifier(ident) => Some(ident.name.to_string()),
+            _ => None,
+        });
+        let map_ctx = Context { map_index: index, ..ctx.clone() };
+        self.analyze_function_like(issues, None, span, params, body, &map_ctx, file_path, source_code);
+    }
+
+    fn check_call(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        call_expr: &CallExpression,
+        ctx: &Context,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        let Some(callee) = member_path(&call_expr.callee) else {
+            return;
+        };
+        let hook = last_segment(&callee);
+        if !Self::is_hook_name(hook) {
+            return;
+        }
+        let Some(scope) = &ctx.scope else {
+            return;
+        };
+
+        let violation = if ctx.nested_function {
+            Some("di dalam fungsi bersarang (callback)")
+        } else if ctx.in_loop {
+            Some("di dalam loop")
+        } else if ctx.conditional {
+            Some("secara kondisional")
+        } else {
+            None
+        };
+
+        if let Some(violation) = violation {
+            self.add_issue(
+                issues,
+                file_path,
+                source_code,
+                call_expr.span,
+                format!(
+                    "Hook '{}' dipanggil {}. Hook harus dipanggil di level teratas komponen atau custom hook, dalam urutan yang sama di setiap render",
+                    hook, violation
+                ),
+                "rules-of-hooks".to_string(),
+                Severity::Error,
+                Category::React,
+            );
+            return;
+        }
+
+        if DEPENDENCY_HOOKS.contains(&hook) {
+            self.check_dependencies(issues, call_expr, hook, scope, file_path, source_code);
+        }
+    }
+
+    fn check_dependencies(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        call_expr: &CallExpression,
+        hook: &str,
+        scope: &ComponentScope,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        let Some(callback) = call_expr.arguments.first().and_then(|arg| arg.as_expression()) else {
+            return;
+        };
+        let (params, body) = match callback {
+            Expression::ArrowFunctionExpression(arrow) => (&arrow.params, &*arrow.body),
+            Expression::FunctionExpression(func) => match &func.body {
+                Some(body) => (&func.params, &**body),
+                None => return,
+            },
+            _ => return,
+        };
+
+        let mut references = Vec::new();
+        let mut declared = Vec::new();
+        for param in &params.items {
+            binding_names(&param.pattern, &mut declared);
+        }
+        for stmt in &body.statements {
+            Self::collect_statement_references(stmt, &mut references, &mut declared);
+        }
+
+        let deps = match call_expr.arguments.get(1).and_then(|arg| arg.as_expression()) {
+            Some(Expression::ArrayExpression(array)) => array,
+            Some(_) => return,
+            None => {
+                // No array: the effect runs after every render, which loops forever if it sets state
+                let sets_state = references.iter().any(|path| scope.stable.contains(path) && path.starts_with("set"));
+                if sets_state || hook == "useCallback" || hook == "useMemo" {
+                    self.add_issue(
+                        issues,
+                        file_path,
+                        source_code,
+                        call_expr.span,
+                        if sets_state {
+                            format!("{} tanpa array dependensi memanggil setter state sehingga memicu render ulang tanpa henti. Tambahkan array dependensi", hook)
+                        } else {
+                            format!("{} tanpa array dependensi dihitung ulang di setiap render. Tambahkan array dependensi", hook)
+                        },
+                        "react-hooks-exhaustive-deps".to_string(),
+                        Severity::Warning,
+                        Category::React,
+                    );
+                }
+                return;
+            }
+        };
+
+        let mut listed = Vec::new();
+        for elem in &deps.elements {
+            let Some(expr) = elem.as_expression() else {
+                continue;
+            };
+            let expr = Self::unwrap_parens(expr);
+
+            let unstable = match expr {
+                Expression::ObjectExpression(_)
+                | Expression::ArrayExpression(_)
+                | Expression::ArrowFunctionExpression(_)
+                | Expression::FunctionExpression(_) => Some("literal"),
+                Expression::Identifier(ident) if scope.unstable.contains(ident.name.as_str()) => {
+                    Some(ident.name.as_str())
+                }
+                _ => None,
+            };
+            if let Some(unstable) = unstable {
+                let message = if unstable == "literal" {
+                    format!("Dependensi {} berupa literal objek/array/fungsi yang dibuat ulang di setiap render, sehingga hook selalu berjalan ulang", hook)
+                } else {
+                    format!(
+                        "Dependensi '{}' dibuat ulang di setiap render, sehingga {} selalu berjalan ulang. Bungkus dengan useMemo/useCallback atau pindahkan ke dalam hook",
+                        unstable, hook
+                    )
+                };
+                self.add_issue(
+                    issues,
+                    file_path,
+                    source_code,
+                    expr.span(),
+                    message,
+                    "react-hooks-exhaustive-deps".to_string(),
+                    Severity::Warning,
+                    Category::React,
+                );
+            }
+
+            if let Some(path) = member_path(expr) {
+                listed.push(path);
+            }
+        }
+
+        let mut missing: Vec<String> = Vec::new();
+        for path in &references {
+            let root = path.split('.').next().unwrap_or(path).to_string();
+            if !scope.locals.contains(&root) || scope.stable.contains(&root) || declared.contains(&root) {
+                continue;
+            }
+            let covered = listed.iter().any(|dep| *dep == root || dep.starts_with(&format!("{}.", root)));
+            if !covered && !missing.contains(&root) {
+                missing.push(root);
+            }
+        }
+
+        if !missing.is_empty() {
+            self.add_issue(
+                issues,
+                file_path,
+                source_code,
+                deps.span,
+                format!(
+                    "{} tidak mencantumkan dependensi: {}. Nilai lama akan terpakai (stale closure)",
+                    hook,
+                    missing.join(", ")
+                ),
+                "react-hooks-exhaustive-deps".to_string(),
+                Severity::Warning,
+                Category::React,
+            );
+        }
+    }
+
+    fn collect_statement_references(stmt: &Statement, references: &mut Vec<String>, declared: &mut Vec<String>) {
+        match stmt {
+            Statement::VariableDeclaration(var_decl) => {
+                for var in &var_decl.declarations {
+                    binding_names(&var.id, declared);
+                    if let Some(init) = &var.init {
+                        Self::collect_references(init, references, declared);
+                    }
+                }
+            }
+            Statement::ExpressionStatement(expr_stmt) => {
+                Self::collect_references(&expr_stmt.expression, references, declared);
+            }
+            Statement::ReturnStatement(ret_stmt) => {
+                if let Some(argument) = &ret_stmt.argument {
+                    Self::collect_references(argument, references, declared);
+                }
+            }
+            Statement::BlockStatement(block) => {
+                for stmt in &block.body {
+                    Self::collect_statement_references(stmt, references, declared);
+                }
+            }
+            Statement::IfStatement(if_stmt) => {
+                Self::collect_references(&if_stmt.test, references, declared);
+                Self::collect_statement_references(&if_stmt.consequent, references, declared);
+                if let Some(alternate) = &if_stmt.alternate {
+                    Self::collect_statement_references(alternate, references, declared);
+                }
+            }
+            Statement::ForOfStatement(for_of) => {
+                Self::collect_references(&for_of.right, references, declared);
+                Self::collect_statement_references(&for_of.body, references, declared);
+            }
+            Statement::ForStatement(for_stmt) => {
+                Self::collect_statement_references(&for_stmt.body, references, declared);
+            }
+            Statement::WhileStatement(while_stmt) => {
+                Self::collect_references(&while_stmt.test, references, declared);
+                Self::collect_statement_references(&while_stmt.body, references, declared);
+            }
+            Statement::TryStatement(try_stmt) => {
+                for stmt in &try_stmt.block.body {
+                    Self::collect_statement_references(stmt, references, declared);
+                }
+                if let Some(handler) = &try_stmt.handler {
+                    for stmt in &handler.body.body {
+                        Self::collect_statement_references(stmt, references, declared);
+                    }
+                }
+            }
+            Statement::FunctionDeclaration(func) => {
+                if let Some(id) = &func.id {
+                    declared.push(id.name.to_string());
+                }
+                if let Some(body) = &func.body {
+                    for stmt in &body.statements {
+                        Self::collect_statement_references(stmt, references, declared);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Identifiers and member paths (`props.user.id`) read by an expression
+    fn collect_references(expr: &Expression, references: &mut Vec<String>, declared: &mut Vec<String>) {
+        if let Some(path) = member_path(expr) {
+            if path != "this" && !path.starts_with("this.") {
+                references.push(path);
+            }
+            return;
+        }
+
+        match expr {
+            Expression::CallExpression(call_expr) => {
+                Self::collect_references(&call_expr.callee, references, declared);
+                for arg in &call_expr.arguments {
+                    if let Some(expr) = arg.as_expression() {
+                        Self::collect_references(expr, references, declared);
+                    }
+                }
+            }
+            Expression::NewExpression(new_expr) => {
+                Self::collect_references(&new_expr.callee, references, declared);
+                for arg in &new_expr.arguments {
+                    if let Some(expr) = arg.as_expression() {
+                        Self::collect_references(expr, references, declared);
+                    }
+                }
+            }
+            Expression::StaticMemberExpression(member) => {
+                Self::collect_references(&member.object, references, declared);
+            }
+            Expression::ComputedMemberExpression(member) => {
+                Self::collect_references(&member.object, references, declared);
+                Self::collect_references(&member.expression, references, declared);
+            }
+            Expression::AssignmentExpression(assign_expr) => {
+                Self::collect_references(&assign_expr.right, references, declared);
+            }
+            Expression::BinaryExpression(bin_expr) => {
+                Self::collect_references(&bin_expr.left, references, declared);
+                Self::collect_references(&bin_expr.right, references, declared);
+            }
+            Expression::LogicalExpression(logical) => {
+                Self::collect_references(&logical.left, references, declared);
+                Self::collect_references(&logical.right, references, declared);
+            }
+            Expression::ConditionalExpression(cond) => {
+                Self::collect_references(&cond.test, references, declared);
+                Self::collect_references(&cond.consequent, references, declared);
+                Self::collect_references(&cond.alternate, references, declared);
+            }
+            Expression::UnaryExpression(unary) => {
+                Self::collect_references(&unary.argument, references, declared);
+            }
+            Expression::AwaitExpression(await_expr) => {
+                Self::collect_references(&await_expr.argument, references, declared);
+            }
+            Expression::TemplateLiteral(template) => {
+                for expr in &template.expressions {
+                    Self::collect_references(expr, references, declared);
+                }
+            }
+            Expression::ArrayExpression(array) => {
+                for elem in &array.elements {
+                    if let Some(expr) = elem.as_expression() {
+                        Self::collect_references(expr, references, declared);
+                    }
+                }
+            }
+            Expression::ObjectExpression(obj_expr) => {
+                for prop in &obj_expr.properties {
+                    if let ObjectPropertyKind::ObjectProperty(prop) = prop {
+                        Self::collect_references(&prop.value, references, declared);
+                    }
+                }
+            }
+            Expression::ArrowFunctionExpression(arrow) => {
+                for param in &arrow.params.items {
+                    binding_names(&param.pattern, declared);
+                }
+                for stmt in &arrow.body.statements {
+                    Self::collect_statement_references(stmt, references, declared);
+                }
+            }
+            Expression::FunctionExpression(func) => {
+                for param in &func.params.items {
+                    binding_names(&param.pattern, declared);
+                }
+                if let Some(body) = &func.body {
+                    for stmt in &body.statements {
+                        Self::collect_statement_references(stmt, references, declared);
+                    }
+                }
+            }
+            Expression::ParenthesizedExpression(paren) => {
+                Self::collect_references(&paren.expression, references, declared);
+            }
+            Expression::SequenceExpression(seq) => {
+                for expr in &seq.expressions {
+                    Self::collect_references(expr, references, declared);
+                }
+            }
+            Expression::TSAsExpression(as_expr) => {
+                Self::collect_references(&as_expr.expression, references, declared);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Default for ReactAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
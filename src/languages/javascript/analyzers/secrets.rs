@@ -0,0 +1,324 @@
+use super::Analyzer;
+use crate::config::SecretsConfig;
+use crate::secrets::SecretScanner;
+use crate::types::CodeIssue;
+use oxc_ast::ast::*;
+use oxc_span::Span;
+use std::path::Path;
+
+/// Reports string literals that look like credentials, using the scanner shared by all languages
+pub struct SecretsAnalyzer {
+    scanner: SecretScanner,
+}
+
+impl SecretsAnalyzer {
+    pub fn with_config(config: &SecretsConfig) -> Self {
+        Self {
+            scanner: SecretScanner::with_config(config),
+        }
+    }
+
+    fn get_line_column(source_code: &str, span: Span) -> (usize, usize) {
+        let start = span.start as usize;
+        let before = &source_code[..start];
+        let line = before.lines().count();
+        let last_newline = before.rfind('\n').unwrap_or(0);
+        let column = start - last_newline;
+        (line, column)
+    }
+
+    fn check_literal(&self, issues: &mut Vec<CodeIssue>, value: &str, span: Span, file_path: &Path, source_code: &str) {
+        if let Some(finding) = self.scanner.scan(file_path, value) {
+            let (line, column) = Self::get_line_column(source_code, span);
+            issues.push(self.scanner.issue(file_path, &finding, line, column, None));
+        }
+    }
+}
+
+impl Analyzer for SecretsAnalyzer {
+    fn analyze(&self, program: &Program, file_path: &Path, source_code: &str) -> Vec<CodeIssue> {
+        let mut issues = Vec::new();
+
+        for stmt in &program.body {
+            self.analyze_statement(&mut issues, stmt, file_path, source_code);
+        }
+
+        issues
+    }
+}
+
+impl SecretsAnalyzer {
+    fn analyze_statement(&self, issues: &mut Vec<CodeIssue>, stmt: &Statement, file_path: &Path, source_code: &str) {
+        match stmt {
+            Statement::VariableDeclaration(var_decl) => {
+                self.analyze_variable_declaration(issues, var_decl, file_path, source_code);
+            }
+            Statement::ExpressionStatement(expr_stmt) => {
+                self.analyze_expression(issues, &expr_stmt.expression, file_path, source_code);
+            }
+            Statement::ReturnStatement(ret_stmt) => {
+                if let Some(argument) = &ret_stmt.argument {
+                    self.analyze_expression(issues, argument, file_path, source_code);
+                }
+            }
+            Statement::ThrowStatement(throw_stmt) => {
+                self.analyze_expression(issues, &throw_stmt.argument, file_path, source_code);
+            }
+            Statement::BlockStatement(block) => {
+                for stmt in &block.body {
+                    self.analyze_statement(issues, stmt, file_path, source_code);
+                }
+            }
+            Statement::IfStatement(if_stmt) => {
+                self.analyze_expression(issues, &if_stmt.test, file_path, source_code);
+                self.analyze_statement(issues, &if_stmt.consequent, file_path, source_code);
+                if let Some(alternate) = &if_stmt.alternate {
+                    self.analyze_statement(issues, alternate, file_path, source_code);
+                }
+            }
+            Statement::ForStatement(for_stmt) => {
+                if let Some(ForStatementInit::VariableDeclaration(var_decl)) = &for_stmt.init {
+                    self.analyze_variable_declaration(issues, var_decl, file_path, source_code);
+                }
+                self.analyze_statement(issues, &for_stmt.body, file_path, source_code);
+            }
+            Statement::ForOfStatement(for_of) => {
+                self.analyze_expression(issues, &for_of.right, file_path, source_code);
+                self.analyze_statement(issues, &for_of.body, file_path, source_code);
+            }
+            Statement::ForInStatement(for_in) => {
+                self.analyze_statement(issues, &for_in.body, file_path, source_code);
+            }
+            Statement::WhileStatement(while_stmt) => {
+                self.analyze_statement(issues, &while_stmt.body, file_path, source_code);
+            }
+            Statement::DoWhileStatement(do_while) => {
+                self.analyze_statement(issues, &do_while.body, file_path, source_code);
+            }
+            Statement::TryStatement(try_stmt) => {
+                for stmt in &try_stmt.block.body {
+                    self.analyze_statement(issues, stmt, file_path, source_code);
+                }
+                if let Some(handler) = &try_stmt.handler {
+                    for stmt in &handler.body.body {
+                        self.analyze_statement(issues, stmt, file_path, source_code);
+                    }
+                }
+                if let Some(finalizer) = &try_stmt.finalizer {
+                    for stmt in &finalizer.body {
+                        self.analyze_statement(issues, stmt, file_path, source_code);
+                    }
+                }
+            }
+            Statement::SwitchStatement(switch_stmt) => {
+                for case in &switch_stmt.cases {
+                    for stmt in &case.consequent {
+                        self.analyze_statement(issues, stmt, file_path, source_code);
+                    }
+                }
+            }
+            Statement::FunctionDeclaration(func) => {
+                self.analyze_function(issues, func, file_path, source_code);
+            }
+            Statement::ClassDeclaration(class) => {
+                self.analyze_class(issues, class, file_path, source_code);
+            }
+            Statement::ExportNamedDeclaration(export) => match &export.declaration {
+                Some(Declaration::VariableDeclaration(var_decl)) => {
+                    self.analyze_variable_declaration(issues, var_decl, file_path, source_code);
+                }
+                Some(Declaration::FunctionDeclaration(func)) => {
+                    self.analyze_function(issues, func, file_path, source_code);
+                }
+                Some(Declaration::ClassDeclaration(class)) => {
+                    self.analyze_class(issues, class, file_path, source_code);
+                }
+                _ => {}
+            },
+            Statement::ExportDefaultDeclaration(export) => match &export.declaration {
+                ExportDefaultDeclarationKind::FunctionDeclaration(func) => {
+                    self.analyze_function(issues, func, file_path, source_code);
+                }
+                ExportDefaultDeclarationKind::ClassDeclaration(class) => {
+                    self.analyze_class(issues, class, file_path, source_code);
+                }
+                declaration => {
+                    if let Some(expr) = declaration.as_expression() {
+                        self.analyze_expression(issues, expr, file_path, source_code);
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    fn analyze_variable_declaration(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        var_decl: &VariableDeclaration,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        for var in &var_decl.declarations {
+            if let Some(init) = &var.init {
+                self.analyze_expression(issues, init, file_path, source_code);
+            }
+        }
+    }
+
+    fn analyze_function(&self, issues: &mut Vec<CodeIssue>, func: &Function, file_path: &Path, source_code: &str) {
+        if let Some(body) = &func.body {
+            for stmt in &body.statements {
+                self.analyze_statement(issues, stmt, file_path, source_code);
+            }
+        }
+    }
+
+    fn analyze_class(&self, issues: &mut Vec<CodeIssue>, class: &Class, file_path: &Path, source_code: &str) {
+        for element in &class.body.body {
+            match element {
+                ClassElement::MethodDefinition(method) => {
+                    self.analyze_function(issues, &method.value, file_path, source_code);
+                }
+                ClassElement::PropertyDefinition(prop) => {
+                    if let Some(value) = &prop.value {
+                        self.analyze_expression(issues, value, file_path, source_code);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn analyze_expression(&self, issues: &mut Vec<CodeIssue>, expr: &Expression, file_path: &Path, source_code: &str) {
+        match expr {
+            Expression::StringLiteral(lit) => {
+                self.check_literal(issues, lit.value.as_str(), lit.span, file_path, source_code);
+            }
+            Expression::TemplateLiteral(template) => {
+                self.analyze_template(issues, template, file_path, source_code);
+            }
+            Expression::TaggedTemplateExpression(tagged) => {
+                self.analyze_template(issues, &tagged.quasi, file_path, source_code);
+            }
+            Expression::CallExpression(call_expr) => {
+                self.analyze_expression(issues, &call_expr.callee, file_path, source_code);
+                for arg in &call_expr.arguments {
+                    if let Some(expr) = arg.as_expression() {
+                        self.analyze_expression(issues, expr, file_path, source_code);
+                    }
+                }
+            }
+            Expression::NewExpression(new_expr) => {
+                for arg in &new_expr.arguments {
+                    if let Some(expr) = arg.as_expression() {
+                        self.analyze_expression(issues, expr, file_path, source_code);
+                    }
+                }
+            }
+            Expression::AssignmentExpression(assign_expr) => {
+                self.analyze_expression(issues, &assign_expr.right, file_path, source_code);
+            }
+            Expression::BinaryExpression(bin_expr) => {
+                self.analyze_expression(issues, &bin_expr.left, file_path, source_code);
+                self.analyze_expression(issues, &bin_expr.right, file_path, source_code);
+            }
+            Expression::LogicalExpression(logical) => {
+                self.analyze_expression(issues, &logical.left, file_path, source_code);
+                self.analyze_expression(issues, &logical.right, file_path, source_code);
+            }
+            Expression::ConditionalExpression(cond) => {
+                self.analyze_expression(issues, &cond.test, file_path, source_code);
+                self.analyze_expression(issues, &cond.consequent, file_path, source_code);
+                self.analyze_expression(issues, &cond.alternate, file_path, source_code);
+            }
+            Expression::ArrayExpression(array) => {
+                for elem in &array.elements {
+                    if let Some(expr) = elem.as_expression() {
+                        self.analyze_expression(issues, expr, file_path, source_code);
+                    }
+                }
+            }
+            Expression::ObjectExpression(obj_expr) => {
+                for prop in &obj_expr.properties {
+                    if let ObjectPropertyKind::ObjectProperty(prop) = prop {
+                        self.analyze_expression(issues, &prop.value, file_path, source_code);
+                    }
+                }
+            }
+            Expression::ArrowFunctionExpression(arrow) => {
+                for stmt in &arrow.body.statements {
+                    self.analyze_statement(issues, stmt, file_path, source_code);
+                }
+            }
+            Expression::FunctionExpression(func) => {
+                self.analyze_function(issues, func, file_path, source_code);
+            }
+            Expression::ClassExpression(class) => {
+                self.analyze_class(issues, class, file_path, source_code);
+            }
+            Expression::StaticMemberExpression(member) => {
+                self.analyze_expression(issues, &member.object, file_path, source_code);
+            }
+            Expression::ComputedMemberExpression(member) => {
+                self.analyze_expression(issues, &member.object, file_path, source_code);
+            }
+            Expression::ParenthesizedExpression(paren) => {
+                self.analyze_expression(issues, &paren.expression, file_path, source_code);
+            }
+            Expression::AwaitExpression(await_expr) => {
+                self.analyze_expression(issues, &await_expr.argument, file_path, source_code);
+            }
+            Expression::SequenceExpression(seq) => {
+                for expr in &seq.expressions {
+                    self.analyze_expression(issues, expr, file_path, source_code);
+                }
+            }
+            Expression::TSAsExpression(as_expr) => {
+                self.analyze_expression(issues, &as_expr.expression, file_path, source_code);
+            }
+            Expression::JSXElement(element) => {
+                self.analyze_jsx_element(issues, element, file_path, source_code);
+            }
+            _ => {}
+        }
+    }
+
+    fn analyze_template(&self, issues: &mut Vec<CodeIssue>, template: &TemplateLiteral, file_path: &Path, source_code: &str) {
+        for quasi in &template.quasis {
+            self.check_literal(issues, quasi.value.raw.as_str(), quasi.span, file_path, source_code);
+        }
+        for expr in &template.expressions {
+            self.analyze_expression(issues, expr, file_path, source_code);
+        }
+    }
+
+    fn analyze_jsx_element(&self, issues: &mut Vec<CodeIssue>, element: &JSXElement, file_path: &Path, source_code: &str) {
+        for attr in &element.opening_element.attributes {
+            if let JSXAttributeItem::Attribute(attr) = attr {
+                match &attr.value {
+                    Some(JSXAttributeValue::StringLiteral(lit)) => {
+                        self.check_literal(issues, lit.value.as_str(), lit.span, file_path, source_code);
+                    }
+                    Some(JSXAttributeValue::ExpressionContainer(container)) => {
+                        if let Some(expr) = container.expression.as_expression() {
+                            self.analyze_expression(issues, expr, file_path, source_code);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        for child in &element.children {
+            match child {
+                JSXChild::Element(child) => self.analyze_jsx_element(issues, child, file_path, source_code),
+                JSXChild::ExpressionContainer(container) => {
+                    if let Some(expr) = container.expression.as_expression() {
+                        self.analyze_expression(issues, expr, file_path, source_code);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use super::Analyzer;
+use crate::secrets;
 use crate::types::{CodeIssue, Category, Severity};
 use oxc_ast::ast::*;
 use oxc_span::Span;
@@ -47,6 +48,16 @@ impl SecurityAnalyzer {
             code_snippet,
         });
     }
+
+    /// Literal values can't carry attacker data, so the sink rules leave them alone
+    fn is_constant(expr: &Expression) -> bool {
+        match expr {
+            Expression::StringLiteral(_) | Expression::NumericLiteral(_) | Expression::BooleanLiteral(_) => true,
+            Expression::TemplateLiteral(template) => template.expressions.is_empty(),
+            Expression::ParenthesizedExpression(paren) => Self::is_constant(&paren.expression),
+            _ => false,
+        }
+    }
 }
 
 impl Analyzer for SecurityAnalyzer {
@@ -79,16 +90,19 @@ impl SecurityAnalyzer {
                             name_lower.contains("token") || 
                             name_lower.contains("apikey") {
                             
-                            if let Some(init) = &var.init {
-                                if let Expression::StringLiteral(_) = init {
-                                    self.add_issue(
-                                        issues,
-                                        file_path,
-                                        source_code,
-                                        var.span,
-                                        format!("Kemungkinan password/rahasia di-hardcode pada variabel '{}'", ident.name),
-                                        "no-hardcoded-secrets".to_string(),
-                                    );
+                            if let Some(Expression::StringLiteral(lit)) = &var.init {
+                                self.add_issue(
+                                    issues,
+                                    file_path,
+                                    source_code,
+                                    var.span,
+                                    format!("Kemungkinan password/rahasia di-hardcode pada variabel '{}'", ident.name),
+                                    "no-hardcoded-secrets".to_string(),
+                                );
+                                // Never echo the secret itself in the report
+                                if let Some(issue) = issues.last_mut() {
+                                    issue.code_snippet =
+                                        Some(format!("{} = \"{}\"", ident.name, secrets::redact(&lit.value)));
                                 }
                             }
                         }
@@ -156,7 +170,12 @@ impl SecurityAnalyzer {
         match expr {
             Expression::CallExpression(call_expr) => {
                 if let Expression::Identifier(ident) = &call_expr.callee {
-                    if ident.name == "eval" {
+                    let constant_argument = call_expr
+                        .arguments
+                        .first()
+                        .and_then(|arg| arg.as_expression())
+                        .is_some_and(Self::is_constant);
+                    if ident.name == "eval" && !constant_argument {
                         self.add_issue(
                             issues,
                             file_path,
@@ -267,7 +286,8 @@ impl SecurityAnalyzer {
             Expression::AssignmentExpression(assign_expr) => {
                 match &assign_expr.left {
                     AssignmentTarget::StaticMemberExpression(member) => {
-                        if member.property.name == "innerHTML" {
+                        let constant = Self::is_constant(&assign_expr.right);
+                        if member.property.name == "innerHTML" && !constant {
                             self.add_issue(
                                 issues,
                                 file_path,
@@ -277,7 +297,7 @@ impl SecurityAnalyzer {
                                 "no-inner-html".to_string(),
                             );
                         }
-                        if member.property.name == "outerHTML" {
+                        if member.property.name == "outerHTML" && !constant {
                             self.add_issue(
                                 issues,
                                 file_path,
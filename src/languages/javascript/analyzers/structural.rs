@@ -0,0 +1,83 @@
+use super::Analyzer;
+use crate::config::JavascriptConfig;
+use crate::languages::javascript::structural::{self, Bindings, StructuralRule};
+use crate::types::CodeIssue;
+use oxc_ast::ast::Program;
+use oxc_span::Span;
+use std::path::Path;
+
+/// Runs the structural pattern rules loaded from `javascript.rule_files`
+pub struct StructuralAnalyzer {
+    rules: Vec<StructuralRule>,
+}
+
+impl StructuralAnalyzer {
+    pub fn with_config(config: &JavascriptConfig) -> Self {
+        Self {
+            rules: config.rules.clone(),
+        }
+    }
+
+    fn get_line_column(source_code: &str, span: Span) -> (usize, usize) {
+        let start = span.start as usize;
+        let before = &source_code[..start];
+        let line = before.bytes().filter(|&byte| byte == b'\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        let column = start - line_start + 1;
+        (line, column)
+    }
+
+    /// The rule message with each `$NAME` replaced by the code bound to it, longest names first
+    fn message(rule: &StructuralRule, bindings: &Bindings, source_code: &str) -> String {
+        let mut names: Vec<_> = bindings.iter().collect();
+        names.sort_by_key(|(name, _)| std::cmp::Reverse(name.len()));
+
+        let mut message = rule.message.clone();
+        for (name, span) in names {
+            message = message.replace(name.as_str(), &source_code[span.start as usize..span.end as usize]);
+        }
+        message
+    }
+
+    fn check_rule(&self, rule: &StructuralRule, program: &Program, file_path: &Path, source_code: &str) -> Vec<CodeIssue> {
+        let regions = |patterns: &[structural::Pattern]| -> Vec<Vec<Span>> {
+            patterns
+                .iter()
+                .map(|pattern| structural::find(pattern, program, source_code).into_iter().map(|m| m.span).collect())
+                .collect()
+        };
+        let inside = regions(&rule.inside);
+        let not_inside = regions(&rule.not_inside);
+        let within = |spans: &Vec<Span>, span: Span| spans.iter().any(|outer| outer.start <= span.start && span.end <= outer.end);
+
+        structural::find(&rule.pattern, program, source_code)
+            .into_iter()
+            .filter(|m| inside.iter().all(|spans| within(spans, m.span)))
+            .filter(|m| !not_inside.iter().any(|spans| within(spans, m.span)))
+            .map(|m| {
+                let (line, column) = Self::get_line_column(source_code, m.span);
+                CodeIssue {
+                    file_path: file_path.display().to_string(),
+                    line,
+                    column,
+                    end_line: None,
+                    end_column: None,
+                    message: Self::message(rule, &m.bindings, source_code),
+                    severity: rule.severity,
+                    category: rule.category.clone(),
+                    rule: rule.id.clone(),
+                    code_snippet: source_code.get(m.span.start as usize..m.span.end as usize).map(|s| s.to_string()),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Analyzer for StructuralAnalyzer {
+    fn analyze(&self, program: &Program, file_path: &Path, source_code: &str) -> Vec<CodeIssue> {
+        self.rules
+            .iter()
+            .flat_map(|rule| self.check_rule(rule, program, file_path, source_code))
+            .collect()
+    }
+}
@@ -0,0 +1,1033 @@
+use super::bindings::{last_segment, member_path, ModuleBindings};
+use super::Analyzer;
+use crate::config::TaintConfig;
+use crate::types::{CodeIssue, Category, Severity};
+use oxc_ast::ast::*;
+use oxc_span::Span;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Member paths whose value is controlled by the user
+const SOURCES: &[&str] = &[
+    "location",
+    "document.location",
+    "document.URL",
+    "document.documentURI",
+    "document.referrer",
+    "document.cookie",
+    "window.name",
+    "req.query",
+    "req.body",
+    "req.params",
+    "req.headers",
+    "req.cookies",
+    "request.query",
+    "request.body",
+    "request.params",
+];
+
+/// Functions that always return clean data
+const DEFAULT_SANITIZERS: &[&str] = &[
+    "DOMPurify.sanitize",
+    "sanitizeHtml",
+    "escapeHtml",
+    "encodeURIComponent",
+    "encodeURI",
+    "parseInt",
+    "parseFloat",
+    "Number",
+    "Boolean",
+    "path.basename",
+    "validator.escape",
+    "escape",
+];
+
+/// `fs` functions whose first argument is a path, shared with the Node.js rules
+pub(crate) const FS_PATH_FUNCTIONS: &[&str] = &[
+    "readFile", "readFileSync", "writeFile", "writeFileSync", "appendFile", "appendFileSync",
+    "createReadStream", "createWriteStream", "unlink", "unlinkSync", "rm", "rmSync",
+    "readdir", "readdirSync", "stat", "statSync", "open", "openSync", "access", "existsSync",
+];
+
+const CHILD_PROCESS_FUNCTIONS: &[&str] = &[
+    "exec", "execSync", "execFile", "execFileSync", "spawn", "spawnSync",
+];
+
+/// Where a tainted value originally came from
+#[derive(Debug, Clone, PartialEq)]
+enum Origin {
+    /// A user-controlled source such as `req.query` or `location.hash`
+    Source(String),
+    /// The n-th parameter of the function being summarized
+    Param(usize),
+}
+
+/// A tainted value together with the steps it took since its origin
+#[derive(Debug, Clone)]
+struct Taint {
+    origin: Origin,
+    /// (description, line) pairs, starting with the origin
+    path: Vec<(String, usize)>,
+}
+
+impl Taint {
+    fn step(&self, what: &str, line: usize) -> Self {
+        let mut taint = self.clone();
+        let is_repeat = taint
+            .path
+            .last()
+            .is_some_and(|(last, last_line)| last == what && *last_line == line);
+        if !is_repeat {
+            taint.path.push((what.to_string(), line));
+        }
+        taint
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum SinkKind {
+    Xss,
+    CodeInjection,
+    CommandInjection,
+    SqlInjection,
+    PathTraversal,
+}
+
+impl SinkKind {
+    fn rule(&self) -> &'static str {
+        match self {
+            SinkKind::Xss => "taint-xss",
+            SinkKind::CodeInjection => "taint-code-injection",
+            SinkKind::CommandInjection => "taint-command-injection",
+            SinkKind::SqlInjection => "taint-sql-injection",
+            SinkKind::PathTraversal => "taint-path-traversal",
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            SinkKind::Xss => "XSS",
+            SinkKind::CodeInjection => "code injection",
+            SinkKind::CommandInjection => "command injection",
+            SinkKind::SqlInjection => "SQL injection",
+            SinkKind::PathTraversal => "path traversal",
+        }
+    }
+}
+
+/// Which argument of a sink call carries the dangerous value
+#[derive(Debug, Clone, Copy)]
+enum SinkArg {
+    Index(usize),
+    Any,
+}
+
+/// A tainted value that reached a sink
+#[derive(Debug, Clone)]
+struct SinkHit {
+    taint: Taint,
+    sink: String,
+    sink_line: usize,
+    kind: SinkKind,
+    /// Where the issue is reported
+    span: Span,
+}
+
+/// How a function declared in the same file moves taint from its parameters
+#[derive(Debug, Clone, Default)]
+struct FunctionSummary {
+    /// Parameters that flow into the return value
+    returns: HashSet<usize>,
+    /// Parameters that reach a sink inside the function
+    sinks: Vec<(usize, SinkHit)>,
+}
+
+impl FunctionSummary {
+    /// What callers can observe, ignoring how long the recorded paths are
+    fn signature(&self) -> (Vec<usize>, Vec<(usize, SinkKind, u32)>) {
+        let mut returns: Vec<usize> = self.returns.iter().copied().collect();
+        returns.sort_unstable();
+        let mut sinks: Vec<_> = self.sinks.iter().map(|(index, hit)| (*index, hit.kind, hit.span.start)).collect();
+        sinks.sort_unstable_by_key(|&(index, kind, start)| (index, kind.rule(), start));
+        (returns, sinks)
+    }
+}
+
+/// Names bound in scope; `None` means the binding holds clean data
+type Env = HashMap<String, Option<Taint>>;
+
+pub struct TaintAnalyzer {
+    sanitizers: Vec<String>,
+}
+
+impl TaintAnalyzer {
+    pub fn with_config(config: &TaintConfig) -> Self {
+        let mut sanitizers: Vec<String> = DEFAULT_SANITIZERS.iter().map(|s| s.to_string()).collect();
+        sanitizers.extend(config.sanitizers.iter().cloned());
+        Self { sanitizers }
+    }
+
+    fn get_line_column(source_code: &str, span: Span) -> (usize, usize) {
+        let start = span.start as usize;
+        let before = &source_code[..start];
+        let line = before.lines().count();
+        let last_newline = before.rfind('\n').unwrap_or(0);
+        let column = start - last_newline;
+        (line, column)
+    }
+
+    fn add_issue(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        file_path: &Path,
+        source_code: &str,
+        span: Span,
+        message: String,
+        rule: String,
+    ) {
+        let (line, column) = Self::get_line_column(source_code, span);
+        let start = span.start as usize;
+        let end = span.end as usize;
+        let code_snippet = source_code.get(start..end).map(|s| s.to_string());
+
+        issues.push(CodeIssue {
+            file_path: file_path.display().to_string(),
+            line,
+            column,
+            end_line: None,
+            end_column: None,
+            message,
+            severity: Severity::Error,
+            category: Category::Security,
+            rule,
+            code_snippet,
+        });
+    }
+
+    fn is_sanitizer(&self, callee: &str) -> bool {
+        self.sanitizers.iter().any(|sanitizer| {
+            sanitizer == callee || (!sanitizer.contains('.') && last_segment(callee) == sanitizer)
+        })
+    }
+
+    /// Summarize every top-level function so calls to them can be followed.
+    /// Repeats until the summaries stop changing, so helper chains of any depth and
+    /// helpers declared further down the file are followed.
+    fn summarize_functions(
+        &self,
+        program: &Program,
+        source_code: &str,
+        bindings: &ModuleBindings,
+    ) -> HashMap<String, FunctionSummary> {
+        let mut functions: Vec<(String, &FormalParameters, &FunctionBody, bool)> = Vec::new();
+
+        for stmt in &program.body {
+            match stmt {
+                Statement::FunctionDeclaration(func) => collect_function(&mut functions, func),
+                Statement::VariableDeclaration(var_decl) => collect_variable_functions(&mut functions, var_decl),
+                Statement::ExportNamedDeclaration(export) => match &export.declaration {
+                    Some(Declaration::FunctionDeclaration(func)) => collect_function(&mut functions, func),
+                    Some(Declaration::VariableDeclaration(var_decl)) => {
+                        collect_variable_functions(&mut functions, var_decl)
+                    }
+                    _ => {}
+                },
+                Statement::ExportDefaultDeclaration(export) => {
+                    if let ExportDefaultDeclarationKind::FunctionDeclaration(func) = &export.declaration {
+                        collect_function(&mut functions, func);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut summaries: HashMap<String, FunctionSummary> = HashMap::new();
+        // Each pass can only add flows, so this settles within one pass per function
+        for _ in 0..=functions.len() {
+            let mut next = HashMap::new();
+            for (name, params, body, expression_body) in &functions {
+                let mut walker = Walker::new(self, source_code, bindings, &summaries);
+                let mut env = Env::new();
+                for (index, param) in params.items.iter().enumerate() {
+                    let line = Self::get_line_column(source_code, param.span).0;
+                    let taint = Taint {
+                        origin: Origin::Param(index),
+                        path: vec![(format!("parameter {} dari {}()", index + 1, name), line)],
+                    };
+                    walker.bind_pattern(&param.pattern, Some(taint), &mut env);
+                }
+                walker.walk_body(body, *expression_body, &mut env);
+
+                let mut summary = FunctionSummary::default();
+                for taint in &walker.returns {
+                    if let Origin::Param(index) = taint.origin {
+                        summary.returns.insert(index);
+                    }
+                }
+                for hit in walker.hits {
+                    if let Origin::Param(index) = hit.taint.origin {
+                        summary.sinks.push((index, hit));
+                    }
+                }
+                next.insert(name.clone(), summary);
+            }
+            let settled = next.len() == summaries.len()
+                && next.iter().all(|(name, summary)| {
+                    summaries.get(name).is_some_and(|previous| previous.signature() == summary.signature())
+                });
+            summaries = next;
+            if settled {
+                break;
+            }
+        }
+
+        summaries
+    }
+}
+
+fn collect_function<'p, 'a>(
+    functions: &mut Vec<(String, &'p FormalParameters<'a>, &'p FunctionBody<'a>, bool)>,
+    func: &'p Function<'a>,
+) {
+    if let (Some(id), Some(body)) = (&func.id, &func.body) {
+        functions.push((id.name.to_string(), &func.params, body, false));
+    }
+}
+
+fn collect_variable_functions<'p, 'a>(
+    functions: &mut Vec<(String, &'p FormalParameters<'a>, &'p FunctionBody<'a>, bool)>,
+    var_decl: &'p VariableDeclaration<'a>,
+) {
+    for var in &var_decl.declarations {
+        let BindingPatternKind::BindingIdentifier(ident) = &var.id.kind else {
+            continue;
+        };
+        match &var.init {
+            Some(Expression::ArrowFunctionExpression(arrow)) => {
+                functions.push((ident.name.to_string(), &arrow.params, &arrow.body, arrow.expression));
+            }
+            Some(Expression::FunctionExpression(func)) => {
+                if let Some(body) = &func.body {
+                    functions.push((ident.name.to_string(), &func.params, body, false));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn merge(left: Option<Taint>, right: Option<Taint>) -> Option<Taint> {
+    match (left, right) {
+        (Some(left), Some(right)) => {
+            // Prefer a real source so it is not hidden behind a parameter
+            if matches!(left.origin, Origin::Param(_)) && matches!(right.origin, Origin::Source(_)) {
+                Some(right)
+            } else {
+                Some(left)
+            }
+        }
+        (left, right) => left.or(right),
+    }
+}
+
+fn merge_env(into: &mut Env, other: Env) {
+    for (name, taint) in other {
+        if let Some(taint) = taint {
+            let entry = into.entry(name).or_insert(None);
+            if entry.is_none() {
+                *entry = Some(taint);
+            }
+        }
+    }
+}
+
+fn source_for_path(path: &str) -> Option<&'static str> {
+    let matches = |path: &str| {
+        SOURCES.iter().copied().find(|source| {
+            path == *source || (path.starts_with(source) && path[source.len()..].starts_with('.'))
+        })
+    };
+    // `window.name` is a source of its own, `window.location` is the global `location`
+    matches(path).or_else(|| path.strip_prefix("window.").and_then(matches))
+}
+
+fn sink_for_call(name: &str) -> Option<(SinkKind, SinkArg)> {
+    let name = name.strip_prefix("window.").unwrap_or(name);
+    let (object, method) = match name.rsplit_once('.') {
+        Some((object, method)) => (Some(object), method),
+        None => (None, name),
+    };
+    let root = name.split('.').next().unwrap_or(name);
+
+    match (object, method) {
+        (None, "eval") => Some((SinkKind::CodeInjection, SinkArg::Index(0))),
+        (None, "Function") => Some((SinkKind::CodeInjection, SinkArg::Any)),
+        (None, "setTimeout" | "setInterval") => Some((SinkKind::CodeInjection, SinkArg::Index(0))),
+        (Some("document"), "write" | "writeln") => Some((SinkKind::Xss, SinkArg::Any)),
+        (Some(_), "insertAdjacentHTML") => Some((SinkKind::Xss, SinkArg::Index(1))),
+        (Some("child_process"), method) if CHILD_PROCESS_FUNCTIONS.contains(&method) => {
+            Some((SinkKind::CommandInjection, SinkArg::Index(0)))
+        }
+        (Some("fs" | "fs.promises"), method) if FS_PATH_FUNCTIONS.contains(&method) => {
+            Some((SinkKind::PathTraversal, SinkArg::Index(0)))
+        }
+        (Some(_), "sendFile" | "download") if matches!(root, "res" | "response") => {
+            Some((SinkKind::PathTraversal, SinkArg::Index(0)))
+        }
+        (Some(_), "send" | "write" | "end") if matches!(root, "res" | "response") => {
+            Some((SinkKind::Xss, SinkArg::Index(0)))
+        }
+        (Some(_), "query" | "execute" | "raw") if !matches!(root, "document" | "req" | "request") => {
+            Some((SinkKind::SqlInjection, SinkArg::Index(0)))
+        }
+        _ => None,
+    }
+}
+
+/// Walks one function (or the whole program) and records every sink reached by tainted data
+struct Walker<'w> {
+    analyzer: &'w TaintAnalyzer,
+    source_code: &'w str,
+    bindings: &'w ModuleBindings,
+    summaries: &'w HashMap<String, FunctionSummary>,
+    hits: Vec<SinkHit>,
+    returns: Vec<Taint>,
+}
+
+impl<'w> Walker<'w> {
+    fn new(
+        analyzer: &'w TaintAnalyzer,
+        source_code: &'w str,
+        bindings: &'w ModuleBindings,
+        summaries: &'w HashMap<String, FunctionSummary>,
+    ) -> Self {
+        Self {
+            analyzer,
+            source_code,
+            bindings,
+            summaries,
+            hits: Vec::new(),
+            returns: Vec::new(),
+        }
+    }
+
+    fn line(&self, span: Span) -> usize {
+        TaintAnalyzer::get_line_column(self.source_code, span).0
+    }
+
+    fn hit(&mut self, taint: Taint, sink: &str, kind: SinkKind, span: Span) {
+        let sink_line = self.line(span);
+        self.hits.push(SinkHit {
+            taint,
+            sink: sink.to_string(),
+            sink_line,
+            kind,
+            span,
+        });
+    }
+
+    fn walk_body(&mut self, body: &FunctionBody, expression_body: bool, env: &mut Env) {
+        if expression_body {
+            if let Some(Statement::ExpressionStatement(expr_stmt)) = body.statements.first() {
+                if let Some(taint) = self.eval(&expr_stmt.expression, env) {
+                    self.returns.push(taint);
+                }
+            }
+        } else {
+            self.walk_statements(&body.statements, env);
+        }
+    }
+
+    /// Walk a nested function. Parameters shadow outer names, `seed` marks
+    /// values that are tainted on entry (e.g. `event.data` of a message handler).
+    fn walk_function(
+        &mut self,
+        params: &FormalParameters,
+        body: &FunctionBody,
+        expression_body: bool,
+        env: &Env,
+        seed: Option<(String, Taint)>,
+    ) {
+        let mut inner = env.clone();
+        // Parameters hide outer bindings but hold whatever the caller passes, so like
+        // globals they are still matched against the sources (`req.query` of a route handler)
+        let mut bound = Env::new();
+        for param in &params.items {
+            self.bind_pattern(&param.pattern, None, &mut bound);
+        }
+        for (name, taint) in bound {
+            match taint {
+                Some(taint) => inner.insert(name, Some(taint)),
+                None => inner.remove(&name),
+            };
+        }
+        if let Some((name, taint)) = seed {
+            inner.insert(name, Some(taint));
+        }
+
+        let saved_returns = std::mem::take(&mut self.returns);
+        self.walk_body(body, expression_body, &mut inner);
+        self.returns = saved_returns;
+    }
+
+    fn walk_callback(&mut self, expr: &Expression, env: &Env, seed: Option<Taint>) {
+        let seed_for = |params: &FormalParameters| {
+            let first = params.items.first()?;
+            let BindingPatternKind::BindingIdentifier(ident) = &first.pattern.kind else {
+                return None;
+            };
+            seed.clone().map(|taint| (format!("{}.data", ident.name), taint))
+        };
+
+        match expr {
+            Expression::ArrowFunctionExpression(arrow) => {
+                let seed = seed_for(&arrow.params);
+                self.walk_function(&arrow.params, &arrow.body, arrow.expression, env, seed);
+            }
+            Expression::FunctionExpression(func) => {
+                if let Some(body) = &func.body {
+                    let seed = seed_for(&func.params);
+                    self.walk_function(&func.params, body, false, env, seed);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn bind_pattern(&mut self, pattern: &BindingPattern, taint: Option<Taint>, env: &mut Env) {
+        match &pattern.kind {
+            BindingPatternKind::BindingIdentifier(ident) => {
+                let name = ident.name.to_string();
+                let line = self.line(ident.span);
+                env.insert(name.clone(), taint.map(|taint| taint.step(&name, line)));
+            }
+            BindingPatternKind::ObjectPattern(obj_pattern) => {
+                for prop in &obj_pattern.properties {
+                    self.bind_pattern(&prop.value, taint.clone(), env);
+                }
+                if let Some(rest) = &obj_pattern.rest {
+                    self.bind_pattern(&rest.argument, taint, env);
+                }
+            }
+            BindingPatternKind::ArrayPattern(arr_pattern) => {
+                for elem in arr_pattern.elements.iter().flatten() {
+                    self.bind_pattern(elem, taint.clone(), env);
+                }
+                if let Some(rest) = &arr_pattern.rest {
+                    self.bind_pattern(&rest.argument, taint, env);
+                }
+            }
+            BindingPatternKind::AssignmentPattern(assign_pattern) => {
+                let default_taint = self.eval(&assign_pattern.right, env);
+                self.bind_pattern(&assign_pattern.left, merge(taint, default_taint), env);
+            }
+        }
+    }
+
+    fn walk_statements(&mut self, statements: &[Statement], env: &mut Env) {
+        for stmt in statements {
+            self.walk_statement(stmt, env);
+        }
+    }
+
+    fn walk_branch(&mut self, stmt: &Statement, env: &Env) -> Env {
+        let mut branch = env.clone();
+        self.walk_statement(stmt, &mut branch);
+        branch
+    }
+
+    fn walk_statement(&mut self, stmt: &Statement, env: &mut Env) {
+        match stmt {
+            Statement::VariableDeclaration(var_decl) => self.walk_variable_declaration(var_decl, env),
+            Statement::FunctionDeclaration(func) => {
+                if let Some(body) = &func.body {
+                    self.walk_function(&func.params, body, false, env, None);
+                }
+            }
+            Statement::ClassDeclaration(class) => self.walk_class(class, env),
+            Statement::ExpressionStatement(expr_stmt) => {
+                self.eval(&expr_stmt.expression, env);
+            }
+            Statement::ReturnStatement(ret_stmt) => {
+                if let Some(argument) = &ret_stmt.argument {
+                    if let Some(taint) = self.eval(argument, env) {
+                        self.returns.push(taint);
+                    }
+                }
+            }
+            Statement::ThrowStatement(throw_stmt) => {
+                self.eval(&throw_stmt.argument, env);
+            }
+            Statement::BlockStatement(block) => self.walk_statements(&block.body, env),
+            Statement::IfStatement(if_stmt) => {
+                self.eval(&if_stmt.test, env);
+                let consequent = self.walk_branch(&if_stmt.consequent, env);
+                if let Some(alternate) = &if_stmt.alternate {
+                    let alternate = self.walk_branch(alternate, env);
+                    merge_env(env, alternate);
+                }
+                merge_env(env, consequent);
+            }
+            Statement::ForStatement(for_stmt) => {
+                if let Some(init) = &for_stmt.init {
+                    match init {
+                        ForStatementInit::VariableDeclaration(var_decl) => {
+                            self.walk_variable_declaration(var_decl, env);
+                        }
+                        _ => {
+                            if let Some(expr) = init.as_expression() {
+                                self.eval(expr, env);
+                            }
+                        }
+                    }
+                }
+                if let Some(test) = &for_stmt.test {
+                    self.eval(test, env);
+                }
+                if let Some(update) = &for_stmt.update {
+                    self.eval(update, env);
+                }
+                let body = self.walk_branch(&for_stmt.body, env);
+                merge_env(env, body);
+            }
+            Statement::ForOfStatement(for_of) => {
+                let taint = self.eval(&for_of.right, env);
+                self.bind_for_left(&for_of.left, taint, env);
+                let body = self.walk_branch(&for_of.body, env);
+                merge_env(env, body);
+            }
+            Statement::ForInStatement(for_in) => {
+                let taint = self.eval(&for_in.right, env);
+                self.bind_for_left(&for_in.left, taint, env);
+                let body = self.walk_branch(&for_in.body, env);
+                merge_env(env, body);
+            }
+            Statement::WhileStatement(while_stmt) => {
+                self.eval(&while_stmt.test, env);
+                let body = self.walk_branch(&while_stmt.body, env);
+                merge_env(env, body);
+            }
+            Statement::DoWhileStatement(do_while) => {
+                self.walk_statement(&do_while.body, env);
+                self.eval(&do_while.test, env);
+            }
+            Statement::TryStatement(try_stmt) => {
+                self.walk_statements(&try_stmt.block.body, env);
+                if let Some(handler) = &try_stmt.handler {
+                    self.walk_statements(&handler.body.body, env);
+                }
+                if let Some(finalizer) = &try_stmt.finalizer {
+                    self.walk_statements(&finalizer.body, env);
+                }
+            }
+            Statement::SwitchStatement(switch_stmt) => {
+                self.eval(&switch_stmt.discriminant, env);
+                let before = env.clone();
+                for case in &switch_stmt.cases {
+                    let mut branch = before.clone();
+                    self.walk_statements(&case.consequent, &mut branch);
+                    merge_env(env, branch);
+                }
+            }
+            Statement::LabeledStatement(labeled) => self.walk_statement(&labeled.body, env),
+            Statement::ExportNamedDeclaration(export) => match &export.declaration {
+                Some(Declaration::VariableDeclaration(var_decl)) => self.walk_variable_declaration(var_decl, env),
+                Some(Declaration::FunctionDeclaration(func)) => {
+                    if let Some(body) = &func.body {
+                        self.walk_function(&func.params, body, false, env, None);
+                    }
+                }
+                Some(Declaration::ClassDeclaration(class)) => self.walk_class(class, env),
+                _ => {}
+            },
+            Statement::ExportDefaultDeclaration(export) => match &export.declaration {
+                ExportDefaultDeclarationKind::FunctionDeclaration(func) => {
+                    if let Some(body) = &func.body {
+                        self.walk_function(&func.params, body, false, env, None);
+                    }
+                }
+                ExportDefaultDeclarationKind::ClassDeclaration(class) => self.walk_class(class, env),
+                kind => {
+                    if let Some(expr) = kind.as_expression() {
+                        self.eval(expr, env);
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    fn walk_variable_declaration(&mut self, var_decl: &VariableDeclaration, env: &mut Env) {
+        for var in &var_decl.declarations {
+            let taint = match &var.init {
+                Some(init) => self.eval(init, env),
+                None => None,
+            };
+            self.bind_pattern(&var.id, taint, env);
+        }
+    }
+
+    fn bind_for_left(&mut self, left: &ForStatementLeft, taint: Option<Taint>, env: &mut Env) {
+        match left {
+            ForStatementLeft::VariableDeclaration(var_decl) => {
+                for var in &var_decl.declarations {
+                    self.bind_pattern(&var.id, taint.clone(), env);
+                }
+            }
+            ForStatementLeft::AssignmentTargetIdentifier(ident) => {
+                let name = ident.name.to_string();
+                let line = self.line(ident.span);
+                env.insert(name.clone(), taint.map(|taint| taint.step(&name, line)));
+            }
+            _ => {}
+        }
+    }
+
+    fn walk_class(&mut self, class: &Class, env: &mut Env) {
+        for element in &class.body.body {
+            match element {
+                ClassElement::MethodDefinition(method) => {
+                    if let Some(body) = &method.value.body {
+                        self.walk_function(&method.value.params, body, false, env, None);
+                    }
+                }
+                ClassElement::PropertyDefinition(prop) => {
+                    if let Some(value) = &prop.value {
+                        self.eval(value, env);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// The taint of `path`, from the closest bound prefix or from a source when its root is a global
+    fn lookup_path(&self, path: &str, env: &Env, span: Span) -> Option<Taint> {
+        let mut prefix = path;
+        loop {
+            if let Some(binding) = env.get(prefix) {
+                return binding.clone();
+            }
+            match prefix.rsplit_once('.') {
+                Some((shorter, _)) => prefix = shorter,
+                None => break,
+            }
+        }
+
+        source_for_path(path).map(|source| Taint {
+            origin: Origin::Source(source.to_string()),
+            path: vec![(path.to_string(), self.line(span))],
+        })
+    }
+
+    /// Evaluate the taint of an expression, recording any sink it reaches on the way
+    fn eval(&mut self, expr: &Expression, env: &mut Env) -> Option<Taint> {
+        match expr {
+            Expression::Identifier(ident) => self.lookup_path(ident.name.as_str(), env, ident.span),
+            Expression::StaticMemberExpression(member) => match member_path(expr) {
+                Some(path) => self.lookup_path(&path, env, member.span),
+                None => self.eval(&member.object, env),
+            },
+            Expression::ComputedMemberExpression(member) => {
+                if let Some(path) = member_path(expr) {
+                    return self.lookup_path(&path, env, member.span);
+                }
+                let object = self.eval(&member.object, env);
+                self.eval(&member.expression, env);
+                object
+            }
+            Expression::CallExpression(call) => self.eval_call(call, env),
+            Expression::NewExpression(new_expr) => {
+                let mut taint = None;
+                for arg in &new_expr.arguments {
+                    taint = merge(taint, self.eval_argument(arg, env));
+                }
+                match member_path(&new_expr.callee).as_deref() {
+                    Some("URLSearchParams") => Some(Taint {
+                        origin: Origin::Source("URLSearchParams".to_string()),
+                        path: vec![("new URLSearchParams()".to_string(), self.line(new_expr.span))],
+                    }),
+                    Some("Function") => {
+                        if let Some(taint) = taint {
+                            self.hit(taint, "new Function()", SinkKind::CodeInjection, new_expr.span);
+                        }
+                        None
+                    }
+                    _ => taint,
+                }
+            }
+            Expression::AssignmentExpression(assign) => self.eval_assignment(assign, env),
+            Expression::BinaryExpression(bin_expr) => {
+                let left = self.eval(&bin_expr.left, env);
+                let right = self.eval(&bin_expr.right, env);
+                merge(left, right)
+            }
+            Expression::LogicalExpression(logical_expr) => {
+                let left = self.eval(&logical_expr.left, env);
+                let right = self.eval(&logical_expr.right, env);
+                merge(left, right)
+            }
+            Expression::ConditionalExpression(cond_expr) => {
+                self.eval(&cond_expr.test, env);
+                let consequent = self.eval(&cond_expr.consequent, env);
+                let alternate = self.eval(&cond_expr.alternate, env);
+                merge(consequent, alternate)
+            }
+            Expression::TemplateLiteral(template) => {
+                let mut taint = None;
+                for expr in &template.expressions {
+                    taint = merge(taint, self.eval(expr, env));
+                }
+                taint
+            }
+            Expression::TaggedTemplateExpression(tagged) => {
+                // Tagged templates such as sql`...` escape their values
+                for expr in &tagged.quasi.expressions {
+                    self.eval(expr, env);
+                }
+                None
+            }
+            Expression::ArrayExpression(arr_expr) => {
+                let mut taint = None;
+                for elem in &arr_expr.elements {
+                    let elem_taint = match elem {
+                        ArrayExpressionElement::SpreadElement(spread) => self.eval(&spread.argument, env),
+                        _ => elem.as_expression().and_then(|expr| self.eval(expr, env)),
+                    };
+                    taint = merge(taint, elem_taint);
+                }
+                taint
+            }
+            Expression::ObjectExpression(obj_expr) => {
+                let mut taint = None;
+                for prop in &obj_expr.properties {
+                    let prop_taint = match prop {
+                        ObjectPropertyKind::ObjectProperty(prop) => self.eval(&prop.value, env),
+                        ObjectPropertyKind::SpreadProperty(spread) => self.eval(&spread.argument, env),
+                    };
+                    taint = merge(taint, prop_taint);
+                }
+                taint
+            }
+            Expression::ParenthesizedExpression(paren) => self.eval(&paren.expression, env),
+            Expression::AwaitExpression(await_expr) => self.eval(&await_expr.argument, env),
+            Expression::SequenceExpression(seq_expr) => {
+                let mut last = None;
+                for expr in &seq_expr.expressions {
+                    last = self.eval(expr, env);
+                }
+                last
+            }
+            Expression::UnaryExpression(unary_expr) => {
+                self.eval(&unary_expr.argument, env);
+                None
+            }
+            Expression::TSAsExpression(as_expr) => self.eval(&as_expr.expression, env),
+            Expression::TSSatisfiesExpression(satisfies) => self.eval(&satisfies.expression, env),
+            Expression::TSNonNullExpression(non_null) => self.eval(&non_null.expression, env),
+            Expression::ChainExpression(chain) => match &chain.expression {
+                ChainElement::CallExpression(call) => self.eval_call(call, env),
+                ChainElement::StaticMemberExpression(member) => self.eval(&member.object, env),
+                ChainElement::ComputedMemberExpression(member) => self.eval(&member.object, env),
+                _ => None,
+            },
+            Expression::ArrowFunctionExpression(arrow) => {
+                self.walk_function(&arrow.params, &arrow.body, arrow.expression, env, None);
+                None
+            }
+            Expression::FunctionExpression(func) => {
+                if let Some(body) = &func.body {
+                    self.walk_function(&func.params, body, false, env, None);
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn eval_argument(&mut self, arg: &Argument, env: &mut Env) -> Option<Taint> {
+        match arg {
+            Argument::SpreadElement(spread) => self.eval(&spread.argument, env),
+            _ => arg.as_expression().and_then(|expr| self.eval(expr, env)),
+        }
+    }
+
+    fn eval_call(&mut self, call: &CallExpression, env: &mut Env) -> Option<Taint> {
+        let callee_path = member_path(&call.callee);
+        let resolved = callee_path.as_deref().map(|path| self.bindings.resolve(path));
+        let line = self.line(call.span);
+
+        // window.addEventListener('message', handler) - `event.data` comes from another origin
+        if let Some(path) = &callee_path {
+            let is_message_listener = last_segment(path) == "addEventListener"
+                && matches!(call.arguments.first(), Some(Argument::StringLiteral(lit)) if lit.value == "message");
+            if is_message_listener {
+                if let Some(handler) = call.arguments.get(1).and_then(|arg| arg.as_expression()) {
+                    let seed = Taint {
+                        origin: Origin::Source("postMessage event.data".to_string()),
+                        path: vec![("postMessage event.data".to_string(), line)],
+                    };
+                    self.walk_callback(handler, env, Some(seed));
+                    return None;
+                }
+            }
+        }
+
+        let receiver = match &call.callee {
+            Expression::StaticMemberExpression(member) => self.eval(&member.object, env),
+            Expression::ComputedMemberExpression(member) => {
+                self.eval(&member.expression, env);
+                self.eval(&member.object, env)
+            }
+            callee => {
+                self.eval(callee, env);
+                None
+            }
+        };
+        let args: Vec<Option<Taint>> = call.arguments.iter().map(|arg| self.eval_argument(arg, env)).collect();
+
+        let Some(name) = resolved else {
+            return args.into_iter().fold(receiver, merge);
+        };
+
+        if let Some((kind, sink_arg)) = sink_for_call(&name) {
+            let taint = match sink_arg {
+                SinkArg::Index(index) => args.get(index).cloned().flatten(),
+                SinkArg::Any => args.iter().cloned().fold(None, merge),
+            };
+            if let Some(taint) = taint {
+                let sink = format!("{}()", callee_path.as_deref().unwrap_or(&name));
+                self.hit(taint, &sink, kind, call.span);
+            }
+            return None;
+        }
+
+        if self.analyzer.is_sanitizer(&name) {
+            return None;
+        }
+
+        if let Some(summary) = self.summaries.get(&name) {
+            let call_step = format!("argumen ke {}()", name);
+            for (index, inner) in &summary.sinks {
+                if let Some(Some(arg_taint)) = args.get(*index) {
+                    let mut taint = arg_taint.step(&call_step, line);
+                    taint.path.extend(inner.taint.path.iter().cloned());
+                    self.hits.push(SinkHit {
+                        taint,
+                        sink: inner.sink.clone(),
+                        sink_line: inner.sink_line,
+                        kind: inner.kind,
+                        span: call.span,
+                    });
+                }
+            }
+
+            let mut returned = None;
+            for index in &summary.returns {
+                if let Some(Some(arg_taint)) = args.get(*index) {
+                    returned = merge(returned, Some(arg_taint.step(&format!("{}()", name), line)));
+                }
+            }
+            return returned;
+        }
+
+        args.into_iter().fold(receiver, merge)
+    }
+
+    fn eval_assignment(&mut self, assign: &AssignmentExpression, env: &mut Env) -> Option<Taint> {
+        let target_path = match &assign.left {
+            AssignmentTarget::AssignmentTargetIdentifier(ident) => Some(ident.name.to_string()),
+            AssignmentTarget::StaticMemberExpression(member) => {
+                member_path(&member.object).map(|object| format!("{}.{}", object, member.property.name))
+            }
+            _ => None,
+        };
+
+        // window.onmessage = (event) => { ... }
+        if let Some(path) = &target_path {
+            if last_segment(path) == "onmessage" {
+                let seed = Taint {
+                    origin: Origin::Source("postMessage event.data".to_string()),
+                    path: vec![("postMessage event.data".to_string(), self.line(assign.span))],
+                };
+                self.walk_callback(&assign.right, env, Some(seed));
+                return None;
+            }
+        }
+
+        let mut taint = self.eval(&assign.right, env);
+        if assign.operator != AssignmentOperator::Assign {
+            if let Some(path) = &target_path {
+                taint = merge(env.get(path).cloned().flatten(), taint);
+            }
+        }
+
+        if let AssignmentTarget::StaticMemberExpression(member) = &assign.left {
+            self.eval(&member.object, env);
+            let property = member.property.name.as_str();
+            if matches!(property, "innerHTML" | "outerHTML") {
+                if let Some(taint) = &taint {
+                    self.hit(taint.clone(), property, SinkKind::Xss, assign.span);
+                }
+            }
+        }
+
+        if let Some(path) = target_path {
+            let line = self.line(assign.span);
+            env.insert(path.clone(), taint.as_ref().map(|taint| taint.step(&path, line)));
+        }
+
+        taint
+    }
+}
+
+impl Analyzer for TaintAnalyzer {
+    fn analyze(&self, program: &Program, file_path: &Path, source_code: &str) -> Vec<CodeIssue> {
+        let mut issues = Vec::new();
+
+        let bindings = ModuleBindings::collect(program);
+        let summaries = self.summarize_functions(program, source_code, &bindings);
+
+        let mut walker = Walker::new(self, source_code, &bindings, &summaries);
+        let mut env = Env::new();
+        walker.walk_statements(&program.body, &mut env);
+
+        let mut reported: HashSet<(u32, SinkKind)> = HashSet::new();
+        for hit in walker.hits {
+            // Flows that start at a parameter are reported where the function is called
+            let Origin::Source(source) = &hit.taint.origin else {
+                continue;
+            };
+            if !reported.insert((hit.span.start, hit.kind)) {
+                continue;
+            }
+
+            let path = hit
+                .taint
+                .path
+                .iter()
+                .map(|(what, line)| format!("{} (baris {})", what, line))
+                .collect::<Vec<_>>()
+                .join(" -> ");
+
+            self.add_issue(
+                &mut issues,
+                file_path,
+                source_code,
+                hit.span,
+                format!(
+                    "Data tidak tepercaya dari '{}' mencapai {} tanpa sanitasi (risiko {}). Jalur: {} -> {} (baris {})",
+                    source,
+                    hit.sink,
+                    hit.kind.label(),
+                    path,
+                    hit.sink,
+                    hit.sink_line
+                ),
+                hit.kind.rule().to_string(),
+            );
+        }
+
+        issues
+    }
+}
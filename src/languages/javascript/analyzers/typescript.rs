@@ -4,6 +4,21 @@ use oxc_ast::ast::*;
 use oxc_span::Span;
 use std::path::Path;
 
+/// Comment directives that silence the compiler
+const TS_DIRECTIVES: &[&str] = &["@ts-ignore", "@ts-nocheck", "@ts-expect-error"];
+
+/// Minimum length of the explanation that has to follow a directive
+const MIN_DIRECTIVE_REASON: usize = 3;
+
+/// Types that accept almost anything and should be spelled out instead
+const BANNED_TYPES: &[(&str, &str)] = &[
+    ("Function", "tulis signature-nya, misalnya '() => void'"),
+    ("Object", "gunakan 'object', 'Record<string, unknown>' atau interface yang spesifik"),
+    ("String", "gunakan tipe primitif 'string'"),
+    ("Number", "gunakan tipe primitif 'number'"),
+    ("Boolean", "gunakan tipe primitif 'boolean'"),
+];
+
 pub struct TypeScriptAnalyzer;
 
 impl TypeScriptAnalyzer {
@@ -48,6 +63,60 @@ impl TypeScriptAnalyzer {
             code_snippet,
         });
     }
+
+    /// `@ts-ignore` and friends must say why; scanned from the source text so every comment style is covered
+    fn check_directives(&self, issues: &mut Vec<CodeIssue>, file_path: &Path, source_code: &str) {
+        let mut offset = 0;
+        for line in source_code.split_inclusive('\n') {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("//") || trimmed.starts_with("/*") || trimmed.starts_with('*') {
+                for directive in TS_DIRECTIVES {
+                    let Some(position) = line.find(directive) else {
+                        continue;
+                    };
+                    let reason = line[position + directive.len()..]
+                        .trim()
+                        .trim_end_matches("*/")
+                        .trim_start_matches([':', '-'])
+                        .trim();
+                    if reason.len() < MIN_DIRECTIVE_REASON {
+                        let start = (offset + position) as u32;
+                        self.add_issue(
+                            issues,
+                            file_path,
+                            source_code,
+                            Span::new(start, start + directive.len() as u32),
+                            format!("{} tanpa alasan. Tambahkan penjelasan, misalnya '// {} - library X belum punya tipe'", directive, directive),
+                            "ban-ts-comment".to_string(),
+                            Severity::Warning,
+                        );
+                    }
+                }
+            }
+            offset += line.len();
+        }
+    }
+
+    fn literal_value(expr: &Expression) -> Option<String> {
+        match expr {
+            Expression::StringLiteral(lit) => Some(format!("\"{}\"", lit.value)),
+            Expression::NumericLiteral(lit) => Some(lit.value.to_string()),
+            Expression::BooleanLiteral(lit) => Some(lit.value.to_string()),
+            _ => None,
+        }
+    }
+
+    fn literal_type_value(ts_type: &TSType) -> Option<String> {
+        match ts_type {
+            TSType::TSLiteralType(literal_type) => match &literal_type.literal {
+                TSLiteral::StringLiteral(lit) => Some(format!("\"{}\"", lit.value)),
+                TSLiteral::NumericLiteral(lit) => Some(lit.value.to_string()),
+                TSLiteral::BooleanLiteral(lit) => Some(lit.value.to_string()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
 }
 
 impl Analyzer for TypeScriptAnalyzer {
@@ -61,6 +130,8 @@ impl Analyzer for TypeScriptAnalyzer {
             return issues;
         }
 
+        self.check_directives(&mut issues, file_path, source_code);
+
         for stmt in &program.body {
             self.analyze_statement(&mut issues, stmt, file_path, source_code);
         }
@@ -79,32 +150,534 @@ impl TypeScriptAnalyzer {
     ) {
         match stmt {
             Statement::VariableDeclaration(var_decl) => {
-                for var in &var_decl.declarations {
-                    if let Some(type_ann) = &var.id.type_annotation {
+                self.analyze_variable_declaration(issues, var_decl, false, file_path, source_code);
+            }
+            Statement::FunctionDeclaration(func) => {
+                self.analyze_function(issues, func, false, file_path, source_code);
+            }
+            Statement::ClassDeclaration(class) => {
+                self.analyze_class(issues, class, file_path, source_code);
+            }
+            Statement::TSInterfaceDeclaration(interface) => {
+                self.analyze_interface(issues, interface, file_path, source_code);
+            }
+            Statement::TSTypeAliasDeclaration(alias) => {
+                self.analyze_type_alias(issues, alias, file_path, source_code);
+            }
+            Statement::TSEnumDeclaration(enum_decl) => {
+                self.analyze_enum(issues, enum_decl, file_path, source_code);
+            }
+            Statement::ExpressionStatement(expr_stmt) => {
+                self.analyze_expression(issues, &expr_stmt.expression, file_path, source_code);
+            }
+            Statement::ReturnStatement(ret_stmt) => {
+                if let Some(argument) = &ret_stmt.argument {
+                    self.analyze_expression(issues, argument, file_path, source_code);
+                }
+            }
+            Statement::ThrowStatement(throw_stmt) => {
+                self.analyze_expression(issues, &throw_stmt.argument, file_path, source_code);
+            }
+            Statement::BlockStatement(block) => {
+                for stmt in &block.body {
+                    self.analyze_statement(issues, stmt, file_path, source_code);
+                }
+            }
+            Statement::IfStatement(if_stmt) => {
+                self.analyze_expression(issues, &if_stmt.test, file_path, source_code);
+                self.analyze_statement(issues, &if_stmt.consequent, file_path, source_code);
+                if let Some(alternate) = &if_stmt.alternate {
+                    self.analyze_statement(issues, alternate, file_path, source_code);
+                }
+            }
+            Statement::ForStatement(for_stmt) => {
+                if let Some(ForStatementInit::VariableDeclaration(var_decl)) = &for_stmt.init {
+                    self.analyze_variable_declaration(issues, var_decl, false, file_path, source_code);
+                }
+                self.analyze_statement(issues, &for_stmt.body, file_path, source_code);
+            }
+            Statement::ForOfStatement(for_of) => {
+                self.analyze_expression(issues, &for_of.right, file_path, source_code);
+                self.analyze_statement(issues, &for_of.body, file_path, source_code);
+            }
+            Statement::ForInStatement(for_in) => {
+                self.analyze_statement(issues, &for_in.body, file_path, source_code);
+            }
+            Statement::WhileStatement(while_stmt) => {
+                self.analyze_expression(issues, &while_stmt.test, file_path, source_code);
+                self.analyze_statement(issues, &while_stmt.body, file_path, source_code);
+            }
+            Statement::DoWhileStatement(do_while) => {
+                self.analyze_statement(issues, &do_while.body, file_path, source_code);
+            }
+            Statement::TryStatement(try_stmt) => {
+                for stmt in &try_stmt.block.body {
+                    self.analyze_statement(issues, stmt, file_path, source_code);
+                }
+                if let Some(handler) = &try_stmt.handler {
+                    for stmt in &handler.body.body {
+                        self.analyze_statement(issues, stmt, file_path, source_code);
+                    }
+                }
+                if let Some(finalizer) = &try_stmt.finalizer {
+                    for stmt in &finalizer.body {
+                        self.analyze_statement(issues, stmt, file_path, source_code);
+                    }
+                }
+            }
+            Statement::SwitchStatement(switch_stmt) => {
+                for case in &switch_stmt.cases {
+                    for stmt in &case.consequent {
+                        self.analyze_statement(issues, stmt, file_path, source_code);
+                    }
+                }
+            }
+            Statement::ExportNamedDeclaration(export) => match &export.declaration {
+                Some(Declaration::VariableDeclaration(var_decl)) => {
+                    self.analyze_variable_declaration(issues, var_decl, true, file_path, source_code);
+                }
+                Some(Declaration::FunctionDeclaration(func)) => {
+                    self.analyze_function(issues, func, true, file_path, source_code);
+                }
+                Some(Declaration::ClassDeclaration(class)) => {
+                    self.analyze_class(issues, class, file_path, source_code);
+                }
+                Some(Declaration::TSInterfaceDeclaration(interface)) => {
+                    self.analyze_interface(issues, interface, file_path, source_code);
+                }
+                Some(Declaration::TSTypeAliasDeclaration(alias)) => {
+                    self.analyze_type_alias(issues, alias, file_path, source_code);
+                }
+                Some(Declaration::TSEnumDeclaration(enum_decl)) => {
+                    self.analyze_enum(issues, enum_decl, file_path, source_code);
+                }
+                _ => {}
+            },
+            Statement::ExportDefaultDeclaration(export) => match &export.declaration {
+                ExportDefaultDeclarationKind::FunctionDeclaration(func) => {
+                    self.analyze_function(issues, func, true, file_path, source_code);
+                }
+                ExportDefaultDeclarationKind::ClassDeclaration(class) => {
+                    self.analyze_class(issues, class, file_path, source_code);
+                }
+                ExportDefaultDeclarationKind::TSInterfaceDeclaration(interface) => {
+                    self.analyze_interface(issues, interface, file_path, source_code);
+                }
+                declaration => {
+                    if let Some(expr) = declaration.as_expression() {
+                        self.analyze_exported_expression(issues, expr, file_path, source_code);
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    fn analyze_type_alias(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        alias: &TSTypeAliasDeclaration,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        if let Some(type_params) = &alias.type_parameters {
+            self.analyze_type_parameters(issues, type_params, file_path, source_code);
+        }
+        self.analyze_ts_type(issues, &alias.type_annotation, file_path, source_code);
+    }
+
+    fn analyze_enum(&self, issues: &mut Vec<CodeIssue>, enum_decl: &TSEnumDeclaration, file_path: &Path, source_code: &str) {
+        self.add_issue(
+            issues,
+            file_path,
+            source_code,
+            enum_decl.id.span,
+            format!(
+                "Enum '{}' menghasilkan kode runtime dan tidak bisa di-tree-shake. Pertimbangkan union literal (type {} = 'a' | 'b') atau objek 'as const'",
+                enum_decl.id.name, enum_decl.id.name
+            ),
+            "prefer-union-over-enum".to_string(),
+            Severity::Suggestion,
+        );
+    }
+
+    fn analyze_variable_declaration(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        var_decl: &VariableDeclaration,
+        exported: bool,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        for var in &var_decl.declarations {
+            if let Some(type_ann) = &var.id.type_annotation {
+                self.analyze_ts_type(issues, &type_ann.type_annotation, file_path, source_code);
+            }
+            if let Some(init) = &var.init {
+                // An annotated variable (`const f: Handler = () => ...`) already has its return type
+                if exported && var.id.type_annotation.is_none() {
+                    self.analyze_exported_expression(issues, init, file_path, source_code);
+                } else {
+                    self.analyze_expression(issues, init, file_path, source_code);
+                }
+            }
+        }
+    }
+
+    /// Exported arrow functions and function expressions are part of the module's API
+    fn analyze_exported_expression(&self, issues: &mut Vec<CodeIssue>, expr: &Expression, file_path: &Path, source_code: &str) {
+        match expr {
+            Expression::ArrowFunctionExpression(arrow) => {
+                if arrow.return_type.is_none() {
+                    self.report_missing_return_type(issues, arrow.span, file_path, source_code);
+                }
+                self.analyze_expression(issues, expr, file_path, source_code);
+            }
+            Expression::FunctionExpression(func) => {
+                self.analyze_function(issues, func, true, file_path, source_code);
+            }
+            _ => self.analyze_expression(issues, expr, file_path, source_code),
+        }
+    }
+
+    fn report_missing_return_type(&self, issues: &mut Vec<CodeIssue>, span: Span, file_path: &Path, source_code: &str) {
+        self.add_issue(
+            issues,
+            file_path,
+            source_code,
+            span,
+            "Tipe return hilang pada fungsi yang di-export - tambahkan tipe return eksplisit agar API modul tidak berubah tanpa disadari".to_string(),
+            "explicit-function-return-type".to_string(),
+            Severity::Suggestion,
+        );
+    }
+
+    fn analyze_function(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        func: &Function,
+        exported: bool,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        if let Some(type_params) = &func.type_parameters {
+            self.analyze_type_parameters(issues, type_params, file_path, source_code);
+        }
+        self.analyze_params(issues, &func.params, file_path, source_code);
+
+        match &func.return_type {
+            Some(return_type) => self.analyze_ts_type(issues, &return_type.type_annotation, file_path, source_code),
+            None if exported => self.report_missing_return_type(issues, func.span, file_path, source_code),
+            None => {}
+        }
+
+        if let Some(body) = &func.body {
+            for stmt in &body.statements {
+                self.analyze_statement(issues, stmt, file_path, source_code);
+            }
+        }
+    }
+
+    fn analyze_params(&self, issues: &mut Vec<CodeIssue>, params: &FormalParameters, file_path: &Path, source_code: &str) {
+        for param in &params.items {
+            if let Some(type_ann) = &param.pattern.type_annotation {
+                self.analyze_ts_type(issues, &type_ann.type_annotation, file_path, source_code);
+            }
+        }
+        if let Some(rest) = &params.rest {
+            if let Some(type_ann) = &rest.argument.type_annotation {
+                self.analyze_ts_type(issues, &type_ann.type_annotation, file_path, source_code);
+            }
+        }
+    }
+
+    fn analyze_type_parameters(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        type_params: &TSTypeParameterDeclaration,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        for param in &type_params.params {
+            if let Some(constraint) = &param.constraint {
+                self.analyze_ts_type(issues, constraint, file_path, source_code);
+            }
+            if let Some(default) = &param.default {
+                self.analyze_ts_type(issues, default, file_path, source_code);
+            }
+        }
+    }
+
+    fn analyze_class(&self, issues: &mut Vec<CodeIssue>, class: &Class, file_path: &Path, source_code: &str) {
+        if let Some(type_params) = &class.type_parameters {
+            self.analyze_type_parameters(issues, type_params, file_path, source_code);
+        }
+
+        for element in &class.body.body {
+            match element {
+                ClassElement::MethodDefinition(method) => {
+                    self.analyze_function(issues, &method.value, false, file_path, source_code);
+                }
+                ClassElement::PropertyDefinition(prop) => {
+                    if let Some(type_ann) = &prop.type_annotation {
                         self.analyze_ts_type(issues, &type_ann.type_annotation, file_path, source_code);
                     }
+                    if let Some(value) = &prop.value {
+                        self.analyze_expression(issues, value, file_path, source_code);
+                    }
                 }
+                _ => {}
             }
-            Statement::FunctionDeclaration(func) => {
-                // Check for missing return type on exported functions
-                if func.return_type.is_none() {
-                    self.add_issue(
-                        issues,
-                        file_path,
-                        source_code,
-                        func.span,
-                        "Tipe return hilang pada fungsi - tambahkan tipe return eksplisit untuk keamanan tipe yang lebih baik".to_string(),
-                        "explicit-function-return-type".to_string(),
-                        Severity::Suggestion,
-                    );
-                } else if let Some(return_type) = &func.return_type {
+        }
+    }
+
+    fn analyze_interface(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        interface: &TSInterfaceDeclaration,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        let extends_count = interface.extends.as_ref().map_or(0, |extends| extends.len());
+        if interface.body.body.is_empty() && extends_count <= 1 {
+            let message = if extends_count == 1 {
+                format!("Interface '{}' kosong dan hanya meng-extend satu tipe. Gunakan tipe tersebut langsung atau type alias", interface.id.name)
+            } else {
+                format!("Interface '{}' kosong dan menerima nilai apa pun selain null/undefined", interface.id.name)
+            };
+            self.add_issue(
+                issues,
+                file_path,
+                source_code,
+                interface.id.span,
+                message,
+                "no-empty-interface".to_string(),
+                Severity::Warning,
+            );
+        }
+
+        if let Some(type_params) = &interface.type_parameters {
+            self.analyze_type_parameters(issues, type_params, file_path, source_code);
+        }
+        if let Some(extends) = &interface.extends {
+            for heritage in extends {
+                if let Some(type_args) = &heritage.type_parameters {
+                    for ts_type in &type_args.params {
+                        self.analyze_ts_type(issues, ts_type, file_path, source_code);
+                    }
+                }
+            }
+        }
+        for signature in &interface.body.body {
+            self.analyze_signature(issues, signature, file_path, source_code);
+        }
+    }
+
+    fn analyze_signature(&self, issues: &mut Vec<CodeIssue>, signature: &TSSignature, file_path: &Path, source_code: &str) {
+        match signature {
+            TSSignature::TSPropertySignature(prop) => {
+                if let Some(type_ann) = &prop.type_annotation {
+                    self.analyze_ts_type(issues, &type_ann.type_annotation, file_path, source_code);
+                }
+            }
+            TSSignature::TSIndexSignature(index) => {
+                self.analyze_ts_type(issues, &index.type_annotation.type_annotation, file_path, source_code);
+            }
+            TSSignature::TSMethodSignature(method) => {
+                self.analyze_params(issues, &method.params, file_path, source_code);
+                if let Some(return_type) = &method.return_type {
+                    self.analyze_ts_type(issues, &return_type.type_annotation, file_path, source_code);
+                }
+            }
+            TSSignature::TSCallSignatureDeclaration(call) => {
+                self.analyze_params(issues, &call.params, file_path, source_code);
+                if let Some(return_type) = &call.return_type {
+                    self.analyze_ts_type(issues, &return_type.type_annotation, file_path, source_code);
+                }
+            }
+            TSSignature::TSConstructSignatureDeclaration(construct) => {
+                self.analyze_params(issues, &construct.params, file_path, source_code);
+                if let Some(return_type) = &construct.return_type {
+                    self.analyze_ts_type(issues, &return_type.type_annotation, file_path, source_code);
+                }
+            }
+        }
+    }
+
+    fn analyze_expression(&self, issues: &mut Vec<CodeIssue>, expr: &Expression, file_path: &Path, source_code: &str) {
+        match expr {
+            Expression::TSNonNullExpression(non_null) => {
+                self.add_issue(
+                    issues,
+                    file_path,
+                    source_code,
+                    non_null.span,
+                    "Non-null assertion (!) mematikan pemeriksaan null dari compiler. Gunakan optional chaining (?.), pengecekan eksplisit, atau nilai default".to_string(),
+                    "no-non-null-assertion".to_string(),
+                    Severity::Warning,
+                );
+                self.analyze_expression(issues, &non_null.expression, file_path, source_code);
+            }
+            Expression::TSAsExpression(as_expr) => {
+                self.check_literal_cast(issues, &as_expr.expression, &as_expr.type_annotation, as_expr.span, file_path, source_code);
+                self.analyze_ts_type(issues, &as_expr.type_annotation, file_path, source_code);
+                self.analyze_expression(issues, &as_expr.expression, file_path, source_code);
+            }
+            Expression::TSTypeAssertion(assertion) => {
+                self.check_literal_cast(issues, &assertion.expression, &assertion.type_annotation, assertion.span, file_path, source_code);
+                self.analyze_ts_type(issues, &assertion.type_annotation, file_path, source_code);
+                self.analyze_expression(issues, &assertion.expression, file_path, source_code);
+            }
+            Expression::TSSatisfiesExpression(satisfies) => {
+                self.analyze_ts_type(issues, &satisfies.type_annotation, file_path, source_code);
+                self.analyze_expression(issues, &satisfies.expression, file_path, source_code);
+            }
+            Expression::CallExpression(call_expr) => {
+                if let Some(type_args) = &call_expr.type_parameters {
+                    for ts_type in &type_args.params {
+                        self.analyze_ts_type(issues, ts_type, file_path, source_code);
+                    }
+                }
+                self.analyze_expression(issues, &call_expr.callee, file_path, source_code);
+                for arg in &call_expr.arguments {
+                    if let Some(expr) = arg.as_expression() {
+                        self.analyze_expression(issues, expr, file_path, source_code);
+                    }
+                }
+            }
+            Expression::NewExpression(new_expr) => {
+                if let Some(type_args) = &new_expr.type_parameters {
+                    for ts_type in &type_args.params {
+                        self.analyze_ts_type(issues, ts_type, file_path, source_code);
+                    }
+                }
+                for arg in &new_expr.arguments {
+                    if let Some(expr) = arg.as_expression() {
+                        self.analyze_expression(issues, expr, file_path, source_code);
+                    }
+                }
+            }
+            Expression::ArrowFunctionExpression(arrow) => {
+                if let Some(type_params) = &arrow.type_parameters {
+                    self.analyze_type_parameters(issues, type_params, file_path, source_code);
+                }
+                self.analyze_params(issues, &arrow.params, file_path, source_code);
+                if let Some(return_type) = &arrow.return_type {
                     self.analyze_ts_type(issues, &return_type.type_annotation, file_path, source_code);
                 }
+                for stmt in &arrow.body.statements {
+                    self.analyze_statement(issues, stmt, file_path, source_code);
+                }
+            }
+            Expression::FunctionExpression(func) => {
+                self.analyze_function(issues, func, false, file_path, source_code);
+            }
+            Expression::ClassExpression(class) => {
+                self.analyze_class(issues, class, file_path, source_code);
+            }
+            Expression::AssignmentExpression(assign_expr) => {
+                self.analyze_expression(issues, &assign_expr.right, file_path, source_code);
+            }
+            Expression::BinaryExpression(bin_expr) => {
+                self.analyze_expression(issues, &bin_expr.left, file_path, source_code);
+                self.analyze_expression(issues, &bin_expr.right, file_path, source_code);
+            }
+            Expression::LogicalExpression(logical) => {
+                self.analyze_expression(issues, &logical.left, file_path, source_code);
+                self.analyze_expression(issues, &logical.right, file_path, source_code);
+            }
+            Expression::ConditionalExpression(cond) => {
+                self.analyze_expression(issues, &cond.test, file_path, source_code);
+                self.analyze_expression(issues, &cond.consequent, file_path, source_code);
+                self.analyze_expression(issues, &cond.alternate, file_path, source_code);
+            }
+            Expression::ArrayExpression(array) => {
+                for elem in &array.elements {
+                    if let Some(expr) = elem.as_expression() {
+                        self.analyze_expression(issues, expr, file_path, source_code);
+                    }
+                }
+            }
+            Expression::ObjectExpression(obj_expr) => {
+                for prop in &obj_expr.properties {
+                    if let ObjectPropertyKind::ObjectProperty(prop) = prop {
+                        self.analyze_expression(issues, &prop.value, file_path, source_code);
+                    }
+                }
+            }
+            Expression::StaticMemberExpression(member) => {
+                self.analyze_expression(issues, &member.object, file_path, source_code);
+            }
+            Expression::ComputedMemberExpression(member) => {
+                self.analyze_expression(issues, &member.object, file_path, source_code);
+                self.analyze_expression(issues, &member.expression, file_path, source_code);
+            }
+            Expression::ParenthesizedExpression(paren) => {
+                self.analyze_expression(issues, &paren.expression, file_path, source_code);
+            }
+            Expression::AwaitExpression(await_expr) => {
+                self.analyze_expression(issues, &await_expr.argument, file_path, source_code);
+            }
+            Expression::UnaryExpression(unary) => {
+                self.analyze_expression(issues, &unary.argument, file_path, source_code);
+            }
+            Expression::SequenceExpression(seq) => {
+                for expr in &seq.expressions {
+                    self.analyze_expression(issues, expr, file_path, source_code);
+                }
+            }
+            Expression::TemplateLiteral(template) => {
+                for expr in &template.expressions {
+                    self.analyze_expression(issues, expr, file_path, source_code);
+                }
             }
             _ => {}
         }
     }
 
+    /// `'a' as 'b'` or `1 as 'one'`: the value can never have the asserted type
+    fn check_literal_cast(
+        &self,
+        issues: &mut Vec<CodeIssue>,
+        expr: &Expression,
+        ts_type: &TSType,
+        span: Span,
+        file_path: &Path,
+        source_code: &str,
+    ) {
+        let Some(value) = Self::literal_value(expr) else {
+            return;
+        };
+
+        let target = match ts_type {
+            TSType::TSStringKeyword(_) => value.starts_with('"').then_some(value.clone()),
+            TSType::TSNumberKeyword(_) => value.parse::<f64>().is_ok().then_some(value.clone()),
+            TSType::TSBooleanKeyword(_) => (value == "true" || value == "false").then_some(value.clone()),
+            TSType::TSLiteralType(_) => Self::literal_type_value(ts_type),
+            _ => return,
+        };
+
+        if target.as_deref() != Some(value.as_str()) {
+            let type_text = source_code
+                .get(span.start as usize..span.end as usize)
+                .and_then(|text| text.rsplit(" as ").next())
+                .unwrap_or("?");
+            self.add_issue(
+                issues,
+                file_path,
+                source_code,
+                span,
+                format!(
+                    "Cast tidak aman: literal {} tidak pernah bertipe {}. Perbaiki nilainya atau tipe tujuannya",
+                    value,
+                    type_text.trim()
+                ),
+                "no-unsafe-literal-cast".to_string(),
+                Severity::Warning,
+            );
+        }
+    }
+
     fn analyze_ts_type(
         &self,
         issues: &mut Vec<CodeIssue>,
@@ -124,6 +697,26 @@ impl TypeScriptAnalyzer {
                     Severity::Suggestion,
                 );
             }
+            TSType::TSTypeReference(reference) => {
+                if let TSTypeName::IdentifierReference(ident) = &reference.type_name {
+                    if let Some((name, hint)) = BANNED_TYPES.iter().find(|(name, _)| ident.name == *name) {
+                        self.add_issue(
+                            issues,
+                            file_path,
+                            source_code,
+                            reference.span,
+                            format!("Hindari tipe '{}' yang terlalu longgar - {}", name, hint),
+                            "ban-types".to_string(),
+                            Severity::Warning,
+                        );
+                    }
+                }
+                if let Some(type_args) = &reference.type_parameters {
+                    for ts_type in &type_args.params {
+                        self.analyze_ts_type(issues, ts_type, file_path, source_code);
+                    }
+                }
+            }
             TSType::TSArrayType(array_type) => {
                 // Recursively check element type
                 self.analyze_ts_type(issues, &array_type.element_type, file_path, source_code);
@@ -134,6 +727,48 @@ impl TypeScriptAnalyzer {
                     self.analyze_ts_type(issues, type_ann, file_path, source_code);
                 }
             }
+            TSType::TSIntersectionType(intersection) => {
+                for type_ann in &intersection.types {
+                    self.analyze_ts_type(issues, type_ann, file_path, source_code);
+                }
+            }
+            TSType::TSTupleType(tuple) => {
+                for element in &tuple.element_types {
+                    if let Some(ts_type) = element.as_ts_type() {
+                        self.analyze_ts_type(issues, ts_type, file_path, source_code);
+                    }
+                }
+            }
+            TSType::TSParenthesizedType(paren) => {
+                self.analyze_ts_type(issues, &paren.type_annotation, file_path, source_code);
+            }
+            TSType::TSTypeOperatorType(operator) => {
+                self.analyze_ts_type(issues, &operator.type_annotation, file_path, source_code);
+            }
+            TSType::TSIndexedAccessType(indexed) => {
+                self.analyze_ts_type(issues, &indexed.object_type, file_path, source_code);
+                self.analyze_ts_type(issues, &indexed.index_type, file_path, source_code);
+            }
+            TSType::TSConditionalType(conditional) => {
+                self.analyze_ts_type(issues, &conditional.check_type, file_path, source_code);
+                self.analyze_ts_type(issues, &conditional.extends_type, file_path, source_code);
+                self.analyze_ts_type(issues, &conditional.true_type, file_path, source_code);
+                self.analyze_ts_type(issues, &conditional.false_type, file_path, source_code);
+            }
+            TSType::TSFunctionType(func_type) => {
+                self.analyze_params(issues, &func_type.params, file_path, source_code);
+                self.analyze_ts_type(issues, &func_type.return_type.type_annotation, file_path, source_code);
+            }
+            TSType::TSTypeLiteral(literal) => {
+                for signature in &literal.members {
+                    self.analyze_signature(issues, signature, file_path, source_code);
+                }
+            }
+            TSType::TSMappedType(mapped) => {
+                if let Some(type_ann) = &mapped.type_annotation {
+                    self.analyze_ts_type(issues, type_ann, file_path, source_code);
+                }
+            }
             _ => {}
         }
     }
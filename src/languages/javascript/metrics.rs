@@ -0,0 +1,353 @@
+//! Code metrics for JS/TS files, collected from the oxc AST
+
+use super::analyzers::complexity::ComplexityAnalyzer;
+use crate::metrics::halstead;
+use crate::metrics::{maintainability_index, round, FileMetrics, FunctionMetrics};
+use oxc_ast::ast::*;
+use oxc_span::Span;
+use std::path::Path;
+
+/// Counts for the function currently being walked
+#[derive(Default)]
+struct FunctionStats {
+    statements: usize,
+    decisions: usize,
+    max_nesting: usize,
+}
+
+impl FunctionStats {
+    fn nest(&mut self, depth: usize) {
+        self.max_nesting = self.max_nesting.max(depth + 1);
+    }
+}
+
+/// Walks a program and measures every function-like node; nested functions are measured on their own
+pub struct MetricsCollector<'s> {
+    source_code: &'s str,
+    complexity: ComplexityAnalyzer,
+    functions: Vec<FunctionMetrics>,
+}
+
+impl<'s> MetricsCollector<'s> {
+    pub fn collect(program: &Program, file_path: &Path, source_code: &'s str) -> FileMetrics {
+        let mut collector = Self {
+            source_code,
+            complexity: ComplexityAnalyzer::new(),
+            functions: Vec::new(),
+        };
+
+        let mut stats = FunctionStats::default();
+        for stmt in &program.body {
+            collector.statement(stmt, 0, &mut stats);
+        }
+
+        let logical = stats.statements + collector.functions.iter().map(|function| function.loc.logical).sum::<usize>();
+        let text = halstead::measure(source_code, &halstead::JAVASCRIPT);
+        FileMetrics::new(file_path, text.loc(logical), text.volume(), collector.functions)
+    }
+
+    fn line(&self, offset: u32) -> usize {
+        self.source_code[..offset as usize].matches('\n').count() + 1
+    }
+
+    fn function(&mut self, name: &str, span: Span, params: &FormalParameters, body: &FunctionBody) {
+        let mut stats = FunctionStats::default();
+        for stmt in &body.statements {
+            self.statement(stmt, 0, &mut stats);
+        }
+
+        let text = halstead::measure(&self.source_code[span.start as usize..span.end as usize], &halstead::JAVASCRIPT);
+        let logical = stats.statements.max(1);
+        let cyclomatic = 1 + stats.decisions;
+        let volume = text.volume();
+
+        self.functions.push(FunctionMetrics {
+            name: name.to_string(),
+            line: self.line(span.start),
+            end_line: self.line(span.end),
+            loc: text.loc(logical),
+            params: params.items.len() + usize::from(params.rest.is_some()),
+            cyclomatic,
+            cognitive: self.complexity.cognitive_score(name, body, self.source_code).total(),
+            max_nesting: stats.max_nesting,
+            halstead_volume: round(volume),
+            maintainability_index: maintainability_index(volume, cyclomatic, logical),
+        });
+    }
+
+    fn function_declaration(&mut self, fallback_name: &str, func: &Function) {
+        let name = func.id.as_ref().map_or(fallback_name, |id| id.name.as_str());
+        if let Some(body) = &func.body {
+            self.function(name, func.span, &func.params, body);
+        }
+    }
+
+    /// Functions assigned to a variable or property take its name
+    fn named_expression(&mut self, name: &str, expr: &Expression, depth: usize, stats: &mut FunctionStats) {
+        match expr {
+            Expression::ArrowFunctionExpression(arrow) => self.function(name, arrow.span, &arrow.params, &arrow.body),
+            Expression::FunctionExpression(func) => self.function_declaration(name, func),
+            _ => self.expression(expr, depth, stats),
+        }
+    }
+
+    fn class(&mut self, class: &Class, depth: usize, stats: &mut FunctionStats) {
+        for element in &class.body.body {
+            match element {
+                ClassElement::MethodDefinition(method) => {
+                    let name = method.key.static_name();
+                    self.function_declaration(name.as_deref().unwrap_or("<computed>"), &method.value);
+                }
+                ClassElement::PropertyDefinition(prop) => {
+                    if let Some(value) = &prop.value {
+                        let name = prop.key.static_name();
+                        self.named_expression(name.as_deref().unwrap_or("<computed>"), value, depth, stats);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn statement(&mut self, stmt: &Statement, depth: usize, stats: &mut FunctionStats) {
+        match stmt {
+            Statement::BlockStatement(block) => {
+                for stmt in &block.body {
+                    self.statement(stmt, depth, stats);
+                }
+                return;
+            }
+            Statement::LabeledStatement(labeled) => {
+                self.statement(&labeled.body, depth, stats);
+                return;
+            }
+            Statement::EmptyStatement(_) => return,
+            _ => stats.statements += 1,
+        }
+
+        match stmt {
+            Statement::VariableDeclaration(var_decl) => self.variable_declaration(var_decl, depth, stats),
+            Statement::FunctionDeclaration(func) => self.function_declaration("<anonymous>", func),
+            Statement::ClassDeclaration(class) => self.class(class, depth, stats),
+            Statement::ExpressionStatement(expr_stmt) => self.expression(&expr_stmt.expression, depth, stats),
+            Statement::ReturnStatement(ret_stmt) => {
+                if let Some(argument) = &ret_stmt.argument {
+                    self.expression(argument, depth, stats);
+                }
+            }
+            Statement::ThrowStatement(throw_stmt) => self.expression(&throw_stmt.argument, depth, stats),
+            Statement::IfStatement(if_stmt) => {
+                stats.decisions += 1;
+                stats.nest(depth);
+                self.expression(&if_stmt.test, depth, stats);
+                self.statement(&if_stmt.consequent, depth + 1, stats);
+                match &if_stmt.alternate {
+                    // `else if` stays at the same level
+                    Some(alternate @ Statement::IfStatement(_)) => self.statement(alternate, depth, stats),
+                    Some(alternate) => self.statement(alternate, depth + 1, stats),
+                    None => {}
+                }
+            }
+            Statement::ForStatement(for_stmt) => {
+                stats.decisions += 1;
+                stats.nest(depth);
+                if let Some(ForStatementInit::VariableDeclaration(var_decl)) = &for_stmt.init {
+                    self.variable_declaration(var_decl, depth, stats);
+                }
+                if let Some(test) = &for_stmt.test {
+                    self.expression(test, depth, stats);
+                }
+                self.statement(&for_stmt.body, depth + 1, stats);
+            }
+            Statement::ForOfStatement(for_of) => {
+                stats.decisions += 1;
+                stats.nest(depth);
+                self.expression(&for_of.right, depth, stats);
+                self.statement(&for_of.body, depth + 1, stats);
+            }
+            Statement::ForInStatement(for_in) => {
+                stats.decisions += 1;
+                stats.nest(depth);
+                self.expression(&for_in.right, depth, stats);
+                self.statement(&for_in.body, depth + 1, stats);
+            }
+            Statement::WhileStatement(while_stmt) => {
+                stats.decisions += 1;
+                stats.nest(depth);
+                self.expression(&while_stmt.test, depth, stats);
+                self.statement(&while_stmt.body, depth + 1, stats);
+            }
+            Statement::DoWhileStatement(do_while) => {
+                stats.decisions += 1;
+                stats.nest(depth);
+                self.statement(&do_while.body, depth + 1, stats);
+                self.expression(&do_while.test, depth, stats);
+            }
+            Statement::SwitchStatement(switch_stmt) => {
+                stats.decisions += switch_stmt.cases.iter().filter(|case| case.test.is_some()).count();
+                stats.nest(depth);
+                self.expression(&switch_stmt.discriminant, depth, stats);
+                for case in &switch_stmt.cases {
+                    for stmt in &case.consequent {
+                        self.statement(stmt, depth + 1, stats);
+                    }
+                }
+            }
+            Statement::TryStatement(try_stmt) => {
+                for stmt in &try_stmt.block.body {
+                    self.statement(stmt, depth, stats);
+                }
+                if let Some(handler) = &try_stmt.handler {
+                    stats.decisions += 1;
+                    stats.nest(depth);
+                    for stmt in &handler.body.body {
+                        self.statement(stmt, depth + 1, stats);
+                    }
+                }
+                if let Some(finalizer) = &try_stmt.finalizer {
+                    for stmt in &finalizer.body {
+                        self.statement(stmt, depth, stats);
+                    }
+                }
+            }
+            Statement::ExportNamedDeclaration(export) => match &export.declaration {
+                Some(Declaration::VariableDeclaration(var_decl)) => self.variable_declaration(var_decl, depth, stats),
+                Some(Declaration::FunctionDeclaration(func)) => self.function_declaration("<anonymous>", func),
+                Some(Declaration::ClassDeclaration(class)) => self.class(class, depth, stats),
+                _ => {}
+            },
+            Statement::ExportDefaultDeclaration(export) => match &export.declaration {
+                ExportDefaultDeclarationKind::FunctionDeclaration(func) => self.function_declaration("default", func),
+                ExportDefaultDeclarationKind::ClassDeclaration(class) => self.class(class, depth, stats),
+                declaration => {
+                    if let Some(expr) = declaration.as_expression() {
+                        self.named_expression("default", expr, depth, stats);
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    fn variable_declaration(&mut self, var_decl: &VariableDeclaration, depth: usize, stats: &mut FunctionStats) {
+        for var in &var_decl.declarations {
+            let Some(init) = &var.init else {
+                continue;
+            };
+            match &var.id.kind {
+                BindingPatternKind::BindingIdentifier(id) => self.named_expression(id.name.as_str(), init, depth, stats),
+                _ => self.expression(init, depth, stats),
+            }
+        }
+    }
+
+    fn expression(&mut self, expr: &Expression, depth: usize, stats: &mut FunctionStats) {
+        match expr {
+            Expression::LogicalExpression(logical) => {
+                stats.decisions += 1;
+                self.expression(&logical.left, depth, stats);
+                self.expression(&logical.right, depth, stats);
+            }
+            Expression::ConditionalExpression(cond) => {
+                stats.decisions += 1;
+                stats.nest(depth);
+                self.expression(&cond.test, depth, stats);
+                self.expression(&cond.consequent, depth + 1, stats);
+                self.expression(&cond.alternate, depth + 1, stats);
+            }
+            Expression::ArrowFunctionExpression(arrow) => {
+                self.function("<anonymous>", arrow.span, &arrow.params, &arrow.body);
+            }
+            Expression::FunctionExpression(func) => self.function_declaration("<anonymous>", func),
+            Expression::ClassExpression(class) => self.class(class, depth, stats),
+            Expression::CallExpression(call_expr) => {
+                self.expression(&call_expr.callee, depth, stats);
+                for arg in &call_expr.arguments {
+                    if let Some(expr) = arg.as_expression() {
+                        self.expression(expr, depth, stats);
+                    }
+                }
+            }
+            Expression::NewExpression(new_expr) => {
+                for arg in &new_expr.arguments {
+                    if let Some(expr) = arg.as_expression() {
+                        self.expression(expr, depth, stats);
+                    }
+                }
+            }
+            Expression::BinaryExpression(bin_expr) => {
+                self.expression(&bin_expr.left, depth, stats);
+                self.expression(&bin_expr.right, depth, stats);
+            }
+            Expression::AssignmentExpression(assign_expr) => {
+                self.expression(&assign_expr.right, depth, stats);
+            }
+            Expression::UnaryExpression(unary) => self.expression(&unary.argument, depth, stats),
+            Expression::AwaitExpression(await_expr) => self.expression(&await_expr.argument, depth, stats),
+            Expression::ParenthesizedExpression(paren) => self.expression(&paren.expression, depth, stats),
+            Expression::SequenceExpression(seq) => {
+                for expr in &seq.expressions {
+                    self.expression(expr, depth, stats);
+                }
+            }
+            Expression::StaticMemberExpression(member) => self.expression(&member.object, depth, stats),
+            Expression::ComputedMemberExpression(member) => {
+                self.expression(&member.object, depth, stats);
+                self.expression(&member.expression, depth, stats);
+            }
+            Expression::ArrayExpression(array) => {
+                for elem in &array.elements {
+                    if let Some(expr) = elem.as_expression() {
+                        self.expression(expr, depth, stats);
+                    }
+                }
+            }
+            Expression::ObjectExpression(obj_expr) => {
+                for prop in &obj_expr.properties {
+                    if let ObjectPropertyKind::ObjectProperty(prop) = prop {
+                        let name = prop.key.static_name();
+                        self.named_expression(name.as_deref().unwrap_or("<computed>"), &prop.value, depth, stats);
+                    }
+                }
+            }
+            Expression::TemplateLiteral(template) => {
+                for expr in &template.expressions {
+                    self.expression(expr, depth, stats);
+                }
+            }
+            Expression::TSAsExpression(as_expr) => self.expression(&as_expr.expression, depth, stats),
+            Expression::TSNonNullExpression(non_null) => self.expression(&non_null.expression, depth, stats),
+            Expression::JSXElement(element) => self.jsx_element(element, depth, stats),
+            Expression::JSXFragment(fragment) => self.jsx_children(&fragment.children, depth, stats),
+            _ => {}
+        }
+    }
+
+    fn jsx_element(&mut self, element: &JSXElement, depth: usize, stats: &mut FunctionStats) {
+        for attr in &element.opening_element.attributes {
+            if let JSXAttributeItem::Attribute(attr) = attr {
+                if let Some(JSXAttributeValue::ExpressionContainer(container)) = &attr.value {
+                    if let Some(expr) = container.expression.as_expression() {
+                        self.expression(expr, depth, stats);
+                    }
+                }
+            }
+        }
+        self.jsx_children(&element.children, depth, stats);
+    }
+
+    fn jsx_children(&mut self, children: &[JSXChild], depth: usize, stats: &mut FunctionStats) {
+        for child in children {
+            match child {
+                JSXChild::Element(element) => self.jsx_element(element, depth, stats),
+                JSXChild::Fragment(fragment) => self.jsx_children(&fragment.children, depth, stats),
+                JSXChild::ExpressionContainer(container) => {
+                    if let Some(expr) = container.expression.as_expression() {
+                        self.expression(expr, depth, stats);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
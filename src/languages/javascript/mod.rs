@@ -1,4 +1,6 @@
 pub mod parser;
 pub mod analyzers;
+pub mod metrics;
+pub mod structural;
 
 pub use parser::JsParser;
@@ -1,32 +1,40 @@
 use super::analyzers::Analyzers;
+use super::metrics::MetricsCollector;
+use crate::clones::CloneDetector;
+use crate::config::Config;
 use crate::error::{AnalyzerError, Result};
+use crate::metrics::halstead;
+use crate::metrics::FileMetrics;
 use crate::types::{AnalysisResult, FileAnalysis, SeveritySummary};
 use std::path::{Path, PathBuf};
 use std::fs;
 use oxc_allocator::Allocator;
+use oxc_ast::ast::Program;
 use oxc_parser::Parser;
 use oxc_span::SourceType;
 
 pub struct JsParser {
     allocator: Allocator,
     analyzers: Analyzers,
+    clones: CloneDetector,
 }
 
 impl JsParser {
     pub fn new() -> Self {
+        Self::with_config(&Config::default())
+    }
+
+    pub fn with_config(config: &Config) -> Self {
         Self {
             allocator: Allocator::default(),
-            analyzers: Analyzers::new(),
+            analyzers: Analyzers::with_config(config),
+            clones: CloneDetector::with_config(&config.clones),
         }
     }
 
-    pub fn analyze_file(&self, file_path: &Path) -> Result<FileAnalysis> {
-        let code = fs::read_to_string(file_path).map_err(|_| AnalyzerError::FileReadError {
-            path: file_path.display().to_string(),
-        })?;
-
+    fn parse<'a>(&'a self, code: &'a str, file_path: &Path) -> Result<Program<'a>> {
         let source_type = SourceType::from_path(file_path).unwrap_or(SourceType::default());
-        let parser = Parser::new(&self.allocator, &code, source_type);
+        let parser = Parser::new(&self.allocator, code, source_type);
 
         let ret = parser.parse();
 
@@ -39,7 +47,15 @@ impl JsParser {
             });
         }
 
-        let program = ret.program;
+        Ok(ret.program)
+    }
+
+    pub fn analyze_file(&self, file_path: &Path) -> Result<FileAnalysis> {
+        let code = fs::read_to_string(file_path).map_err(|_| AnalyzerError::FileReadError {
+            path: file_path.display().to_string(),
+        })?;
+
+        let program = self.parse(&code, file_path)?;
 
         let issues = self.analyzers.analyze_module(&program, file_path, &code);
 
@@ -55,6 +71,19 @@ impl JsParser {
         })
     }
 
+    pub fn metrics_file(&self, file_path: &Path) -> Result<FileMetrics> {
+        let code = fs::read_to_string(file_path).map_err(|_| AnalyzerError::FileReadError {
+            path: file_path.display().to_string(),
+        })?;
+        let program = self.parse(&code, file_path)?;
+        Ok(MetricsCollector::collect(&program, file_path, &code))
+    }
+
+    pub fn metrics_directory(&self, dir_path: &Path) -> Result<Vec<FileMetrics>> {
+        let files = self.find_js_files(dir_path)?;
+        Ok(files.iter().filter_map(|file_path| self.metrics_file(file_path).ok()).collect())
+    }
+
     pub fn analyze_directory(&self, dir_path: &Path) -> Result<AnalysisResult> {
         let mut result = AnalysisResult::new();
 
@@ -71,9 +100,15 @@ impl JsParser {
             }
         }
 
+        self.detect_clones(&mut result);
         Ok(result)
     }
 
+    /// Report duplicated code within and across the files of `result`
+    pub fn detect_clones(&self, result: &mut AnalysisResult) {
+        self.clones.detect(result, &halstead::JAVASCRIPT);
+    }
+
     fn find_js_files(&self, dir_path: &Path) -> Result<Vec<PathBuf>> {
         let mut js_files = Vec::new();
 
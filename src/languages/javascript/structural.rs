@@ -0,0 +1,543 @@
+//! Structural code patterns for JS/TS rules loaded from YAML files.
+//!
+//! A pattern is JavaScript with metavariables: `$OBJ.innerHTML = $X` or
+//! `fetch($URL, { method: "POST" })`. `$NAME` matches any expression and must match the same
+//! code wherever it repeats, `$_` matches anything without binding, and `...` stands for any
+//! number of arguments or array elements. Object patterns match objects that have at least the
+//! listed properties, and string literals match regardless of quote style.
+
+use crate::error::{AnalyzerError, Result};
+use crate::types::{Category, Severity};
+use oxc_allocator::Allocator;
+use oxc_ast::ast::*;
+use oxc_ast::visit::walk;
+use oxc_ast::Visit;
+use oxc_parser::Parser;
+use oxc_span::{GetSpan, SourceType, Span};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Identifier that `...` is rewritten to before the pattern is parsed
+const ELLIPSIS: &str = "$__ELLIPSIS__";
+
+/// A compiled pattern, independent of the allocator it was parsed with
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    /// `$_`
+    Any,
+    /// `$NAME`
+    Metavariable(String),
+    /// `...` in argument and element lists
+    Ellipsis,
+    Identifier(String),
+    String(String),
+    Number(f64),
+    Boolean(bool),
+    Null,
+    This,
+    /// `object.property`, also matching `object["property"]`
+    Member { object: Box<Pattern>, property: Box<Pattern> },
+    /// `object[index]`
+    Index { object: Box<Pattern>, index: Box<Pattern> },
+    Call { callee: Box<Pattern>, arguments: Vec<Pattern> },
+    New { callee: Box<Pattern>, arguments: Vec<Pattern> },
+    Assign { operator: &'static str, target: Box<Pattern>, value: Box<Pattern> },
+    Binary { operator: &'static str, left: Box<Pattern>, right: Box<Pattern> },
+    Unary { operator: &'static str, argument: Box<Pattern> },
+    Await(Box<Pattern>),
+    /// Properties the matched object must have, in any order
+    Object(Vec<(Pattern, Pattern)>),
+    Array(Vec<Pattern>),
+    /// Any other syntax, compared by its source text without whitespace
+    Source(String),
+}
+
+/// Source spans bound to metavariables by one match
+pub type Bindings = HashMap<String, Span>;
+
+/// A rule from a YAML rule file, with its patterns compiled
+#[derive(Debug, Clone)]
+pub struct StructuralRule {
+    pub id: String,
+    pub pattern: Pattern,
+    /// Matches must lie inside code matching each of these
+    pub inside: Vec<Pattern>,
+    /// Matches must not lie inside code matching any of these
+    pub not_inside: Vec<Pattern>,
+    /// Issue message; `$NAME` is replaced by the code bound to the metavariable
+    pub message: String,
+    pub severity: Severity,
+    pub category: Category,
+}
+
+/// One or several patterns, so YAML can give `inside: foo($X)` or a list
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Patterns {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Default for Patterns {
+    fn default() -> Self {
+        Patterns::Many(Vec::new())
+    }
+}
+
+impl Patterns {
+    fn into_vec(self) -> Vec<String> {
+        match self {
+            Patterns::One(pattern) => vec![pattern],
+            Patterns::Many(patterns) => patterns,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RuleSource {
+    id: String,
+    pattern: String,
+    #[serde(default)]
+    inside: Patterns,
+    #[serde(default)]
+    not_inside: Patterns,
+    message: String,
+    #[serde(default = "RuleSource::default_severity")]
+    severity: Severity,
+    #[serde(default = "RuleSource::default_category")]
+    category: Category,
+}
+
+impl RuleSource {
+    fn default_severity() -> Severity {
+        Severity::Warning
+    }
+
+    fn default_category() -> Category {
+        Category::Security
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RuleFile {
+    rules: Vec<RuleSource>,
+}
+
+impl StructuralRule {
+    /// Load the rules of a YAML file, or of every `.yml`/`.yaml` file in a directory
+    pub fn load(path: &Path) -> Result<Vec<Self>> {
+        if path.is_dir() {
+            let mut files: Vec<_> = fs::read_dir(path)?
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|file| file.extension().is_some_and(|ext| ext == "yml" || ext == "yaml"))
+                .collect();
+            files.sort();
+            let mut rules = Vec::new();
+            for file in files {
+                rules.extend(Self::load(&file)?);
+            }
+            return Ok(rules);
+        }
+
+        let content = fs::read_to_string(path).map_err(|_| AnalyzerError::FileReadError {
+            path: path.display().to_string(),
+        })?;
+        let file: RuleFile = serde_yaml::from_str(&content).map_err(|e| AnalyzerError::ConfigError {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })?;
+        file.rules.into_iter().map(Self::compile).collect()
+    }
+
+    fn compile(source: RuleSource) -> Result<Self> {
+        let compile = |pattern: &str| {
+            Pattern::parse(pattern).map_err(|message| AnalyzerError::RuleError {
+                rule: source.id.clone(),
+                message,
+            })
+        };
+        let all = |patterns: Patterns| patterns.into_vec().iter().map(|pattern| compile(pattern)).collect::<Result<Vec<_>>>();
+
+        Ok(Self {
+            pattern: compile(&source.pattern)?,
+            inside: all(source.inside)?,
+            not_inside: all(source.not_inside)?,
+            id: source.id,
+            message: source.message,
+            severity: source.severity,
+            category: source.category,
+        })
+    }
+}
+
+impl Pattern {
+    /// Compile a single JS expression with metavariables
+    pub fn parse(source: &str) -> std::result::Result<Self, String> {
+        let source = rewrite_ellipses(source);
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, &source, SourceType::default()).parse();
+        if let Some(error) = ret.errors.first() {
+            return Err(format!("pattern `{}` doesn't parse: {}", source, error));
+        }
+
+        match ret.program.body.as_slice() {
+            [Statement::ExpressionStatement(statement)] => Ok(Compiler { source: &source }.expression(&statement.expression)),
+            _ => Err(format!("pattern `{}` must be a single expression", source)),
+        }
+    }
+}
+
+/// Replace `...` that doesn't start a spread with an identifier the parser accepts
+fn rewrite_ellipses(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(index) = rest.find("...") {
+        out.push_str(&rest[..index]);
+        let after = &rest[index + 3..];
+        let spread = after.trim_start().starts_with(|c: char| c.is_alphanumeric() || c == '$' || c == '_' || c == '(' || c == '[' || c == '{');
+        out.push_str(if spread { "..." } else { ELLIPSIS });
+        rest = after;
+    }
+    out.push_str(rest);
+    out
+}
+
+fn is_metavariable(name: &str) -> bool {
+    name.strip_prefix('$').is_some_and(|rest| {
+        !rest.is_empty() && rest.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+    })
+}
+
+fn without_whitespace(text: &str) -> String {
+    text.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+struct Compiler<'s> {
+    source: &'s str,
+}
+
+impl Compiler<'_> {
+    fn name(&self, name: &str) -> Pattern {
+        match name {
+            "$_" => Pattern::Any,
+            ELLIPSIS => Pattern::Ellipsis,
+            name if is_metavariable(name) => Pattern::Metavariable(name.to_string()),
+            name => Pattern::Identifier(name.to_string()),
+        }
+    }
+
+    fn source(&self, span: Span) -> Pattern {
+        Pattern::Source(without_whitespace(&self.source[span.start as usize..span.end as usize]))
+    }
+
+    fn expression(&self, expr: &Expression) -> Pattern {
+        let boxed = |expr: &Expression| Box::new(self.expression(expr));
+        match expr.without_parentheses() {
+            Expression::Identifier(ident) => self.name(&ident.name),
+            Expression::StringLiteral(lit) => Pattern::String(lit.value.to_string()),
+            Expression::NumericLiteral(lit) => Pattern::Number(lit.value),
+            Expression::BooleanLiteral(lit) => Pattern::Boolean(lit.value),
+            Expression::NullLiteral(_) => Pattern::Null,
+            Expression::ThisExpression(_) => Pattern::This,
+            Expression::StaticMemberExpression(member) => Pattern::Member {
+                object: boxed(&member.object),
+                property: Box::new(self.name(&member.property.name)),
+            },
+            Expression::ComputedMemberExpression(member) => Pattern::Index {
+                object: boxed(&member.object),
+                index: boxed(&member.expression),
+            },
+            Expression::CallExpression(call) => Pattern::Call {
+                callee: boxed(&call.callee),
+                arguments: call.arguments.iter().map(|arg| self.argument(arg)).collect(),
+            },
+            Expression::NewExpression(new) => Pattern::New {
+                callee: boxed(&new.callee),
+                arguments: new.arguments.iter().map(|arg| self.argument(arg)).collect(),
+            },
+            Expression::AssignmentExpression(assign) => Pattern::Assign {
+                operator: assign.operator.as_str(),
+                target: Box::new(self.target(&assign.left)),
+                value: boxed(&assign.right),
+            },
+            Expression::BinaryExpression(binary) => Pattern::Binary {
+                operator: binary.operator.as_str(),
+                left: boxed(&binary.left),
+                right: boxed(&binary.right),
+            },
+            Expression::LogicalExpression(logical) => Pattern::Binary {
+                operator: logical.operator.as_str(),
+                left: boxed(&logical.left),
+                right: boxed(&logical.right),
+            },
+            Expression::UnaryExpression(unary) => Pattern::Unary {
+                operator: unary.operator.as_str(),
+                argument: boxed(&unary.argument),
+            },
+            Expression::AwaitExpression(await_expr) => Pattern::Await(boxed(&await_expr.argument)),
+            Expression::ObjectExpression(object) => Pattern::Object(
+                object
+                    .properties
+                    .iter()
+                    .filter_map(|property| match property {
+                        ObjectPropertyKind::ObjectProperty(prop) if prop.shorthand && prop.key.is_specific_id(ELLIPSIS) => None,
+                        ObjectPropertyKind::ObjectProperty(prop) if !prop.computed => {
+                            let key = prop.key.static_name().map_or_else(|| self.source(prop.key.span()), |name| self.name(&name));
+                            Some((key, self.expression(&prop.value)))
+                        }
+                        other => Some((Pattern::Any, self.source(other.span()))),
+                    })
+                    .collect(),
+            ),
+            Expression::ArrayExpression(array) => Pattern::Array(
+                array
+                    .elements
+                    .iter()
+                    .map(|element| element.as_expression().map_or_else(|| self.source(element.span()), |expr| self.expression(expr)))
+                    .collect(),
+            ),
+            other => self.source(other.span()),
+        }
+    }
+
+    fn argument(&self, arg: &Argument) -> Pattern {
+        arg.as_expression().map_or_else(|| self.source(arg.span()), |expr| self.expression(expr))
+    }
+
+    fn target(&self, target: &AssignmentTarget) -> Pattern {
+        match target {
+            AssignmentTarget::AssignmentTargetIdentifier(ident) => self.name(&ident.name),
+            AssignmentTarget::StaticMemberExpression(member) => Pattern::Member {
+                object: Box::new(self.expression(&member.object)),
+                property: Box::new(self.name(&member.property.name)),
+            },
+            AssignmentTarget::ComputedMemberExpression(member) => Pattern::Index {
+                object: Box::new(self.expression(&member.object)),
+                index: Box::new(self.expression(&member.expression)),
+            },
+            other => self.source(other.span()),
+        }
+    }
+}
+
+/// Matches one compiled pattern against the code of a file
+pub struct Matcher<'s> {
+    source: &'s str,
+}
+
+impl<'s> Matcher<'s> {
+    pub fn new(source: &'s str) -> Self {
+        Self { source }
+    }
+
+    fn text(&self, span: Span) -> &'s str {
+        &self.source[span.start as usize..span.end as usize]
+    }
+
+    /// Bind `name` to `span`, or check it against the code it is already bound to
+    fn bind(&self, name: &str, span: Span, bindings: &mut Bindings) -> bool {
+        match bindings.get(name) {
+            Some(bound) => without_whitespace(self.text(*bound)) == without_whitespace(self.text(span)),
+            None => {
+                bindings.insert(name.to_string(), span);
+                true
+            }
+        }
+    }
+
+    /// Match a name (identifier or property) that has no node of its own
+    fn name(&self, pattern: &Pattern, name: &str, span: Span, bindings: &mut Bindings) -> bool {
+        match pattern {
+            Pattern::Any => true,
+            Pattern::Metavariable(meta) => self.bind(meta, span, bindings),
+            Pattern::Identifier(expected) | Pattern::String(expected) => expected == name,
+            _ => false,
+        }
+    }
+
+    pub fn expression(&self, pattern: &Pattern, expr: &Expression, bindings: &mut Bindings) -> bool {
+        let expr = expr.without_parentheses();
+        match (pattern, expr) {
+            (Pattern::Any, _) => true,
+            (Pattern::Metavariable(name), _) => self.bind(name, expr.span(), bindings),
+            (Pattern::Source(text), _) => *text == without_whitespace(self.text(expr.span())),
+            (Pattern::Identifier(name), Expression::Identifier(ident)) => ident.name == name.as_str(),
+            (Pattern::String(value), Expression::StringLiteral(lit)) => lit.value == value.as_str(),
+            (Pattern::Number(value), Expression::NumericLiteral(lit)) => lit.value == *value,
+            (Pattern::Boolean(value), Expression::BooleanLiteral(lit)) => lit.value == *value,
+            (Pattern::Null, Expression::NullLiteral(_)) | (Pattern::This, Expression::ThisExpression(_)) => true,
+            (Pattern::Member { object, property }, _) | (Pattern::Index { object, index: property }, _)
+                if expr.is_member_expression() =>
+            {
+                let member = expr.as_member_expression().unwrap();
+                self.member(pattern, member.object(), member, object, property, bindings)
+            }
+            (Pattern::Call { callee, arguments }, Expression::CallExpression(call)) => {
+                self.expression(callee, &call.callee, bindings) && self.arguments(arguments, &call.arguments, bindings)
+            }
+            (Pattern::New { callee, arguments }, Expression::NewExpression(new)) => {
+                self.expression(callee, &new.callee, bindings) && self.arguments(arguments, &new.arguments, bindings)
+            }
+            (Pattern::Assign { operator, target, value }, Expression::AssignmentExpression(assign)) => {
+                assign.operator.as_str() == *operator
+                    && self.target(target, &assign.left, bindings)
+                    && self.expression(value, &assign.right, bindings)
+            }
+            (Pattern::Binary { operator, left, right }, Expression::BinaryExpression(binary)) => {
+                binary.operator.as_str() == *operator
+                    && self.expression(left, &binary.left, bindings)
+                    && self.expression(right, &binary.right, bindings)
+            }
+            (Pattern::Binary { operator, left, right }, Expression::LogicalExpression(logical)) => {
+                logical.operator.as_str() == *operator
+                    && self.expression(left, &logical.left, bindings)
+                    && self.expression(right, &logical.right, bindings)
+            }
+            (Pattern::Unary { operator, argument }, Expression::UnaryExpression(unary)) => {
+                unary.operator.as_str() == *operator && self.expression(argument, &unary.argument, bindings)
+            }
+            (Pattern::Await(argument), Expression::AwaitExpression(await_expr)) => {
+                self.expression(argument, &await_expr.argument, bindings)
+            }
+            (Pattern::Object(properties), Expression::ObjectExpression(object)) => {
+                properties.iter().all(|(key, value)| {
+                    object.properties.iter().any(|property| self.attempt(bindings, |matcher, trial| matcher.property(key, value, property, trial)))
+                })
+            }
+            (Pattern::Array(elements), Expression::ArrayExpression(array)) => {
+                let items: Vec<_> = array.elements.iter().collect();
+                self.list(elements, &items, bindings, &|matcher, pattern, element, trial| match element.as_expression() {
+                    Some(expr) => matcher.expression(pattern, expr, trial),
+                    None => matches!(pattern, Pattern::Source(text) if *text == without_whitespace(matcher.text(element.span()))),
+                })
+            }
+            _ => false,
+        }
+    }
+
+    /// `object.property` and `object["property"]` are the same access
+    fn member(
+        &self,
+        pattern: &Pattern,
+        object: &Expression,
+        member: &MemberExpression,
+        object_pattern: &Pattern,
+        property_pattern: &Pattern,
+        bindings: &mut Bindings,
+    ) -> bool {
+        if !self.expression(object_pattern, object, bindings) {
+            return false;
+        }
+        match (pattern, member) {
+            (Pattern::Member { .. }, MemberExpression::StaticMemberExpression(m)) => {
+                self.name(property_pattern, &m.property.name, m.property.span, bindings)
+            }
+            (Pattern::Member { .. }, MemberExpression::ComputedMemberExpression(m)) => match &m.expression {
+                Expression::StringLiteral(lit) => self.name(property_pattern, &lit.value, lit.span, bindings),
+                _ => false,
+            },
+            (Pattern::Index { .. }, MemberExpression::ComputedMemberExpression(m)) => self.expression(property_pattern, &m.expression, bindings),
+            _ => false,
+        }
+    }
+
+    fn target(&self, pattern: &Pattern, target: &AssignmentTarget, bindings: &mut Bindings) -> bool {
+        match (pattern, target) {
+            (Pattern::Any, _) => true,
+            (Pattern::Metavariable(name), _) => self.bind(name, target.span(), bindings),
+            (Pattern::Source(text), _) => *text == without_whitespace(self.text(target.span())),
+            (Pattern::Identifier(name), AssignmentTarget::AssignmentTargetIdentifier(ident)) => ident.name == name.as_str(),
+            (Pattern::Member { object, property }, _) | (Pattern::Index { object, index: property }, _) => {
+                match target.as_member_expression() {
+                    Some(member) => self.member(pattern, member.object(), member, object, property, bindings),
+                    None => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    fn property(&self, key: &Pattern, value: &Pattern, property: &ObjectPropertyKind, bindings: &mut Bindings) -> bool {
+        match property {
+            ObjectPropertyKind::ObjectProperty(prop) if !prop.computed => {
+                let key_matches = match prop.key.static_name() {
+                    Some(name) => self.name(key, &name, prop.key.span(), bindings),
+                    None => false,
+                };
+                key_matches && self.expression(value, &prop.value, bindings)
+            }
+            other => matches!(value, Pattern::Source(text) if *text == without_whitespace(self.text(other.span()))),
+        }
+    }
+
+    fn arguments(&self, patterns: &[Pattern], arguments: &[Argument], bindings: &mut Bindings) -> bool {
+        let items: Vec<_> = arguments.iter().collect();
+        self.list(patterns, &items, bindings, &|matcher, pattern, arg, trial| match arg.as_expression() {
+            Some(expr) => matcher.expression(pattern, expr, trial),
+            None => matches!(pattern, Pattern::Source(text) if *text == without_whitespace(matcher.text(arg.span()))),
+        })
+    }
+
+    /// Match a list where `...` stands for any run of items
+    fn list<T>(
+        &self,
+        patterns: &[Pattern],
+        items: &[T],
+        bindings: &mut Bindings,
+        item: &dyn Fn(&Self, &Pattern, &T, &mut Bindings) -> bool,
+    ) -> bool {
+        match patterns.split_first() {
+            None => items.is_empty(),
+            Some((Pattern::Ellipsis, rest)) => {
+                (0..=items.len()).any(|skip| self.attempt(bindings, |matcher, trial| matcher.list(rest, &items[skip..], trial, item)))
+            }
+            Some((first, rest)) => match items.split_first() {
+                Some((head, tail)) => item(self, first, head, bindings) && self.list(rest, tail, bindings, item),
+                None => false,
+            },
+        }
+    }
+
+    /// Try a match on a copy of the bindings, keeping them only if it succeeds
+    fn attempt(&self, bindings: &mut Bindings, f: impl FnOnce(&Self, &mut Bindings) -> bool) -> bool {
+        let mut trial = bindings.clone();
+        let matched = f(self, &mut trial);
+        if matched {
+            *bindings = trial;
+        }
+        matched
+    }
+}
+
+/// A place in a file where a pattern matched
+#[derive(Debug, Clone)]
+pub struct Match {
+    pub span: Span,
+    pub bindings: Bindings,
+}
+
+/// Every expression of `program` that `pattern` matches, outermost first
+pub fn find(pattern: &Pattern, program: &Program, source: &str) -> Vec<Match> {
+    let mut finder = Finder { pattern, matcher: Matcher::new(source), matches: Vec::new() };
+    finder.visit_program(program);
+    finder.matches
+}
+
+struct Finder<'p, 's> {
+    pattern: &'p Pattern,
+    matcher: Matcher<'s>,
+    matches: Vec<Match>,
+}
+
+impl<'a> Visit<'a> for Finder<'_, '_> {
+    fn visit_expression(&mut self, expr: &Expression<'a>) {
+        let mut bindings = Bindings::new();
+        if !matches!(expr, Expression::ParenthesizedExpression(_)) && self.matcher.expression(self.pattern, expr, &mut bindings) {
+            self.matches.push(Match { span: expr.span(), bindings });
+        }
+        walk::walk_expression(self, expr);
+    }
+}
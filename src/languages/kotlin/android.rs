@@ -0,0 +1,254 @@
+//! Android rules, turned on by the `android` config profile: debug logging, hard-coded UI text,
+//! contexts and views in static holders, WebView JavaScript, world-readable files and `allowBackup`
+
+use crate::cfg::tree::named_children;
+use crate::types::{Category, CodeIssue, Severity};
+use std::path::Path;
+use tree_sitter::Node;
+
+/// File name of the manifest checked for `allowBackup`
+pub const MANIFEST: &str = "AndroidManifest.xml";
+
+/// `Log` levels that are only meant for development
+const DEBUG_LOG_LEVELS: &[&str] = &["d", "v"];
+
+/// File modes that let every other app read or write the file
+const WORLD_MODES: &[&str] = &["MODE_WORLD_READABLE", "MODE_WORLD_WRITEABLE"];
+
+fn text<'a>(node: Node, code: &'a str) -> &'a str {
+    node.utf8_text(code.as_bytes()).unwrap_or("")
+}
+
+#[allow(clippy::too_many_arguments)]
+fn issue(file_path: &Path, line: usize, column: usize, end: Option<(usize, usize)>, snippet: &str, message: String, rule: &str, severity: Severity, category: Category) -> CodeIssue {
+    CodeIssue {
+        file_path: file_path.display().to_string(),
+        line,
+        column,
+        end_line: end.map(|(line, _)| line),
+        end_column: end.map(|(_, column)| column),
+        message,
+        severity,
+        category,
+        rule: rule.to_string(),
+        code_snippet: Some(snippet.to_string()),
+    }
+}
+
+fn node_issue(file_path: &Path, node: Node, code: &str, message: String, rule: &str, severity: Severity, category: Category) -> CodeIssue {
+    let start = node.start_position();
+    let end = node.end_position();
+    issue(
+        file_path,
+        start.row + 1,
+        start.column + 1,
+        Some((end.row + 1, end.column + 1)),
+        text(node, code),
+        message,
+        rule,
+        severity,
+        category,
+    )
+}
+
+/// The arguments of a call, in order
+fn arguments(call: Node) -> Vec<Node> {
+    named_children(call)
+        .into_iter()
+        .filter(|child| child.kind() == "call_suffix")
+        .flat_map(named_children)
+        .filter(|child| child.kind() == "value_arguments")
+        .flat_map(named_children)
+        .filter_map(|argument| argument.named_child(argument.named_child_count().saturating_sub(1)))
+        .collect()
+}
+
+/// A string literal with user-visible words and no templates
+fn is_literal_text(node: Node, code: &str) -> bool {
+    node.kind() == "string_literal"
+        && !text(node, code).contains('$')
+        && text(node, code).chars().any(char::is_alphabetic)
+}
+
+/// Whether `node` only runs in debug builds: inside `if (BuildConfig.DEBUG)`
+fn debug_only(node: Node, code: &str) -> bool {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if parent.kind() == "if_expression" && parent.named_child(0).is_some_and(|condition| text(condition, code).contains("BuildConfig.DEBUG")) {
+            return true;
+        }
+        if parent.kind() == "function_declaration" {
+            return false;
+        }
+        current = parent.parent();
+    }
+    false
+}
+
+/// Whether a property outlives every instance: top-level, in an `object` or a `companion object`
+fn is_static(property: Node) -> bool {
+    match property.parent() {
+        Some(parent) if parent.kind() == "source_file" => true,
+        Some(parent) if parent.kind() == "class_body" => parent.parent().is_some_and(|holder| matches!(holder.kind(), "companion_object" | "object_declaration")),
+        _ => false,
+    }
+}
+
+/// Whether a declared type keeps an activity's view hierarchy alive
+fn is_leaky_type(declared: &str) -> bool {
+    let name = declared.trim_end_matches('?').split('<').next().unwrap_or(declared);
+    let name = name.rsplit('.').next().unwrap_or(name);
+    name == "Context" || name.ends_with("Activity") || name.ends_with("View") || name.ends_with("Fragment")
+}
+
+/// Run the Android rules over a parsed Kotlin file
+pub fn analyze_tree(root: Node, code: &str, file_path: &Path) -> Vec<CodeIssue> {
+    let mut issues = Vec::new();
+
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        match node.kind() {
+            "call_expression" => {
+                let callee = node.named_child(0).map(|callee| text(callee, code)).unwrap_or("");
+                let (receiver, method) = callee.rsplit_once('.').unwrap_or(("", callee));
+                let arguments = arguments(node);
+                if receiver.rsplit('.').next() == Some("Log") && DEBUG_LOG_LEVELS.contains(&method) && !debug_only(node, code) {
+                    issues.push(node_issue(
+                        file_path,
+                        node,
+                        code,
+                        format!("Log.{} is left in release builds. Remove it or guard it with BuildConfig.DEBUG.", method),
+                        "android-debug-log",
+                        Severity::Warning,
+                        Category::BestPractice,
+                    ));
+                }
+
+                let shown = match (receiver, method) {
+                    (_, "setText") => arguments.first(),
+                    ("Toast", "makeText") => arguments.get(1),
+                    _ => None,
+                };
+                if let Some(literal) = shown.filter(|literal| is_literal_text(**literal, code)) {
+                    issues.push(node_issue(
+                        file_path,
+                        *literal,
+                        code,
+                        "User-facing text is hard-coded. Move it to strings.xml and use R.string so it can be translated.".to_string(),
+                        "android-hardcoded-text",
+                        Severity::Warning,
+                        Category::BestPractice,
+                    ));
+                }
+
+                if method == "setJavaScriptEnabled" && arguments.first().is_some_and(|enabled| text(*enabled, code) == "true") {
+                    issues.push(javascript_issue(file_path, node, code));
+                }
+            }
+            "assignment" => {
+                let children = named_children(node);
+                if let [target, value] = children.as_slice() {
+                    if text(*target, code).ends_with("javaScriptEnabled") && text(*value, code) == "true" {
+                        issues.push(javascript_issue(file_path, node, code));
+                    }
+                }
+            }
+            "property_declaration" if is_static(node) => {
+                let declared = named_children(node)
+                    .into_iter()
+                    .find(|child| child.kind() == "variable_declaration")
+                    .and_then(|variable| variable.named_child(1))
+                    .map(|declared| text(declared, code));
+                if let Some(declared) = declared.filter(|declared| is_leaky_type(declared)) {
+                    issues.push(node_issue(
+                        file_path,
+                        node,
+                        code,
+                        format!("A static {} outlives the activity and leaks it. Keep it in an instance or hold a WeakReference.", declared),
+                        "android-static-context",
+                        Severity::Warning,
+                        Category::Performance,
+                    ));
+                }
+            }
+            "simple_identifier" if WORLD_MODES.contains(&text(node, code)) && node.parent().is_none_or(|parent| parent.kind() != "identifier") => {
+                issues.push(node_issue(
+                    file_path,
+                    node,
+                    code,
+                    format!("{} lets every app on the device access the file and throws on Android 7+. Use MODE_PRIVATE and a FileProvider.", text(node, code)),
+                    "android-world-readable",
+                    Severity::Error,
+                    Category::Security,
+                ));
+            }
+            _ => {}
+        }
+        stack.extend(named_children(node));
+    }
+
+    issues.sort_by_key(|issue| (issue.line, issue.column));
+    issues
+}
+
+fn javascript_issue(file_path: &Path, node: Node, code: &str) -> CodeIssue {
+    node_issue(
+        file_path,
+        node,
+        code,
+        "JavaScript is enabled in a WebView, which exposes the app to XSS from loaded pages. Only enable it for trusted content.".to_string(),
+        "android-webview-javascript",
+        Severity::Warning,
+        Category::Security,
+    )
+}
+
+const ANDROID_NS: &str = "http://schemas.android.com/apk/res/android";
+
+/// Check an `AndroidManifest.xml` for app data that backups can copy off the device
+pub fn analyze_manifest(code: &str, file_path: &Path) -> Vec<CodeIssue> {
+    // A manifest that isn't well-formed XML fails the Android build long before backups matter
+    let Ok(document) = roxmltree::Document::parse(code) else {
+        return Vec::new();
+    };
+    let snippet = |row: u32| code.lines().nth(row as usize - 1).unwrap_or("").trim();
+
+    let mut issues = Vec::new();
+    for application in document.descendants().filter(|node| node.has_tag_name("application")) {
+        let allow_backup = application
+            .attributes()
+            .find(|attribute| attribute.namespace() == Some(ANDROID_NS) && attribute.name() == "allowBackup");
+        match allow_backup {
+            Some(attribute) if attribute.value() == "true" => {
+                let position = document.text_pos_at(attribute.range().start);
+                issues.push(issue(
+                    file_path,
+                    position.row as usize,
+                    position.col as usize,
+                    None,
+                    snippet(position.row),
+                    "android:allowBackup=\"true\" lets adb backup copy the app's private data. Set it to false or add backup rules.".to_string(),
+                    "android-allow-backup",
+                    Severity::Warning,
+                    Category::Security,
+                ));
+            }
+            Some(_) => {}
+            None => {
+                let position = document.text_pos_at(application.range().start);
+                issues.push(issue(
+                    file_path,
+                    position.row as usize,
+                    position.col as usize,
+                    None,
+                    snippet(position.row),
+                    "android:allowBackup defaults to true, which lets adb backup copy the app's private data. Set it explicitly.".to_string(),
+                    "android-allow-backup",
+                    Severity::Warning,
+                    Category::Security,
+                ));
+            }
+        }
+    }
+    issues
+}
@@ -0,0 +1,277 @@
+//! Kotlin coroutine rules. The candidates are captured by the query in `KotlinParser::analyze_file`;
+//! this module decides from the surrounding tree whether each one is a problem
+
+use crate::cfg::tree::named_children;
+use crate::types::{Category, CodeIssue, Severity};
+use std::path::Path;
+use tree_sitter::Node;
+
+/// Captures handled by [`check`]
+pub const CAPTURES: &[&str] = &["global_scope", "run_blocking", "thread_sleep", "blocking_io", "catch_block", "async_call"];
+
+/// Builders whose lambda runs as a coroutine
+const BUILDERS: &[&str] = &[
+    "launch",
+    "async",
+    "withContext",
+    "coroutineScope",
+    "supervisorScope",
+    "runBlocking",
+    "withTimeout",
+    "withTimeoutOrNull",
+    "flow",
+];
+
+fn text<'a>(node: Node, code: &'a str) -> &'a str {
+    node.utf8_text(code.as_bytes()).unwrap_or("")
+}
+
+fn issue(file_path: &Path, node: Node, code: &str, message: String, rule: &str, category: Category) -> CodeIssue {
+    let start = node.start_position();
+    let end = node.end_position();
+    CodeIssue {
+        file_path: file_path.display().to_string(),
+        line: start.row + 1,
+        column: start.column + 1,
+        end_line: Some(end.row + 1),
+        end_column: Some(end.column + 1),
+        message,
+        severity: Severity::Warning,
+        category,
+        rule: rule.to_string(),
+        code_snippet: Some(text(node, code).to_string()),
+    }
+}
+
+/// The builder a lambda is passed to, with the text of its parenthesised arguments
+///
+/// `launch { }` has the name as callee; `withContext(Dispatchers.IO) { }` has a call as callee.
+fn builder<'a>(lambda: Node, code: &'a str) -> Option<(&'a str, &'a str)> {
+    let call = lambda.parent().filter(|parent| parent.kind() == "annotated_lambda")?.parent()?.parent()?;
+    let callee = call.named_child(0)?;
+    let (name, arguments) = if callee.kind() == "call_expression" {
+        let arguments = callee.named_child(1).map(|suffix| text(suffix, code)).unwrap_or("");
+        (callee.named_child(0)?, arguments)
+    } else {
+        (callee, "")
+    };
+    let name = text(name, code);
+    Some((name.rsplit('.').next().unwrap_or(name), arguments))
+}
+
+fn modifiers<'a>(function: Node, code: &'a str) -> &'a str {
+    named_children(function)
+        .into_iter()
+        .find(|child| child.kind() == "modifiers")
+        .map(|modifiers| text(modifiers, code))
+        .unwrap_or("")
+}
+
+fn function_name<'a>(function: Node, code: &'a str) -> &'a str {
+    named_children(function)
+        .into_iter()
+        .find(|child| child.kind() == "simple_identifier")
+        .map(|name| text(name, code))
+        .unwrap_or("")
+}
+
+/// Whether `node` runs in a coroutine: inside a `suspend fun` or a builder lambda
+fn in_coroutine(node: Node, code: &str) -> bool {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        match parent.kind() {
+            "lambda_literal" if builder(parent, code).is_some_and(|(name, _)| BUILDERS.contains(&name)) => return true,
+            "function_declaration" => return modifiers(parent, code).split_whitespace().any(|modifier| modifier == "suspend"),
+            "class_body" => return false,
+            _ => {}
+        }
+        current = parent.parent();
+    }
+    false
+}
+
+/// Whether `node` runs in a builder lambda that moved it to `Dispatchers.IO`
+fn on_io_dispatcher(node: Node, code: &str) -> bool {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if parent.kind() == "lambda_literal" && builder(parent, code).is_some_and(|(_, arguments)| arguments.contains("Dispatchers.IO")) {
+            return true;
+        }
+        if parent.kind() == "function_declaration" {
+            return false;
+        }
+        current = parent.parent();
+    }
+    false
+}
+
+/// Whether blocking the thread is expected: in `fun main`, a test function or class, or a test source set
+fn in_entry_point(node: Node, code: &str, file_path: &Path) -> bool {
+    let test_source = file_path
+        .components()
+        .any(|component| matches!(component.as_os_str().to_str(), Some("test" | "androidTest")))
+        || file_path.file_stem().and_then(|stem| stem.to_str()).is_some_and(|stem| stem.ends_with("Test"));
+    if test_source {
+        return true;
+    }
+
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        match parent.kind() {
+            "function_declaration" if function_name(parent, code) == "main" || modifiers(parent, code).contains("@Test") => return true,
+            "class_declaration" => {
+                let name = named_children(parent)
+                    .into_iter()
+                    .find(|child| child.kind() == "type_identifier")
+                    .map(|name| text(name, code))
+                    .unwrap_or("");
+                if name.ends_with("Test") || name.ends_with("Tests") {
+                    return true;
+                }
+            }
+            _ => {}
+        }
+        current = parent.parent();
+    }
+    false
+}
+
+/// Whether a catch-all `catch` in a coroutine drops `CancellationException`
+fn swallows_cancellation(catch: Node, code: &str) -> bool {
+    let children = named_children(catch);
+    let name = children.iter().find(|child| child.kind() == "simple_identifier").map(|name| text(*name, code));
+    let caught = children.iter().find(|child| child.kind() == "user_type").map(|caught| text(*caught, code));
+    let catch_all = caught.is_some_and(|caught| matches!(caught.rsplit('.').next(), Some("Exception" | "Throwable")));
+    if !catch_all || !in_coroutine(catch, code) {
+        return false;
+    }
+
+    let mut previous = catch.prev_named_sibling();
+    while let Some(sibling) = previous.filter(|sibling| sibling.kind() == "catch_block") {
+        if named_children(sibling).iter().any(|child| child.kind() == "user_type" && text(*child, code).ends_with("CancellationException")) {
+            return false;
+        }
+        previous = sibling.prev_named_sibling();
+    }
+
+    let mut stack: Vec<Node> = children.into_iter().filter(|child| child.kind() == "statements").collect();
+    while let Some(node) = stack.pop() {
+        match node.kind() {
+            "jump_expression" if text(node, code).starts_with("throw") && node.named_child(0).is_some_and(|thrown| Some(text(thrown, code)) == name) => {
+                return false
+            }
+            "simple_identifier" if text(node, code) == "ensureActive" => return false,
+            _ => {}
+        }
+        stack.extend(named_children(node));
+    }
+    true
+}
+
+/// Whether the `Deferred` of an `async` call is dropped or kept in a local that is never awaited
+fn never_awaited(call: Node, code: &str) -> bool {
+    let mut call = call;
+    while let Some(parent) = call.parent().filter(|parent| parent.kind() == "call_expression" && parent.named_child(0) == Some(call)) {
+        call = parent;
+    }
+    let Some(parent) = call.parent() else {
+        return false;
+    };
+
+    match parent.kind() {
+        "statements" => {
+            let returned = parent.parent().is_some_and(|block| block.kind() == "lambda_literal") && parent.named_child(parent.named_child_count() - 1) == Some(call);
+            !returned
+        }
+        "property_declaration" => {
+            let Some(name) = named_children(parent)
+                .into_iter()
+                .find(|child| child.kind() == "variable_declaration")
+                .and_then(|variable| variable.named_child(0))
+            else {
+                return false;
+            };
+            let mut scope = parent;
+            while let Some(outer) = scope.parent() {
+                scope = outer;
+                if matches!(scope.kind(), "function_body" | "lambda_literal" | "class_body") {
+                    break;
+                }
+            }
+
+            let mut stack = vec![scope];
+            while let Some(node) = stack.pop() {
+                if node.kind() == "simple_identifier" && node != name && text(node, code) == text(name, code) {
+                    let member = node
+                        .parent()
+                        .filter(|parent| parent.kind() == "navigation_expression" && parent.named_child(0) == Some(node))
+                        .and_then(|navigation| navigation.named_child(1))
+                        .and_then(|suffix| suffix.named_child(0))
+                        .map(|member| text(member, code));
+                    // `.await()` consumes it; passing it on means it may be awaited elsewhere
+                    if member.is_none_or(|member| member == "await") {
+                        return false;
+                    }
+                }
+                stack.extend(named_children(node));
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Check a node captured by one of [`CAPTURES`]
+pub fn check(capture: &str, node: Node, code: &str, file_path: &Path) -> Option<CodeIssue> {
+    match capture {
+        "global_scope" => Some(issue(
+            file_path,
+            node,
+            code,
+            "GlobalScope coroutines are not tied to any lifecycle and leak when their caller goes away. Launch in a scoped CoroutineScope instead.".to_string(),
+            "kotlin-global-scope",
+            Category::BestPractice,
+        )),
+        "run_blocking" if !in_entry_point(node, code, file_path) => Some(issue(
+            file_path,
+            node,
+            code,
+            "runBlocking blocks the calling thread. Only use it in main or tests; make the caller suspend instead.".to_string(),
+            "kotlin-run-blocking",
+            Category::BestPractice,
+        )),
+        "thread_sleep" if in_coroutine(node, code) => Some(issue(
+            file_path,
+            node,
+            code,
+            "Thread.sleep blocks the coroutine's thread. Use delay instead.".to_string(),
+            "kotlin-thread-sleep",
+            Category::Performance,
+        )),
+        "blocking_io" if in_coroutine(node, code) && !on_io_dispatcher(node, code) => Some(issue(
+            file_path,
+            node,
+            code,
+            "Blocking IO in a coroutine holds up its dispatcher. Wrap it in withContext(Dispatchers.IO).".to_string(),
+            "kotlin-blocking-io",
+            Category::Performance,
+        )),
+        "catch_block" if swallows_cancellation(node, code) => Some(issue(
+            file_path,
+            node,
+            code,
+            "This catch-all also catches CancellationException, so the coroutine can't be cancelled. Rethrow it or catch CancellationException first.".to_string(),
+            "kotlin-swallowed-cancellation",
+            Category::CodeQuality,
+        )),
+        "async_call" if never_awaited(node, code) => Some(issue(
+            file_path,
+            node,
+            code,
+            "The Deferred from async is never awaited, so its result and exceptions are lost. Await it or use launch.".to_string(),
+            "kotlin-unawaited-async",
+            Category::CodeQuality,
+        )),
+        _ => None,
+    }
+}
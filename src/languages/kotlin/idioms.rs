@@ -0,0 +1,279 @@
+//! Kotlin idiom rules: `if`/`else` null checks that are an elvis, redundant `Unit` return types,
+//! `when` without `else` on subjects that aren't known to be exhaustive and string concatenation in loops
+
+use crate::cfg::tree::named_children;
+use crate::types::{Category, CodeIssue, Severity};
+use std::collections::HashSet;
+use std::path::Path;
+use tree_sitter::Node;
+
+/// Calls whose lambda runs once per element
+const LOOP_FUNCTIONS: &[&str] = &["forEach", "forEachIndexed", "repeat"];
+
+fn text<'a>(node: Node, code: &'a str) -> &'a str {
+    node.utf8_text(code.as_bytes()).unwrap_or("")
+}
+
+fn issue(file_path: &Path, node: Node, code: &str, message: String, rule: &str, category: Category) -> CodeIssue {
+    let start = node.start_position();
+    let end = node.end_position();
+    CodeIssue {
+        file_path: file_path.display().to_string(),
+        line: start.row + 1,
+        column: start.column + 1,
+        end_line: Some(end.row + 1),
+        end_column: Some(end.column + 1),
+        message,
+        severity: if category == Category::Performance { Severity::Warning } else { Severity::Suggestion },
+        category,
+        rule: rule.to_string(),
+        code_snippet: Some(text(node, code).to_string()),
+    }
+}
+
+/// The single expression of a branch, with or without braces
+fn only_expression(body: Node) -> Option<Node> {
+    let mut node = body;
+    while matches!(node.kind(), "control_structure_body" | "statements") {
+        let children = named_children(node);
+        match children.as_slice() {
+            [only] => node = *only,
+            _ => return None,
+        }
+    }
+    Some(node)
+}
+
+/// Types known to be exhaustive when matched in full: sealed class hierarchies and enum entries declared in this file
+#[derive(Default)]
+struct ClosedTypes {
+    sealed_subtypes: HashSet<String>,
+    enum_entries: HashSet<String>,
+}
+
+impl ClosedTypes {
+    fn collect(root: Node, code: &str) -> Self {
+        let mut classes = Vec::new();
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            if matches!(node.kind(), "class_declaration" | "object_declaration") {
+                classes.push(node);
+            }
+            stack.extend(named_children(node));
+        }
+
+        let name = |class: Node| {
+            named_children(class)
+                .into_iter()
+                .find(|child| child.kind() == "type_identifier")
+                .map(|name| text(name, code).to_string())
+                .unwrap_or_default()
+        };
+        let sealed: HashSet<String> = classes
+            .iter()
+            .filter(|class| text(**class, code).trim_start().starts_with("sealed"))
+            .map(|class| name(*class))
+            .collect();
+
+        let mut closed = Self::default();
+        for class in &classes {
+            let extends_sealed = named_children(*class)
+                .into_iter()
+                .filter(|child| child.kind() == "delegation_specifier")
+                .any(|supertype| sealed.contains(text(supertype, code).split(['(', '<']).next().unwrap_or("").trim()));
+            if extends_sealed {
+                closed.sealed_subtypes.insert(name(*class));
+            }
+            for body in named_children(*class).into_iter().filter(|child| child.kind() == "enum_class_body") {
+                for entry in named_children(body).into_iter().filter(|child| child.kind() == "enum_entry") {
+                    if let Some(entry_name) = entry.named_child(0) {
+                        closed.enum_entries.insert(text(entry_name, code).to_string());
+                    }
+                }
+            }
+        }
+        closed
+    }
+
+    /// Whether every condition of a `when` names a sealed subtype, an enum entry or a boolean
+    fn covers(&self, conditions: &[Node], code: &str) -> bool {
+        !conditions.is_empty()
+            && conditions.iter().all(|condition| {
+                let condition = text(*condition, code).trim();
+                let tested = condition.trim_start_matches("!is ").trim_start_matches("is ").trim();
+                let last = condition.rsplit('.').next().unwrap_or(condition);
+                matches!(condition, "true" | "false")
+                    || self.sealed_subtypes.contains(tested.split('<').next().unwrap_or(tested))
+                    || self.enum_entries.contains(last)
+            })
+    }
+}
+
+/// Whether `name` is declared as a `String` var, in the function around `node` or as a property
+fn is_string_var(node: Node, name: &str, code: &str) -> bool {
+    let mut scope = node;
+    while let Some(parent) = scope.parent() {
+        scope = parent;
+        if scope.kind() == "function_declaration" {
+            break;
+        }
+    }
+
+    let mut stack = vec![scope];
+    while let Some(candidate) = stack.pop() {
+        if candidate.kind() == "property_declaration" {
+            let children = named_children(candidate);
+            let variable = children.iter().find(|child| child.kind() == "variable_declaration");
+            let named = variable.and_then(|variable| variable.named_child(0)).is_some_and(|declared| text(declared, code) == name);
+            let mutable = children.iter().any(|child| text(*child, code) == "var");
+            let string = variable.and_then(|variable| variable.named_child(1)).is_some_and(|declared| text(declared, code) == "String")
+                || children.last().is_some_and(|value| value.kind() == "string_literal");
+            if named && mutable && string {
+                return true;
+            }
+        }
+        stack.extend(named_children(candidate));
+    }
+    false
+}
+
+/// Whether `node` runs repeatedly: it is in the body of a loop or of a `forEach`-style lambda in the same function
+fn in_loop(node: Node, code: &str) -> bool {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        match parent.kind() {
+            "for_statement" | "while_statement" | "do_while_statement" => return true,
+            "lambda_literal" => {
+                let call = parent.parent().and_then(|lambda| lambda.parent()).and_then(|suffix| suffix.parent());
+                let callee = call.and_then(|call| call.named_child(0)).map(|callee| text(callee, code)).unwrap_or("");
+                return LOOP_FUNCTIONS.contains(&callee.rsplit('.').next().unwrap_or(callee));
+            }
+            "function_declaration" | "class_body" => return false,
+            _ => {}
+        }
+        current = parent.parent();
+    }
+    false
+}
+
+/// Run the idiom rules over a parsed Kotlin file
+pub fn analyze_tree(root: Node, code: &str, file_path: &Path) -> Vec<CodeIssue> {
+    let closed = ClosedTypes::collect(root, code);
+    let mut issues = Vec::new();
+
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        match node.kind() {
+            "if_expression" => {
+                let children = named_children(node);
+                let branches: Vec<Node> = children.iter().copied().filter(|child| child.kind() == "control_structure_body").collect();
+                if let ([condition, ..], [then, otherwise]) = (children.as_slice(), branches.as_slice()) {
+                    let (then, otherwise) = (only_expression(*then), only_expression(*otherwise));
+                    if let (Some(then), Some(otherwise)) = (then, otherwise) {
+                        let (then, otherwise) = (text(then, code), text(otherwise, code));
+                        let elvis = match null_tested(*condition, code) {
+                            Some((name, true)) if then == name => Some((name, otherwise)),
+                            Some((name, false)) if otherwise == name => Some((name, then)),
+                            _ => None,
+                        };
+                        if let Some((name, fallback)) = elvis {
+                            issues.push(issue(
+                                file_path,
+                                node,
+                                code,
+                                format!("This null check is an elvis: write `{} ?: {}`.", name, fallback),
+                                "kotlin-prefer-elvis",
+                                Category::BestPractice,
+                            ));
+                        }
+                    }
+                }
+            }
+            "function_declaration" => {
+                let children = named_children(node);
+                let block_body = children
+                    .iter()
+                    .find(|child| child.kind() == "function_body")
+                    .is_some_and(|body| text(*body, code).starts_with('{'));
+                let return_type = children
+                    .iter()
+                    .skip_while(|child| child.kind() != "function_value_parameters")
+                    .find(|child| child.kind() == "user_type");
+                if let Some(return_type) = return_type.filter(|return_type| block_body && text(**return_type, code) == "Unit") {
+                    issues.push(issue(
+                        file_path,
+                        *return_type,
+                        code,
+                        "The Unit return type is implied for functions with a block body. Remove it.".to_string(),
+                        "kotlin-redundant-unit",
+                        Category::BestPractice,
+                    ));
+                }
+            }
+            "when_expression" => {
+                let children = named_children(node);
+                let has_subject = children.iter().any(|child| child.kind() == "when_subject");
+                let entries: Vec<Node> = children.into_iter().filter(|child| child.kind() == "when_entry").collect();
+                let has_else = entries.iter().any(|entry| text(*entry, code).trim_start().starts_with("else"));
+                let conditions: Vec<Node> = entries
+                    .iter()
+                    .flat_map(|entry| named_children(*entry))
+                    .filter(|child| child.kind() == "when_condition")
+                    .collect();
+                if has_subject && !has_else && !closed.covers(&conditions, code) {
+                    issues.push(issue(
+                        file_path,
+                        node,
+                        code,
+                        "`when` has no else branch and its subject is not a sealed class or enum from this file. Add an else branch so new values are handled.".to_string(),
+                        "kotlin-when-else",
+                        Category::BestPractice,
+                    ));
+                }
+            }
+            "assignment" => {
+                let children = named_children(node);
+                if let [target, value] = children.as_slice() {
+                    let name = text(*target, code);
+                    let operator = target.next_sibling().map(|operator| text(operator, code)).unwrap_or("");
+                    let appends = operator == "+="
+                        || (operator == "=" && value.kind() == "additive_expression" && value.named_child(0).is_some_and(|left| text(left, code) == name));
+                    if appends && target.named_child_count() == 1 && is_string_var(node, name, code) && in_loop(node, code) {
+                        issues.push(issue(
+                            file_path,
+                            node,
+                            code,
+                            format!("'{}' is rebuilt with + on every iteration. Use buildString or a StringBuilder.", name),
+                            "kotlin-string-concat-in-loop",
+                            Category::Performance,
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+        stack.extend(named_children(node));
+    }
+
+    issues.sort_by_key(|issue| (issue.line, issue.column));
+    issues
+}
+
+/// The expression an `if` condition compares with null, and whether it checks for `!= null`
+fn null_tested<'a>(condition: Node, code: &'a str) -> Option<(&'a str, bool)> {
+    if condition.kind() != "equality_expression" {
+        return None;
+    }
+    let mut cursor = condition.walk();
+    let parts: Vec<Node> = condition.children(&mut cursor).collect();
+    match parts.as_slice() {
+        [subject, operator, null] | [null, operator, subject] if text(*null, code) == "null" && text(*subject, code) != "null" => {
+            match text(*operator, code) {
+                "!=" => Some((text(*subject, code), true)),
+                "==" => Some((text(*subject, code), false)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
@@ -1,3 +1,8 @@
+pub mod android;
+pub mod coroutines;
+pub mod idioms;
+pub mod null_safety;
 pub mod parser;
+pub mod unused;
 
 pub use parser::KotlinParser;
@@ -0,0 +1,383 @@
+//! Kotlin null-safety rules: `!!`, `lateinit` that should be nullable or constructor-injected,
+//! platform types leaking from Java calls and deeply nested `?.let` chains
+
+use crate::cfg::tree::named_children;
+use crate::types::{Category, CodeIssue, Severity};
+use std::collections::HashSet;
+use std::path::Path;
+use tree_sitter::Node;
+
+/// `java.lang` classes, which are visible without an import
+const JAVA_LANG: &[&str] = &["System", "Thread", "Runtime", "Class", "Integer", "Long", "Double", "Character", "ClassLoader"];
+
+/// Framework classes the system instantiates, so they can't take constructor arguments
+const FRAMEWORK_SUPERTYPES: &[&str] = &["Activity", "Fragment", "Service", "Application", "BroadcastReceiver", "ContentProvider", "View"];
+
+fn text<'a>(node: Node, code: &'a str) -> &'a str {
+    node.utf8_text(code.as_bytes()).unwrap_or("")
+}
+
+fn issue(file_path: &Path, node: Node, code: &str, message: String, rule: &str, severity: Severity) -> CodeIssue {
+    let start = node.start_position();
+    let end = node.end_position();
+    CodeIssue {
+        file_path: file_path.display().to_string(),
+        line: start.row + 1,
+        column: start.column + 1,
+        end_line: Some(end.row + 1),
+        end_column: Some(end.column + 1),
+        message,
+        severity,
+        category: Category::CodeQuality,
+        rule: rule.to_string(),
+        code_snippet: Some(text(node, code).to_string()),
+    }
+}
+
+fn modifiers(node: Node, code: &str) -> Vec<String> {
+    named_children(node)
+        .into_iter()
+        .filter(|child| child.kind() == "modifiers")
+        .flat_map(named_children)
+        .map(|modifier| text(modifier, code).to_string())
+        .collect()
+}
+
+/// The name of a property or function declaration
+fn declared_name<'a>(node: Node, code: &'a str) -> Option<&'a str> {
+    let children = named_children(node);
+    let name = match node.kind() {
+        "property_declaration" => children
+            .iter()
+            .find(|child| child.kind() == "variable_declaration")
+            .and_then(|variable| variable.named_child(0)),
+        _ => children.into_iter().find(|child| child.kind() == "simple_identifier"),
+    };
+    name.map(|name| text(name, code))
+}
+
+/// Whether `node` is a `?.let { }` call
+fn is_safe_let(node: Node, code: &str) -> bool {
+    node.kind() == "call_expression"
+        && node
+            .named_child(0)
+            .filter(|callee| callee.kind() == "navigation_expression")
+            .and_then(|callee| named_children(callee).last().copied())
+            .is_some_and(|suffix| text(suffix, code).replace(char::is_whitespace, "") == "?.let")
+}
+
+/// The variable a null check tests, and whether it tests for `!= null`
+fn null_check<'a>(condition: Node, code: &'a str) -> Vec<(&'a str, bool)> {
+    match condition.kind() {
+        "conjunction_expression" => named_children(condition)
+            .into_iter()
+            .flat_map(|operand| null_check(operand, code))
+            .filter(|(_, not_null)| *not_null)
+            .collect(),
+        "parenthesized_expression" => condition.named_child(0).map(|inner| null_check(inner, code)).unwrap_or_default(),
+        "equality_expression" => {
+            let mut cursor = condition.walk();
+            let parts: Vec<&str> = condition.children(&mut cursor).map(|part| text(part, code)).collect();
+            match parts.as_slice() {
+                [name, "!=", "null"] | ["null", "!=", name] => vec![(*name, true)],
+                [name, "==", "null"] | ["null", "==", name] => vec![(*name, false)],
+                _ => Vec::new(),
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Whether `name` is a parameter or local `val` of the function around `node`, which the compiler can smart-cast
+fn is_stable(node: Node, name: &str, code: &str) -> bool {
+    let mut current = node.parent();
+    while let Some(scope) = current {
+        match scope.kind() {
+            "function_declaration" | "lambda_literal" => {
+                if declares_stable(scope, node, name, code) {
+                    return true;
+                }
+                if scope.kind() == "function_declaration" {
+                    return false;
+                }
+            }
+            "class_body" | "source_file" => return false,
+            _ => {}
+        }
+        current = scope.parent();
+    }
+    false
+}
+
+/// Whether `scope` has a parameter named `name`, or a `val` declared before `usage`, outside nested scopes
+fn declares_stable(scope: Node, usage: Node, name: &str, code: &str) -> bool {
+    let mut stack = named_children(scope);
+    while let Some(node) = stack.pop() {
+        let declares = match node.kind() {
+            "parameter" => declared_name(node, code) == Some(name),
+            "lambda_parameters" => named_children(node)
+                .iter()
+                .any(|parameter| parameter.named_child(0).is_some_and(|declared| text(declared, code) == name)),
+            "property_declaration" => {
+                node.start_byte() < usage.start_byte()
+                    && declared_name(node, code) == Some(name)
+                    && named_children(node).iter().any(|child| text(*child, code) == "val")
+            }
+            "function_declaration" | "lambda_literal" | "class_declaration" | "object_declaration" => continue,
+            _ => false,
+        };
+        if declares {
+            return true;
+        }
+        stack.extend(named_children(node));
+    }
+    false
+}
+
+/// Whether an `if` around `node` already proves `name` non-null where `node` is
+fn checked_for_null(node: Node, name: &str, code: &str) -> bool {
+    let mut child = node;
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if parent.kind() == "if_expression" {
+            let branches: Vec<Node> = named_children(parent)
+                .into_iter()
+                .filter(|branch| branch.kind() == "control_structure_body")
+                .collect();
+            let condition = parent.named_child(0);
+            for (checked, not_null) in condition.map(|condition| null_check(condition, code)).unwrap_or_default() {
+                let branch = if not_null { branches.first() } else { branches.get(1) };
+                if checked == name && branch == Some(&child) {
+                    return true;
+                }
+            }
+        }
+        if matches!(parent.kind(), "function_declaration" | "lambda_literal") {
+            return false;
+        }
+        child = parent;
+        current = parent.parent();
+    }
+    false
+}
+
+/// Classes imported from Java packages, plus `java.lang`
+fn java_types(root: Node, code: &str) -> HashSet<String> {
+    let mut types: HashSet<String> = JAVA_LANG.iter().map(|name| name.to_string()).collect();
+    for import in named_children(root).into_iter().filter(|child| child.kind() == "import_list").flat_map(named_children) {
+        let children = named_children(import);
+        let Some(path) = children.iter().find(|child| child.kind() == "identifier").map(|path| text(*path, code)) else {
+            continue;
+        };
+        if !["java.", "javax.", "android."].iter().any(|prefix| path.starts_with(prefix)) {
+            continue;
+        }
+        let alias = children
+            .iter()
+            .find(|child| child.kind() == "import_alias")
+            .and_then(|alias| alias.named_child(0))
+            .map(|alias| text(alias, code));
+        types.insert(alias.unwrap_or_else(|| path.rsplit('.').next().unwrap_or("")).to_string());
+    }
+    types
+}
+
+/// A member call or property read directly on a Java class or object, whose result has a platform type
+fn is_java_call(expression: Node, java_types: &HashSet<String>, code: &str) -> bool {
+    if !matches!(expression.kind(), "call_expression" | "navigation_expression") {
+        return false;
+    }
+    let mut root = expression;
+    let mut members = 0;
+    while matches!(root.kind(), "call_expression" | "navigation_expression") {
+        if root.kind() == "navigation_expression" {
+            members += 1;
+        }
+        match root.named_child(0) {
+            Some(receiver) => root = receiver,
+            None => return false,
+        }
+    }
+    // Longer chains may end in Kotlin calls such as `.toInt()` whose type is known
+    members == 1 && root.kind() == "simple_identifier" && java_types.contains(text(root, code))
+}
+
+/// The class around a member declaration
+fn enclosing_class(node: Node) -> Option<Node> {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if parent.kind() == "class_declaration" {
+            return Some(parent);
+        }
+        current = parent.parent();
+    }
+    None
+}
+
+fn is_framework_class(class: Node, code: &str) -> bool {
+    let name = declared_type_name(class, code);
+    let supertypes = named_children(class)
+        .into_iter()
+        .filter(|child| child.kind() == "delegation_specifier")
+        .map(|specifier| text(specifier, code).to_string())
+        .collect::<Vec<_>>();
+    name.ends_with("Test")
+        || supertypes.iter().any(|supertype| {
+            let supertype = supertype.split(['(', '<']).next().unwrap_or("");
+            FRAMEWORK_SUPERTYPES.iter().any(|framework| supertype.ends_with(framework))
+        })
+}
+
+fn declared_type_name<'a>(class: Node, code: &'a str) -> &'a str {
+    named_children(class)
+        .into_iter()
+        .find(|child| child.kind() == "type_identifier")
+        .map(|name| text(name, code))
+        .unwrap_or("")
+}
+
+/// Properties checked with `::name.isInitialized`
+fn initialization_checks(root: Node, code: &str) -> HashSet<String> {
+    let mut names = HashSet::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "navigation_expression" {
+            let children = named_children(node);
+            if let [reference, suffix] = children.as_slice() {
+                if reference.kind() == "callable_reference" && text(*suffix, code).ends_with("isInitialized") {
+                    names.insert(text(*reference, code).trim_start_matches("this").trim_start_matches("::").to_string());
+                }
+            }
+        }
+        stack.extend(named_children(node));
+    }
+    names
+}
+
+/// Run the null-safety rules; `max_let_depth` is how many `?.let` may nest before it is reported
+pub fn analyze_tree(root: Node, code: &str, file_path: &Path, max_let_depth: usize) -> Vec<CodeIssue> {
+    let java_types = java_types(root, code);
+    let initialization_checks = initialization_checks(root, code);
+    let mut issues = Vec::new();
+
+    // (node, depth of the `?.let` lambdas around it)
+    let mut stack = vec![(root, 0)];
+    while let Some((node, let_depth)) = stack.pop() {
+        let mut child_depth = let_depth;
+        match node.kind() {
+            "postfix_expression" if node.child(node.child_count().saturating_sub(1)).is_some_and(|last| last.kind() == "!!") => {
+                let operand = node.named_child(0).filter(|operand| operand.kind() == "simple_identifier");
+                let name = operand.map(|operand| text(operand, code)).unwrap_or("");
+                if operand.is_some() && is_stable(node, name, code) && checked_for_null(node, name, code) {
+                    issues.push(issue(
+                        file_path,
+                        node,
+                        code,
+                        format!("'{}' was already checked for null, so it is smart-cast here. Drop the !!.", name),
+                        "kotlin-smart-cast",
+                        Severity::Warning,
+                    ));
+                } else {
+                    issues.push(issue(
+                        file_path,
+                        node,
+                        code,
+                        "The !! operator throws a NullPointerException on null. Handle null with ?., ?: or a null check.".to_string(),
+                        "kotlin-not-null-assertion",
+                        Severity::Warning,
+                    ));
+                }
+            }
+            "call_expression" if is_safe_let(node, code) => {
+                child_depth += 1;
+                if child_depth == max_let_depth + 1 {
+                    issues.push(issue(
+                        file_path,
+                        node,
+                        code,
+                        format!(
+                            "?.let chains are nested {} levels deep (max {}). Use early returns with ?: or a single null check.",
+                            child_depth, max_let_depth
+                        ),
+                        "kotlin-nested-let",
+                        Severity::Warning,
+                    ));
+                }
+            }
+            "property_declaration" if modifiers(node, code).iter().any(|modifier| modifier == "lateinit") => {
+                let name = declared_name(node, code).unwrap_or("");
+                let injected = modifiers(node, code).iter().any(|modifier| modifier == "@Inject");
+                if initialization_checks.contains(name) {
+                    issues.push(issue(
+                        file_path,
+                        node,
+                        code,
+                        format!("'{}' is checked with isInitialized, so it may legitimately be absent. Make it nullable instead of lateinit.", name),
+                        "kotlin-lateinit-optional",
+                        Severity::Warning,
+                    ));
+                } else if injected && enclosing_class(node).is_some_and(|class| !is_framework_class(class, code)) {
+                    issues.push(issue(
+                        file_path,
+                        node,
+                        code,
+                        format!("'{}' is field-injected into a lateinit var. Inject it through the constructor as a val.", name),
+                        "kotlin-lateinit-injection",
+                        Severity::Warning,
+                    ));
+                }
+            }
+            _ => {}
+        }
+        if leaks_platform_type(node, &java_types, code) {
+            issues.push(issue(
+                file_path,
+                node,
+                code,
+                format!(
+                    "'{}' exposes the platform type of a Java call. Declare its type explicitly, nullable or not.",
+                    declared_name(node, code).unwrap_or("")
+                ),
+                "kotlin-platform-type",
+                Severity::Warning,
+            ));
+        }
+
+        stack.extend(named_children(node).into_iter().map(|child| (child, child_depth)));
+    }
+
+    issues.sort_by_key(|issue| (issue.line, issue.column));
+    issues
+}
+
+/// Public properties and expression-bodied functions without a declared type whose value comes from Java
+fn leaks_platform_type(node: Node, java_types: &HashSet<String>, code: &str) -> bool {
+    let member = node.parent().is_some_and(|parent| matches!(parent.kind(), "class_body" | "source_file"));
+    if !member || modifiers(node, code).iter().any(|modifier| modifier == "private" || modifier == "override") {
+        return false;
+    }
+    let children = named_children(node);
+    let value = match node.kind() {
+        "property_declaration" => {
+            let typed = children
+                .iter()
+                .find(|child| child.kind() == "variable_declaration")
+                .is_some_and(|variable| variable.named_child_count() > 1);
+            if typed {
+                return false;
+            }
+            children.last().copied()
+        }
+        "function_declaration" => {
+            let parameters = children.iter().position(|child| child.kind() == "function_value_parameters");
+            let typed = parameters.is_some_and(|parameters| children[parameters + 1..].iter().any(|child| child.kind().ends_with("type")));
+            let body = children.iter().find(|child| child.kind() == "function_body");
+            match body {
+                Some(body) if !typed && text(*body, code).starts_with('=') => body.named_child(0),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+    value.is_some_and(|value| is_java_call(value, java_types, code))
+}
@@ -1,16 +1,263 @@
+use super::{android, coroutines, idioms, null_safety, unused};
 use crate::types::{AnalysisResult, FileAnalysis, SeveritySummary, CodeIssue, Severity, Category};
+use crate::cfg::{self, tree::{named_children, unparenthesized, Flow, FlowGrammar}};
+use crate::clones::CloneDetector;
+use crate::cognitive::{self, CognitiveGrammar};
+use crate::config::Config;
 use crate::error::{AnalyzerError, Result};
+use crate::languages::grammar::Grammar;
+use crate::languages::query_rules::QueryRules;
+use crate::metrics::halstead;
+use crate::metrics::tree::{self, MetricsGrammar};
+use crate::metrics::FileMetrics;
+use crate::plugins::{self, Plugin};
+use crate::secrets::{self, SecretScanner};
 use std::path::{Path, PathBuf};
 use std::fs;
-use tree_sitter::{Parser, Query, QueryCursor};
+use tree_sitter::{Node, QueryCursor};
+
+/// Kotlin node kinds scored by the cognitive complexity rule
+const COGNITIVE_GRAMMAR: CognitiveGrammar = CognitiveGrammar {
+    functions: &["function_declaration", "anonymous_function", "lambda_literal"],
+    nesting: &[
+        "for_statement",
+        "while_statement",
+        "do_while_statement",
+        "when_expression",
+        "catch_block",
+    ],
+    ifs: &["if_expression"],
+    else_clauses: &[],
+    boolean_operators: &["conjunction_expression", "disjunction_expression"],
+    jumps: &["jump_expression"],
+    function_name,
+    callee,
+};
+
+fn function_name(node: Node, code: &str) -> Option<String> {
+    let mut cursor = node.walk();
+    let name = node
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == "simple_identifier")?;
+    name.utf8_text(code.as_bytes()).ok().map(String::from)
+}
+
+fn callee(node: Node, code: &str) -> Option<String> {
+    if node.kind() != "call_expression" {
+        return None;
+    }
+    let function = node.named_child(0)?;
+    function.utf8_text(code.as_bytes()).ok().map(String::from)
+}
+
+/// Kotlin node kinds used by the `metrics` subcommand
+const METRICS_GRAMMAR: MetricsGrammar = MetricsGrammar {
+    cognitive: &COGNITIVE_GRAMMAR,
+    lexicon: &halstead::KOTLIN,
+    decisions: &[
+        "if_expression",
+        "for_statement",
+        "while_statement",
+        "do_while_statement",
+        "when_entry",
+        "catch_block",
+        "conjunction_expression",
+        "disjunction_expression",
+        "elvis_expression",
+    ],
+    statement_lists: &["statements"],
+    parameter_lists: &["function_value_parameters", "lambda_parameters"],
+    parameters: &["parameter", "variable_declaration"],
+    header: tree::same_node,
+};
+
+/// Kotlin statements mapped to control flow. The compiler already rejects missing returns.
+const FLOW_GRAMMAR: FlowGrammar = FlowGrammar {
+    cognitive: &COGNITIVE_GRAMMAR,
+    body: flow_body,
+    header: tree::same_node,
+    classify,
+    constant: |node, code| {
+        let node = unparenthesized(node);
+        (node.kind() == "boolean_literal").then(|| node.utf8_text(code.as_bytes()) == Ok("true"))
+    },
+    generator: |_, _| false,
+    check_returns: false,
+};
+
+/// Block bodies only; `fun f() = expr` has no statements
+fn flow_body(node: Node) -> Option<Node> {
+    if node.kind() == "lambda_literal" {
+        return Some(node);
+    }
+    named_children(node)
+        .into_iter()
+        .find(|child| child.kind() == "function_body" && child.child(0).is_some_and(|brace| brace.kind() == "{"))
+}
+
+fn classify<'tree>(node: Node<'tree>, code: &str) -> Flow<'tree> {
+    let children_of = |kinds: &[&str]| -> Vec<Node<'tree>> {
+        named_children(node)
+            .into_iter()
+            .filter(|child| kinds.contains(&child.kind()))
+            .collect()
+    };
+    let text = node.utf8_text(code.as_bytes()).unwrap_or("");
+
+    match node.kind() {
+        "line_comment" | "multiline_comment" | "label" | "annotation" | "lambda_parameters" => Flow::Ignore,
+        "statements" | "control_structure_body" | "function_body" | "finally_block" => {
+            Flow::Sequence(named_children(node))
+        }
+        "catch_block" => Flow::Sequence(children_of(&["statements"])),
+        "if_expression" => {
+            let mut bodies = children_of(&["control_structure_body"]);
+            let mut cursor = node.walk();
+            let has_else = node.children(&mut cursor).any(|child| child.kind() == "else");
+            let consequence = if has_else && bodies.len() < 2 { None } else { bodies.first().copied() };
+            if consequence.is_some() {
+                bodies.remove(0);
+            }
+            Flow::If {
+                condition: node.named_child(0),
+                consequence,
+                alternatives: bodies,
+            }
+        }
+        "while_statement" | "do_while_statement" | "for_statement" => {
+            let body = children_of(&["control_structure_body"]).pop();
+            let condition = match node.kind() {
+                "while_statement" => node.named_child(0),
+                "do_while_statement" => node.named_child(node.named_child_count().saturating_sub(1)),
+                _ => None,
+            };
+            Flow::Loop {
+                condition: condition.filter(|condition| condition.kind() != "control_structure_body"),
+                body,
+                label: loop_label(node, code),
+                test_first: node.kind() != "do_while_statement",
+                endless: false,
+            }
+        }
+        "when_expression" => {
+            let entries = children_of(&["when_entry"]);
+            Flow::Branches {
+                exhaustive: entries
+                    .iter()
+                    .any(|entry| named_children(*entry).iter().all(|child| child.kind() != "when_condition")),
+                arms: entries
+                    .iter()
+                    .map(|entry| {
+                        named_children(*entry)
+                            .into_iter()
+                            .filter(|child| child.kind() == "control_structure_body")
+                            .collect()
+                    })
+                    .collect(),
+                breakable: false,
+            }
+        }
+        "try_expression" => Flow::Try {
+            body: children_of(&["statements"]).pop(),
+            handlers: children_of(&["catch_block"]),
+            otherwise: None,
+            finalizer: children_of(&["finally_block"]).pop(),
+        },
+        "jump_expression" => {
+            let label = children_of(&["label"])
+                .first()
+                .and_then(|label| label.utf8_text(code.as_bytes()).ok())
+                .map(String::from);
+            if text.starts_with("throw") {
+                Flow::Throw
+            } else if text.starts_with("return") {
+                Flow::Return {
+                    value: named_children(node).iter().any(|child| child.kind() != "label"),
+                }
+            } else if text.starts_with("break") {
+                Flow::Break(label)
+            } else {
+                Flow::Continue(label)
+            }
+        }
+        _ => Flow::Statement,
+    }
+}
+
+/// `outer@ while (...)`: the label is a sibling right before the loop
+fn loop_label(node: Node, code: &str) -> Option<String> {
+    let label = node.prev_named_sibling().filter(|sibling| sibling.kind() == "label")?;
+    let text = label.utf8_text(code.as_bytes()).ok()?;
+    Some(text.trim().trim_end_matches('@').to_string())
+}
+
+/// Rule patterns matched against every Kotlin file, compiled once per parser
+const QUERY: &str = "
+    (call_expression (simple_identifier) @function_name (#match? @function_name \"^print(ln)?$\")) @print_call
+
+    (integer_literal) @magic_number
+
+    (class_declaration (type_identifier) @class_name)
+
+    (property_declaration (variable_declaration (simple_identifier) @variable_name))
+
+    (if_expression) @if_stmt
+
+    (string_literal) @string_literal
+
+    (call_expression (navigation_expression (simple_identifier) @_scope (navigation_suffix (simple_identifier) @_builder))
+        (#eq? @_scope \"GlobalScope\") (#match? @_builder \"^(launch|async)$\")) @global_scope
+
+    (call_expression (simple_identifier) @_builder (#eq? @_builder \"runBlocking\")) @run_blocking
+
+    (call_expression (navigation_expression (simple_identifier) @_thread (navigation_suffix (simple_identifier) @_sleep))
+        (#eq? @_thread \"Thread\") (#eq? @_sleep \"sleep\")) @thread_sleep
+
+    (call_expression (navigation_expression (navigation_suffix (simple_identifier) @_io))
+        (#match? @_io \"^(readText|readBytes|readLines|writeText|writeBytes|appendText|forEachLine|useLines|readAllBytes|readAllLines|execute|executeQuery|executeUpdate)$\")) @blocking_io
+
+    (call_expression (simple_identifier) @_io
+        (#match? @_io \"^(FileInputStream|FileOutputStream|FileReader|FileWriter|RandomAccessFile)$\")) @blocking_io
+
+    (catch_block) @catch_block
+
+    (call_expression (simple_identifier) @_builder (#eq? @_builder \"async\")) @async_call
+
+    (call_expression (navigation_expression (navigation_suffix (simple_identifier) @_builder)) (#eq? @_builder \"async\")) @async_call
+";
 
 pub struct KotlinParser {
+    grammar: Grammar,
+    rules: QueryRules,
+    plugins: Vec<Plugin>,
+    secrets: SecretScanner,
+    clones: CloneDetector,
+    cognitive_threshold: usize,
+    max_let_depth: usize,
+    android: bool,
 }
 
 impl KotlinParser {
-    pub fn new() -> Self {
-        Self {
-        }
+    /// Library entry point; the binary always builds parsers from the resolved config
+    #[allow(dead_code)]
+    pub fn new() -> Result<Self> {
+        Self::with_config(&Config::default())
+    }
+
+    /// Build the front end, compiling its grammar query and the project rules once. Fails if a query doesn't compile.
+    pub fn with_config(config: &Config) -> Result<Self> {
+        let grammar = Grammar::new("Kotlin", tree_sitter_kotlin::language(), QUERY)?;
+        let rules = QueryRules::compile(&grammar, &config.rules)?;
+        Ok(Self {
+            grammar,
+            rules,
+            plugins: config.loaded_plugins.clone(),
+            secrets: SecretScanner::with_config(&config.secrets),
+            clones: CloneDetector::with_config(&config.clones),
+            cognitive_threshold: config.complexity.cognitive_threshold,
+            max_let_depth: config.kotlin.max_let_depth,
+            android: config.has_profile("android"),
+        })
     }
 
     pub fn analyze_file(&self, file_path: &Path) -> Result<FileAnalysis> {
@@ -18,15 +265,11 @@ impl KotlinParser {
             path: file_path.display().to_string(),
         })?;
 
-        let mut parser = Parser::new();
-        parser.set_language(&tree_sitter_kotlin::language()).expect("Error loading Kotlin grammar");
+        if self.android && file_path.file_name().is_some_and(|name| name == android::MANIFEST) {
+            return Ok(Self::file_analysis(file_path, android::analyze_manifest(&code, file_path)));
+        }
 
-        let tree = parser.parse(&code, None).ok_or_else(|| AnalyzerError::ParseError {
-            file: file_path.display().to_string(),
-            line: 0,
-            column: 0,
-            message: "Failed to parse Kotlin file".to_string(),
-        })?;
+        let tree = self.grammar.parse(&code, file_path)?;
 
         let mut issues = Vec::new();
 
@@ -48,24 +291,9 @@ impl KotlinParser {
         }
 
         // 2. Custom Rule: Avoid println
-        let query_source = "
-            (call_expression (simple_identifier) @function_name (#match? @function_name \"^print(ln)?$\")) @print_call
-            
-            (integer_literal) @magic_number
-            
-            (class_declaration (type_identifier) @class_name)
-            
-            (property_declaration (variable_declaration (simple_identifier) @variable_name))
-            
-            (if_expression) @if_stmt
-
-            (property_declaration (variable_declaration (simple_identifier) @unused_variable)) 
-
-        ";
-        
-        let query = Query::new(&tree_sitter_kotlin::language(), query_source).unwrap();
+        let query = self.grammar.query();
         let mut query_cursor = QueryCursor::new();
-        let matches = query_cursor.matches(&query, root_node, code.as_bytes());
+        let matches = query_cursor.matches(query, root_node, code.as_bytes());
 
         for m in matches {
             for capture in m.captures {
@@ -144,41 +372,18 @@ impl KotlinParser {
                             None
                         }
                     },
-                    "unused_variable" => {
+                    "string_literal" => {
                         let text = node.utf8_text(code.as_bytes()).unwrap();
-                        
-                        // Very basic check: search if the variable name appears elsewhere in the file
-                        // This is NOT accurate scope analysis but a heuristic for this demo
-                        let count = code.matches(text).count();
-                        if count <= 1 { // Only declaration
-                             Some(CodeIssue {
-                                file_path: file_path.display().to_string(),
-                                line: start_position.row + 1,
-                                column: start_position.column + 1,
-                                end_line: Some(end_position.row + 1),
-                                end_column: Some(end_position.column + 1),
-                                message: format!("Variable '{}' appears to be unused.", text),
-                                severity: Severity::Warning,
-                                category: Category::Maintainability,
-                                rule: "unused-variable".to_string(),
-                                code_snippet: Some(text.to_string()),
-                            })
-                        } else {
-                            None
-                        }
+                        self.secrets.scan(file_path, secrets::unquote(text)).map(|finding| {
+                            self.secrets.issue(
+                                file_path,
+                                &finding,
+                                start_position.row + 1,
+                                start_position.column + 1,
+                                Some((end_position.row + 1, end_position.column + 1)),
+                            )
+                        })
                     },
-                     "null_value" =>  Some(CodeIssue {
-                        file_path: file_path.display().to_string(),
-                        line: start_position.row + 1,
-                        column: start_position.column + 1,
-                        end_line: Some(end_position.row + 1),
-                        end_column: Some(end_position.column + 1),
-                        message: "Avoid using 'null' directly. Use Kotlin's null safety features like '?' or '?:'.".to_string(),
-                        severity: Severity::Suggestion,
-                        category: Category::CodeQuality,
-                        rule: "avoid-null".to_string(),
-                        code_snippet: Some("null".to_string()),
-                    }),
                      "nested_if" => { /* Removed specific query logic, handled by generic if_stmt */ None }, // Keep for compatibility if I revert
                      "if_stmt" => {
                         // Check nesting level manually
@@ -208,6 +413,7 @@ impl KotlinParser {
                             None
                         }
                      },
+                    name if coroutines::CAPTURES.contains(&name) => coroutines::check(name, node, &code, file_path),
                     _ => None,
                 };
 
@@ -217,22 +423,66 @@ impl KotlinParser {
             }
         }
 
+        issues.extend(cognitive::analyze_tree(
+            &COGNITIVE_GRAMMAR,
+            root_node,
+            &code,
+            file_path,
+            self.cognitive_threshold,
+        ));
+        issues.extend(cfg::tree::analyze_tree(&FLOW_GRAMMAR, root_node, &code, file_path));
+        issues.extend(unused::analyze_tree(root_node, &code, file_path));
+        issues.extend(null_safety::analyze_tree(root_node, &code, file_path, self.max_let_depth));
+        issues.extend(idioms::analyze_tree(root_node, &code, file_path));
+        if self.android {
+            issues.extend(android::analyze_tree(root_node, &code, file_path));
+        }
+        issues.extend(self.rules.check(root_node, &code, file_path));
+        issues.extend(plugins::run_all(&self.plugins, file_path, "kotlin", &code, || plugins::tree_sitter_ast(root_node, &code)));
+
+        Ok(Self::file_analysis(file_path, issues))
+    }
+
+    fn file_analysis(file_path: &Path, issues: Vec<CodeIssue>) -> FileAnalysis {
         let mut summary = SeveritySummary::new();
         for issue in &issues {
             summary.add(issue.severity);
         }
 
-        Ok(FileAnalysis {
+        FileAnalysis {
             file_path: file_path.display().to_string(),
             issues,
             summary,
-        })
+        }
+    }
+
+    /// Code metrics for a file and each function in it
+    pub fn metrics_file(&self, file_path: &Path) -> Result<FileMetrics> {
+        let code = fs::read_to_string(file_path).map_err(|_| AnalyzerError::FileReadError {
+            path: file_path.display().to_string(),
+        })?;
+        let tree = self.grammar.parse(&code, file_path)?;
+        Ok(tree::file_metrics(&METRICS_GRAMMAR, tree.root_node(), &code, file_path))
+    }
+
+    pub fn metrics_directory(&self, dir_path: &Path) -> Result<Vec<FileMetrics>> {
+        let files = self.find_kt_files(dir_path)?;
+        Ok(files.iter().filter_map(|file_path| self.metrics_file(file_path).ok()).collect())
     }
 
     pub fn analyze_directory(&self, dir_path: &Path) -> Result<AnalysisResult> {
         let mut result = AnalysisResult::new();
 
-        let kt_files = self.find_kt_files(dir_path)?;
+        let mut kt_files = self.find_kt_files(dir_path)?;
+        if self.android {
+            kt_files.extend(
+                walkdir::WalkDir::new(dir_path)
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                    .filter(|entry| entry.file_type().is_file() && entry.file_name() == android::MANIFEST)
+                    .map(|entry| entry.into_path()),
+            );
+        }
 
         for file_path in kt_files {
             match self.analyze_file(&file_path) {
@@ -245,9 +495,20 @@ impl KotlinParser {
             }
         }
 
+        self.detect_clones(&mut result);
         Ok(result)
     }
 
+    /// Report duplicated code within and across the Kotlin files of `result`; manifests are XML and left out
+    pub fn detect_clones(&self, result: &mut AnalysisResult) {
+        let (sources, manifests): (Vec<_>, Vec<_>) = std::mem::take(&mut result.files)
+            .into_iter()
+            .partition(|file| Path::new(&file.file_path).file_name().is_none_or(|name| name != android::MANIFEST));
+        result.files = sources;
+        self.clones.detect(result, &halstead::KOTLIN);
+        result.files.extend(manifests);
+    }
+
     fn find_kt_files(&self, dir_path: &Path) -> Result<Vec<PathBuf>> {
         let mut kt_files = Vec::new();
 
@@ -268,9 +529,3 @@ impl KotlinParser {
         Ok(kt_files)
     }
 }
-
-impl Default for KotlinParser {
-    fn default() -> Self {
-        Self::new()
-    }
-}
@@ -0,0 +1,421 @@
+//! Unused declarations in Kotlin, resolved through a scope model of the file rather than by text search:
+//! local `val`/`var`, private functions and properties, parameters of private functions and imports
+
+use crate::types::{Category, CodeIssue, Severity};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use tree_sitter::Node;
+
+/// Names the compiler calls by convention, so their imports are used without being named
+const IMPLICIT_IMPORTS: &[&str] = &[
+    "getValue", "setValue", "provideDelegate", "invoke", "get", "set", "contains", "iterator", "next", "hasNext",
+    "plus", "minus", "times", "div", "rem", "rangeTo", "compareTo", "plusAssign", "minusAssign", "unaryMinus", "not",
+    "inc", "dec", "component1", "component2", "component3", "component4", "component5",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Local,
+    PrivateProperty,
+    PrivateFunction,
+    Parameter,
+    /// Declarations that only shadow outer ones and are never reported
+    Other,
+}
+
+struct Declaration<'tree> {
+    name: String,
+    node: Node<'tree>,
+    kind: Kind,
+    used: bool,
+}
+
+struct Resolver<'a, 'tree> {
+    code: &'a str,
+    declarations: Vec<Declaration<'tree>>,
+    scopes: Vec<HashMap<String, usize>>,
+    /// Names read as `receiver.name`, which may be any class's member
+    member_reads: HashSet<String>,
+}
+
+fn text<'a>(node: Node, code: &'a str) -> &'a str {
+    node.utf8_text(code.as_bytes()).unwrap_or("")
+}
+
+fn named_children(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.named_children(&mut cursor).collect();
+    children
+}
+
+/// The identifier a declaration node introduces
+fn name_of(node: Node) -> Option<Node> {
+    named_children(node).into_iter().find(|child| child.kind() == "simple_identifier")
+}
+
+fn modifiers(node: Node, code: &str) -> Vec<String> {
+    named_children(node)
+        .into_iter()
+        .filter(|child| child.kind() == "modifiers")
+        .flat_map(named_children)
+        .map(|modifier| text(modifier, code).to_string())
+        .collect()
+}
+
+fn is_private(node: Node, code: &str) -> bool {
+    modifiers(node, code).iter().any(|modifier| modifier == "private")
+}
+
+/// Annotated declarations may be reached through reflection or generated code
+fn is_annotated(node: Node, code: &str) -> bool {
+    modifiers(node, code).iter().any(|modifier| modifier.starts_with('@'))
+}
+
+fn is_member(node: Node) -> bool {
+    node.parent().is_some_and(|parent| matches!(parent.kind(), "class_body" | "source_file"))
+}
+
+impl<'a, 'tree> Resolver<'a, 'tree> {
+    fn declare(&mut self, name: Node<'tree>, kind: Kind) {
+        let text = text(name, self.code).to_string();
+        let kind = if text == "_" { Kind::Other } else { kind };
+        self.declarations.push(Declaration {
+            name: text.clone(),
+            node: name,
+            kind,
+            used: false,
+        });
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(text, self.declarations.len() - 1);
+        }
+    }
+
+    /// Declare every name bound by a `variable_declaration` or destructuring `multi_variable_declaration`
+    fn declare_variables(&mut self, node: Node<'tree>, kind: Kind) {
+        match node.kind() {
+            "variable_declaration" => {
+                if let Some(name) = name_of(node) {
+                    self.declare(name, kind);
+                }
+            }
+            "multi_variable_declaration" => {
+                for variable in named_children(node) {
+                    self.declare_variables(variable, kind);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn read(&mut self, name: &str) {
+        let found = self.scopes.iter().rev().find_map(|scope| scope.get(name).copied());
+        if let Some(index) = found {
+            self.declarations[index].used = true;
+        }
+    }
+
+    fn scoped(&mut self, visit: impl FnOnce(&mut Self)) {
+        self.scopes.push(HashMap::new());
+        visit(self);
+        self.scopes.pop();
+    }
+
+    /// Classes and files see all of their members, wherever they are declared, and those of their companion
+    fn declare_members(&mut self, container: Node<'tree>) {
+        let mut members = Vec::new();
+        for child in named_children(container) {
+            match child.kind() {
+                "class_body" => {
+                    for member in named_children(child) {
+                        if member.kind() == "companion_object" {
+                            let bodies = named_children(member).into_iter().filter(|body| body.kind() == "class_body");
+                            members.extend(bodies.flat_map(named_children));
+                        }
+                        members.push(member);
+                    }
+                }
+                "primary_constructor" => members.extend(named_children(child)),
+                _ => members.push(child),
+            }
+        }
+        // Data classes use their constructor properties in the generated equals, hashCode and toString
+        let data_class = modifiers(container, self.code).iter().any(|modifier| modifier == "data");
+
+        for member in members {
+            let private = is_private(member, self.code) && !is_annotated(member, self.code);
+            match member.kind() {
+                "function_declaration" => {
+                    if let Some(name) = name_of(member) {
+                        self.declare(name, if private { Kind::PrivateFunction } else { Kind::Other });
+                    }
+                }
+                "property_declaration" => {
+                    let kind = if private { Kind::PrivateProperty } else { Kind::Other };
+                    for variable in named_children(member) {
+                        self.declare_variables(variable, kind);
+                    }
+                }
+                // Constructor parameters; only `private val` ones are properties
+                "class_parameter" => {
+                    let property = named_children(member).iter().any(|child| child.kind() == "binding_pattern_kind");
+                    if let Some(name) = name_of(member) {
+                        let kind = if private && property && !data_class { Kind::PrivateProperty } else { Kind::Other };
+                        self.declare(name, kind);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn visit(&mut self, node: Node<'tree>) {
+        match node.kind() {
+            "import_list" | "package_header" | "line_comment" | "multiline_comment" => {}
+            "source_file" | "class_declaration" | "object_declaration" => self.scoped(|resolver| {
+                resolver.declare_members(node);
+                resolver.visit_children(node);
+            }),
+            // Its members were declared with the enclosing class
+            "companion_object" => self.scoped(|resolver| resolver.visit_children(node)),
+            "function_declaration" => {
+                if !is_member(node) {
+                    if let Some(name) = name_of(node) {
+                        self.declare(name, Kind::Other);
+                    }
+                }
+                let private = is_private(node, self.code) && !is_annotated(node, self.code);
+                let has_body = named_children(node).iter().any(|child| child.kind() == "function_body");
+                let kind = if private && has_body { Kind::Parameter } else { Kind::Other };
+                self.scoped(|resolver| resolver.visit_function(node, kind));
+            }
+            "secondary_constructor" | "anonymous_function" | "setter" | "getter" => {
+                self.scoped(|resolver| resolver.visit_function(node, Kind::Other));
+            }
+            "lambda_literal" => self.scoped(|resolver| {
+                for child in named_children(node) {
+                    if child.kind() == "lambda_parameters" {
+                        for parameter in named_children(child) {
+                            resolver.declare_variables(parameter, Kind::Other);
+                        }
+                    } else {
+                        resolver.visit(child);
+                    }
+                }
+            }),
+            "for_statement" => self.scoped(|resolver| {
+                let children = named_children(node);
+                // The iterated expression is evaluated outside the loop variable's scope
+                for child in children.iter().filter(|child| !child.kind().ends_with("variable_declaration")) {
+                    if child.kind() != "control_structure_body" {
+                        resolver.visit(*child);
+                    }
+                }
+                for child in &children {
+                    resolver.declare_variables(*child, Kind::Other);
+                }
+                for child in children.iter().filter(|child| child.kind() == "control_structure_body") {
+                    resolver.visit(*child);
+                }
+            }),
+            "catch_block" => self.scoped(|resolver| {
+                if let Some(name) = name_of(node) {
+                    resolver.declare(name, Kind::Other);
+                }
+                resolver.visit_children(node);
+            }),
+            "control_structure_body" | "statements" | "when_entry" => self.scoped(|resolver| resolver.visit_children(node)),
+            "property_declaration" if is_member(node) => self.visit_children(node),
+            "property_declaration" => {
+                // The initializer can't see the variable it initializes
+                self.visit_children(node);
+                for child in named_children(node) {
+                    self.declare_variables(child, Kind::Local);
+                }
+            }
+            // `when (val x = ...)` and other bindings outside property declarations
+            "variable_declaration" => self.declare_variables(node, Kind::Other),
+            "simple_identifier" | "interpolated_identifier" => {
+                let name = text(node, self.code).to_string();
+                self.read(&name);
+            }
+            "navigation_suffix" => {
+                if let Some(name) = name_of(node) {
+                    self.member_reads.insert(text(name, self.code).to_string());
+                }
+            }
+            "callable_reference" => {
+                if let Some(name) = name_of(node) {
+                    let name = text(name, self.code).to_string();
+                    self.read(&name);
+                    self.member_reads.insert(name);
+                }
+            }
+            // `name = value` only writes the variable
+            "directly_assignable_expression" => {
+                let plain = node
+                    .next_sibling()
+                    .is_some_and(|operator| text(operator, self.code) == "=");
+                match named_children(node).as_slice() {
+                    [identifier] if plain && identifier.kind() == "simple_identifier" => {}
+                    _ => self.visit_children(node),
+                }
+            }
+            // The name of a named argument belongs to the callee
+            "value_argument" => {
+                let children = named_children(node);
+                let named = children.len() > 1
+                    && children[0].kind() == "simple_identifier"
+                    && children[0].next_sibling().is_some_and(|sign| text(sign, self.code) == "=");
+                for child in children.into_iter().skip(usize::from(named)) {
+                    self.visit(child);
+                }
+            }
+            _ => self.visit_children(node),
+        }
+    }
+
+    /// Visit children, skipping the names and variables that `node` itself declares
+    fn visit_children(&mut self, node: Node<'tree>) {
+        let name = match node.kind() {
+            "class_parameter" | "parameter" | "function_declaration" => name_of(node),
+            _ => None,
+        };
+        let declares_variables = node.kind() == "property_declaration";
+        for child in named_children(node) {
+            let is_variable = declares_variables && child.kind().ends_with("variable_declaration");
+            if Some(child) != name && !is_variable {
+                self.visit(child);
+            }
+        }
+    }
+
+    fn visit_function(&mut self, function: Node<'tree>, parameter_kind: Kind) {
+        for child in named_children(function) {
+            match child.kind() {
+                "function_value_parameters" => {
+                    // Default values follow their parameter and can only see the ones before it
+                    for parameter in named_children(child) {
+                        match name_of(parameter).filter(|_| parameter.kind() == "parameter") {
+                            Some(name) => {
+                                self.visit_children(parameter);
+                                self.declare(name, parameter_kind);
+                            }
+                            None => self.visit(parameter),
+                        }
+                    }
+                }
+                "simple_identifier" if function.kind() == "function_declaration" => {}
+                _ => self.visit(child),
+            }
+        }
+    }
+}
+
+/// Names of imports that nothing in the file refers to
+fn unused_imports<'tree>(root: Node<'tree>, code: &str) -> Vec<(Node<'tree>, String)> {
+    let mut referenced = HashSet::new();
+    let mut imports = Vec::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        match node.kind() {
+            "package_header" => continue,
+            // A comment after the last import is parsed as part of it
+            "import_header" => {
+                imports.push(node);
+                stack.extend(named_children(node).into_iter().filter(|child| child.kind().ends_with("comment")));
+                continue;
+            }
+            "simple_identifier" | "type_identifier" | "interpolated_identifier" => {
+                referenced.insert(text(node, code).to_string());
+            }
+            // KDoc links like [Foo] count, as they do in the IDE
+            "line_comment" | "multiline_comment" => {
+                for link in text(node, code).split('[').skip(1) {
+                    if let Some(end) = link.find(']') {
+                        referenced.insert(link[..end].rsplit('.').next().unwrap_or("").to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+        stack.extend(named_children(node));
+    }
+
+    let mut unused = Vec::new();
+    for import in imports {
+        let children = named_children(import);
+        if children.iter().any(|child| child.kind() == "wildcard_import") {
+            continue;
+        }
+        let alias = children
+            .iter()
+            .find(|child| child.kind() == "import_alias")
+            .and_then(|alias| alias.named_child(0));
+        let path = children.iter().find(|child| child.kind() == "identifier");
+        let name = match (alias, path) {
+            (Some(alias), _) => text(alias, code),
+            (None, Some(path)) => text(*path, code).rsplit('.').next().unwrap_or(""),
+            _ => continue,
+        };
+        if name.is_empty() || referenced.contains(name) || IMPLICIT_IMPORTS.contains(&name) {
+            continue;
+        }
+        unused.push((import, path.map(|path| text(*path, code)).unwrap_or(name).to_string()));
+    }
+    unused
+}
+
+fn issue(file_path: &Path, node: Node, code: &str, message: String, rule: &str) -> CodeIssue {
+    let start = node.start_position();
+    let end = node.end_position();
+    CodeIssue {
+        file_path: file_path.display().to_string(),
+        line: start.row + 1,
+        column: start.column + 1,
+        end_line: Some(end.row + 1),
+        end_column: Some(end.column + 1),
+        message,
+        severity: Severity::Warning,
+        category: Category::Maintainability,
+        rule: rule.to_string(),
+        code_snippet: Some(text(node, code).to_string()),
+    }
+}
+
+/// Report every unused declaration in a parsed Kotlin file
+pub fn analyze_tree(root: Node, code: &str, file_path: &Path) -> Vec<CodeIssue> {
+    let mut resolver = Resolver {
+        code,
+        declarations: Vec::new(),
+        scopes: Vec::new(),
+        member_reads: HashSet::new(),
+    };
+    resolver.visit(root);
+
+    let mut issues = Vec::new();
+    for declaration in &resolver.declarations {
+        if declaration.used {
+            continue;
+        }
+        let name = &declaration.name;
+        let (message, rule) = match declaration.kind {
+            Kind::Local => (format!("Variable '{}' is never used.", name), "unused-variable"),
+            Kind::Parameter => (format!("Parameter '{}' of a private function is never used.", name), "unused-parameter"),
+            Kind::PrivateProperty if !resolver.member_reads.contains(name) => {
+                (format!("Private property '{}' is never used.", name), "unused-private-property")
+            }
+            Kind::PrivateFunction if !resolver.member_reads.contains(name) => {
+                (format!("Private function '{}' is never used.", name), "unused-private-function")
+            }
+            _ => continue,
+        };
+        issues.push(issue(file_path, declaration.node, code, message, rule));
+    }
+
+    for (import, path) in unused_imports(root, code) {
+        issues.push(issue(file_path, import, code, format!("Import '{}' is never used.", path), "unused-import"));
+    }
+
+    issues.sort_by_key(|issue| (issue.line, issue.column));
+    issues
+}
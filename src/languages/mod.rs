@@ -2,3 +2,5 @@ pub mod javascript;
 pub mod kotlin;
 pub mod dart;
 pub mod python;
+pub mod grammar;
+pub mod query_rules;
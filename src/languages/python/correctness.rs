@@ -0,0 +1,680 @@
+//! Correctness rules for Python: mutable defaults, broad or silent `except`, comparisons to `None` and literals,
+//! shadowed builtins, unused imports and variables, late-binding closures, `return` in `finally` and missing `await`
+
+use crate::types::{Category, CodeIssue, Severity};
+use std::collections::HashSet;
+use std::path::Path;
+use tree_sitter::Node;
+
+/// Builtins whose shadowing causes confusing errors later in the same scope
+const BUILTINS: &[&str] = &[
+    "all", "any", "bool", "bytes", "callable", "dict", "dir", "filter", "float", "format", "frozenset", "hash",
+    "id", "input", "int", "iter", "len", "list", "map", "max", "min", "next", "object", "open", "print", "range",
+    "set", "sorted", "str", "sum", "tuple", "type", "vars", "zip",
+];
+
+/// Calls that build a fresh mutable object
+const MUTABLE_CONSTRUCTORS: &[&str] = &["list", "dict", "set", "bytearray", "defaultdict", "OrderedDict", "deque", "Counter"];
+
+/// Methods that keep their argument around for later
+const STORING_METHODS: &[&str] = &["append", "add", "insert", "extend", "setdefault", "appendleft"];
+
+/// Keyword arguments that register a callback
+const CALLBACK_KEYWORDS: &[&str] = &["command", "callback", "target"];
+
+const COMPREHENSIONS: &[&str] = &["list_comprehension", "set_comprehension", "dictionary_comprehension", "generator_expression"];
+
+/// `asyncio` coroutine functions that do nothing unless awaited
+const ASYNCIO_COROUTINES: &[&str] = &[
+    "asyncio.sleep",
+    "asyncio.wait_for",
+    "asyncio.wait",
+    "asyncio.open_connection",
+    "asyncio.create_subprocess_exec",
+    "asyncio.create_subprocess_shell",
+    "asyncio.to_thread",
+];
+
+/// Run every correctness rule over a parsed Python file
+pub fn analyze_tree(root: Node, code: &str, file_path: &Path) -> Vec<CodeIssue> {
+    let mut checker = Checker {
+        code,
+        file_path,
+        coroutines: coroutine_names(root, code),
+        issues: Vec::new(),
+    };
+
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        match node.kind() {
+            "default_parameter" | "typed_default_parameter" => checker.mutable_default(node),
+            "except_clause" => checker.except_clause(node),
+            "comparison_operator" => checker.comparison(node),
+            "finally_clause" => checker.finally_clause(node),
+            "lambda" => checker.loop_closure(node),
+            "function_definition" => {
+                checker.loop_closure(node);
+                checker.unused_variables(node);
+                if is_async(node) {
+                    checker.missing_await(node);
+                }
+            }
+            "import_statement" | "import_from_statement" => checker.unused_imports(node),
+            _ => {}
+        }
+        checker.shadowed_builtin(node);
+
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.named_children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+
+    checker.issues.sort_by_key(|issue| (issue.line, issue.column));
+    checker.issues
+}
+
+fn text<'a>(node: Node, code: &'a str) -> &'a str {
+    node.utf8_text(code.as_bytes()).unwrap_or("")
+}
+
+fn named_children(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.named_children(&mut cursor).collect();
+    children
+}
+
+fn is_async(function: Node) -> bool {
+    function.child(0).is_some_and(|first| first.kind() == "async")
+}
+
+fn is_scope(node: Node) -> bool {
+    matches!(node.kind(), "function_definition" | "lambda" | "class_definition" | "module")
+}
+
+/// Innermost function or module around `node`
+fn enclosing_function(node: Node) -> Option<Node> {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if matches!(parent.kind(), "function_definition" | "module") {
+            return Some(parent);
+        }
+        current = parent.parent();
+    }
+    None
+}
+
+/// Names of every `async def` in the file
+fn coroutine_names(root: Node, code: &str) -> HashSet<String> {
+    let mut names = HashSet::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "function_definition" && is_async(node) {
+            if let Some(name) = node.child_by_field_name("name") {
+                names.insert(text(name, code).to_string());
+            }
+        }
+        stack.extend(named_children(node));
+    }
+    names
+}
+
+/// Whether an identifier is read, as opposed to bound by an assignment, definition, parameter or import
+fn is_read(identifier: Node) -> bool {
+    let Some(parent) = identifier.parent() else {
+        return true;
+    };
+    let is_field = |name: &str| parent.child_by_field_name(name) == Some(identifier);
+    match parent.kind() {
+        "assignment" => !is_field("left"),
+        "function_definition" | "class_definition" => !is_field("name"),
+        "keyword_argument" => !is_field("name"),
+        "attribute" => !is_field("attribute"),
+        "default_parameter" | "typed_default_parameter" => !is_field("name"),
+        "parameters" | "lambda_parameters" | "typed_parameter" | "list_splat_pattern" | "dictionary_splat_pattern" => false,
+        "dotted_name" | "aliased_import" | "global_statement" | "nonlocal_statement" => false,
+        _ => true,
+    }
+}
+
+/// Names read anywhere inside `scope`, including nested functions that close over them
+fn reads(scope: Node, code: &str) -> HashSet<String> {
+    let mut names = HashSet::new();
+    let mut stack = vec![scope];
+    while let Some(node) = stack.pop() {
+        match node.kind() {
+            "identifier" if is_read(node) => {
+                names.insert(text(node, code).to_string());
+            }
+            // Quoted annotations like `items: "List[Item]"`
+            "string" if node.parent().is_some_and(|parent| parent.kind() == "type") => {
+                for word in text(node, code).split(|c: char| !c.is_alphanumeric() && c != '_') {
+                    names.insert(word.to_string());
+                }
+            }
+            _ => {}
+        }
+        stack.extend(named_children(node));
+    }
+    names
+}
+
+struct Checker<'a> {
+    code: &'a str,
+    file_path: &'a Path,
+    coroutines: HashSet<String>,
+    issues: Vec<CodeIssue>,
+}
+
+impl<'a> Checker<'a> {
+    fn report(&mut self, node: Node, message: String, rule: &str, severity: Severity, category: Category) {
+        let start = node.start_position();
+        let end = node.end_position();
+        self.issues.push(CodeIssue {
+            file_path: self.file_path.display().to_string(),
+            line: start.row + 1,
+            column: start.column + 1,
+            end_line: Some(end.row + 1),
+            end_column: Some(end.column + 1),
+            message,
+            severity,
+            category,
+            rule: rule.to_string(),
+            code_snippet: Some(text(node, self.code).to_string()),
+        });
+    }
+
+    fn mutable_default(&mut self, parameter: Node) {
+        let (Some(name), Some(value)) = (parameter.child_by_field_name("name"), parameter.child_by_field_name("value")) else {
+            return;
+        };
+        let mutable = match value.kind() {
+            "list" | "dictionary" | "set" | "list_comprehension" | "dictionary_comprehension" | "set_comprehension" => true,
+            "call" => value
+                .child_by_field_name("function")
+                .is_some_and(|function| MUTABLE_CONSTRUCTORS.contains(&text(function, self.code).rsplit('.').next().unwrap_or(""))),
+            _ => false,
+        };
+        if mutable {
+            let name = text(name, self.code).to_string();
+            self.report(
+                value,
+                format!("Mutable default argument '{}' is created once and shared by every call. Default to None and create it in the function.", name),
+                "python-mutable-default",
+                Severity::Warning,
+                Category::CodeQuality,
+            );
+        }
+    }
+
+    fn except_clause(&mut self, clause: Node) {
+        let children = named_children(clause);
+        let caught = children.iter().find(|child| !matches!(child.kind(), "block" | "comment"));
+        let Some(caught) = caught else {
+            self.report(
+                clause,
+                "Bare except: also catches KeyboardInterrupt and SystemExit. Catch Exception or a narrower type.".to_string(),
+                "python-bare-except",
+                Severity::Warning,
+                Category::CodeQuality,
+            );
+            return;
+        };
+
+        // `except Exception as error:`
+        let caught = match caught.kind() {
+            "as_pattern" => caught.named_child(0).unwrap_or(*caught),
+            _ => *caught,
+        };
+        let broad = matches!(text(caught, self.code), "Exception" | "BaseException");
+        let silent = children
+            .iter()
+            .find(|child| child.kind() == "block")
+            .is_some_and(|block| {
+                named_children(*block).iter().all(|statement| match statement.kind() {
+                    "pass_statement" | "comment" => true,
+                    "expression_statement" => statement.named_child(0).is_some_and(|expression| expression.kind() == "ellipsis"),
+                    _ => false,
+                })
+            });
+        if broad && silent {
+            self.report(
+                clause,
+                "Every error is caught and silently ignored here. Handle or log it, or catch the specific exception you expect.".to_string(),
+                "python-swallowed-exception",
+                Severity::Warning,
+                Category::CodeQuality,
+            );
+        }
+    }
+
+    fn comparison(&mut self, comparison: Node) {
+        let mut cursor = comparison.walk();
+        let mut operands: Vec<Node> = Vec::new();
+        let mut operators: Vec<(usize, String)> = Vec::new();
+        if cursor.goto_first_child() {
+            loop {
+                let child = cursor.node();
+                if cursor.field_name() == Some("operators") {
+                    operators.push((operands.len(), text(child, self.code).split_whitespace().collect::<Vec<_>>().join(" ")));
+                } else if child.is_named() && child.kind() != "comment" {
+                    operands.push(child);
+                }
+                if !cursor.goto_next_sibling() {
+                    break;
+                }
+            }
+        }
+
+        for (index, operator) in operators {
+            let (Some(left), Some(right)) = (operands.get(index.wrapping_sub(1)), operands.get(index)) else {
+                continue;
+            };
+            let sides = [*left, *right];
+            match operator.as_str() {
+                "==" | "!=" if sides.iter().any(|side| side.kind() == "none") => {
+                    let replacement = if operator == "==" { "is" } else { "is not" };
+                    self.report(
+                        comparison,
+                        format!("Compare to None with '{}' instead of '{}'; '==' can be overridden by __eq__.", replacement, operator),
+                        "python-none-comparison",
+                        Severity::Warning,
+                        Category::BestPractice,
+                    );
+                }
+                "is" | "is not" => {
+                    let literal = sides.iter().find(|side| {
+                        matches!(
+                            side.kind(),
+                            "integer" | "float" | "string" | "concatenated_string" | "list" | "dictionary" | "tuple" | "set"
+                        )
+                    });
+                    if let Some(literal) = literal {
+                        let literal = text(*literal, self.code).to_string();
+                        let replacement = if operator == "is" { "==" } else { "!=" };
+                        self.report(
+                            comparison,
+                            format!("'{}' compares identity, and whether {} is a shared object is an implementation detail. Use '{}'.", operator, literal, replacement),
+                            "python-is-literal",
+                            Severity::Error,
+                            Category::CodeQuality,
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn finally_clause(&mut self, clause: Node) {
+        // (node, inside a loop nested in the finally block, where break and continue stay local)
+        let mut stack: Vec<(Node, bool)> = named_children(clause).into_iter().map(|child| (child, false)).collect();
+        while let Some((node, in_loop)) = stack.pop() {
+            let jumps_out = match node.kind() {
+                "function_definition" | "lambda" | "class_definition" => continue,
+                "return_statement" => true,
+                "break_statement" | "continue_statement" => !in_loop,
+                _ => false,
+            };
+            if jumps_out {
+                let keyword = node.kind().trim_end_matches("_statement");
+                self.report(
+                    node,
+                    format!("'{}' in finally discards any exception raised in the try block and overrides its result.", keyword),
+                    "python-finally-return",
+                    Severity::Warning,
+                    Category::CodeQuality,
+                );
+            }
+            let in_loop = in_loop || matches!(node.kind(), "for_statement" | "while_statement");
+            stack.extend(named_children(node).into_iter().map(|child| (child, in_loop)));
+        }
+    }
+
+    fn shadowed_builtin(&mut self, node: Node) {
+        let Some(parent) = node.parent() else {
+            return;
+        };
+        if node.kind() != "identifier" {
+            return;
+        }
+        let is_field = |name: &str| parent.child_by_field_name(name) == Some(node);
+        let what = match parent.kind() {
+            // Class attributes and methods don't shadow anything
+            "assignment" if is_field("left") && !in_class_body(parent) => "Variable",
+            "pattern_list" | "tuple_pattern" if parent.parent().is_some_and(|assignment| assignment.kind() == "assignment") => "Variable",
+            "for_statement" | "for_in_clause" if is_field("left") => "Loop variable",
+            "function_definition" if is_field("name") && !in_class_body(parent) => "Function",
+            "class_definition" if is_field("name") => "Class",
+            "parameters" | "lambda_parameters" => "Parameter",
+            "default_parameter" | "typed_default_parameter" if is_field("name") => "Parameter",
+            "typed_parameter" if parent.named_child(0) == Some(node) => "Parameter",
+            _ => return,
+        };
+
+        let name = text(node, self.code);
+        if BUILTINS.contains(&name) {
+            let message = format!("{} '{}' shadows the builtin {}() for the rest of its scope. Pick another name.", what, name, name);
+            self.report(node, message, "python-shadowed-builtin", Severity::Warning, Category::CodeQuality);
+        }
+    }
+
+    /// Locals that are assigned but never read in the function or the closures inside it
+    fn unused_variables(&mut self, function: Node) {
+        let Some(body) = function.child_by_field_name("body") else {
+            return;
+        };
+        let read = reads(body, self.code);
+        if read.contains("locals") || read.contains("vars") {
+            return;
+        }
+
+        let mut declared_elsewhere = HashSet::new();
+        let mut assigned: Vec<Node> = Vec::new();
+        let mut stack = vec![body];
+        while let Some(node) = stack.pop() {
+            match node.kind() {
+                "function_definition" | "lambda" | "class_definition" => continue,
+                "global_statement" | "nonlocal_statement" => {
+                    declared_elsewhere.extend(named_children(node).iter().map(|name| text(*name, self.code).to_string()));
+                }
+                "assignment" => {
+                    let left = node.child_by_field_name("left").filter(|left| left.kind() == "identifier");
+                    if let (Some(left), Some(_)) = (left, node.child_by_field_name("right")) {
+                        assigned.push(left);
+                    }
+                }
+                _ => {}
+            }
+            stack.extend(named_children(node));
+        }
+
+        let mut reported = HashSet::new();
+        assigned.sort_by_key(|name| name.start_byte());
+        for name in assigned {
+            let text = text(name, self.code).to_string();
+            if text.starts_with('_') || read.contains(&text) || declared_elsewhere.contains(&text) || !reported.insert(text.clone()) {
+                continue;
+            }
+            self.report(
+                name,
+                format!("Local variable '{}' is assigned but never used.", text),
+                "python-unused-variable",
+                Severity::Warning,
+                Category::CodeQuality,
+            );
+        }
+    }
+
+    fn unused_imports(&mut self, statement: Node) {
+        if self.file_path.file_name().is_some_and(|name| name == "__init__.py") {
+            return;
+        }
+        let module = statement.child_by_field_name("module_name").map(|module| text(module, self.code));
+        if module == Some("__future__") {
+            return;
+        }
+        let Some(scope) = enclosing_function(statement) else {
+            return;
+        };
+
+        let exported = module_exports(scope, self.code);
+        let read = reads(scope, self.code);
+        let mut cursor = statement.walk();
+        let names: Vec<Node> = statement.children_by_field_name("name", &mut cursor).collect();
+        for name in names {
+            let local = match name.kind() {
+                "aliased_import" => {
+                    let alias = name.child_by_field_name("alias").map(|alias| text(alias, self.code));
+                    let original = name.child_by_field_name("name").map(|original| text(original, self.code));
+                    // `import x as x` is an explicit re-export
+                    if alias == original {
+                        continue;
+                    }
+                    alias.unwrap_or("")
+                }
+                _ => text(name, self.code).split('.').next().unwrap_or(""),
+            };
+            if local.is_empty() || read.contains(local) || exported.contains(local) {
+                continue;
+            }
+            self.report(
+                name,
+                format!("'{}' is imported but never used.", text(name, self.code)),
+                "python-unused-import",
+                Severity::Warning,
+                Category::CodeQuality,
+            );
+        }
+    }
+
+    /// Lambdas and nested functions created in a loop that capture its variable and outlive the iteration
+    fn loop_closure(&mut self, closure: Node) {
+        let Some((loop_node, variables)) = enclosing_loop(closure, self.code) else {
+            return;
+        };
+        let escapes = match closure.kind() {
+            "lambda" => lambda_escapes(closure, self.code),
+            _ => function_escapes(closure, loop_node, self.code),
+        };
+        if !escapes {
+            return;
+        }
+
+        let own = parameter_names(closure, self.code);
+        let captured = free_reads(closure, self.code)
+            .into_iter()
+            .find(|name| variables.contains(name) && !own.contains(name));
+        if let Some(name) = captured {
+            let target = match closure.kind() {
+                "lambda" => closure,
+                _ => closure.child_by_field_name("name").unwrap_or(closure),
+            };
+            self.report(
+                target,
+                format!("Closure captures the loop variable '{}' by reference, so every copy sees its last value. Bind it as a default argument ({}={}).", name, name, name),
+                "python-loop-closure",
+                Severity::Warning,
+                Category::CodeQuality,
+            );
+        }
+    }
+
+    /// Calls to coroutines whose result is dropped, so they never run
+    fn missing_await(&mut self, function: Node) {
+        let Some(body) = function.child_by_field_name("body") else {
+            return;
+        };
+        let mut stack = vec![body];
+        while let Some(node) = stack.pop() {
+            match node.kind() {
+                "function_definition" | "lambda" | "class_definition" => continue,
+                "expression_statement" => {
+                    let call = node.named_child(0).filter(|expression| expression.kind() == "call");
+                    if let Some(name) = call.and_then(|call| self.coroutine_callee(call)) {
+                        self.report(
+                            node,
+                            format!("Coroutine '{}' is called without await, so it never runs. Await it or wrap it in asyncio.create_task().", name),
+                            "python-missing-await",
+                            Severity::Error,
+                            Category::CodeQuality,
+                        );
+                    }
+                }
+                _ => {}
+            }
+            stack.extend(named_children(node));
+        }
+    }
+
+    fn coroutine_callee(&self, call: Node) -> Option<String> {
+        let function = call.child_by_field_name("function")?;
+        let path = text(function, self.code);
+        if ASYNCIO_COROUTINES.contains(&path) {
+            return Some(path.to_string());
+        }
+        let name = match function.kind() {
+            "identifier" => path,
+            // Only `self.method()`; other receivers may be different classes
+            "attribute" if function.child_by_field_name("object").is_some_and(|object| matches!(text(object, self.code), "self" | "cls")) => {
+                text(function.child_by_field_name("attribute")?, self.code)
+            }
+            _ => return None,
+        };
+        self.coroutines.contains(name).then(|| path.to_string())
+    }
+}
+
+fn in_class_body(node: Node) -> bool {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        match parent.kind() {
+            "class_definition" => return true,
+            "function_definition" | "lambda" | "module" => return false,
+            _ => current = parent.parent(),
+        }
+    }
+    false
+}
+
+/// Names listed in `__all__`, which count as used
+fn module_exports(scope: Node, code: &str) -> HashSet<String> {
+    let mut names = HashSet::new();
+    if scope.kind() != "module" {
+        return names;
+    }
+    for statement in named_children(scope) {
+        let Some(assignment) = statement.named_child(0).filter(|child| child.kind() == "assignment") else {
+            continue;
+        };
+        if assignment.child_by_field_name("left").is_some_and(|left| text(left, code) == "__all__") {
+            if let Some(right) = assignment.child_by_field_name("right") {
+                for item in named_children(right) {
+                    names.insert(text(item, code).trim_matches(&['\'', '"'][..]).to_string());
+                }
+            }
+        }
+    }
+    names
+}
+
+/// The innermost loop in the same function whose body contains `node`, with the variables it binds
+fn enclosing_loop<'tree>(node: Node<'tree>, code: &str) -> Option<(Node<'tree>, HashSet<String>)> {
+    let mut child = node;
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if parent.kind() == "for_statement" && parent.child_by_field_name("body") == Some(child) {
+            return Some((parent, bound_names(parent.child_by_field_name("left")?, code)));
+        }
+        if COMPREHENSIONS.contains(&parent.kind()) && parent.child_by_field_name("body") == Some(child) {
+            let mut variables = HashSet::new();
+            for clause in named_children(parent).into_iter().filter(|clause| clause.kind() == "for_in_clause") {
+                if let Some(left) = clause.child_by_field_name("left") {
+                    variables.extend(bound_names(left, code));
+                }
+            }
+            return Some((parent, variables));
+        }
+        if is_scope(parent) {
+            return None;
+        }
+        child = parent;
+        current = parent.parent();
+    }
+    None
+}
+
+fn bound_names(target: Node, code: &str) -> HashSet<String> {
+    let mut names = HashSet::new();
+    let mut stack = vec![target];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "identifier" {
+            names.insert(text(node, code).to_string());
+        }
+        stack.extend(named_children(node));
+    }
+    names
+}
+
+/// Whether a lambda is stored somewhere instead of being called during the iteration
+fn lambda_escapes(lambda: Node, code: &str) -> bool {
+    let mut node = lambda;
+    while let Some(parent) = node.parent().filter(|parent| parent.kind() == "parenthesized_expression") {
+        node = parent;
+    }
+    let Some(parent) = node.parent() else {
+        return false;
+    };
+    match parent.kind() {
+        "list" | "set" | "tuple" | "pair" | "dictionary" => true,
+        kind if COMPREHENSIONS.contains(&kind) => true,
+        "assignment" => parent
+            .child_by_field_name("left")
+            .is_some_and(|left| matches!(left.kind(), "subscript" | "attribute")),
+        "keyword_argument" => parent
+            .child_by_field_name("name")
+            .is_some_and(|name| CALLBACK_KEYWORDS.contains(&text(name, code))),
+        "argument_list" => parent
+            .parent()
+            .and_then(|call| call.child_by_field_name("function"))
+            .filter(|function| function.kind() == "attribute")
+            .and_then(|function| function.child_by_field_name("attribute"))
+            .is_some_and(|method| STORING_METHODS.contains(&text(method, code))),
+        _ => false,
+    }
+}
+
+/// Whether a function defined in a loop is used as a value there, rather than only called
+fn function_escapes(function: Node, loop_node: Node, code: &str) -> bool {
+    let Some(name) = function.child_by_field_name("name").map(|name| text(name, code)) else {
+        return false;
+    };
+    let mut stack = vec![loop_node];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "identifier" && text(node, code) == name && is_read(node) {
+            let called = node
+                .parent()
+                .is_some_and(|parent| parent.kind() == "call" && parent.child_by_field_name("function") == Some(node));
+            if !called {
+                return true;
+            }
+        }
+        stack.extend(named_children(node));
+    }
+    false
+}
+
+fn parameter_names(closure: Node, code: &str) -> HashSet<String> {
+    let parameters = closure.child_by_field_name("parameters");
+    let mut names = HashSet::new();
+    for parameter in parameters.map(named_children).unwrap_or_default() {
+        let name = match parameter.kind() {
+            "identifier" => Some(parameter),
+            "default_parameter" | "typed_default_parameter" => parameter.child_by_field_name("name"),
+            _ => parameter.named_child(0),
+        };
+        if let Some(name) = name {
+            names.insert(text(name, code).to_string());
+        }
+    }
+    names
+}
+
+/// Names read in a closure's body, in source order
+fn free_reads(closure: Node, code: &str) -> Vec<String> {
+    let Some(body) = closure.child_by_field_name("body") else {
+        return Vec::new();
+    };
+    let mut names = Vec::new();
+    let mut seen = HashSet::new();
+    let mut stack = vec![body];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "identifier" && is_read(node) {
+            let name = text(node, code).to_string();
+            if seen.insert(name.clone()) {
+                names.push(name);
+            }
+        }
+        stack.extend(named_children(node));
+    }
+    names
+}
@@ -1,2 +1,5 @@
+pub mod correctness;
 pub mod parser;
+pub mod security;
+pub mod type_hints;
 pub use parser::PythonParser;
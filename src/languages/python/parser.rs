@@ -1,14 +1,233 @@
+use super::type_hints::{self, TypeCoverage};
+use super::{correctness, security};
+use crate::cfg::{self, tree::{named_children, unparenthesized, Flow, FlowGrammar}};
+use crate::clones::CloneDetector;
+use crate::cognitive::{self, CognitiveGrammar};
+use crate::config::Config;
 use crate::error::{AnalyzerError, Result};
+use crate::languages::grammar::Grammar;
+use crate::languages::query_rules::QueryRules;
+use crate::metrics::halstead;
+use crate::metrics::tree::{self, MetricsGrammar};
+use crate::metrics::FileMetrics;
+use crate::plugins::{self, Plugin};
+use crate::secrets::{self, SecretScanner};
 use crate::types::{AnalysisResult, Category, CodeIssue, FileAnalysis, Severity, SeveritySummary};
 use std::fs;
 use std::path::{Path, PathBuf};
-use tree_sitter::{Parser, Query, QueryCursor};
+use tree_sitter::{Node, QueryCursor};
 
-pub struct PythonParser {}
+/// Python node kinds scored by the cognitive complexity rule
+const COGNITIVE_GRAMMAR: CognitiveGrammar = CognitiveGrammar {
+    functions: &["function_definition", "lambda"],
+    nesting: &[
+        "for_statement",
+        "while_statement",
+        "except_clause",
+        "match_statement",
+        "conditional_expression",
+    ],
+    ifs: &["if_statement"],
+    else_clauses: &["elif_clause", "else_clause"],
+    boolean_operators: &["boolean_operator"],
+    jumps: &[],
+    function_name,
+    callee,
+};
+
+fn function_name(node: Node, code: &str) -> Option<String> {
+    let name = node.child_by_field_name("name")?;
+    name.utf8_text(code.as_bytes()).ok().map(String::from)
+}
+
+fn callee(node: Node, code: &str) -> Option<String> {
+    if node.kind() != "call" {
+        return None;
+    }
+    let function = node.child_by_field_name("function")?;
+    function.utf8_text(code.as_bytes()).ok().map(String::from)
+}
+
+/// Python node kinds used by the `metrics` subcommand
+const METRICS_GRAMMAR: MetricsGrammar = MetricsGrammar {
+    cognitive: &COGNITIVE_GRAMMAR,
+    lexicon: &halstead::PYTHON,
+    decisions: &[
+        "if_statement",
+        "elif_clause",
+        "for_statement",
+        "while_statement",
+        "except_clause",
+        "case_clause",
+        "conditional_expression",
+        "boolean_operator",
+        "for_in_clause",
+        "if_clause",
+    ],
+    statement_lists: &["module", "block"],
+    parameter_lists: &["parameters", "lambda_parameters"],
+    parameters: &[
+        "identifier",
+        "typed_parameter",
+        "default_parameter",
+        "typed_default_parameter",
+        "list_splat_pattern",
+        "dictionary_splat_pattern",
+    ],
+    header: tree::same_node,
+};
+
+/// Python statements mapped to control flow
+const FLOW_GRAMMAR: FlowGrammar = FlowGrammar {
+    cognitive: &COGNITIVE_GRAMMAR,
+    body: |node| node.child_by_field_name("body"),
+    header: tree::same_node,
+    classify,
+    constant,
+    generator: |node, _| cfg::tree::contains_kind(&COGNITIVE_GRAMMAR, node, &["yield"]),
+    check_returns: true,
+};
+
+fn classify<'tree>(node: Node<'tree>, _code: &str) -> Flow<'tree> {
+    let field = |name| node.child_by_field_name(name);
+    let children_of = |kinds: &[&str]| -> Vec<Node<'tree>> {
+        named_children(node)
+            .into_iter()
+            .filter(|child| kinds.contains(&child.kind()))
+            .collect()
+    };
+
+    match node.kind() {
+        "comment" => Flow::Ignore,
+        "block" | "module" => Flow::Sequence(named_children(node)),
+        "else_clause" => Flow::Sequence(field("body").into_iter().collect()),
+        "except_clause" | "except_group_clause" | "finally_clause" => Flow::Sequence(children_of(&["block"])),
+        "if_statement" => {
+            let mut cursor = node.walk();
+            Flow::If {
+                condition: field("condition"),
+                consequence: field("consequence"),
+                alternatives: node.children_by_field_name("alternative", &mut cursor).collect(),
+            }
+        }
+        "elif_clause" => Flow::If {
+            condition: field("condition"),
+            consequence: field("consequence"),
+            alternatives: Vec::new(),
+        },
+        "while_statement" | "for_statement" => Flow::Loop {
+            condition: field("condition"),
+            body: field("body"),
+            label: None,
+            test_first: true,
+            endless: false,
+        },
+        "with_statement" => Flow::Compound(field("body").into_iter().collect()),
+        "try_statement" => Flow::Try {
+            body: field("body"),
+            handlers: children_of(&["except_clause", "except_group_clause"]),
+            otherwise: children_of(&["else_clause"]).pop(),
+            finalizer: children_of(&["finally_clause"]).pop(),
+        },
+        "match_statement" => {
+            let cases = field("body").map(named_children).unwrap_or_default();
+            let cases: Vec<Node> = cases.into_iter().filter(|case| case.kind() == "case_clause").collect();
+            Flow::Branches {
+                exhaustive: cases.iter().any(|case| is_wildcard_case(*case)),
+                arms: cases
+                    .iter()
+                    .map(|case| case.child_by_field_name("consequence").into_iter().collect())
+                    .collect(),
+                breakable: false,
+            }
+        }
+        "return_statement" => Flow::Return {
+            value: node.named_child_count() > 0,
+        },
+        "raise_statement" => Flow::Throw,
+        "break_statement" => Flow::Break(None),
+        "continue_statement" => Flow::Continue(None),
+        _ => Flow::Statement,
+    }
+}
+
+/// `case _:` without a guard matches everything
+fn is_wildcard_case(case: Node) -> bool {
+    let mut cursor = case.walk();
+    let has_wildcard = case.children(&mut cursor).any(|child| {
+        child.kind() == "case_pattern" && child.named_child_count() == 0
+    });
+    has_wildcard && case.child_by_field_name("guard").is_none()
+}
+
+fn constant(node: Node, code: &str) -> Option<bool> {
+    let node = unparenthesized(node);
+    match node.kind() {
+        "true" => Some(true),
+        "false" | "none" => Some(false),
+        "integer" => node.utf8_text(code.as_bytes()).ok().map(|text| text != "0"),
+        _ => None,
+    }
+}
+
+/// Rule patterns matched against every Python file, compiled once per parser
+const QUERY: &str = "
+    (call
+        function: (identifier) @func_name
+        arguments: (argument_list)
+        (#match? @func_name \"^print$\")
+    ) @print_call
+
+    (integer) @magic_number
+    (float) @magic_number
+
+    (string) @string_literal
+
+    (class_definition
+        name: (identifier) @class_name
+    )
+
+    (function_definition
+        name: (identifier) @def_func_name
+    )
+
+    (assignment
+        left: (identifier) @var_assign
+    )
+
+    (if_statement) @if_stmt
+
+    (function_definition
+        parameters: (parameters) @params
+    ) @func_def_params
+";
+
+pub struct PythonParser {
+    grammar: Grammar,
+    rules: QueryRules,
+    plugins: Vec<Plugin>,
+    secrets: SecretScanner,
+    clones: CloneDetector,
+    cognitive_threshold: usize,
+}
 
 impl PythonParser {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new() -> Result<Self> {
+        Self::with_config(&Config::default())
+    }
+
+    /// Build the front end, compiling its grammar query and the project rules once. Fails if a query doesn't compile.
+    pub fn with_config(config: &Config) -> Result<Self> {
+        let grammar = Grammar::new("Python", tree_sitter_python::language(), QUERY)?;
+        let rules = QueryRules::compile(&grammar, &config.rules)?;
+        Ok(Self {
+            grammar,
+            rules,
+            plugins: config.loaded_plugins.clone(),
+            secrets: SecretScanner::with_config(&config.secrets),
+            clones: CloneDetector::with_config(&config.clones),
+            cognitive_threshold: config.complexity.cognitive_threshold,
+        })
     }
 
     pub fn analyze_file(&self, file_path: &Path) -> Result<FileAnalysis> {
@@ -16,19 +235,7 @@ impl PythonParser {
             path: file_path.display().to_string(),
         })?;
 
-        let mut parser = Parser::new();
-        parser
-            .set_language(&tree_sitter_python::language())
-            .expect("Error loading Python grammar");
-
-        let tree = parser
-            .parse(&code, None)
-            .ok_or_else(|| AnalyzerError::ParseError {
-                file: file_path.display().to_string(),
-                line: 0,
-                column: 0,
-                message: "Failed to parse Python file".to_string(),
-            })?;
+        let tree = self.grammar.parse(&code, file_path)?;
 
         let mut issues = Vec::new();
         let root_node = tree.root_node();
@@ -50,314 +257,294 @@ impl PythonParser {
         }
 
         // Queries for Python
-        let query_source = "
-            (call
-                function: (identifier) @func_name
-                arguments: (argument_list)
-                (#match? @func_name \"^print$\")
-            ) @print_call
-
-            (integer) @magic_number
-            (float) @magic_number
-            
-            (string) @string_literal
-
-            (class_definition
-                name: (identifier) @class_name
-            )
-
-            (function_definition
-                name: (identifier) @def_func_name
-            )
-
-            (assignment
-                left: (identifier) @var_assign
-            )
-            
-            (if_statement) @if_stmt
-            
-            (function_definition
-                parameters: (parameters) @params
-            ) @func_def_params
-        ";
-
-        if let Ok(query) = Query::new(&tree_sitter_python::language(), query_source) {
-            let mut query_cursor = QueryCursor::new();
-            let matches = query_cursor.matches(&query, root_node, code.as_bytes());
-
-            for m in matches {
-                for capture in m.captures {
-                    let node = capture.node;
-                    let start = node.start_position();
-                    let end = node.end_position();
-                    let capture_name = query.capture_names()[capture.index as usize];
-
-                    if capture_name == "print_call" {
-                        issues.push(CodeIssue {
-                            file_path: file_path.display().to_string(),
-                            line: start.row + 1,
-                            column: start.column + 1,
-                            end_line: Some(end.row + 1),
-                            end_column: Some(end.column + 1),
-                            message: "Avoid using print() in production. Use a logger.".to_string(),
-                            severity: Severity::Warning,
-                            category: Category::BestPractice,
-                            rule: "no-print".to_string(),
-                            code_snippet: Some(
-                                node.utf8_text(code.as_bytes()).unwrap_or("").to_string(),
-                            ),
-                        });
-                    } else if capture_name == "magic_number" {
-                        let text = node.utf8_text(code.as_bytes()).unwrap_or("");
-                        // Ignore common small numbers
-                        if text != "0"
-                            && text != "1"
-                            && text != "-1"
-                            && text != "2"
-                            && text != "10"
-                            && text != "100"
-                        {
-                            // Ignore if it's in a CONSTANT assignment (UPPERCASE variable) or default parameter value
-                            let mut is_const = false;
-                            let mut parent = node.parent();
-                            while let Some(p) = parent {
-                                if p.kind() == "assignment" {
-                                    // Check if left side is UPPERCASE
-                                    if let Some(left) = p.child_by_field_name("left") {
-                                        if let Ok(name) = left.utf8_text(code.as_bytes()) {
-                                            if name
-                                                .chars()
-                                                .all(|c| !c.is_alphabetic() || c.is_uppercase())
-                                            {
-                                                is_const = true;
-                                            }
-                                        }
-                                    }
-                                } else if p.kind() == "keyword_argument"
-                                    || p.kind() == "default_parameter"
-                                {
-                                    // Allow magic numbers as default values? Maybe not, but often acceptable.
-                                    // Let's stick strictly to 'define a constant'.
-                                }
 
-                                if is_const {
-                                    break;
-                                }
-                                parent = p.parent();
-                                if parent.is_none()
-                                    || p.kind() == "function_definition"
-                                    || p.kind() == "class_definition"
-                                {
-                                    break;
-                                }
-                            }
+        let query = self.grammar.query();
+        let mut query_cursor = QueryCursor::new();
+        let matches = query_cursor.matches(query, root_node, code.as_bytes());
 
-                            if !is_const {
-                                issues.push(CodeIssue {
-                                    file_path: file_path.display().to_string(),
-                                    line: start.row + 1,
-                                    column: start.column + 1,
-                                    end_line: Some(end.row + 1),
-                                    end_column: Some(end.column + 1),
-                                    message: format!(
-                                        "magic number detected: {}. Define a constant.",
-                                        text
-                                    ),
-                                    severity: Severity::Suggestion,
-                                    category: Category::BestPractice,
-                                    rule: "no-magic-numbers".to_string(),
-                                    code_snippet: Some(text.to_string()),
-                                });
-                            }
-                        }
-                    } else if capture_name == "string_literal" {
-                        let text = node.utf8_text(code.as_bytes()).unwrap_or("");
-                        let clean_text = text.trim_matches(&['\'', '"'][..]);
-                        if clean_text.len() > 20 && !clean_text.contains("{") {
-                            // Ignore docstrings
-                            let mut is_docstring = false;
-                            if let Some(parent) = node.parent() {
-                                if parent.kind() == "expression_statement" {
-                                    if let Some(grandparent) = parent.parent() {
-                                        if grandparent.kind() == "function_definition"
-                                            || grandparent.kind() == "class_definition"
-                                            || grandparent.kind() == "module"
+        for m in matches {
+            for capture in m.captures {
+                let node = capture.node;
+                let start = node.start_position();
+                let end = node.end_position();
+                let capture_name = query.capture_names()[capture.index as usize];
+
+                if capture_name == "print_call" {
+                    issues.push(CodeIssue {
+                        file_path: file_path.display().to_string(),
+                        line: start.row + 1,
+                        column: start.column + 1,
+                        end_line: Some(end.row + 1),
+                        end_column: Some(end.column + 1),
+                        message: "Avoid using print() in production. Use a logger.".to_string(),
+                        severity: Severity::Warning,
+                        category: Category::BestPractice,
+                        rule: "no-print".to_string(),
+                        code_snippet: Some(
+                            node.utf8_text(code.as_bytes()).unwrap_or("").to_string(),
+                        ),
+                    });
+                } else if capture_name == "magic_number" {
+                    let text = node.utf8_text(code.as_bytes()).unwrap_or("");
+                    // Ignore common small numbers
+                    if text != "0"
+                        && text != "1"
+                        && text != "-1"
+                        && text != "2"
+                        && text != "10"
+                        && text != "100"
+                    {
+                        // Ignore if it's in a CONSTANT assignment (UPPERCASE variable) or default parameter value
+                        let mut is_const = false;
+                        let mut parent = node.parent();
+                        while let Some(p) = parent {
+                            if p.kind() == "assignment" {
+                                // Check if left side is UPPERCASE
+                                if let Some(left) = p.child_by_field_name("left") {
+                                    if let Ok(name) = left.utf8_text(code.as_bytes()) {
+                                        if name
+                                            .chars()
+                                            .all(|c| !c.is_alphabetic() || c.is_uppercase())
                                         {
-                                            // It's likely a docstring if it's the first statement
-                                            let mut cursor = grandparent.walk();
-                                            for child in grandparent.children(&mut cursor) {
-                                                if child.kind() == "expression_statement" {
-                                                    if child == parent {
-                                                        is_docstring = true;
-                                                    }
-                                                    break; // Only check the first one
-                                                } else if child.kind() == "comment" {
-                                                    continue;
-                                                } else {
-                                                    // If we hit something else before our node, it's not a docstring (usually)
-                                                    if child.start_position().row
-                                                        < parent.start_position().row
-                                                    {
-                                                        // preceeding code
-                                                    }
-                                                }
-                                            }
+                                            is_const = true;
                                         }
                                     }
                                 }
+                            } else if p.kind() == "keyword_argument"
+                                || p.kind() == "default_parameter"
+                            {
+                                // Allow magic numbers as default values? Maybe not, but often acceptable.
+                                // Let's stick strictly to 'define a constant'.
                             }
 
-                            if !is_docstring {
-                                issues.push(CodeIssue {
-                                    file_path: file_path.display().to_string(),
-                                    line: start.row + 1,
-                                    column: start.column + 1,
-                                    end_line: Some(end.row + 1),
-                                    end_column: Some(end.column + 1),
-                                    message: format!("hardcoded string detected: \"{}...\". Consider extracting to a constant.", &clean_text.chars().take(20).collect::<String>()),
-                                    severity: Severity::Suggestion,
-                                    category: Category::BestPractice,
-                                    rule: "no-hardcoded-strings".to_string(),
-                                    code_snippet: Some(clean_text.to_string()),
-                                });
+                            if is_const {
+                                break;
+                            }
+                            parent = p.parent();
+                            if parent.is_none()
+                                || p.kind() == "function_definition"
+                                || p.kind() == "class_definition"
+                            {
+                                break;
                             }
                         }
-                    } else if capture_name == "class_name" {
-                        let text = node.utf8_text(code.as_bytes()).unwrap_or("");
-                        if !text.chars().next().map_or(false, |c| c.is_uppercase()) {
-                            issues.push(CodeIssue {
-                                file_path: file_path.display().to_string(),
-                                line: start.row + 1,
-                                column: start.column + 1,
-                                end_line: Some(end.row + 1),
-                                end_column: Some(end.column + 1),
-                                message: format!("Class name '{}' should be PascalCase.", text),
-                                severity: Severity::Warning,
-                                category: Category::CodeQuality,
-                                rule: "class-naming".to_string(),
-                                code_snippet: Some(text.to_string()),
-                            });
-                        }
-                    } else if capture_name == "def_func_name" {
-                        let text = node.utf8_text(code.as_bytes()).unwrap_or("");
-                        if !text
-                            .chars()
-                            .all(|c| c.is_lowercase() || c == '_' || c.is_numeric())
-                        {
-                            issues.push(CodeIssue {
-                                file_path: file_path.display().to_string(),
-                                line: start.row + 1,
-                                column: start.column + 1,
-                                end_line: Some(end.row + 1),
-                                end_column: Some(end.column + 1),
-                                message: format!("Function name '{}' should be snake_case.", text),
-                                severity: Severity::Warning,
-                                category: Category::CodeQuality,
-                                rule: "function-naming".to_string(),
-                                code_snippet: Some(text.to_string()),
-                            });
-                        }
-                    } else if capture_name == "var_assign" {
-                        let text = node.utf8_text(code.as_bytes()).unwrap_or("");
-
-                        // Check for snake_case
-                        // Allow CONSTANTS (all uppercase)
-                        let is_constant =
-                            text.chars().all(|c| !c.is_alphabetic() || c.is_uppercase());
-                        if !is_constant
-                            && !text
-                                .chars()
-                                .all(|c| c.is_lowercase() || c == '_' || c.is_numeric())
-                        {
+
+                        if !is_const {
                             issues.push(CodeIssue {
                                 file_path: file_path.display().to_string(),
                                 line: start.row + 1,
                                 column: start.column + 1,
                                 end_line: Some(end.row + 1),
                                 end_column: Some(end.column + 1),
-                                message: format!("Variable name '{}' should be snake_case (or UPPER_CASE for constants).", text),
-                                severity: Severity::Warning,
-                                category: Category::CodeQuality,
-                                rule: "variable-naming".to_string(),
+                                message: format!(
+                                    "magic number detected: {}. Define a constant.",
+                                    text
+                                ),
+                                severity: Severity::Suggestion,
+                                category: Category::BestPractice,
+                                rule: "no-magic-numbers".to_string(),
                                 code_snippet: Some(text.to_string()),
                             });
                         }
-                    } else if capture_name == "if_stmt" {
-                        // Check nesting
-                        let mut depth = 0;
-                        let mut parent = node.parent();
-                        while let Some(p) = parent {
-                            if p.kind() == "if_statement" {
-                                depth += 1;
+                    }
+                } else if capture_name == "string_literal" {
+                    let text = node.utf8_text(code.as_bytes()).unwrap_or("");
+                    let secret = self.secrets.scan(file_path, secrets::unquote(text));
+                    if let Some(finding) = &secret {
+                        issues.push(self.secrets.issue(
+                            file_path,
+                            finding,
+                            start.row + 1,
+                            start.column + 1,
+                            Some((end.row + 1, end.column + 1)),
+                        ));
+                    }
+                    let clean_text = text.trim_matches(&['\'', '"'][..]);
+                    if secret.is_none() && clean_text.len() > 20 && !clean_text.contains("{") {
+                        // Ignore docstrings
+                        let mut is_docstring = false;
+                        if let Some(parent) = node.parent() {
+                            if parent.kind() == "expression_statement" {
+                                if let Some(grandparent) = parent.parent() {
+                                    if grandparent.kind() == "function_definition"
+                                        || grandparent.kind() == "class_definition"
+                                        || grandparent.kind() == "module"
+                                    {
+                                        // It's likely a docstring if it's the first statement
+                                        let mut cursor = grandparent.walk();
+                                        for child in grandparent.children(&mut cursor) {
+                                            if child.kind() == "expression_statement" {
+                                                if child == parent {
+                                                    is_docstring = true;
+                                                }
+                                                break; // Only check the first one
+                                            } else if child.kind() == "comment" {
+                                                continue;
+                                            } else {
+                                                // If we hit something else before our node, it's not a docstring (usually)
+                                                if child.start_position().row
+                                                    < parent.start_position().row
+                                                {
+                                                    // preceeding code
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
                             }
-                            parent = p.parent();
                         }
-                        if depth >= 2 {
+
+                        if !is_docstring {
                             issues.push(CodeIssue {
                                 file_path: file_path.display().to_string(),
                                 line: start.row + 1,
                                 column: start.column + 1,
                                 end_line: Some(end.row + 1),
                                 end_column: Some(end.column + 1),
-                                message: "Avoid deeply nested if statements.".to_string(),
-                                severity: Severity::Warning,
-                                category: Category::Complexity,
-                                rule: "nested-if".to_string(),
-                                code_snippet: Some("if ...".to_string()),
+                                message: format!("hardcoded string detected: \"{}...\". Consider extracting to a constant.", &clean_text.chars().take(20).collect::<String>()),
+                                severity: Severity::Suggestion,
+                                category: Category::BestPractice,
+                                rule: "no-hardcoded-strings".to_string(),
+                                code_snippet: Some(clean_text.to_string()),
                             });
                         }
-                    } else if capture_name == "params" {
-                        // Check parameter count
-                        let param_count = node.child_count();
-                        // Tree-sitter 'parameters' node contains '(' and ')' and commas.
-                        // We count actual identifiers or named parameters.
-                        let mut actual_params = 0;
-                        let mut cursor = node.walk();
-                        for child in node.children(&mut cursor) {
-                            if child.kind() == "identifier"
-                                || child.kind() == "typed_parameter"
-                                || child.kind() == "default_parameter"
-                                || child.kind() == "typed_default_parameter"
-                            {
-                                actual_params += 1;
-                            }
-                        }
+                    }
+                } else if capture_name == "class_name" {
+                    let text = node.utf8_text(code.as_bytes()).unwrap_or("");
+                    if !text.chars().next().map_or(false, |c| c.is_uppercase()) {
+                        issues.push(CodeIssue {
+                            file_path: file_path.display().to_string(),
+                            line: start.row + 1,
+                            column: start.column + 1,
+                            end_line: Some(end.row + 1),
+                            end_column: Some(end.column + 1),
+                            message: format!("Class name '{}' should be PascalCase.", text),
+                            severity: Severity::Warning,
+                            category: Category::CodeQuality,
+                            rule: "class-naming".to_string(),
+                            code_snippet: Some(text.to_string()),
+                        });
+                    }
+                } else if capture_name == "def_func_name" {
+                    let text = node.utf8_text(code.as_bytes()).unwrap_or("");
+                    if !text
+                        .chars()
+                        .all(|c| c.is_lowercase() || c == '_' || c.is_numeric())
+                    {
+                        issues.push(CodeIssue {
+                            file_path: file_path.display().to_string(),
+                            line: start.row + 1,
+                            column: start.column + 1,
+                            end_line: Some(end.row + 1),
+                            end_column: Some(end.column + 1),
+                            message: format!("Function name '{}' should be snake_case.", text),
+                            severity: Severity::Warning,
+                            category: Category::CodeQuality,
+                            rule: "function-naming".to_string(),
+                            code_snippet: Some(text.to_string()),
+                        });
+                    }
+                } else if capture_name == "var_assign" {
+                    let text = node.utf8_text(code.as_bytes()).unwrap_or("");
 
-                        if actual_params > 5 {
-                            issues.push(CodeIssue {
-                                file_path: file_path.display().to_string(),
-                                line: start.row + 1,
-                                column: start.column + 1,
-                                end_line: Some(end.row + 1),
-                                end_column: Some(end.column + 1),
-                                message: format!(
-                                    "Function has too many parameters ({}). Max allowed is 5.",
-                                    actual_params
-                                ),
-                                severity: Severity::Warning,
-                                category: Category::Complexity,
-                                rule: "complexity".to_string(),
-                                code_snippet: Some("def func(...)".to_string()),
-                            });
+                    // Check for snake_case
+                    // Allow CONSTANTS (all uppercase)
+                    let is_constant =
+                        text.chars().all(|c| !c.is_alphabetic() || c.is_uppercase());
+                    if !is_constant
+                        && !text
+                            .chars()
+                            .all(|c| c.is_lowercase() || c == '_' || c.is_numeric())
+                    {
+                        issues.push(CodeIssue {
+                            file_path: file_path.display().to_string(),
+                            line: start.row + 1,
+                            column: start.column + 1,
+                            end_line: Some(end.row + 1),
+                            end_column: Some(end.column + 1),
+                            message: format!("Variable name '{}' should be snake_case (or UPPER_CASE for constants).", text),
+                            severity: Severity::Warning,
+                            category: Category::CodeQuality,
+                            rule: "variable-naming".to_string(),
+                            code_snippet: Some(text.to_string()),
+                        });
+                    }
+                } else if capture_name == "if_stmt" {
+                    // Check nesting
+                    let mut depth = 0;
+                    let mut parent = node.parent();
+                    while let Some(p) = parent {
+                        if p.kind() == "if_statement" {
+                            depth += 1;
+                        }
+                        parent = p.parent();
+                    }
+                    if depth >= 2 {
+                        issues.push(CodeIssue {
+                            file_path: file_path.display().to_string(),
+                            line: start.row + 1,
+                            column: start.column + 1,
+                            end_line: Some(end.row + 1),
+                            end_column: Some(end.column + 1),
+                            message: "Avoid deeply nested if statements.".to_string(),
+                            severity: Severity::Warning,
+                            category: Category::Complexity,
+                            rule: "nested-if".to_string(),
+                            code_snippet: Some("if ...".to_string()),
+                        });
+                    }
+                } else if capture_name == "params" {
+                    // Check parameter count
+                    let param_count = node.child_count();
+                    // Tree-sitter 'parameters' node contains '(' and ')' and commas.
+                    // We count actual identifiers or named parameters.
+                    let mut actual_params = 0;
+                    let mut cursor = node.walk();
+                    for child in node.children(&mut cursor) {
+                        if child.kind() == "identifier"
+                            || child.kind() == "typed_parameter"
+                            || child.kind() == "default_parameter"
+                            || child.kind() == "typed_default_parameter"
+                        {
+                            actual_params += 1;
                         }
                     }
+
+                    if actual_params > 5 {
+                        issues.push(CodeIssue {
+                            file_path: file_path.display().to_string(),
+                            line: start.row + 1,
+                            column: start.column + 1,
+                            end_line: Some(end.row + 1),
+                            end_column: Some(end.column + 1),
+                            message: format!(
+                                "Function has too many parameters ({}). Max allowed is 5.",
+                                actual_params
+                            ),
+                            severity: Severity::Warning,
+                            category: Category::Complexity,
+                            rule: "complexity".to_string(),
+                            code_snippet: Some("def func(...)".to_string()),
+                        });
+                    }
                 }
             }
-        } else if let Err(e) = Query::new(&tree_sitter_python::language(), query_source) {
-            issues.push(CodeIssue {
-                file_path: file_path.display().to_string(),
-                line: 1,
-                column: 1,
-                end_line: None,
-                end_column: None,
-                message: format!("Internal Error: Failed to compile Python AST Query: {}", e),
-                severity: Severity::Error,
-                category: Category::CodeQuality,
-                rule: "internal-error".to_string(),
-                code_snippet: None,
-            });
         }
 
+        issues.extend(cognitive::analyze_tree(
+            &COGNITIVE_GRAMMAR,
+            root_node,
+            &code,
+            file_path,
+            self.cognitive_threshold,
+        ));
+        issues.extend(cfg::tree::analyze_tree(&FLOW_GRAMMAR, root_node, &code, file_path));
+        issues.extend(security::analyze_tree(root_node, &code, file_path));
+        issues.extend(correctness::analyze_tree(root_node, &code, file_path));
+        issues.extend(type_hints::analyze_tree(root_node, &code, file_path));
+        issues.extend(self.rules.check(root_node, &code, file_path));
+        issues.extend(plugins::run_all(&self.plugins, file_path, "python", &code, || plugins::tree_sitter_ast(root_node, &code)));
+
         let mut summary = SeveritySummary::new();
         for issue in &issues {
             summary.add(issue.severity);
@@ -370,6 +557,34 @@ impl PythonParser {
         })
     }
 
+    /// Code metrics for a file and each function in it
+    pub fn metrics_file(&self, file_path: &Path) -> Result<FileMetrics> {
+        let code = fs::read_to_string(file_path).map_err(|_| AnalyzerError::FileReadError {
+            path: file_path.display().to_string(),
+        })?;
+        let tree = self.grammar.parse(&code, file_path)?;
+        Ok(tree::file_metrics(&METRICS_GRAMMAR, tree.root_node(), &code, file_path))
+    }
+
+    pub fn metrics_directory(&self, dir_path: &Path) -> Result<Vec<FileMetrics>> {
+        let files = self.find_files(dir_path)?;
+        Ok(files.iter().filter_map(|file_path| self.metrics_file(file_path).ok()).collect())
+    }
+
+    /// Type-hint coverage of a file
+    pub fn type_coverage_file(&self, file_path: &Path) -> Result<TypeCoverage> {
+        let code = fs::read_to_string(file_path).map_err(|_| AnalyzerError::FileReadError {
+            path: file_path.display().to_string(),
+        })?;
+        let tree = self.grammar.parse(&code, file_path)?;
+        Ok(type_hints::file_coverage(tree.root_node(), &code, file_path))
+    }
+
+    pub fn type_coverage_directory(&self, dir_path: &Path) -> Result<Vec<TypeCoverage>> {
+        let files = self.find_files(dir_path)?;
+        Ok(files.iter().filter_map(|file_path| self.type_coverage_file(file_path).ok()).collect())
+    }
+
     pub fn analyze_directory(&self, dir_path: &Path) -> Result<AnalysisResult> {
         let mut result = AnalysisResult::new();
         let files = self.find_files(dir_path)?;
@@ -379,9 +594,15 @@ impl PythonParser {
                 result.add_file(analysis);
             }
         }
+        self.detect_clones(&mut result);
         Ok(result)
     }
 
+    /// Report duplicated code within and across the files of `result`
+    pub fn detect_clones(&self, result: &mut AnalysisResult) {
+        self.clones.detect(result, &halstead::PYTHON);
+    }
+
     fn find_files(&self, dir_path: &Path) -> Result<Vec<PathBuf>> {
         let mut files = Vec::new();
         for entry in walkdir::WalkDir::new(dir_path)
@@ -400,9 +621,3 @@ impl PythonParser {
         Ok(files)
     }
 }
-
-impl Default for PythonParser {
-    fn default() -> Self {
-        Self::new()
-    }
-}
@@ -0,0 +1,486 @@
+//! Security rules for Python: code execution, shell commands, unsafe deserialization, SQL built from strings,
+//! disabled certificate checks, weak hashes, predictable tokens, assert-based auth checks and debug settings
+
+use crate::types::{Category, CodeIssue, Severity};
+use std::collections::HashMap;
+use std::path::Path;
+use tree_sitter::Node;
+
+const SUBPROCESS_FUNCTIONS: &[&str] = &[
+    "subprocess.run",
+    "subprocess.call",
+    "subprocess.check_call",
+    "subprocess.check_output",
+    "subprocess.Popen",
+];
+
+const SHELL_FUNCTIONS: &[&str] = &["os.system", "os.popen", "subprocess.getoutput", "subprocess.getstatusoutput"];
+
+const DESERIALIZERS: &[&str] = &[
+    "pickle.load",
+    "pickle.loads",
+    "cPickle.load",
+    "cPickle.loads",
+    "dill.load",
+    "dill.loads",
+    "marshal.load",
+    "marshal.loads",
+    "shelve.open",
+    "yaml.unsafe_load",
+    "yaml.full_load",
+];
+
+/// `yaml.load` is only safe with one of these loaders
+const SAFE_YAML_LOADERS: &[&str] = &["SafeLoader", "CSafeLoader", "BaseLoader"];
+
+/// Cursor and ORM methods that run a raw SQL string
+const SQL_METHODS: &[&str] = &["execute", "executemany", "executescript", "raw", "extra", "read_sql", "read_sql_query"];
+
+const WEAK_HASHES: &[&str] = &["md5", "sha1"];
+
+const RANDOM_FUNCTIONS: &[&str] = &[
+    "random.random",
+    "random.randint",
+    "random.randrange",
+    "random.choice",
+    "random.choices",
+    "random.sample",
+    "random.getrandbits",
+    "random.uniform",
+];
+
+/// Names that hold something an attacker must not be able to guess
+const SECRET_NAME_PARTS: &[&str] = &["token", "password", "passwd", "secret", "salt", "nonce", "otp", "csrf", "session", "api_key", "apikey", "reset_code"];
+
+/// Names that suggest an `assert` is guarding access
+const AUTH_NAME_PARTS: &[&str] = &[
+    "is_authenticated",
+    "is_admin",
+    "is_staff",
+    "is_superuser",
+    "has_perm",
+    "permission",
+    "authorized",
+    "authorised",
+    "logged_in",
+    "is_owner",
+    "role",
+];
+
+/// Run every security rule over a parsed Python file
+pub fn analyze_tree(root: Node, code: &str, file_path: &Path) -> Vec<CodeIssue> {
+    let imports = Imports::collect(root, code);
+    let mut issues = Vec::new();
+    let mut stack = vec![root];
+
+    while let Some(node) = stack.pop() {
+        match node.kind() {
+            "call" => check_call(node, code, file_path, &imports, &mut issues),
+            "assert_statement" => check_assert(node, code, file_path, &mut issues),
+            "expression_statement" if is_settings_file(file_path) => check_debug(node, code, file_path, &mut issues),
+            _ => {}
+        }
+
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.named_children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+
+    issues
+}
+
+/// Local names bound by imports, mapped to the dotted path they stand for
+struct Imports {
+    aliases: HashMap<String, String>,
+}
+
+impl Imports {
+    fn collect(root: Node, code: &str) -> Self {
+        let mut aliases = HashMap::new();
+        let mut cursor = root.walk();
+        let statements: Vec<Node> = root.named_children(&mut cursor).collect();
+
+        for statement in statements {
+            let module = match statement.kind() {
+                "import_statement" => None,
+                "import_from_statement" => match statement.child_by_field_name("module_name") {
+                    Some(module) => Some(text(module, code).to_string()),
+                    None => continue,
+                },
+                _ => continue,
+            };
+
+            let mut cursor = statement.walk();
+            let names: Vec<Node> = statement.children_by_field_name("name", &mut cursor).collect();
+            for name in names {
+                let (path, local) = match name.kind() {
+                    "aliased_import" => match (name.child_by_field_name("name"), name.child_by_field_name("alias")) {
+                        (Some(path), Some(alias)) => (text(path, code), text(alias, code)),
+                        _ => continue,
+                    },
+                    // `import os.path` binds `os`
+                    _ => (text(name, code), text(name, code).split('.').next().unwrap_or("")),
+                };
+                let full = match &module {
+                    Some(module) => format!("{}.{}", module, path),
+                    None if name.kind() == "aliased_import" => path.to_string(),
+                    None => local.to_string(),
+                };
+                aliases.insert(local.to_string(), full);
+            }
+        }
+
+        Self { aliases }
+    }
+
+    /// Dotted path of a callee with its first segment resolved through the imports
+    fn resolve(&self, callee: Node, code: &str) -> Option<String> {
+        let path = dotted_path(callee, code)?;
+        let (head, rest) = match path.split_once('.') {
+            Some((head, rest)) => (head, Some(rest)),
+            None => (path.as_str(), None),
+        };
+        let head = self.aliases.get(head).map_or(head, String::as_str);
+        Some(match rest {
+            Some(rest) => format!("{}.{}", head, rest),
+            None => head.to_string(),
+        })
+    }
+}
+
+fn text<'a>(node: Node, code: &'a str) -> &'a str {
+    node.utf8_text(code.as_bytes()).unwrap_or("")
+}
+
+fn dotted_path(node: Node, code: &str) -> Option<String> {
+    match node.kind() {
+        "identifier" => Some(text(node, code).to_string()),
+        "attribute" => {
+            let object = dotted_path(node.child_by_field_name("object")?, code)?;
+            let attribute = node.child_by_field_name("attribute")?;
+            Some(format!("{}.{}", object, text(attribute, code)))
+        }
+        _ => None,
+    }
+}
+
+fn keyword_argument<'tree>(call: Node<'tree>, code: &str, name: &str) -> Option<Node<'tree>> {
+    let arguments = call.child_by_field_name("arguments")?;
+    let mut cursor = arguments.walk();
+    let keyword = arguments.named_children(&mut cursor).find(|argument| {
+        argument.kind() == "keyword_argument"
+            && argument.child_by_field_name("name").is_some_and(|key| text(key, code) == name)
+    });
+    keyword.and_then(|keyword| keyword.child_by_field_name("value"))
+}
+
+fn first_argument(call: Node) -> Option<Node> {
+    let arguments = call.child_by_field_name("arguments")?;
+    let mut cursor = arguments.walk();
+    let first = arguments
+        .named_children(&mut cursor)
+        .find(|argument| argument.kind() != "keyword_argument" && argument.kind() != "comment");
+    first
+}
+
+/// A string literal without interpolation
+fn is_plain_string(node: Node) -> bool {
+    let mut cursor = node.walk();
+    let plain = node.kind() == "string" && !node.named_children(&mut cursor).any(|child| child.kind() == "interpolation");
+    plain
+}
+
+/// A string put together at run time: f-strings, `%` formatting, `.format()` and concatenation
+fn is_built_string(node: Node, code: &str) -> bool {
+    match node.kind() {
+        "string" => !is_plain_string(node),
+        "concatenated_string" => {
+            let mut cursor = node.walk();
+            let built = node.named_children(&mut cursor).any(|part| !is_plain_string(part));
+            built
+        }
+        "binary_operator" => {
+            let operator = node.child_by_field_name("operator").map(|operator| text(operator, code));
+            let (Some(left), Some(right)) = (node.child_by_field_name("left"), node.child_by_field_name("right")) else {
+                return false;
+            };
+            match operator {
+                Some("%") => is_plain_string(left) || is_built_string(left, code),
+                Some("+") => {
+                    let has_string = |side: Node| is_plain_string(side) || is_built_string(side, code);
+                    (has_string(left) || has_string(right)) && !(is_plain_string(left) && is_plain_string(right))
+                }
+                _ => false,
+            }
+        }
+        "call" => node
+            .child_by_field_name("function")
+            .filter(|function| function.kind() == "attribute")
+            .is_some_and(|function| {
+                let object = function.child_by_field_name("object");
+                let method = function.child_by_field_name("attribute").map(|attribute| text(attribute, code));
+                method == Some("format") && object.is_some_and(|object| object.kind() == "string")
+            }),
+        "parenthesized_expression" => node.named_child(0).is_some_and(|inner| is_built_string(inner, code)),
+        _ => false,
+    }
+}
+
+/// Whatever the identifier `name` was last assigned before `before`, in the same function
+fn last_assignment<'tree>(name: Node<'tree>, code: &str) -> Option<Node<'tree>> {
+    let target = text(name, code);
+    let mut scope = name.parent();
+    while let Some(node) = scope {
+        if matches!(node.kind(), "function_definition" | "module") {
+            break;
+        }
+        scope = node.parent();
+    }
+
+    let mut value = None;
+    let mut stack = vec![scope?];
+    while let Some(node) = stack.pop() {
+        if node.start_byte() >= name.start_byte() {
+            continue;
+        }
+        if node.kind() == "assignment"
+            && node.child_by_field_name("left").is_some_and(|left| text(left, code) == target)
+        {
+            if let Some(right) = node.child_by_field_name("right") {
+                if value.is_none_or(|previous: Node| previous.start_byte() < right.start_byte()) {
+                    value = Some(right);
+                }
+            }
+        }
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.named_children(&mut cursor).collect();
+        stack.extend(children.into_iter().filter(|child| child.kind() != "function_definition"));
+    }
+    value
+}
+
+fn is_false(node: Option<Node>) -> bool {
+    node.is_some_and(|node| node.kind() == "false")
+}
+
+fn is_true(node: Option<Node>) -> bool {
+    node.is_some_and(|node| node.kind() == "true")
+}
+
+fn check_call(call: Node, code: &str, file_path: &Path, imports: &Imports, issues: &mut Vec<CodeIssue>) {
+    let Some(function) = call.child_by_field_name("function") else {
+        return;
+    };
+    let callee = imports.resolve(function, code).unwrap_or_default();
+    let method = match function.kind() {
+        "attribute" => function.child_by_field_name("attribute").map(|attribute| text(attribute, code)),
+        _ => None,
+    };
+    let report = |issues: &mut Vec<CodeIssue>, message: String, rule: &str, severity: Severity| {
+        issues.push(issue(file_path, call, code, message, rule, severity));
+    };
+
+    if matches!(callee.as_str(), "eval" | "exec" | "builtins.eval" | "builtins.exec") {
+        report(
+            issues,
+            format!("{}() runs arbitrary code. Parse the input instead, e.g. with ast.literal_eval or json.loads.", callee.trim_start_matches("builtins.")),
+            "python-eval",
+            Severity::Error,
+        );
+        return;
+    }
+
+    let command_is_dynamic = first_argument(call).is_some_and(|command| !is_plain_string(command) && command.kind() != "list");
+    if SUBPROCESS_FUNCTIONS.contains(&callee.as_str()) && is_true(keyword_argument(call, code, "shell")) {
+        report(
+            issues,
+            format!("{}() with shell=True lets shell metacharacters in the command run as code. Pass an argument list without shell=True.", callee),
+            "python-shell-injection",
+            if command_is_dynamic { Severity::Error } else { Severity::Warning },
+        );
+        return;
+    }
+    if SHELL_FUNCTIONS.contains(&callee.as_str()) {
+        report(
+            issues,
+            format!("{}() runs its command through the shell. Use subprocess.run() with an argument list.", callee),
+            "python-shell-injection",
+            if command_is_dynamic { Severity::Error } else { Severity::Warning },
+        );
+        return;
+    }
+
+    if DESERIALIZERS.contains(&callee.as_str()) {
+        report(
+            issues,
+            format!("{}() can execute code hidden in the data. Never use it on untrusted input; prefer json.", callee),
+            "python-unsafe-deserialization",
+            Severity::Error,
+        );
+        return;
+    }
+    if matches!(callee.as_str(), "yaml.load" | "yaml.load_all") {
+        let loader = keyword_argument(call, code, "Loader").map(|loader| text(loader, code).rsplit('.').next().unwrap_or("").to_string());
+        if !loader.is_some_and(|loader| SAFE_YAML_LOADERS.contains(&loader.as_str())) {
+            report(
+                issues,
+                format!("{}() without Loader=yaml.SafeLoader can construct arbitrary Python objects. Use yaml.safe_load().", callee),
+                "python-unsafe-deserialization",
+                Severity::Error,
+            );
+        }
+        return;
+    }
+
+    if method.is_some_and(|method| SQL_METHODS.contains(&method)) {
+        if let Some(query) = first_argument(call) {
+            let built = match query.kind() {
+                "identifier" => last_assignment(query, code).is_some_and(|value| is_built_string(value, code)),
+                _ => is_built_string(query, code),
+            };
+            if built {
+                report(
+                    issues,
+                    "SQL query built with string formatting is open to SQL injection. Pass the values as query parameters.".to_string(),
+                    "python-sql-injection",
+                    Severity::Error,
+                );
+                return;
+            }
+        }
+    }
+
+    if is_false(keyword_argument(call, code, "verify")) {
+        report(
+            issues,
+            "verify=False turns off TLS certificate checks, so anyone on the network can intercept the request.".to_string(),
+            "python-insecure-request",
+            Severity::Error,
+        );
+        return;
+    }
+
+    let weak_hash = match callee.strip_prefix("hashlib.") {
+        Some("new") => first_argument(call)
+            .map(|name| text(name, code).trim_matches(&['\'', '"'][..]).to_lowercase())
+            .filter(|name| WEAK_HASHES.contains(&name.as_str())),
+        Some(name) if WEAK_HASHES.contains(&name) => Some(name.to_string()),
+        _ => None,
+    };
+    if let Some(name) = weak_hash {
+        if !is_false(keyword_argument(call, code, "usedforsecurity")) {
+            report(
+                issues,
+                format!("{} is broken for security purposes. Use hashlib.sha256 or better, or pass usedforsecurity=False for checksums.", name.to_uppercase()),
+                "python-weak-hash",
+                Severity::Warning,
+            );
+        }
+        return;
+    }
+
+    if RANDOM_FUNCTIONS.contains(&callee.as_str()) {
+        if let Some(name) = secret_destination(call, code) {
+            report(
+                issues,
+                format!("{}() is predictable and must not generate '{}'. Use the secrets module.", callee, name),
+                "python-insecure-random",
+                Severity::Warning,
+            );
+        }
+    }
+}
+
+/// Name of the variable, attribute, key or function that a value ends up in, if it looks like a secret
+fn secret_destination(node: Node, code: &str) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        let name = match parent.kind() {
+            "assignment" | "augmented_assignment" => parent.child_by_field_name("left").map(|left| text(left, code)),
+            "keyword_argument" => parent.child_by_field_name("name").map(|name| text(name, code)),
+            "pair" => parent.child_by_field_name("key").map(|key| text(key, code).trim_matches(&['\'', '"'][..])),
+            "return_statement" => {
+                let mut function = parent.parent();
+                while let Some(node) = function.filter(|node| node.kind() != "function_definition") {
+                    function = node.parent();
+                }
+                function.and_then(|function| function.child_by_field_name("name")).map(|name| text(name, code))
+            }
+            "function_definition" | "class_definition" | "module" => return None,
+            _ => None,
+        };
+        if let Some(name) = name {
+            let lower = name.to_lowercase();
+            return SECRET_NAME_PARTS
+                .iter()
+                .any(|part| lower.contains(part))
+                .then(|| name.to_string());
+        }
+        current = parent.parent();
+    }
+    None
+}
+
+fn check_assert(node: Node, code: &str, file_path: &Path, issues: &mut Vec<CodeIssue>) {
+    let Some(condition) = node.named_child(0) else {
+        return;
+    };
+    let condition_text = text(condition, code).to_lowercase();
+    if AUTH_NAME_PARTS.iter().any(|part| condition_text.contains(part)) {
+        issues.push(issue(
+            file_path,
+            node,
+            code,
+            "assert is removed when Python runs with -O, so this access check would silently disappear. Raise an exception instead.".to_string(),
+            "python-assert-auth",
+            Severity::Warning,
+        ));
+    }
+}
+
+/// Django-style settings modules
+fn is_settings_file(file_path: &Path) -> bool {
+    let in_settings_package = file_path
+        .parent()
+        .and_then(|parent| parent.file_name())
+        .is_some_and(|name| name == "settings");
+    let stem = file_path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("");
+    in_settings_package || stem == "settings" || stem.starts_with("settings_") || stem.ends_with("_settings")
+}
+
+fn check_debug(node: Node, code: &str, file_path: &Path, issues: &mut Vec<CodeIssue>) {
+    if node.parent().is_none_or(|parent| parent.kind() != "module") {
+        return;
+    }
+    let Some(assignment) = node.named_child(0).filter(|child| child.kind() == "assignment") else {
+        return;
+    };
+    let is_debug = assignment.child_by_field_name("left").is_some_and(|left| text(left, code) == "DEBUG");
+    if is_debug && is_true(assignment.child_by_field_name("right")) {
+        issues.push(issue(
+            file_path,
+            assignment,
+            code,
+            "DEBUG = True in a settings file exposes stack traces and settings to visitors. Read it from the environment and default to False.".to_string(),
+            "python-debug-enabled",
+            Severity::Warning,
+        ));
+    }
+}
+
+fn issue(file_path: &Path, node: Node, code: &str, message: String, rule: &str, severity: Severity) -> CodeIssue {
+    let start = node.start_position();
+    let end = node.end_position();
+    CodeIssue {
+        file_path: file_path.display().to_string(),
+        line: start.row + 1,
+        column: start.column + 1,
+        end_line: Some(end.row + 1),
+        end_column: Some(end.column + 1),
+        message,
+        severity,
+        category: Category::Security,
+        rule: rule.to_string(),
+        code_snippet: Some(text(node, code).to_string()),
+    }
+}
@@ -0,0 +1,221 @@
+//! Type-hint coverage: which functions annotate every parameter and their return type,
+//! rolled up per file and per package, and the rule that flags unannotated public functions
+
+use crate::metrics::round;
+use crate::types::{Category, CodeIssue, Severity};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tree_sitter::Node;
+
+/// Annotated functions in a file, a package or the whole run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TypeCoverage {
+    pub path: String,
+    pub functions: usize,
+    /// Functions with every parameter and the return type annotated
+    pub annotated: usize,
+    /// Percentage of annotated functions; 100 when there are none
+    pub coverage: f64,
+}
+
+impl TypeCoverage {
+    fn new(path: String) -> Self {
+        Self {
+            path,
+            coverage: 100.0,
+            ..Self::default()
+        }
+    }
+
+    fn add(&mut self, functions: usize, annotated: usize) {
+        self.functions += functions;
+        self.annotated += annotated;
+        self.coverage = if self.functions == 0 {
+            100.0
+        } else {
+            round(self.annotated as f64 * 100.0 / self.functions as f64)
+        };
+    }
+}
+
+/// Everything the `type-coverage` subcommand reports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TypeCoverageReport {
+    pub total: TypeCoverage,
+    /// Every directory between the files and the analyzed root
+    pub packages: Vec<TypeCoverage>,
+    pub files: Vec<TypeCoverage>,
+}
+
+impl TypeCoverageReport {
+    /// Build the report, rolling files up into every directory between them and `root`
+    pub fn new(root: &Path, mut files: Vec<TypeCoverage>) -> Self {
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let mut total = TypeCoverage::new(root.display().to_string());
+        let mut packages: BTreeMap<String, TypeCoverage> = BTreeMap::new();
+        for file in &files {
+            total.add(file.functions, file.annotated);
+            for directory in Path::new(&file.path).ancestors().skip(1) {
+                let path = directory.display().to_string();
+                if path.is_empty() {
+                    break;
+                }
+                packages
+                    .entry(path.clone())
+                    .or_insert_with(|| TypeCoverage::new(path))
+                    .add(file.functions, file.annotated);
+                if directory == root {
+                    break;
+                }
+            }
+        }
+
+        Self {
+            total,
+            packages: packages.into_values().collect(),
+            files,
+        }
+    }
+}
+
+/// A `def` and the annotations it lacks
+struct FunctionHints<'tree> {
+    node: Node<'tree>,
+    name: String,
+    missing_parameters: Vec<String>,
+    missing_return: bool,
+    public: bool,
+}
+
+impl FunctionHints<'_> {
+    fn annotated(&self) -> bool {
+        self.missing_parameters.is_empty() && !self.missing_return
+    }
+}
+
+fn text<'a>(node: Node, code: &'a str) -> &'a str {
+    node.utf8_text(code.as_bytes()).unwrap_or("")
+}
+
+/// Every function in the file, in source order
+fn functions<'tree>(root: Node<'tree>, code: &str) -> Vec<FunctionHints<'tree>> {
+    let mut functions = Vec::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "function_definition" {
+            functions.push(function_hints(node, code));
+        }
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.named_children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+    functions
+}
+
+fn function_hints<'tree>(function: Node<'tree>, code: &str) -> FunctionHints<'tree> {
+    let name = function
+        .child_by_field_name("name")
+        .map(|name| text(name, code).to_string())
+        .unwrap_or_default();
+
+    // Functions are public unless underscored or local to another function
+    let mut enclosing = function.parent();
+    let mut in_class = false;
+    let mut nested = false;
+    while let Some(parent) = enclosing {
+        match parent.kind() {
+            "class_definition" => {
+                in_class = true;
+                break;
+            }
+            "function_definition" => {
+                nested = true;
+                break;
+            }
+            _ => enclosing = parent.parent(),
+        }
+    }
+
+    let mut missing_parameters = Vec::new();
+    if let Some(parameters) = function.child_by_field_name("parameters") {
+        let mut cursor = parameters.walk();
+        for (index, parameter) in parameters.named_children(&mut cursor).enumerate() {
+            let unannotated = match parameter.kind() {
+                "identifier" => Some(text(parameter, code)),
+                "default_parameter" => parameter.child_by_field_name("name").map(|name| text(name, code)),
+                "list_splat_pattern" | "dictionary_splat_pattern" => Some(text(parameter, code)),
+                _ => None,
+            };
+            match unannotated {
+                // The receiver of a method is typed by its class
+                Some("self" | "cls") if index == 0 && in_class => {}
+                Some(parameter) => missing_parameters.push(parameter.to_string()),
+                None => {}
+            }
+        }
+    }
+
+    // `__init__` always returns None, so mypy does not require the annotation
+    let missing_return = function.child_by_field_name("return_type").is_none() && name != "__init__";
+
+    FunctionHints {
+        node: function,
+        public: !name.starts_with('_') && !nested,
+        name,
+        missing_parameters,
+        missing_return,
+    }
+}
+
+/// Counts for one file
+pub fn file_coverage(root: Node, code: &str, file_path: &Path) -> TypeCoverage {
+    let functions = functions(root, code);
+    let annotated = functions.iter().filter(|function| function.annotated()).count();
+    let mut coverage = TypeCoverage::new(file_path.display().to_string());
+    coverage.add(functions.len(), annotated);
+    coverage
+}
+
+/// Flag public functions without complete annotations
+pub fn analyze_tree(root: Node, code: &str, file_path: &Path) -> Vec<CodeIssue> {
+    let mut issues = Vec::new();
+    for function in functions(root, code) {
+        if !function.public || function.annotated() {
+            continue;
+        }
+
+        let mut missing = Vec::new();
+        if !function.missing_parameters.is_empty() {
+            missing.push(format!("parameter(s) {}", function.missing_parameters.join(", ")));
+        }
+        if function.missing_return {
+            missing.push("the return type".to_string());
+        }
+
+        let start = function.node.start_position();
+        let name_end = function
+            .node
+            .child_by_field_name("name")
+            .map(|name| name.end_position())
+            .unwrap_or(start);
+        issues.push(CodeIssue {
+            file_path: file_path.display().to_string(),
+            line: start.row + 1,
+            column: start.column + 1,
+            end_line: Some(name_end.row + 1),
+            end_column: Some(name_end.column + 1),
+            message: format!(
+                "Public function '{}' has no type hints for {}.",
+                function.name,
+                missing.join(" and ")
+            ),
+            severity: Severity::Suggestion,
+            category: Category::BestPractice,
+            rule: "python-missing-type-hints".to_string(),
+            code_snippet: Some(format!("def {}(...)", function.name)),
+        });
+    }
+    issues
+}
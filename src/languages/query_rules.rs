@@ -0,0 +1,152 @@
+//! Project rules from the `rules` section of the config, run by the tree-sitter front ends with
+//! the same `Query`/`QueryCursor` machinery as their built-in captures.
+
+use super::grammar::Grammar;
+use crate::config::RuleConfig;
+use crate::error::{AnalyzerError, Result};
+use crate::types::{Category, CodeIssue, Severity};
+use std::path::Path;
+use tree_sitter::{Language, Node, Query, QueryCursor, QueryMatch};
+
+/// A compiled project rule
+struct QueryRule {
+    id: String,
+    query: Query,
+    capture: u32,
+    message: String,
+    severity: Severity,
+    category: Category,
+}
+
+/// The project rules of one front end
+pub struct QueryRules {
+    rules: Vec<QueryRule>,
+}
+
+/// Whether one `--test-rule` snippet behaved as declared
+#[derive(Debug, Clone)]
+pub struct ExampleOutcome {
+    /// `true` for an `invalid` snippet, which must be reported
+    pub invalid: bool,
+    /// Position in the `invalid` or `valid` list
+    pub index: usize,
+    /// Issues the rule reported for the snippet
+    pub reported: usize,
+}
+
+impl ExampleOutcome {
+    pub fn passed(&self) -> bool {
+        self.invalid == (self.reported > 0)
+    }
+}
+
+impl QueryRules {
+    /// Compile the rules declared for the grammar's language
+    pub fn compile(grammar: &Grammar, rules: &[RuleConfig]) -> Result<Self> {
+        let rules = rules
+            .iter()
+            .filter(|rule| rule.language.eq_ignore_ascii_case(grammar.name()))
+            .map(|rule| QueryRule::compile(grammar.language(), rule))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { rules })
+    }
+
+    /// Report every match of every rule in a parsed file
+    pub fn check(&self, root: Node, code: &str, file_path: &Path) -> Vec<CodeIssue> {
+        let mut issues = Vec::new();
+        let mut cursor = QueryCursor::new();
+        for rule in &self.rules {
+            for m in cursor.matches(&rule.query, root, code.as_bytes()) {
+                if let Some(issue) = rule.issue(&m, code, file_path) {
+                    issues.push(issue);
+                }
+            }
+        }
+        issues
+    }
+}
+
+impl QueryRule {
+    fn compile(language: &Language, config: &RuleConfig) -> Result<Self> {
+        let error = |message: String| AnalyzerError::RuleError {
+            rule: config.id.clone(),
+            message,
+        };
+        let query = Query::new(language, &config.query).map_err(|e| error(e.to_string()))?;
+        let capture = query
+            .capture_index_for_name(&config.capture)
+            .ok_or_else(|| error(format!("the query has no capture named '@{}'", config.capture)))?;
+        Ok(Self {
+            id: config.id.clone(),
+            query,
+            capture,
+            message: config.message.clone(),
+            severity: config.severity,
+            category: config.category.clone(),
+        })
+    }
+
+    fn issue(&self, m: &QueryMatch, code: &str, file_path: &Path) -> Option<CodeIssue> {
+        let node = m.nodes_for_capture_index(self.capture).next()?;
+        let text = |node: Node| node.utf8_text(code.as_bytes()).unwrap_or_default().to_string();
+
+        let mut message = self.message.clone();
+        for (index, name) in self.query.capture_names().iter().enumerate() {
+            let placeholder = format!("{{{}}}", name);
+            if message.contains(&placeholder) {
+                let value = m.nodes_for_capture_index(index as u32).next().map(text).unwrap_or_default();
+                message = message.replace(&placeholder, &value);
+            }
+        }
+
+        let start = node.start_position();
+        let end = node.end_position();
+        Some(CodeIssue {
+            file_path: file_path.display().to_string(),
+            line: start.row + 1,
+            column: start.column + 1,
+            end_line: Some(end.row + 1),
+            end_column: Some(end.column + 1),
+            message,
+            severity: self.severity,
+            category: self.category.clone(),
+            rule: self.id.clone(),
+            code_snippet: Some(text(node)),
+        })
+    }
+}
+
+/// The grammar a rule's `language` names, without any built-in query
+fn grammar(language: &str) -> Result<Grammar> {
+    match language {
+        "kotlin" => Grammar::new("Kotlin", tree_sitter_kotlin::language(), ""),
+        "dart" => Grammar::new("Dart", tree_sitter_dart::language(), ""),
+        "python" => Grammar::new("Python", tree_sitter_python::language(), ""),
+        other => Err(AnalyzerError::GrammarError {
+            language: other.to_string(),
+            message: "no tree-sitter front end for this language".to_string(),
+        }),
+    }
+}
+
+/// Run a rule against its `invalid` and `valid` snippets
+pub fn test_examples(rule: &RuleConfig) -> Result<Vec<ExampleOutcome>> {
+    let grammar = grammar(&rule.language)?;
+    let rules = QueryRules::compile(&grammar, std::slice::from_ref(rule))?;
+
+    let snippets = rule
+        .invalid
+        .iter()
+        .enumerate()
+        .map(|(index, code)| (true, index, code))
+        .chain(rule.valid.iter().enumerate().map(|(index, code)| (false, index, code)));
+
+    snippets
+        .map(|(invalid, index, code)| {
+            let file_path = Path::new(&rule.id);
+            let tree = grammar.parse(code, file_path)?;
+            let reported = rules.check(tree.root_node(), code, file_path).len();
+            Ok(ExampleOutcome { invalid, index, reported })
+        })
+        .collect()
+}
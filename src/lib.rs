@@ -1,11 +1,19 @@
 // Library exports for testing and external use
 
 pub mod languages;
+pub mod cfg;
+pub mod clones;
+pub mod cognitive;
+pub mod config;
 pub mod error;
+pub mod metrics;
 pub mod output;
+pub mod plugins;
+pub mod secrets;
 pub mod types;
 
 // Re-export commonly used types
+pub use config::Config;
 pub use error::{AnalyzerError, Result};
 pub use languages::javascript::JsParser;
 pub use languages::kotlin::KotlinParser;
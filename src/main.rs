@@ -1,19 +1,32 @@
 use anyhow::Result;
-use clap::Parser;
-use std::path::PathBuf;
+use clap::{Parser, Subcommand};
+use std::path::{Path, PathBuf};
 
 mod languages;
+mod cfg;
+mod clones;
+mod cognitive;
+mod config;
 mod error;
+mod metrics;
 mod output;
+mod plugins;
+mod secrets;
 mod types;
 
+use config::Config;
 use error::AnalyzerError;
+use metrics::{FileMetrics, MetricsReport};
+use output::metrics::MetricsFormatter;
+use output::type_coverage::TypeCoverageFormatter;
 use output::OutputFormatter;
 use languages::javascript::JsParser;
 use languages::kotlin::KotlinParser;
 use languages::dart::DartParser;
 use languages::python::PythonParser;
-use types::{OutputFormat, Language};
+use languages::python::type_hints::TypeCoverageReport;
+use languages::query_rules;
+use types::{OutputFormat, Language, MetricsFormat};
 
 /// JavaScript/TypeScript AST Analyzer for Code Quality
 #[derive(Parser, Debug)]
@@ -21,10 +34,14 @@ use types::{OutputFormat, Language};
 #[command(author = "Your Name")]
 #[command(version = "0.1.0")]
 #[command(about = "Analyze JavaScript/TypeScript code for quality and security issues", long_about = None)]
+#[command(subcommand_negates_reqs = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Path to file or directory to analyze
-    #[arg(value_name = "PATH")]
-    path: PathBuf,
+    #[arg(value_name = "PATH", required_unless_present = "test_rule")]
+    path: Option<PathBuf>,
 
     /// Output format
     #[arg(short, long, value_enum, default_value_t = OutputFormat::Human)]
@@ -41,60 +58,133 @@ struct Args {
     /// Filter issues by severity (error, warning, suggestion)
     #[arg(short = 'S', long)]
     severity: Option<String>,
+
+    /// Path to a JSON config file (defaults to .js-ast-analyzer.json in the working directory)
+    #[arg(short, long, value_name = "FILE", global = true)]
+    config: Option<PathBuf>,
+
+    /// Check the config's query rules (or only the one with this id) against their `invalid` and `valid` snippets
+    #[arg(long, value_name = "ID", num_args = 0..=1)]
+    test_rule: Option<Option<String>>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Report size and complexity metrics for every file and function
+    Metrics {
+        /// Path to file or directory to measure
+        #[arg(value_name = "PATH")]
+        path: PathBuf,
+
+        /// Programming language to measure
+        #[arg(short, long, value_enum, default_value_t = Language::Javascript)]
+        language: Language,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = MetricsFormat::Human)]
+        format: MetricsFormat,
+
+        /// Number of worst functions listed in the human output
+        #[arg(short, long, default_value_t = 10)]
+        top: usize,
+    },
+    /// Report the share of Python functions with complete type hints, per file and per package
+    TypeCoverage {
+        /// Path to file or directory to measure
+        #[arg(value_name = "PATH")]
+        path: PathBuf,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = MetricsFormat::Human)]
+        format: MetricsFormat,
+
+        /// Exit with error code if the total coverage is below this percentage (overrides `python.min_type_coverage`)
+        #[arg(short, long, value_name = "PERCENT")]
+        min_coverage: Option<f64>,
+    },
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
 
+    match &args.command {
+        Some(Command::Metrics { path, language, format, top }) => {
+            let config = Config::resolve(args.config.as_deref())?;
+            return run_metrics(&config, path, *language, *format, *top);
+        }
+        Some(Command::TypeCoverage { path, format, min_coverage }) => {
+            let config = Config::resolve(args.config.as_deref())?;
+            return run_type_coverage(path, *format, min_coverage.or(config.python.min_type_coverage));
+        }
+        None => {}
+    }
+
+    if let Some(id) = &args.test_rule {
+        let config = Config::resolve(args.config.as_deref())?;
+        return run_rule_tests(&config, id.as_deref());
+    }
+
+    let path = args.path.expect("PATH is required without a subcommand");
+
     // Validate path exists
-    if !args.path.exists() {
-        return Err(AnalyzerError::InvalidPath(args.path.display().to_string()).into());
+    if !path.exists() {
+        return Err(AnalyzerError::InvalidPath(path.display().to_string()).into());
     }
 
+    let config = Config::resolve(args.config.as_deref())?;
+    let mut type_coverage = None;
+
     // Analyze based on language
     let result = match args.language {
         Language::Javascript | Language::Typescript => {
-            let parser = JsParser::new();
-            if args.path.is_file() {
-                let file_analysis = parser.analyze_file(&args.path)?;
+            let parser = JsParser::with_config(&config);
+            if path.is_file() {
+                let file_analysis = parser.analyze_file(&path)?;
                 let mut analysis_result = types::AnalysisResult::new();
                 analysis_result.add_file(file_analysis);
+                parser.detect_clones(&mut analysis_result);
                 analysis_result
             } else {
-                parser.analyze_directory(&args.path)?
+                parser.analyze_directory(&path)?
             }
         },
         Language::Kotlin => {
-            let parser = KotlinParser::new();
-            if args.path.is_file() {
-                let file_analysis = parser.analyze_file(&args.path)?;
+            let parser = KotlinParser::with_config(&config)?;
+            if path.is_file() {
+                let file_analysis = parser.analyze_file(&path)?;
                 let mut analysis_result = types::AnalysisResult::new();
                 analysis_result.add_file(file_analysis);
+                parser.detect_clones(&mut analysis_result);
                 analysis_result
             } else {
-                parser.analyze_directory(&args.path)?
+                parser.analyze_directory(&path)?
             }
         },
         Language::Dart => {
-            let parser = DartParser::new();
-            if args.path.is_file() {
-                let file_analysis = parser.analyze_file(&args.path)?;
+            let parser = DartParser::with_config(&config)?;
+            if path.is_file() {
+                let file_analysis = parser.analyze_file(&path)?;
                 let mut analysis_result = types::AnalysisResult::new();
                 analysis_result.add_file(file_analysis);
+                parser.detect_clones(&mut analysis_result);
                 analysis_result
             } else {
-                parser.analyze_directory(&args.path)?
+                parser.analyze_directory(&path)?
             }
         },
         Language::Python => {
-            let parser = PythonParser::new();
-            if args.path.is_file() {
-                let file_analysis = parser.analyze_file(&args.path)?;
+            let parser = PythonParser::with_config(&config)?;
+            if let Some(min_coverage) = config.python.min_type_coverage {
+                type_coverage = Some((type_coverage_report(&parser, &path)?, min_coverage));
+            }
+            if path.is_file() {
+                let file_analysis = parser.analyze_file(&path)?;
                 let mut analysis_result = types::AnalysisResult::new();
                 analysis_result.add_file(file_analysis);
+                parser.detect_clones(&mut analysis_result);
                 analysis_result
             } else {
-                parser.analyze_directory(&args.path)?
+                parser.analyze_directory(&path)?
             }
         },
         _ => {
@@ -106,6 +196,11 @@ fn main() -> Result<()> {
     // Print results
     OutputFormatter::print(&result, args.format);
 
+    // `python.min_type_coverage` gates the analysis run as well as `type-coverage`
+    if let Some((report, min_coverage)) = type_coverage {
+        check_type_coverage(&report, min_coverage);
+    }
+
     // Exit code for strict mode
     if args.strict && result.summary.total > 0 {
         std::process::exit(1);
@@ -113,3 +208,123 @@ fn main() -> Result<()> {
 
     Ok(())
 }
+
+fn run_metrics(config: &Config, path: &Path, language: Language, format: MetricsFormat, top: usize) -> Result<()> {
+    if !path.exists() {
+        return Err(AnalyzerError::InvalidPath(path.display().to_string()).into());
+    }
+
+    let files: Vec<FileMetrics> = match language {
+        Language::Javascript | Language::Typescript => {
+            let parser = JsParser::with_config(config);
+            if path.is_file() {
+                vec![parser.metrics_file(path)?]
+            } else {
+                parser.metrics_directory(path)?
+            }
+        },
+        Language::Kotlin => {
+            let parser = KotlinParser::with_config(config)?;
+            if path.is_file() {
+                vec![parser.metrics_file(path)?]
+            } else {
+                parser.metrics_directory(path)?
+            }
+        },
+        Language::Dart => {
+            let parser = DartParser::with_config(config)?;
+            if path.is_file() {
+                vec![parser.metrics_file(path)?]
+            } else {
+                parser.metrics_directory(path)?
+            }
+        },
+        Language::Python => {
+            let parser = PythonParser::with_config(config)?;
+            if path.is_file() {
+                vec![parser.metrics_file(path)?]
+            } else {
+                parser.metrics_directory(path)?
+            }
+        },
+    };
+
+    let root = if path.is_file() { path.parent().unwrap_or(path) } else { path };
+    let report = MetricsReport::new(root, files);
+    MetricsFormatter::print(&report, format, top);
+
+    Ok(())
+}
+
+fn run_type_coverage(path: &Path, format: MetricsFormat, min_coverage: Option<f64>) -> Result<()> {
+    if !path.exists() {
+        return Err(AnalyzerError::InvalidPath(path.display().to_string()).into());
+    }
+
+    let report = type_coverage_report(&PythonParser::new()?, path)?;
+    TypeCoverageFormatter::print(&report, format);
+
+    if let Some(min_coverage) = min_coverage {
+        check_type_coverage(&report, min_coverage);
+    }
+
+    Ok(())
+}
+
+fn type_coverage_report(parser: &PythonParser, path: &Path) -> Result<TypeCoverageReport> {
+    let files = if path.is_file() {
+        vec![parser.type_coverage_file(path)?]
+    } else {
+        parser.type_coverage_directory(path)?
+    };
+
+    let root = if path.is_file() { path.parent().unwrap_or(path) } else { path };
+    Ok(TypeCoverageReport::new(root, files))
+}
+
+/// Exit with code 1 when the total type hint coverage is below `min_coverage`
+fn check_type_coverage(report: &TypeCoverageReport, min_coverage: f64) {
+    if report.total.coverage < min_coverage {
+        eprintln!(
+            "Type hint coverage {}% is below the required {}%",
+            report.total.coverage, min_coverage
+        );
+        std::process::exit(1);
+    }
+}
+
+fn run_rule_tests(config: &Config, id: Option<&str>) -> Result<()> {
+    let rules: Vec<_> = config.rules.iter().filter(|rule| id.is_none_or(|id| rule.id == id)).collect();
+    if rules.is_empty() {
+        anyhow::bail!(match id {
+            Some(id) => format!("No rule with id '{}' in the config", id),
+            None => "The config declares no rules".to_string(),
+        });
+    }
+
+    let mut failed = 0;
+    for rule in rules {
+        let outcomes = query_rules::test_examples(rule)?;
+        let failures: Vec<_> = outcomes.iter().filter(|outcome| !outcome.passed()).collect();
+        if failures.is_empty() {
+            println!("PASS {} ({} snippets)", rule.id, outcomes.len());
+            continue;
+        }
+
+        failed += 1;
+        println!("FAIL {}", rule.id);
+        for outcome in failures {
+            if outcome.invalid {
+                println!("  invalid[{}] was not reported", outcome.index);
+            } else {
+                println!("  valid[{}] was reported {} time(s)", outcome.index, outcome.reported);
+            }
+        }
+    }
+
+    if failed > 0 {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,269 @@
+//! A small lexer shared by all languages for line counts, Halstead measures and clone detection.
+//! It only needs to tell comments, literals, identifiers, keywords and punctuation apart.
+
+use super::LocMetrics;
+use std::collections::{BTreeSet, HashMap};
+
+/// Operators recognized by longest match; everything else is a single character
+const MULTI_CHAR_OPERATORS: &[&str] = &[
+    ">>>=", "===", "!==", "...", "**=", "<<=", ">>=", ">>>", "&&=", "||=", "??=", "==", "!=", "<=", ">=", "&&",
+    "||", "??", "?.", "?:", "!!", "=>", "->", "::", "++", "--", "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=",
+    "**", "<<", ">>", "..", "//",
+];
+
+/// Comment and string syntax plus keywords of a language
+pub struct Lexicon {
+    pub line_comments: &'static [&'static str],
+    pub block_comment: Option<(&'static str, &'static str)>,
+    /// Quote characters that start a string literal
+    pub quotes: &'static [char],
+    /// Whether `"""`/`'''` start a multi-line string
+    pub triple_quotes: bool,
+    /// Keywords count as operators; literal keywords (`true`, `null`, `self`...) are left out so they count as operands
+    pub keywords: &'static [&'static str],
+}
+
+pub const JAVASCRIPT: Lexicon = Lexicon {
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\'', '`'],
+    triple_quotes: false,
+    keywords: &[
+        "async", "await", "break", "case", "catch", "class", "const", "continue", "debugger", "default", "delete",
+        "do", "else", "enum", "export", "extends", "finally", "for", "from", "function", "if", "import", "in",
+        "instanceof", "interface", "let", "new", "of", "return", "static", "switch", "throw", "try", "type",
+        "typeof", "var", "void", "while", "with", "yield", "as", "implements", "private", "protected", "public",
+        "readonly",
+    ],
+};
+
+pub const PYTHON: Lexicon = Lexicon {
+    line_comments: &["#"],
+    block_comment: None,
+    quotes: &['"', '\''],
+    triple_quotes: true,
+    keywords: &[
+        "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif", "else",
+        "except", "finally", "for", "from", "global", "if", "import", "in", "is", "lambda", "nonlocal", "not", "or",
+        "pass", "raise", "return", "try", "while", "with", "yield", "match", "case",
+    ],
+};
+
+pub const KOTLIN: Lexicon = Lexicon {
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\''],
+    triple_quotes: true,
+    keywords: &[
+        "as", "break", "by", "catch", "class", "companion", "const", "continue", "data", "do", "else", "enum",
+        "finally", "for", "fun", "if", "import", "in", "init", "interface", "is", "lateinit", "object", "open",
+        "override", "package", "private", "protected", "public", "return", "sealed", "suspend", "throw", "try",
+        "typealias", "val", "var", "when", "where", "while", "internal",
+    ],
+};
+
+pub const DART: Lexicon = Lexicon {
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\''],
+    triple_quotes: true,
+    keywords: &[
+        "abstract", "as", "assert", "async", "await", "break", "case", "catch", "class", "const", "continue",
+        "default", "do", "else", "enum", "export", "extends", "factory", "final", "finally", "for", "if",
+        "implements", "import", "in", "is", "late", "library", "mixin", "new", "on", "part", "required", "rethrow",
+        "return", "static", "switch", "throw", "try", "var", "void", "while", "with", "yield",
+    ],
+};
+
+/// What a token is, as far as line counts, Halstead measures and clone detection care
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Comment,
+    Keyword,
+    Identifier,
+    /// String and number literals
+    Literal,
+    /// Operators and punctuation, including brackets
+    Operator,
+}
+
+/// A lexed token; `line` and `end_line` are 0-based and differ for multi-line strings and comments
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub text: String,
+    pub line: usize,
+    pub end_line: usize,
+}
+
+/// Line counts and Halstead operator/operand tallies of a piece of source
+#[derive(Debug, Clone, Default)]
+pub struct TextMetrics {
+    pub physical: usize,
+    pub comment: usize,
+    pub blank: usize,
+    operators: HashMap<String, usize>,
+    operands: HashMap<String, usize>,
+}
+
+impl TextMetrics {
+    /// Line counts; `logical` comes from the AST
+    pub fn loc(&self, logical: usize) -> LocMetrics {
+        LocMetrics {
+            physical: self.physical,
+            logical,
+            comment: self.comment,
+            blank: self.blank,
+        }
+    }
+
+    /// Halstead volume `N * log2(n)`
+    pub fn volume(&self) -> f64 {
+        let total = self.operators.values().sum::<usize>() + self.operands.values().sum::<usize>();
+        let vocabulary = self.operators.len() + self.operands.len();
+        if vocabulary < 2 {
+            return 0.0;
+        }
+        total as f64 * (vocabulary as f64).log2()
+    }
+}
+
+/// Lex `text` and collect its line counts and Halstead tallies
+pub fn measure(text: &str, lexicon: &Lexicon) -> TextMetrics {
+    let mut metrics = TextMetrics {
+        physical: text.lines().count(),
+        ..TextMetrics::default()
+    };
+    let mut comment_lines = BTreeSet::new();
+    let mut code_lines = BTreeSet::new();
+
+    for token in tokenize(text, lexicon) {
+        let lines = token.line..=token.end_line;
+        match token.kind {
+            TokenKind::Comment => comment_lines.extend(lines),
+            TokenKind::Identifier | TokenKind::Literal => {
+                code_lines.extend(lines);
+                *metrics.operands.entry(token.text).or_insert(0) += 1;
+            }
+            TokenKind::Keyword | TokenKind::Operator => {
+                code_lines.extend(lines);
+                // Closing brackets are counted with their opening half
+                if !matches!(token.text.as_str(), ")" | "]" | "}") {
+                    *metrics.operators.entry(token.text).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+
+    metrics.comment = comment_lines.len();
+    metrics.blank = (0..metrics.physical)
+        .filter(|line| !comment_lines.contains(line) && !code_lines.contains(line))
+        .count();
+    metrics
+}
+
+/// Split `text` into tokens; whitespace is dropped and each comment becomes a single token
+pub fn tokenize(text: &str, lexicon: &Lexicon) -> Vec<Token> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 0;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c == '\n' {
+            line += 1;
+            i += 1;
+            continue;
+        }
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        let start_line = line;
+
+        let kind = if let Some(marker) = lexicon.line_comments.iter().find(|marker| starts_with(&chars, i, marker)) {
+            i += marker.len();
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            TokenKind::Comment
+        } else if let Some((open, close)) = lexicon.block_comment.filter(|(open, _)| starts_with(&chars, i, open)) {
+            i += open.len();
+            while i < chars.len() && !starts_with(&chars, i, close) {
+                if chars[i] == '\n' {
+                    line += 1;
+                }
+                i += 1;
+            }
+            i = (i + close.len()).min(chars.len());
+            TokenKind::Comment
+        } else if lexicon.quotes.contains(&c) {
+            let triple = lexicon.triple_quotes && chars.get(i + 1) == Some(&c) && chars.get(i + 2) == Some(&c);
+            let quote_len = if triple { 3 } else { 1 };
+            i += quote_len;
+            while i < chars.len() {
+                if chars[i] == '\\' {
+                    i += 2;
+                    continue;
+                }
+                if chars[i] == '\n' {
+                    // Only triple-quoted strings and template literals span lines
+                    if !triple && c != '`' {
+                        break;
+                    }
+                    line += 1;
+                }
+                if chars[i] == c && (!triple || (chars.get(i + 1) == Some(&c) && chars.get(i + 2) == Some(&c))) {
+                    i += quote_len;
+                    break;
+                }
+                i += 1;
+            }
+            i = i.min(chars.len());
+            TokenKind::Literal
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '.' || chars[i] == '_') {
+                i += 1;
+            }
+            TokenKind::Literal
+        } else if c.is_alphabetic() || c == '_' || c == '$' {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '$') {
+                i += 1;
+            }
+            // String prefixes like Python's f"..." or Dart's r'...' belong to the literal that follows
+            if i - start <= 2 && chars.get(i).is_some_and(|next| lexicon.quotes.contains(next)) {
+                continue;
+            }
+            let word: String = chars[start..i].iter().collect();
+            if lexicon.keywords.contains(&word.as_str()) {
+                TokenKind::Keyword
+            } else {
+                TokenKind::Identifier
+            }
+        } else {
+            let length = MULTI_CHAR_OPERATORS
+                .iter()
+                .find(|operator| starts_with(&chars, i, operator))
+                .map_or(1, |operator| operator.chars().count());
+            i += length;
+            TokenKind::Operator
+        };
+
+        tokens.push(Token {
+            kind,
+            text: chars[start..i].iter().collect(),
+            line: start_line,
+            end_line: line,
+        });
+    }
+
+    tokens
+}
+
+fn starts_with(chars: &[char], i: usize, pattern: &str) -> bool {
+    pattern.chars().enumerate().all(|(offset, c)| chars.get(i + offset) == Some(&c))
+}
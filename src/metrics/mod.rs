@@ -0,0 +1,185 @@
+//! Per-function, per-file and per-directory code metrics for the `metrics` subcommand
+
+pub mod halstead;
+pub mod tree;
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Line counts
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct LocMetrics {
+    /// Lines in the source, including blank and comment lines
+    pub physical: usize,
+    /// Statements
+    pub logical: usize,
+    /// Lines that contain a comment
+    pub comment: usize,
+    /// Lines with nothing but whitespace
+    pub blank: usize,
+}
+
+impl LocMetrics {
+    fn add(&mut self, other: &LocMetrics) {
+        self.physical += other.physical;
+        self.logical += other.logical;
+        self.comment += other.comment;
+        self.blank += other.blank;
+    }
+}
+
+/// Metrics of a single function, method, lambda or closure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionMetrics {
+    pub name: String,
+    pub line: usize,
+    pub end_line: usize,
+    pub loc: LocMetrics,
+    pub params: usize,
+    pub cyclomatic: usize,
+    pub cognitive: usize,
+    pub max_nesting: usize,
+    pub halstead_volume: f64,
+    pub maintainability_index: f64,
+}
+
+/// Metrics of a file; complexity values are summed over its functions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileMetrics {
+    pub file_path: String,
+    pub loc: LocMetrics,
+    pub cyclomatic: usize,
+    pub cognitive: usize,
+    pub max_nesting: usize,
+    pub halstead_volume: f64,
+    pub maintainability_index: f64,
+    pub functions: Vec<FunctionMetrics>,
+}
+
+impl FileMetrics {
+    /// Roll the functions of a file up; `loc` and `halstead_volume` are measured over the whole file
+    pub fn new(file_path: &Path, loc: LocMetrics, halstead_volume: f64, mut functions: Vec<FunctionMetrics>) -> Self {
+        functions.sort_by_key(|function| function.line);
+        let cyclomatic = functions.iter().map(|function| function.cyclomatic).sum::<usize>();
+        let cognitive = functions.iter().map(|function| function.cognitive).sum();
+        let max_nesting = functions.iter().map(|function| function.max_nesting).max().unwrap_or(0);
+
+        Self {
+            file_path: file_path.display().to_string(),
+            loc,
+            cyclomatic,
+            cognitive,
+            max_nesting,
+            halstead_volume: round(halstead_volume),
+            maintainability_index: maintainability_index(halstead_volume, cyclomatic.max(1), loc.logical),
+            functions,
+        }
+    }
+}
+
+/// Totals for a directory and everything below it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectoryMetrics {
+    pub path: String,
+    pub files: usize,
+    pub functions: usize,
+    pub loc: LocMetrics,
+    pub cyclomatic: usize,
+    pub cognitive: usize,
+    pub max_cognitive: usize,
+    /// Mean of the files' maintainability index
+    pub maintainability_index: f64,
+}
+
+/// Everything the `metrics` subcommand reports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsReport {
+    pub files: Vec<FileMetrics>,
+    pub directories: Vec<DirectoryMetrics>,
+}
+
+impl MetricsReport {
+    /// Build the report, rolling files up into every directory between them and `root`
+    pub fn new(root: &Path, mut files: Vec<FileMetrics>) -> Self {
+        files.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+
+        let mut rollups: BTreeMap<String, (DirectoryMetrics, f64)> = BTreeMap::new();
+        for file in &files {
+            for directory in Path::new(&file.file_path).ancestors().skip(1) {
+                let entry = rollups.entry(directory.display().to_string()).or_insert_with(|| {
+                    (
+                        DirectoryMetrics {
+                            path: directory.display().to_string(),
+                            files: 0,
+                            functions: 0,
+                            loc: LocMetrics::default(),
+                            cyclomatic: 0,
+                            cognitive: 0,
+                            max_cognitive: 0,
+                            maintainability_index: 0.0,
+                        },
+                        0.0,
+                    )
+                });
+                let (rollup, index_sum) = entry;
+                rollup.files += 1;
+                rollup.functions += file.functions.len();
+                rollup.loc.add(&file.loc);
+                rollup.cyclomatic += file.cyclomatic;
+                rollup.cognitive += file.cognitive;
+                rollup.max_cognitive = file
+                    .functions
+                    .iter()
+                    .map(|function| function.cognitive)
+                    .fold(rollup.max_cognitive, usize::max);
+                *index_sum += file.maintainability_index;
+
+                if directory == root || directory.as_os_str().is_empty() {
+                    break;
+                }
+            }
+        }
+
+        let directories = rollups
+            .into_values()
+            .filter(|(rollup, _)| !rollup.path.is_empty())
+            .map(|(mut rollup, index_sum)| {
+                rollup.maintainability_index = round(index_sum / rollup.files as f64);
+                rollup
+            })
+            .collect();
+
+        Self { files, directories }
+    }
+
+    /// Functions across all files, worst first (by cognitive, then cyclomatic complexity)
+    pub fn worst_functions(&self, limit: usize) -> Vec<(&FileMetrics, &FunctionMetrics)> {
+        let mut functions: Vec<(&FileMetrics, &FunctionMetrics)> = self
+            .files
+            .iter()
+            .flat_map(|file| file.functions.iter().map(move |function| (file, function)))
+            .collect();
+        functions.sort_by(|a, b| {
+            b.1.cognitive
+                .cmp(&a.1.cognitive)
+                .then(b.1.cyclomatic.cmp(&a.1.cyclomatic))
+        });
+        functions.truncate(limit);
+        functions
+    }
+}
+
+/// Maintainability index normalized to 0-100 (the Visual Studio variant):
+/// `max(0, (171 - 5.2 ln(V) - 0.23 G - 16.2 ln(LOC)) * 100 / 171)`
+pub fn maintainability_index(halstead_volume: f64, cyclomatic: usize, logical_loc: usize) -> f64 {
+    let volume = halstead_volume.max(1.0);
+    let loc = logical_loc.max(1) as f64;
+    let index = (171.0 - 5.2 * volume.ln() - 0.23 * cyclomatic as f64 - 16.2 * loc.ln()) * 100.0 / 171.0;
+    round(index.clamp(0.0, 100.0))
+}
+
+/// Two decimals are plenty for reports and keep JSON/CSV diffs stable
+pub fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
@@ -0,0 +1,140 @@
+//! Metrics for the tree-sitter front ends, driven by a table of node kinds per grammar
+
+use super::halstead::{self, Lexicon};
+use super::{maintainability_index, round, FileMetrics, FunctionMetrics};
+use crate::cognitive::{self, CognitiveGrammar};
+use std::path::Path;
+use tree_sitter::Node;
+
+/// Node kinds of a tree-sitter grammar that the metrics need on top of the cognitive complexity ones
+pub struct MetricsGrammar {
+    pub cognitive: &'static CognitiveGrammar,
+    pub lexicon: &'static Lexicon,
+    /// Nodes that add a path for cyclomatic complexity (branches, loops, cases, catches, boolean operators)
+    pub decisions: &'static [&'static str],
+    /// Nodes whose named children are statements, for logical LOC
+    pub statement_lists: &'static [&'static str],
+    /// The parameter list of a function
+    pub parameter_lists: &'static [&'static str],
+    /// A single parameter inside a parameter list
+    pub parameters: &'static [&'static str],
+    /// Where a function's declaration starts; differs from the function node when the signature is a sibling
+    pub header: fn(Node) -> Node,
+}
+
+/// Metrics for a whole file and every function in it
+pub fn file_metrics(grammar: &MetricsGrammar, root: Node, code: &str, file_path: &Path) -> FileMetrics {
+    let mut functions = Vec::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if grammar.cognitive.is_function(node) {
+            functions.push(function_metrics(grammar, node, code));
+        }
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+
+    let text = halstead::measure(code, grammar.lexicon);
+    let logical = count_statements(grammar, root, root, true);
+    FileMetrics::new(file_path, text.loc(logical), text.volume(), functions)
+}
+
+fn function_metrics(grammar: &MetricsGrammar, node: Node, code: &str) -> FunctionMetrics {
+    let header = (grammar.header)(node);
+    let text = halstead::measure(&code[header.start_byte()..node.end_byte()], grammar.lexicon);
+    let logical = count_statements(grammar, node, node, false).max(1);
+    let cyclomatic = 1 + count_decisions(grammar, node);
+    let volume = text.volume();
+
+    FunctionMetrics {
+        name: (grammar.cognitive.function_name)(node, code).unwrap_or_else(|| "<anonymous>".to_string()),
+        line: header.start_position().row + 1,
+        end_line: node.end_position().row + 1,
+        loc: text.loc(logical),
+        params: count_params(grammar, header),
+        cyclomatic,
+        cognitive: cognitive::score_function(grammar.cognitive, node, code).total(),
+        max_nesting: max_nesting(grammar, node, 0),
+        halstead_volume: round(volume),
+        maintainability_index: maintainability_index(volume, cyclomatic, logical),
+    }
+}
+
+/// Children of `node` that are not nested functions
+fn own_children<'tree>(grammar: &MetricsGrammar, node: Node<'tree>) -> Vec<Node<'tree>> {
+    let mut cursor = node.walk();
+    node.children(&mut cursor)
+        .filter(|child| !grammar.cognitive.is_function(*child))
+        .collect()
+}
+
+/// Statements inside `node`; `across_functions` (used for whole files) also counts the statements
+/// of nested functions and the top-level declarations under `root`
+fn count_statements(grammar: &MetricsGrammar, node: Node, root: Node, across_functions: bool) -> usize {
+    let mut count = 0;
+    if grammar.statement_lists.contains(&node.kind()) || (across_functions && node == root) {
+        let mut cursor = node.walk();
+        count += node
+            .named_children(&mut cursor)
+            .filter(|child| !child.kind().contains("comment"))
+            .count();
+    }
+
+    let children = if across_functions {
+        let mut cursor = node.walk();
+        node.children(&mut cursor).collect()
+    } else {
+        own_children(grammar, node)
+    };
+    for child in children {
+        count += count_statements(grammar, child, root, across_functions);
+    }
+    count
+}
+
+fn count_decisions(grammar: &MetricsGrammar, node: Node) -> usize {
+    own_children(grammar, node)
+        .into_iter()
+        .map(|child| usize::from(grammar.decisions.contains(&child.kind())) + count_decisions(grammar, child))
+        .sum()
+}
+
+fn max_nesting(grammar: &MetricsGrammar, node: Node, depth: usize) -> usize {
+    own_children(grammar, node)
+        .into_iter()
+        .map(|child| {
+            let nests = grammar.cognitive.nesting.contains(&child.kind()) || grammar.cognitive.ifs.contains(&child.kind());
+            let depth = depth + usize::from(nests);
+            depth.max(max_nesting(grammar, child, depth))
+        })
+        .max()
+        .unwrap_or(depth)
+}
+
+/// Parameters in the first parameter list of the declaration, skipping nested functions
+fn count_params(grammar: &MetricsGrammar, header: Node) -> usize {
+    let mut stack = vec![header];
+    while let Some(node) = stack.pop() {
+        if grammar.parameter_lists.contains(&node.kind()) {
+            return count_kinds(grammar.parameters, node);
+        }
+        stack.extend(own_children(grammar, node).into_iter().rev());
+    }
+    0
+}
+
+/// Nodes of the given kinds below `node`, not looking inside a match
+fn count_kinds(kinds: &[&str], node: Node) -> usize {
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.named_children(&mut cursor).collect();
+    children
+        .into_iter()
+        .map(|child| if kinds.contains(&child.kind()) { 1 } else { count_kinds(kinds, child) })
+        .sum()
+}
+
+/// `MetricsGrammar::header` for grammars where the function node covers its whole declaration
+pub fn same_node(node: Node) -> Node {
+    node
+}
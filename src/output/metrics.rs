@@ -0,0 +1,214 @@
+use crate::metrics::{LocMetrics, MetricsReport};
+use crate::types::MetricsFormat;
+use colored::*;
+use std::io::{self, Write};
+
+const CSV_HEADER: &str = "kind,path,name,line,physical_loc,logical_loc,comment_loc,blank_loc,params,cyclomatic,cognitive,max_nesting,halstead_volume,maintainability_index";
+
+pub struct MetricsFormatter;
+
+impl MetricsFormatter {
+    /// `top` limits the worst-functions table of the human view
+    pub fn format(report: &MetricsReport, format: MetricsFormat, top: usize) -> String {
+        match format {
+            MetricsFormat::Json => Self::format_json(report),
+            MetricsFormat::Csv => Self::format_csv(report),
+            MetricsFormat::Human => Self::format_human(report, top),
+        }
+    }
+
+    fn format_json(report: &MetricsReport) -> String {
+        serde_json::to_string_pretty(report).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// One row per directory, file and function; columns that do not apply are left empty
+    fn format_csv(report: &MetricsReport) -> String {
+        let mut output = format!("{}\n", CSV_HEADER);
+
+        for directory in &report.directories {
+            output.push_str(&Self::csv_row(
+                "directory",
+                &directory.path,
+                "",
+                "",
+                &directory.loc,
+                "",
+                directory.cyclomatic,
+                directory.cognitive,
+                "",
+                "",
+                directory.maintainability_index,
+            ));
+        }
+
+        for file in &report.files {
+            output.push_str(&Self::csv_row(
+                "file",
+                &file.file_path,
+                "",
+                "",
+                &file.loc,
+                "",
+                file.cyclomatic,
+                file.cognitive,
+                &file.max_nesting.to_string(),
+                &file.halstead_volume.to_string(),
+                file.maintainability_index,
+            ));
+
+            for function in &file.functions {
+                output.push_str(&Self::csv_row(
+                    "function",
+                    &file.file_path,
+                    &function.name,
+                    &function.line.to_string(),
+                    &function.loc,
+                    &function.params.to_string(),
+                    function.cyclomatic,
+                    function.cognitive,
+                    &function.max_nesting.to_string(),
+                    &function.halstead_volume.to_string(),
+                    function.maintainability_index,
+                ));
+            }
+        }
+
+        output
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn csv_row(
+        kind: &str,
+        path: &str,
+        name: &str,
+        line: &str,
+        loc: &LocMetrics,
+        params: &str,
+        cyclomatic: usize,
+        cognitive: usize,
+        max_nesting: &str,
+        halstead_volume: &str,
+        maintainability_index: f64,
+    ) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+            kind,
+            Self::csv_field(path),
+            Self::csv_field(name),
+            line,
+            loc.physical,
+            loc.logical,
+            loc.comment,
+            loc.blank,
+            params,
+            cyclomatic,
+            cognitive,
+            max_nesting,
+            halstead_volume,
+            maintainability_index
+        )
+    }
+
+    fn csv_field(value: &str) -> String {
+        if value.contains([',', '"', '\n']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
+
+    fn format_human(report: &MetricsReport, top: usize) -> String {
+        let mut output = String::new();
+
+        output.push_str(&format!(
+            "\n{}\n",
+            "═══════════════════════════════════════".dimmed()
+        ));
+        output.push_str(&format!("{}\n", "  Code Metrics".bold().white()));
+        output.push_str(&format!(
+            "{}\n\n",
+            "═══════════════════════════════════════".dimmed()
+        ));
+
+        let functions: usize = report.files.iter().map(|file| file.functions.len()).sum();
+        output.push_str(&format!("{}: {}\n", "Total Files".bold(), report.files.len()));
+        output.push_str(&format!("{}: {}\n\n", "Total Functions".bold(), functions));
+
+        if !report.directories.is_empty() {
+            output.push_str(&format!("{}\n", "Directories".bold().cyan()));
+            output.push_str(&format!("{}\n", "─".repeat(80).dimmed()));
+            output.push_str(&format!(
+                "  {:<40} {:>6} {:>6} {:>8} {:>6} {:>6}\n",
+                "path".dimmed(),
+                "files".dimmed(),
+                "sloc".dimmed(),
+                "cyclo".dimmed(),
+                "cogn".dimmed(),
+                "MI".dimmed()
+            ));
+            for directory in &report.directories {
+                output.push_str(&format!(
+                    "  {:<40} {:>6} {:>6} {:>8} {:>6} {}\n",
+                    directory.path,
+                    directory.files,
+                    directory.loc.logical,
+                    directory.cyclomatic,
+                    directory.cognitive,
+                    Self::color_index(directory.maintainability_index)
+                ));
+            }
+            output.push('\n');
+        }
+
+        let worst = report.worst_functions(top);
+        if !worst.is_empty() {
+            output.push_str(&format!("{}\n", format!("Top {} worst functions", worst.len()).bold().cyan()));
+            output.push_str(&format!("{}\n", "─".repeat(80).dimmed()));
+            for (file, function) in worst {
+                output.push_str(&format!(
+                    "  {} {}\n",
+                    function.name.bold(),
+                    format!("{}:{}", file.file_path, function.line).dimmed()
+                ));
+                output.push_str(&format!(
+                    "    cognitive {}, cyclomatic {}, nesting {}, params {}, sloc {}, MI {}\n",
+                    Self::color_complexity(function.cognitive),
+                    function.cyclomatic,
+                    function.max_nesting,
+                    function.params,
+                    function.loc.logical,
+                    Self::color_index(function.maintainability_index)
+                ));
+            }
+        }
+
+        output
+    }
+
+    fn color_complexity(value: usize) -> ColoredString {
+        if value <= 5 {
+            value.to_string().green()
+        } else if value <= 15 {
+            value.to_string().yellow()
+        } else {
+            value.to_string().red()
+        }
+    }
+
+    /// Thresholds follow the usual 0-100 maintainability index bands
+    fn color_index(index: f64) -> ColoredString {
+        if index >= 20.0 {
+            index.to_string().green()
+        } else if index >= 10.0 {
+            index.to_string().yellow()
+        } else {
+            index.to_string().red()
+        }
+    }
+
+    pub fn print(report: &MetricsReport, format: MetricsFormat, top: usize) {
+        let output = Self::format(report, format, top);
+        print!("{}", output);
+        io::stdout().flush().unwrap();
+    }
+}
@@ -1,6 +1,12 @@
-use crate::types::{AnalysisResult, OutputFormat};
+pub mod metrics;
+pub mod type_coverage;
+
+use crate::types::{AnalysisResult, Category, CodeIssue, OutputFormat, Severity};
 use colored::*;
+use serde_json::json;
+use std::collections::HashMap;
 use std::io::{self, Write};
+use std::path::Path;
 
 pub struct OutputFormatter;
 
@@ -9,7 +15,151 @@ impl OutputFormatter {
         match format {
             OutputFormat::Json => Self::format_json(result),
             OutputFormat::Human => Self::format_human(result),
+            OutputFormat::Checkstyle => Self::format_checkstyle(result),
+            OutputFormat::Junit => Self::format_junit(result),
+            OutputFormat::Gitlab => Self::format_gitlab(result),
+            OutputFormat::Github => Self::format_github(result),
+        }
+    }
+
+    fn format_gitlab(result: &AnalysisResult) -> String {
+        let mut entries = Vec::new();
+
+        for file in &result.files {
+            let path = relative_path(&file.file_path);
+            // Identical issues in one file are told apart by their order, not their line,
+            // so fingerprints survive code moving up or down. Messages are left out because
+            // some embed line numbers.
+            let mut seen: HashMap<String, usize> = HashMap::new();
+
+            for issue in &file.issues {
+                let snippet = issue.code_snippet.as_deref().unwrap_or("").split_whitespace().collect::<Vec<_>>().join(" ");
+                let key = format!("{}\0{}\0{}", path, issue.rule, snippet);
+                let occurrence = seen.entry(key.clone()).or_insert(0);
+                *occurrence += 1;
+
+                let severity = match issue.severity {
+                    Severity::Error => "major",
+                    Severity::Warning => "minor",
+                    Severity::Suggestion => "info",
+                };
+                entries.push(json!({
+                    "description": issue.message,
+                    "check_name": issue.rule,
+                    "fingerprint": fingerprint(&format!("{}\0{}", key, occurrence)),
+                    "severity": severity,
+                    "categories": [code_climate_category(issue)],
+                    "location": {
+                        "path": path,
+                        "lines": {
+                            "begin": issue.line,
+                            "end": issue.end_line.unwrap_or(issue.line),
+                        },
+                    },
+                }));
+            }
+        }
+
+        serde_json::to_string_pretty(&entries).unwrap_or_else(|_| "[]".to_string()) + "\n"
+    }
+
+    fn format_github(result: &AnalysisResult) -> String {
+        let mut output = String::new();
+
+        for file in &result.files {
+            let path = escape_github_property(&relative_path(&file.file_path));
+            for issue in &file.issues {
+                let command = match issue.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                    Severity::Suggestion => "notice",
+                };
+                let mut properties = format!("file={},line={},col={}", path, issue.line, issue.column);
+                if let Some(end_line) = issue.end_line {
+                    properties.push_str(&format!(",endLine={}", end_line));
+                }
+                if let Some(end_column) = issue.end_column {
+                    properties.push_str(&format!(",endColumn={}", end_column));
+                }
+                properties.push_str(&format!(",title={}", escape_github_property(&issue.rule)));
+
+                output.push_str(&format!("::{} {}::{}\n", command, properties, escape_github_data(&issue.message)));
+            }
         }
+
+        output
+    }
+
+    fn format_checkstyle(result: &AnalysisResult) -> String {
+        let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<checkstyle version=\"4.3\">\n");
+
+        for file in &result.files {
+            output.push_str(&format!("  <file name=\"{}\">\n", escape_xml(&file.file_path)));
+            for issue in &file.issues {
+                let severity = match issue.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                    Severity::Suggestion => "info",
+                };
+                output.push_str(&format!(
+                    "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" source=\"{}\"/>\n",
+                    issue.line,
+                    issue.column,
+                    severity,
+                    escape_xml(&issue.message),
+                    escape_xml(&issue.rule)
+                ));
+            }
+            output.push_str("  </file>\n");
+        }
+
+        output.push_str("</checkstyle>\n");
+        output
+    }
+
+    fn format_junit(result: &AnalysisResult) -> String {
+        let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        output.push_str(&format!(
+            "<testsuites name=\"js-ast-analyzer\" tests=\"{}\" failures=\"{}\">\n",
+            result.summary.total, result.summary.total
+        ));
+
+        for file in &result.files {
+            let path = escape_xml(&file.file_path);
+            output.push_str(&format!(
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">\n",
+                path,
+                file.issues.len(),
+                file.issues.len()
+            ));
+            for issue in &file.issues {
+                output.push_str(&format!(
+                    "    <testcase name=\"{} ({}:{})\" classname=\"{}\" file=\"{}\" line=\"{}\">\n",
+                    escape_xml(&issue.rule),
+                    issue.line,
+                    issue.column,
+                    path,
+                    path,
+                    issue.line
+                ));
+
+                let mut details = format!("{}:{}:{} [{}] {}\n{}", file.file_path, issue.line, issue.column, issue.severity, issue.rule, issue.message);
+                if let Some(snippet) = &issue.code_snippet {
+                    details.push_str(&format!("\n> {}", snippet));
+                }
+                output.push_str(&format!(
+                    "      <failure type=\"{}\" message=\"{}\">{}</failure>\n",
+                    issue.severity,
+                    escape_xml(&issue.message),
+                    escape_xml(&details)
+                ));
+                output.push_str("    </testcase>\n");
+            }
+            output.push_str("  </testsuite>\n");
+        }
+
+        output.push_str("</testsuites>\n");
+        output
     }
 
     fn format_json(result: &AnalysisResult) -> String {
@@ -48,6 +198,17 @@ impl OutputFormatter {
             Self::color_count(result.summary.suggestion, "suggestion")
         ));
 
+        if let Some(duplication) = &result.duplication {
+            output.push_str(&format!(
+                "{}: {}% ({} of {} lines in {} clone groups)\n\n",
+                "Duplication".bold(),
+                duplication.percentage,
+                duplication.duplicated_lines,
+                duplication.total_lines,
+                duplication.clone_groups
+            ));
+        }
+
         // Print issues by file
         for file in &result.files {
             if file.issues.is_empty() {
@@ -135,3 +296,73 @@ impl OutputFormatter {
         io::stdout().flush().unwrap();
     }
 }
+
+/// Escape text for an XML attribute or element, dropping characters XML 1.0 can't represent
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            '\r' => escaped.push_str("&#13;"),
+            '\t' => escaped.push_str("&#9;"),
+            c if (c as u32) < 0x20 || c == '\u{FFFE}' || c == '\u{FFFF}' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// `path` relative to the working directory, as CI systems expect repository paths
+fn relative_path(path: &str) -> String {
+    let path = Path::new(path);
+    let relative = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| path.strip_prefix(cwd).ok())
+        .unwrap_or(path);
+    let relative = relative.strip_prefix(".").unwrap_or(relative);
+    relative.display().to_string()
+}
+
+/// The Code Climate category GitLab groups an issue under
+fn code_climate_category(issue: &CodeIssue) -> &'static str {
+    if issue.rule == "duplicate-code" {
+        return "Duplication";
+    }
+    match issue.category {
+        Category::Security => "Security",
+        Category::Performance => "Performance",
+        Category::Complexity => "Complexity",
+        Category::Maintainability => "Clarity",
+        Category::BestPractice => "Style",
+        Category::CodeQuality
+        | Category::TypeScript
+        | Category::Kotlin
+        | Category::Dart
+        | Category::Python
+        | Category::React => "Bug Risk",
+    }
+}
+
+/// 128-bit FNV-1a as 32 hex digits, stable across runs and Rust versions
+fn fingerprint(text: &str) -> String {
+    const OFFSET: u128 = 0x6c62272e07bb014262b821756295c58d;
+    const PRIME: u128 = 0x0000000001000000000000000000013b;
+
+    let hash = text.bytes().fold(OFFSET, |hash, byte| (hash ^ byte as u128).wrapping_mul(PRIME));
+    format!("{:032x}", hash)
+}
+
+/// Escape the message of a GitHub workflow command
+fn escape_github_data(text: &str) -> String {
+    text.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Escape a property value of a GitHub workflow command
+fn escape_github_property(text: &str) -> String {
+    escape_github_data(text).replace(':', "%3A").replace(',', "%2C")
+}
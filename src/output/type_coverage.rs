@@ -0,0 +1,106 @@
+use crate::languages::python::type_hints::{TypeCoverage, TypeCoverageReport};
+use crate::types::MetricsFormat;
+use colored::*;
+use std::io::{self, Write};
+
+const CSV_HEADER: &str = "kind,path,functions,annotated,coverage";
+
+pub struct TypeCoverageFormatter;
+
+impl TypeCoverageFormatter {
+    pub fn format(report: &TypeCoverageReport, format: MetricsFormat) -> String {
+        match format {
+            MetricsFormat::Json => serde_json::to_string_pretty(report).unwrap_or_else(|_| "{}".to_string()),
+            MetricsFormat::Csv => Self::format_csv(report),
+            MetricsFormat::Human => Self::format_human(report),
+        }
+    }
+
+    /// One row for the total, then one per package and file
+    fn format_csv(report: &TypeCoverageReport) -> String {
+        let mut output = format!("{}\n", CSV_HEADER);
+        output.push_str(&Self::csv_row("total", &report.total));
+        for package in &report.packages {
+            output.push_str(&Self::csv_row("package", package));
+        }
+        for file in &report.files {
+            output.push_str(&Self::csv_row("file", file));
+        }
+        output
+    }
+
+    fn csv_row(kind: &str, coverage: &TypeCoverage) -> String {
+        let path = if coverage.path.contains([',', '"', '\n']) {
+            format!("\"{}\"", coverage.path.replace('"', "\"\""))
+        } else {
+            coverage.path.clone()
+        };
+        format!("{},{},{},{},{}\n", kind, path, coverage.functions, coverage.annotated, coverage.coverage)
+    }
+
+    fn format_human(report: &TypeCoverageReport) -> String {
+        let mut output = String::new();
+
+        output.push_str(&format!(
+            "\n{}\n",
+            "═══════════════════════════════════════".dimmed()
+        ));
+        output.push_str(&format!("{}\n", "  Type Hint Coverage".bold().white()));
+        output.push_str(&format!(
+            "{}\n\n",
+            "═══════════════════════════════════════".dimmed()
+        ));
+
+        output.push_str(&format!("{}: {}\n", "Total Files".bold(), report.files.len()));
+        output.push_str(&format!(
+            "{}: {} of {} ({}%)\n\n",
+            "Annotated Functions".bold(),
+            report.total.annotated,
+            report.total.functions,
+            Self::color_coverage(report.total.coverage)
+        ));
+
+        for (title, rows) in [("Packages", &report.packages), ("Files", &report.files)] {
+            if rows.is_empty() {
+                continue;
+            }
+            output.push_str(&format!("{}\n", title.bold().cyan()));
+            output.push_str(&format!("{}\n", "─".repeat(80).dimmed()));
+            output.push_str(&format!(
+                "  {:<50} {:>10} {:>10} {:>8}\n",
+                "path".dimmed(),
+                "functions".dimmed(),
+                "annotated".dimmed(),
+                "%".dimmed()
+            ));
+            for row in rows {
+                output.push_str(&format!(
+                    "  {:<50} {:>10} {:>10} {:>8}\n",
+                    row.path,
+                    row.functions,
+                    row.annotated,
+                    Self::color_coverage(row.coverage)
+                ));
+            }
+            output.push('\n');
+        }
+
+        output
+    }
+
+    fn color_coverage(coverage: f64) -> ColoredString {
+        if coverage >= 90.0 {
+            coverage.to_string().green()
+        } else if coverage >= 50.0 {
+            coverage.to_string().yellow()
+        } else {
+            coverage.to_string().red()
+        }
+    }
+
+    pub fn print(report: &TypeCoverageReport, format: MetricsFormat) {
+        let output = Self::format(report, format);
+        print!("{}", output);
+        io::stdout().flush().unwrap();
+    }
+}
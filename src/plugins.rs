@@ -0,0 +1,312 @@
+//! Rule plugins compiled to WebAssembly.
+//!
+//! A plugin exports `memory`, `alloc(len: i32) -> i32` and
+//! `analyze(ptr: i32, len: i32) -> i64`. The host writes a JSON request
+//! `{"file_path", "language", "source", "ast"}` into a buffer from `alloc` and calls `analyze`,
+//! which returns the location of its JSON answer packed as `ptr << 32 | len`: an array of
+//! `{"line", "column", "message", "rule", ...}` objects. Plugins get no host imports, run with a
+//! fuel budget per file and a capped linear memory.
+
+use crate::config::PluginConfig;
+use crate::error::{AnalyzerError, Result};
+use crate::types::{Category, CodeIssue, Severity};
+use oxc_ast::ast::Program;
+use oxc_ast::{AstKind, Visit};
+use oxc_span::GetSpan;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fmt::{self, Write};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use tree_sitter::Node;
+use wasmi::{Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+/// Exports every plugin must provide
+const EXPORTS: &[&str] = &["memory", "alloc", "analyze"];
+
+/// A compiled plugin with its limits
+#[derive(Clone)]
+pub struct Plugin {
+    name: String,
+    engine: Engine,
+    module: Arc<Module>,
+    languages: Vec<String>,
+    fuel: u64,
+    max_memory: usize,
+    ast: bool,
+}
+
+impl fmt::Debug for Plugin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Plugin")
+            .field("name", &self.name)
+            .field("languages", &self.languages)
+            .field("fuel", &self.fuel)
+            .field("max_memory", &self.max_memory)
+            .field("ast", &self.ast)
+            .finish()
+    }
+}
+
+/// What the host sends to `analyze`
+#[derive(Serialize)]
+struct Request<'a> {
+    file_path: String,
+    language: &'a str,
+    source: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ast: Option<Value>,
+}
+
+/// An issue as a plugin reports it
+#[derive(Deserialize)]
+struct PluginIssue {
+    line: usize,
+    column: usize,
+    end_line: Option<usize>,
+    end_column: Option<usize>,
+    message: String,
+    rule: String,
+    #[serde(default = "PluginIssue::default_severity")]
+    severity: Severity,
+    #[serde(default = "PluginIssue::default_category")]
+    category: Category,
+    code_snippet: Option<String>,
+}
+
+impl PluginIssue {
+    fn default_severity() -> Severity {
+        Severity::Warning
+    }
+
+    fn default_category() -> Category {
+        Category::BestPractice
+    }
+}
+
+struct HostState {
+    limits: StoreLimits,
+}
+
+impl Plugin {
+    /// Compile the module at `path` and check that it exports the plugin interface and imports nothing
+    pub fn load(config: &PluginConfig, path: &Path) -> Result<Self> {
+        let error = |message: String| AnalyzerError::PluginError {
+            path: path.display().to_string(),
+            message,
+        };
+        let wasm = fs::read(path).map_err(|e| error(e.to_string()))?;
+
+        let mut engine_config = wasmi::Config::default();
+        engine_config.consume_fuel(true);
+        let engine = Engine::new(&engine_config);
+        let module = Module::new(&engine, &wasm[..]).map_err(|e| error(e.to_string()))?;
+
+        if let Some(import) = module.imports().next() {
+            return Err(error(format!(
+                "plugins can't import host functions, found {}.{}",
+                import.module(),
+                import.name()
+            )));
+        }
+        if let Some(missing) = EXPORTS.iter().find(|name| !module.exports().any(|export| export.name() == **name)) {
+            return Err(error(format!("missing export '{}'", missing)));
+        }
+
+        Ok(Self {
+            name: path.file_stem().map_or_else(|| path.display().to_string(), |stem| stem.to_string_lossy().to_string()),
+            engine,
+            module: Arc::new(module),
+            languages: config.languages.clone(),
+            fuel: config.fuel,
+            max_memory: config.max_memory_mb * 1024 * 1024,
+            ast: config.ast,
+        })
+    }
+
+    /// Whether the plugin runs on files of `language`; plugins without languages run on all
+    pub fn runs_on(&self, language: &str) -> bool {
+        self.languages.is_empty() || self.languages.iter().any(|name| name == language)
+    }
+
+    /// Analyze one file. A plugin that traps, runs out of fuel or answers garbage is reported as
+    /// a `plugin-error` issue rather than failing the file.
+    pub fn run(&self, file_path: &Path, language: &str, source: &str, ast: impl FnOnce() -> Value) -> Vec<CodeIssue> {
+        let request = Request {
+            file_path: file_path.display().to_string(),
+            language,
+            source,
+            ast: self.ast.then(ast),
+        };
+
+        match self.call(&request) {
+            Ok(issues) => issues
+                .into_iter()
+                .map(|issue| CodeIssue {
+                    file_path: file_path.display().to_string(),
+                    line: issue.line,
+                    column: issue.column,
+                    end_line: issue.end_line,
+                    end_column: issue.end_column,
+                    message: issue.message,
+                    severity: issue.severity,
+                    category: issue.category,
+                    rule: issue.rule,
+                    code_snippet: issue.code_snippet,
+                })
+                .collect(),
+            Err(message) => vec![CodeIssue {
+                file_path: file_path.display().to_string(),
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                message: format!("Plugin '{}' failed: {}", self.name, message),
+                severity: Severity::Warning,
+                category: Category::CodeQuality,
+                rule: "plugin-error".to_string(),
+                code_snippet: None,
+            }],
+        }
+    }
+
+    fn call(&self, request: &Request) -> std::result::Result<Vec<PluginIssue>, String> {
+        let input = serde_json::to_vec(request).map_err(|e| e.to_string())?;
+
+        let limits = StoreLimitsBuilder::new().memory_size(self.max_memory).build();
+        let mut store = Store::new(&self.engine, HostState { limits });
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(self.fuel).map_err(|e| e.to_string())?;
+
+        let instance = Linker::<HostState>::new(&self.engine)
+            .instantiate(&mut store, &self.module)
+            .and_then(|pre| pre.start(&mut store))
+            .map_err(|e| e.to_string())?;
+        let memory = instance.get_memory(&store, "memory").ok_or("'memory' is not a memory")?;
+        let alloc = instance.get_typed_func::<i32, i32>(&store, "alloc").map_err(|e| e.to_string())?;
+        let analyze = instance.get_typed_func::<(i32, i32), i64>(&store, "analyze").map_err(|e| e.to_string())?;
+
+        let len = i32::try_from(input.len()).map_err(|_| "request too large".to_string())?;
+        let ptr = alloc.call(&mut store, len).map_err(|e| e.to_string())?;
+        memory.write(&mut store, ptr as u32 as usize, &input).map_err(|e| e.to_string())?;
+
+        let packed = analyze.call(&mut store, (ptr, len)).map_err(|e| e.to_string())? as u64;
+        let (answer_ptr, answer_len) = ((packed >> 32) as usize, (packed & 0xffff_ffff) as usize);
+        // The plugin picks the length, so check it against its memory before copying anything
+        let output = answer_ptr
+            .checked_add(answer_len)
+            .and_then(|end| memory.data(&store).get(answer_ptr..end))
+            .ok_or_else(|| format!("answer at {}..+{} is outside the plugin's memory", answer_ptr, answer_len))?;
+        serde_json::from_slice(output).map_err(|e| format!("invalid answer: {}", e))
+    }
+}
+
+/// Run every plugin that handles `language`, building the syntax tree at most once
+pub fn run_all(plugins: &[Plugin], file_path: &Path, language: &str, source: &str, ast: impl Fn() -> Value) -> Vec<CodeIssue> {
+    let mut tree = None;
+    plugins
+        .iter()
+        .filter(|plugin| plugin.runs_on(language))
+        .flat_map(|plugin| plugin.run(file_path, language, source, || tree.get_or_insert_with(&ast).clone()))
+        .collect()
+}
+
+/// Syntax tree node in the shape plugins receive for every language
+fn ast_node(kind: String, start: (usize, usize), end: (usize, usize), text: Option<&str>, children: Vec<Value>) -> Value {
+    let mut node = json!({
+        "type": kind,
+        "start": { "line": start.0, "column": start.1 },
+        "end": { "line": end.0, "column": end.1 },
+    });
+    match text {
+        Some(text) if children.is_empty() => node["text"] = json!(text),
+        _ => node["children"] = Value::Array(children),
+    }
+    node
+}
+
+/// The named nodes of a tree-sitter tree, with the text of leaves
+pub fn tree_sitter_ast(node: Node, code: &str) -> Value {
+    let mut cursor = node.walk();
+    let children: Vec<Value> = node.named_children(&mut cursor).map(|child| tree_sitter_ast(child, code)).collect();
+    let (start, end) = (node.start_position(), node.end_position());
+    ast_node(
+        node.kind().to_string(),
+        (start.row + 1, start.column + 1),
+        (end.row + 1, end.column + 1),
+        node.utf8_text(code.as_bytes()).ok(),
+        children,
+    )
+}
+
+/// The nodes of an oxc program, named after oxc's `AstKind` variants rather than ESTree types
+/// (e.g. `IdentifierReference` where ESTree has `Identifier`)
+pub fn javascript_ast(program: &Program, code: &str) -> Value {
+    let mut builder = JsAstBuilder {
+        code,
+        line_starts: std::iter::once(0).chain(code.match_indices('\n').map(|(i, _)| i + 1)).collect(),
+        stack: vec![Vec::new()],
+    };
+    builder.visit_program(program);
+    builder.stack.pop().and_then(|mut roots| roots.pop()).unwrap_or(Value::Null)
+}
+
+struct JsAstBuilder<'s> {
+    code: &'s str,
+    line_starts: Vec<usize>,
+    /// Children collected so far for each open node
+    stack: Vec<Vec<Value>>,
+}
+
+impl JsAstBuilder<'_> {
+    fn position(&self, offset: usize) -> (usize, usize) {
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        (line, offset - self.line_starts[line - 1] + 1)
+    }
+}
+
+/// Writer that keeps the variant name of a derived `Debug` output and stops before its fields
+struct VariantName(String);
+
+impl Write for VariantName {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match s.find(['(', ' ', '{']) {
+            Some(index) => {
+                self.0.push_str(&s[..index]);
+                Err(fmt::Error)
+            }
+            None => {
+                self.0.push_str(s);
+                Ok(())
+            }
+        }
+    }
+}
+
+fn kind_name(kind: &AstKind) -> String {
+    let mut name = VariantName(String::new());
+    let _ = write!(name, "{:?}", kind);
+    name.0
+}
+
+impl<'a> Visit<'a> for JsAstBuilder<'_> {
+    fn enter_node(&mut self, _kind: AstKind<'a>) {
+        self.stack.push(Vec::new());
+    }
+
+    fn leave_node(&mut self, kind: AstKind<'a>) {
+        let children = self.stack.pop().unwrap_or_default();
+        let span = kind.span();
+        let node = ast_node(
+            kind_name(&kind),
+            self.position(span.start as usize),
+            self.position(span.end as usize),
+            self.code.get(span.start as usize..span.end as usize),
+            children,
+        );
+        if let Some(siblings) = self.stack.last_mut() {
+            siblings.push(node);
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use crate::config::SecretsConfig;
+use crate::types::{Category, CodeIssue, Severity};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Minimum length before a literal is considered for the entropy check
+const MIN_ENTROPY_LENGTH: usize = 20;
+
+/// Shannon entropy (bits per character) above which a base64-like literal is reported.
+/// Short literals can't reach it, so the threshold is lowered to just under `log2(len)` for them.
+const BASE64_ENTROPY_THRESHOLD: f64 = 4.5;
+
+/// Shannon entropy above which a hex literal is reported (hex tops out at 4.0)
+const HEX_ENTROPY_THRESHOLD: f64 = 3.0;
+
+/// A string literal that looks like a credential
+#[derive(Debug, Clone)]
+pub struct SecretFinding {
+    /// Human readable kind, e.g. "AWS access key"
+    pub kind: &'static str,
+    pub rule: &'static str,
+    pub severity: Severity,
+    /// The literal with everything but its first characters masked
+    pub redacted: String,
+}
+
+/// Scans string literals from every language front end for hardcoded credentials
+#[derive(Debug, Clone, Default)]
+pub struct SecretScanner {
+    allowlist: Vec<String>,
+}
+
+impl SecretScanner {
+    pub fn with_config(config: &SecretsConfig) -> Self {
+        Self {
+            allowlist: config.allowlist.clone(),
+        }
+    }
+
+    /// Check a literal's value (without quotes) found in `file_path`
+    pub fn scan(&self, file_path: &Path, value: &str) -> Option<SecretFinding> {
+        let value = value.trim();
+        if value.is_empty() || self.is_allowed(file_path, value) {
+            return None;
+        }
+
+        if let Some(kind) = known_format(value) {
+            return Some(SecretFinding {
+                kind,
+                rule: "hardcoded-credential",
+                severity: Severity::Error,
+                redacted: redact(value),
+            });
+        }
+
+        if looks_random(value) {
+            return Some(SecretFinding {
+                kind: "high-entropy string",
+                rule: "high-entropy-secret",
+                severity: Severity::Warning,
+                redacted: redact(value),
+            });
+        }
+
+        None
+    }
+
+    /// Build the issue for a finding; positions are 1-indexed
+    pub fn issue(
+        &self,
+        file_path: &Path,
+        finding: &SecretFinding,
+        line: usize,
+        column: usize,
+        end: Option<(usize, usize)>,
+    ) -> CodeIssue {
+        let message = if finding.rule == "hardcoded-credential" {
+            format!(
+                "Hardcoded {} detected. Move it to an environment variable or secret manager and rotate it.",
+                finding.kind
+            )
+        } else {
+            "String literal looks like a secret (high entropy). Move it to an environment variable or secret manager, or add it to the allowlist if it is a test fixture.".to_string()
+        };
+
+        CodeIssue {
+            file_path: file_path.display().to_string(),
+            line,
+            column,
+            end_line: end.map(|(line, _)| line),
+            end_column: end.map(|(_, column)| column),
+            message,
+            severity: finding.severity,
+            category: Category::Security,
+            rule: finding.rule.to_string(),
+            code_snippet: Some(finding.redacted.clone()),
+        }
+    }
+
+    /// Entries with a `/` or `*` match the file path (as a wildcard or a trailing path), any other
+    /// entry must equal the secret value
+    fn is_allowed(&self, file_path: &Path, value: &str) -> bool {
+        let path = file_path.to_string_lossy().replace('\\', "/");
+        self.allowlist.iter().any(|entry| {
+            if entry.contains(['/', '*']) {
+                wildcard_match(entry, &path) || path == *entry || path.ends_with(&format!("/{}", entry))
+            } else {
+                entry == value
+            }
+        })
+    }
+}
+
+/// Strip quotes (and Python/Dart string prefixes) from a literal's source text
+pub fn unquote(text: &str) -> &str {
+    let text = text.trim_start_matches(['r', 'R', 'b', 'B', 'u', 'U', 'f', 'F']);
+    for quote in ["\"\"\"", "'''", "\"", "'", "`"] {
+        if text.len() >= quote.len() * 2 && text.starts_with(quote) && text.ends_with(quote) {
+            return &text[quote.len()..text.len() - quote.len()];
+        }
+    }
+    text
+}
+
+/// Keep the first four characters and mask the rest
+pub fn redact(value: &str) -> String {
+    let visible: String = value.chars().take(4).collect();
+    let hidden = value.chars().count().saturating_sub(4).min(16);
+    format!("{}{}", visible, "*".repeat(hidden.max(4)))
+}
+
+fn known_format(value: &str) -> Option<&'static str> {
+    if value.contains("-----BEGIN") && value.contains("PRIVATE KEY") {
+        return Some("private key");
+    }
+
+    for token in value.split(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '=' | ':' | ',')) {
+        if let Some(kind) = known_token(token) {
+            return Some(kind);
+        }
+    }
+
+    None
+}
+
+fn known_token(token: &str) -> Option<&'static str> {
+    let is_upper_alnum = |s: &str| s.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+    let is_alnum = |s: &str| s.chars().all(|c| c.is_ascii_alphanumeric());
+    let is_key_char = |s: &str| s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+
+    // AWS access key IDs: AKIA/ASIA + 16 uppercase alphanumerics
+    for prefix in ["AKIA", "ASIA", "AGPA", "AIDA", "AROA"] {
+        if let Some(rest) = token.strip_prefix(prefix) {
+            if rest.len() == 16 && is_upper_alnum(rest) {
+                return Some("AWS access key");
+            }
+        }
+    }
+
+    // GitHub tokens: ghp_, gho_, ghu_, ghs_, ghr_ + 36 characters, or fine-grained github_pat_
+    for prefix in ["ghp_", "gho_", "ghu_", "ghs_", "ghr_"] {
+        if let Some(rest) = token.strip_prefix(prefix) {
+            if rest.len() >= 36 && is_alnum(rest) {
+                return Some("GitHub token");
+            }
+        }
+    }
+    if let Some(rest) = token.strip_prefix("github_pat_") {
+        if rest.len() >= 60 && is_key_char(rest) {
+            return Some("GitHub token");
+        }
+    }
+
+    // Slack tokens: xoxb-, xoxp-, xoxa-, xoxr-, xoxs-
+    for prefix in ["xoxb-", "xoxp-", "xoxa-", "xoxr-", "xoxs-"] {
+        if let Some(rest) = token.strip_prefix(prefix) {
+            if rest.len() >= 10 && is_key_char(rest) {
+                return Some("Slack token");
+            }
+        }
+    }
+
+    // Google API keys: AIza + 35 characters
+    if let Some(rest) = token.strip_prefix("AIza") {
+        if rest.len() == 35 && is_key_char(rest) {
+            return Some("Google API key");
+        }
+    }
+
+    // JWTs: three base64url segments, header and payload start with `{"` (eyJ)
+    let token = token.strip_prefix("Bearer").unwrap_or(token);
+    let segments: Vec<&str> = token.split('.').collect();
+    if segments.len() == 3
+        && segments[0].starts_with("eyJ")
+        && segments[1].starts_with("eyJ")
+        && segments.iter().all(|segment| segment.len() >= 10 && is_key_char(segment))
+    {
+        return Some("JSON Web Token");
+    }
+
+    None
+}
+
+fn shannon_entropy(value: &str) -> f64 {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for c in value.chars() {
+        *counts.entry(c).or_insert(0) += 1;
+    }
+    let len = value.chars().count() as f64;
+    counts
+        .values()
+        .map(|&count| {
+            let p = count as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+fn looks_random(value: &str) -> bool {
+    if value.len() < MIN_ENTROPY_LENGTH || value.contains("://") || value.starts_with('/') {
+        return false;
+    }
+
+    let digits = value.chars().filter(|c| c.is_ascii_digit()).count();
+    let has_alpha = value.chars().any(|c| c.is_ascii_alphabetic());
+    if digits < 2 || !has_alpha {
+        return false;
+    }
+
+    let is_hex = value.chars().all(|c| c.is_ascii_hexdigit());
+    if is_hex {
+        return value.len() >= 32 && shannon_entropy(value) >= HEX_ENTROPY_THRESHOLD;
+    }
+
+    let is_base64 = value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '=' | '_' | '-'));
+    let threshold = BASE64_ENTROPY_THRESHOLD.min((value.len() as f64).log2() - 0.3);
+    is_base64 && shannon_entropy(value) >= threshold
+}
+
+/// Match `text` against a pattern where `*` matches any run of characters
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    if !pattern.contains('*') {
+        return false;
+    }
+
+    let parts: Vec<&str> = pattern.split('*').filter(|part| !part.is_empty()).collect();
+    let mut rest = text;
+    for (index, part) in parts.iter().enumerate() {
+        if index == 0 && !pattern.starts_with('*') {
+            match rest.strip_prefix(part) {
+                Some(after) => rest = after,
+                None => return false,
+            }
+            continue;
+        }
+        match rest.find(part) {
+            Some(position) => rest = &rest[position + part.len()..],
+            None => return false,
+        }
+    }
+
+    match parts.last() {
+        Some(last) => pattern.ends_with('*') || text.ends_with(last),
+        None => true,
+    }
+}
@@ -34,6 +34,7 @@ pub enum Category {
     Complexity,
     Dart,
     Python,
+    React,
 }
 
 impl fmt::Display for Category {
@@ -49,6 +50,7 @@ impl fmt::Display for Category {
             Category::Complexity => write!(f, "complexity"),
             Category::Dart => write!(f, "dart"),
             Category::Python => write!(f, "python"),
+            Category::React => write!(f, "react"),
         }
     }
 }
@@ -133,11 +135,25 @@ pub struct FileAnalysis {
     pub summary: SeveritySummary,
 }
 
+/// How much of the analyzed code is duplicated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicationSummary {
+    pub clone_groups: usize,
+    /// Lines of code covered by at least one clone
+    pub duplicated_lines: usize,
+    /// Lines that contain code, across all files
+    pub total_lines: usize,
+    pub percentage: f64,
+}
+
 /// Complete analysis result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalysisResult {
     pub files: Vec<FileAnalysis>,
     pub summary: SeveritySummary,
+    /// Set once clone detection has run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duplication: Option<DuplicationSummary>,
 }
 
 impl AnalysisResult {
@@ -145,6 +161,7 @@ impl AnalysisResult {
         Self {
             files: Vec::new(),
             summary: SeveritySummary::new(),
+            duplication: None,
         }
     }
 
@@ -168,6 +185,22 @@ impl Default for AnalysisResult {
 pub enum OutputFormat {
     Json,
     Human,
+    /// Checkstyle XML, e.g. for the Jenkins Warnings NG plugin
+    Checkstyle,
+    /// JUnit XML, one testsuite per file and one failing testcase per issue
+    Junit,
+    /// GitLab Code Quality (Code Climate JSON) for merge request widgets
+    Gitlab,
+    /// GitHub Actions workflow commands that annotate the diff
+    Github,
+}
+
+/// Output format options for the `metrics` subcommand
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum MetricsFormat {
+    Json,
+    Csv,
+    Human,
 }
 
 /// Programming language options
@@ -0,0 +1,16 @@
+import { db } from './db';
+
+export function invoiceTotals(invoices, vat) {
+    const result = [];
+    for (const invoice of invoices) {
+        let sum = 0;
+        for (const item of invoice.items) {
+            sum += item.cost * item.units;
+        }
+        if (invoice.rebate > 0) {
+            sum -= sum * invoice.rebate;
+        }
+        result.push({ id: invoice.id, amount: sum * (1 + vat) });
+    }
+    return result;
+}
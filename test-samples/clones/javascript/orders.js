@@ -0,0 +1,20 @@
+import { db } from './db';
+
+export function orderTotals(orders, taxRate) {
+    const totals = [];
+    for (const order of orders) {
+        let amount = 0;
+        for (const line of order.lines) {
+            amount += line.price * line.quantity;
+        }
+        if (order.discount > 0) {
+            amount -= amount * order.discount;
+        }
+        totals.push({ id: order.id, amount: amount * (1 + taxRate) });
+    }
+    return totals;
+}
+
+export function orderCount(orders) {
+    return db.count(orders);
+}
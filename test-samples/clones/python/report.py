@@ -0,0 +1,18 @@
+def summarize_sales(records):
+    total = 0
+    for record in records:
+        if record["status"] == "paid":
+            total += record["amount"] * record["quantity"]
+        elif record["status"] == "refunded":
+            total -= record["amount"]
+    return {"total": total, "count": len(records)}
+
+
+def summarize_returns(entries):
+    total = 0
+    for entry in entries:
+        if entry["status"] == "paid":
+            total += entry["amount"] * entry["quantity"]
+        elif entry["status"] == "refunded":
+            total -= entry["amount"]
+    return {"total": total, "count": len(entries)}
@@ -0,0 +1,21 @@
+int simple(int value) {
+  if (value > 0) {
+    return 1;
+  }
+  return 0;
+}
+
+int nested(List<Item> items) {
+  for (final item in items) {
+    if (item.active) {
+      while (item.next != null) {
+        item.advance();
+      }
+    } else if (item.pending) {
+      return item.a && item.b || item.c ? 1 : 0;
+    } else {
+      continue;
+    }
+  }
+  return 0;
+}
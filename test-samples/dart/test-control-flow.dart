@@ -0,0 +1,96 @@
+int afterReturn(int value) {
+  return value * 2;
+  print('never printed');
+}
+
+void afterThrow() {
+  throw StateError('boom');
+  cleanup();
+}
+
+void loopJumps(List<Item> items) {
+  for (final item in items) {
+    if (item.skip) {
+      continue;
+      item.visited = true;
+    }
+    break;
+    item.done = true;
+  }
+}
+
+void constantBranch() {
+  if (false) {
+    start();
+  }
+  while (false) {
+    stop();
+  }
+}
+
+dynamic inconsistent(bool flag) {
+  if (flag) {
+    return 42;
+  }
+}
+
+int consistent(bool flag) {
+  if (flag) {
+    return 1;
+  } else if (!flag) {
+    return 2;
+  }
+  return 0;
+}
+
+void spin() {
+  while (true) {
+    tick();
+  }
+}
+
+void poll() {
+  for (;;) {
+    if (ready()) {
+      break;
+    }
+  }
+}
+
+Iterable<int> ids() sync* {
+  var id = 0;
+  while (true) {
+    yield id++;
+  }
+}
+
+int describe(int value) {
+  switch (value) {
+    case 0:
+    case 1:
+      return 0;
+    default:
+      return 1;
+  }
+  print('unreachable');
+}
+
+int guarded() {
+  try {
+    return load();
+  } catch (e) {
+    report(e);
+  } finally {
+    release();
+  }
+  return 0;
+}
+
+int cleanedUp() {
+  try {
+    return load();
+  } finally {
+    release();
+  }
+  print('unreachable');
+}
@@ -0,0 +1,61 @@
+import 'dart:async';
+import 'package:flutter/material.dart';
+
+class ProfilePage extends StatefulWidget {
+  const ProfilePage({super.key});
+
+  @override
+  State<ProfilePage> createState() => _ProfilePageState();
+}
+
+class _ProfilePageState extends State<ProfilePage> {
+  final nameController = TextEditingController();
+  final emailController = TextEditingController();
+  late AnimationController fade;
+  StreamSubscription<int>? updates;
+  String name = '';
+
+  Future<void> load() async {
+    setState(() => name = 'loading');
+    final profile = await fetchProfile();
+    setState(() => name = profile);
+    Navigator.of(context).pop();
+  }
+
+  Future<void> save() async {
+    await saveProfile(name);
+    if (!mounted) return;
+    setState(() => name = '');
+    if (context.mounted) {
+      ScaffoldMessenger.of(context).showSnackBar(const SnackBar(content: Text('Saved')));
+    }
+  }
+
+  Future<void> refresh() async {
+    await fetchProfile();
+    if (mounted) {
+      setState(() => name = 'fresh');
+    }
+  }
+
+  @override
+  void dispose() {
+    nameController.dispose();
+    updates?.cancel();
+    super.dispose();
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    debugPrint('building profile');
+    return Column(
+      children: [
+        Text('Profile'),
+        const Text('Static'),
+        Padding(padding: EdgeInsets.zero, child: Text('Nested')),
+        Text(name),
+        TextField(controller: nameController),
+      ],
+    );
+  }
+}
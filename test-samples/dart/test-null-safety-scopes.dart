@@ -0,0 +1,45 @@
+// Scoped nullable tracking, `!`, late fields, dynamic and nullable casts
+
+class Session {
+  late String token;
+  late final String userId;
+  late int retries;
+  late final DateTime started = DateTime.now();
+  Map<String, int?> scores = {};
+  String? nickname;
+
+  Session(this.userId) {
+    retries = 0;
+  }
+
+  Session.guest() : userId = 'guest', retries = 0;
+
+  void login(String name) {
+    token = name;
+  }
+
+  int total() {
+    var sum = 0;
+    scores.forEach((key, value) => sum += value ?? 0);
+    return scores.length + sum;
+  }
+
+  String greeting(String? override) {
+    final nickname = 'local';
+    print(nickname.length);
+    if (override != null) {
+      return override.trim();
+    }
+    return this.nickname!.trim() + override.toString();
+  }
+}
+
+void parse(dynamic raw, Map<String, dynamic> json, String? label) {
+  final name = label as String;
+  final maybe = label as String?;
+  final count = raw as int;
+  print(label.length);
+  if (label == null) return;
+  print(label.isEmpty);
+  print(name.length + maybe!.length + count);
+}
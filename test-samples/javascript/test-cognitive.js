@@ -0,0 +1,63 @@
+// Fixture for the cognitive complexity rule
+export function simple(a, b) {
+    if (a) {
+        return b;
+    }
+    return a && b;
+}
+
+export function nested(items) {
+    for (const item of items) {
+        if (item.active) {
+            while (item.next) {
+                item = item.next;
+            }
+        } else if (item.pending) {
+            return item.a && item.b || item.c;
+        } else {
+            continue;
+        }
+    }
+    return null;
+}
+
+const handler = (event) => {
+    if (event.type === "click") {
+        if (event.target) {
+            if (event.target.disabled) {
+                return event.ok ? 1 : 2;
+            }
+        }
+    }
+    return 0;
+};
+
+class Tree {
+    depth(node) {
+        if (!node) {
+            return 0;
+        }
+        return 1 + Math.max(this.depth(node.left), this.depth(node.right));
+    }
+
+    walk(node, visit) {
+        outer: for (const child of node.children) {
+            for (const leaf of child.leaves) {
+                if (visit(leaf)) {
+                    continue outer;
+                }
+            }
+        }
+    }
+}
+
+export const api = {
+    parse(input) {
+        return input.map((line) => {
+            if (line.startsWith("#")) {
+                return null;
+            }
+            return line.trim() || (line.length > 0 && line.includes("x") ? "x" : null);
+        });
+    },
+};
@@ -0,0 +1,152 @@
+function afterReturn(value) {
+  return value * 2;
+  console.log('never printed');
+  value += 1;
+}
+
+function afterThrow() {
+  throw new Error('boom');
+  cleanup();
+}
+
+function loopJumps(items) {
+  for (const item of items) {
+    if (item.skip) {
+      continue;
+      item.visited = true;
+    }
+    break;
+    item.done = true;
+  }
+}
+
+function hoisted() {
+  return helper();
+  function helper() {
+    return 1;
+  }
+}
+
+function constantBranch() {
+  if (true) {
+    start();
+  }
+  while (false) {
+    stop();
+  }
+  do {
+    once();
+  } while (false);
+}
+
+function fallthrough(kind) {
+  switch (kind) {
+    case 'a':
+      first();
+    case 'b':
+      second();
+      // falls through
+    case 'c':
+      third();
+      break;
+    case 'd':
+    case 'e':
+      fourth();
+      break;
+    default:
+      other();
+  }
+}
+
+function inconsistent(flag) {
+  if (flag) {
+    return 42;
+  }
+}
+
+function consistent(flag) {
+  if (flag) {
+    return 1;
+  }
+  return 0;
+}
+
+const mixed = (flag) => {
+  if (flag) {
+    return 'yes';
+  }
+  return;
+};
+
+function spin() {
+  while (true) {
+    tick();
+  }
+}
+
+function poll() {
+  for (;;) {
+    if (ready()) {
+      break;
+    }
+  }
+  while (1) {
+    if (check()) {
+      return true;
+    }
+  }
+}
+
+function* ids() {
+  let id = 0;
+  while (true) {
+    yield id++;
+  }
+}
+
+function guarded() {
+  try {
+    return load();
+  } catch (error) {
+    report(error);
+  } finally {
+    release();
+  }
+  return null;
+}
+
+class Worker {
+  run() {
+    outer: while (true) {
+      for (const job of this.jobs) {
+        if (job.last) {
+          break outer;
+        }
+      }
+    }
+    done();
+  }
+}
+
+function cleanedUp() {
+  try {
+    return load();
+  } finally {
+    release();
+  }
+  done();
+}
+
+function retried() {
+  for (const job of jobs) {
+    try {
+      if (job.skip) {
+        continue;
+      }
+      return job.run();
+    } finally {
+      release();
+    }
+  }
+  return null;
+}
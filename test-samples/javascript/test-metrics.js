@@ -0,0 +1,19 @@
+// Order helpers
+
+export function total(items, tax) {
+    let sum = 0;
+    for (const item of items) {
+        if (item.price > 0 && item.qty > 0) {
+            sum += item.price * item.qty;
+        }
+    }
+    return sum * (1 + tax);
+}
+
+export const label = (order) => order.paid ? 'paid' : 'open';
+
+export class Cart {
+    add(item, qty, ...rest) {
+        this.items.push({ item, qty, rest });
+    }
+}
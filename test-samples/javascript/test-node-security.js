@@ -0,0 +1,71 @@
+// Test file for Node.js server-side security rules
+const express = require('express');
+const childProcess = require('child_process');
+const crypto = require('crypto');
+const fs = require('fs/promises');
+const https = require('https');
+
+const app = express();
+
+// Command execution with a non-literal command
+app.post('/convert', (req, res) => {
+    const file = req.body.file;
+    childProcess.execSync('convert ' + file + ' out.png');
+    childProcess.exec('ls -la');
+});
+
+// File path taken from the request
+app.get('/download', async (req, res) => {
+    const { name } = req.query;
+    const data = await fs.readFile('/srv/files/' + name);
+    res.send(data);
+});
+
+// SQL built by concatenation and template literals
+app.get('/users/:id', async (req, res) => {
+    const rows = await db.query("SELECT * FROM users WHERE id = " + req.params.id);
+    const sql = `DELETE FROM sessions WHERE user = ${req.params.id}`;
+    await db.query(sql);
+    await db.query('SELECT * FROM users WHERE id = ?', [req.params.id]);
+    res.json(rows);
+});
+
+// Regular expression from user input
+app.get('/search', (req, res) => {
+    const pattern = new RegExp(req.query.q);
+    res.json(items.filter((item) => pattern.test(item)));
+});
+
+// Weak hashing algorithms
+const digest = crypto.createHash('md5').update(payload).digest('hex');
+const fingerprint = crypto.createHash('sha1').update(payload).digest('hex');
+const strongDigest = crypto.createHash('sha256').update(payload).digest('hex');
+
+// Math.random() used for tokens
+const resetToken = Math.random().toString(36).substring(2);
+const animationDelay = Math.random() * 300;
+
+// Disabled TLS verification
+const agent = new https.Agent({ rejectUnauthorized: false });
+process.env.NODE_TLS_REJECT_UNAUTHORIZED = '0';
+
+// Unvalidated redirect
+app.get('/login', (req, res) => {
+    res.redirect(req.query.next);
+    res.redirect('/home');
+});
+
+// Non-literal command and SQL that don't come from the request
+function rebuild(branch, table) {
+    childProcess.exec('git checkout ' + branch);
+    return db.query('SELECT * FROM ' + table);
+}
+
+// Request input inside loops, templates and arrays
+app.get('/batch', (req, res) => {
+    for (const re = new RegExp(req.query.a); ; ) { break; }
+    while (new RegExp(req.query.b).test(title)) { break; }
+    do { res.redirect(req.query.next); } while (false);
+    res.json(`${new RegExp(req.query.c).source}`);
+    res.json([new RegExp(req.query.d)]);
+});
@@ -0,0 +1,71 @@
+import axios from 'axios';
+
+async function saveUser(user: User): Promise<void> {
+    await db.insert(user);
+}
+
+function loadConfig(): Promise<Config> {
+    return fetch('/config').then((res) => res.json());
+}
+
+function formatName(name: string): string {
+    return name.trim();
+}
+
+export async function handler(users: User[], notify: () => Promise<void>) {
+    saveUser(users[0]);
+    loadConfig();
+    axios.post('/audit', { count: users.length });
+    notify();
+    formatName('x');
+    void saveUser(users[1]);
+    saveUser(users[2]).catch(console.error);
+
+    fetch('/ping').then((res) => console.log(res.status));
+    fetch('/ping').then(onOk, onError);
+
+    for (const user of users) {
+        await saveUser(user);
+    }
+    await Promise.all(users.map((user) => saveUser(user)));
+}
+
+export async function noAwait(value: number) {
+    return value * 2;
+}
+
+export function legacy(url: string) {
+    return new Promise(async (resolve, reject) => {
+        const res = await fetch(url);
+        resolve(res);
+    });
+}
+
+export async function withTry(id: string) {
+    try {
+        return loadConfig();
+    } catch (err) {
+        return null;
+    }
+}
+
+export async function redundant(id: string) {
+    return await loadConfig();
+}
+
+export async function correct(id: string) {
+    try {
+        return await loadConfig();
+    } catch (err) {
+        return null;
+    }
+}
+
+import { readFile } from 'fs/promises';
+import * as fsp from 'node:fs/promises';
+
+export async function reload(path: string) {
+    readFile(path);
+    fsp.writeFile(path, '');
+    await readFile(path);
+}
@@ -0,0 +1,14 @@
+export class Page extends React.Component {
+    render() {
+        return (
+            <div dangerouslySetInnerHTML={{ __html: this.props.html }}>
+                <a href="javascript:void(0)" target="_blank">Home</a>
+                {this.props.items.map(item => <li>{item}</li>)}
+            </div>
+        );
+    }
+}
+
+export default class extends React.Component {
+    renderLink = () => <a href={this.props.url} target="_blank">Docs</a>;
+}
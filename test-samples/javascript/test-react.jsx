@@ -0,0 +1,66 @@
+import React, { useState, useEffect, useCallback, useMemo } from 'react';
+
+export function Profile({ user, html }) {
+    const [count, setCount] = useState(0);
+    const options = { id: user.id };
+
+    useEffect(() => {
+        document.title = user.name + count;
+    }, [count]);
+
+    useEffect(() => {
+        setCount(count + 1);
+    });
+
+    useEffect(() => {
+        fetchProfile(options);
+    }, [options]);
+
+    const onSave = useCallback(() => setCount(0), []);
+
+    const Avatar = () => <img src={user.avatar} />;
+
+    return (
+        <div dangerouslySetInnerHTML={{ __html: html }}>
+            <a href="javascript:void(0)" onClick={onSave}>Save</a>
+            <a href="https://example.com" target="_blank">External</a>
+            <a href="https://example.com" target="_blank" rel="noopener noreferrer">Safe</a>
+            <Avatar />
+        </div>
+    );
+}
+
+export function List({ items }) {
+    for (const item of items) {
+        useMemo(() => item, [item]);
+    }
+
+    const handleClick = () => {
+        const [clicked] = useState(false);
+    };
+
+    return (
+        <ul onClick={handleClick}>
+            {items.map((item) => <li>{item.name}</li>)}
+            {items.map((item, index) => <li key={index}>{item.name}</li>)}
+            {items.map((item, i) => (
+                <li key={`row-${i}`}>{item.name}</li>
+            ))}
+            {items.map((item) => <li key={item.id}>{item.name}</li>)}
+        </ul>
+    );
+}
+
+export function useCounter(initial) {
+    const [value, setValue] = useState(initial);
+    const increment = useCallback(() => setValue(value + 1), [value]);
+    return { value, increment };
+}
+
+export function Guarded({ ready }) {
+    if (!ready) {
+        return null;
+    }
+    const [state] = useState(0);
+    return <p>{state}</p>;
+}
@@ -0,0 +1,21 @@
+function render(el, html) {
+  el.innerHTML = html;
+  el["innerHTML"] = html;
+  el.textContent = html;
+  renderTrusted(() => {
+    el.innerHTML = html;
+  });
+}
+
+form.submit(async () => {
+  await fetch("/api/save", { method: 'POST', body: data });
+  await fetch("/api/load", { method: "GET" });
+  await fetch(url);
+});
+fetch("/api/outside", { method: "POST" });
+
+if (count === count || count === other) {
+  report(count);
+}
+
+document.body.innerHTML = banner;
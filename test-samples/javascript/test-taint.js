@@ -0,0 +1,84 @@
+// Test file for taint tracking from user input to dangerous sinks
+const { exec } = require('child_process');
+const fs = require('fs');
+
+// Taint: URL hash flows into innerHTML through two variables
+function renderGreeting() {
+    const name = location.hash.substring(1);
+    const html = '<h1>Hello ' + name + '</h1>';
+    document.getElementById('greeting').innerHTML = html;
+}
+
+// Safe: sanitized before reaching the sink
+function renderSafeGreeting() {
+    const name = location.hash.substring(1);
+    document.getElementById('greeting').innerHTML = DOMPurify.sanitize(name);
+}
+
+// Safe: innerHTML with a constant string
+function renderStatic() {
+    document.getElementById('greeting').innerHTML = '<b>static</b>';
+}
+
+// Taint: request query flows into a shell command
+app.get('/ping', (req, res) => {
+    const host = req.query.host;
+    exec(`ping -c 1 ${host}`);
+});
+
+// Taint: request body flows into SQL through a helper (inter-procedural)
+function findUser(id) {
+    return db.query("SELECT * FROM users WHERE id = " + id);
+}
+
+app.post('/users', (req, res) => {
+    findUser(req.body.id);
+});
+
+// Taint: request params flow into a file path
+app.get('/files/:name', (req, res) => {
+    const content = fs.readFileSync('/uploads/' + req.params.name);
+    res.json({ content });
+});
+
+// Taint: postMessage data flows into eval
+window.addEventListener('message', (event) => {
+    eval(event.data);
+});
+
+// Taint: URLSearchParams flows into document.write
+const params = new URLSearchParams(location.search);
+document.write(params.get('q'));
+
+// Taint unless `cleanHtml` is configured as a sanitizer
+function renderCustom() {
+    document.body.innerHTML = cleanHtml(location.hash);
+}
+
+// Taint: window.name is a source of its own
+function renderWindowName() {
+    document.body.innerHTML = window.name;
+}
+
+// Safe: a local binding hides the global `location`
+function renderLocal() {
+    const location = "static";
+    document.body.innerHTML = location;
+}
+
+// Taint: followed through three levels of helpers
+function runQuery(sql) {
+    return db.query(sql);
+}
+
+function selectById(id) {
+    return runQuery("SELECT * FROM orders WHERE id = " + id);
+}
+
+function loadOrder(id) {
+    return selectById(id);
+}
+
+app.get('/orders/:id', (req, res) => {
+    loadOrder(req.params.id);
+});
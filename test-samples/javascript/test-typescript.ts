@@ -0,0 +1,54 @@
+// Fixture for the TypeScript rules
+type Handler = (event: any) => void;
+interface Options<T = any> {
+    name: string;
+    meta: Record<string, any>;
+}
+
+class Store {
+    items: any[] = [];
+    private cache: Map<string, number> = new Map();
+}
+
+export function exported(value: string) {
+    return value.length;
+}
+
+function internal(value: string) {
+    return value.trim();
+}
+
+export function typed(value: string): number {
+    return value.length;
+}
+
+export const arrow = (value: number) => value * 2;
+export const typedArrow = (value: number): number => value * 2;
+
+function unwrap(map: Map<string, number>): number {
+    const raw = JSON.parse("{}") as any;
+    return map.get("key")! + raw.count;
+}
+
+// @ts-ignore
+const ignored: number = "text";
+// @ts-expect-error - legacy API still returns a string here
+const expected: number = "text";
+
+const direction = "left" as "right";
+const count = 1 as 2;
+const same = "up" as "up";
+const widened = "up" as string;
+
+enum Color {
+    Red,
+    Green,
+}
+
+interface Empty {}
+interface Alias extends Options {}
+interface Combined extends Options, Store {}
+
+function callback(fn: Function, target: Object): void {
+    fn(target);
+}
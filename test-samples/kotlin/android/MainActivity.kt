@@ -0,0 +1,43 @@
+package com.example.app
+
+import android.content.Context
+import android.content.Context.MODE_WORLD_READABLE
+import android.util.Log
+import android.webkit.WebView
+import android.widget.TextView
+import android.widget.Toast
+import java.lang.ref.WeakReference
+
+lateinit var appContext: Context
+
+class MainActivity : AppCompatActivity() {
+    companion object {
+        private var current: MainActivity? = null
+        private var title: TextView? = null
+        private var owner: WeakReference<Context>? = null
+        private const val TAG = "MainActivity"
+    }
+
+    override fun onCreate(savedInstanceState: Bundle?) {
+        Log.d(TAG, "onCreate")
+        if (BuildConfig.DEBUG) {
+            Log.v(TAG, "debug build")
+        }
+        Log.e(TAG, "always logged")
+
+        val label = findViewById<TextView>(R.id.label)
+        label.setText("Welcome back")
+        label.setText(R.string.welcome)
+        label.setText("$count")
+        Toast.makeText(this, "Saved", Toast.LENGTH_SHORT).show()
+        Toast.makeText(this, R.string.saved, Toast.LENGTH_SHORT).show()
+
+        val web = findViewById<WebView>(R.id.web)
+        web.settings.javaScriptEnabled = true
+        web.settings.setJavaScriptEnabled(true)
+        web.settings.javaScriptEnabled = false
+
+        openFileOutput("prefs", MODE_WORLD_READABLE)
+        openFileOutput("cache", Context.MODE_PRIVATE)
+    }
+}
@@ -0,0 +1,21 @@
+fun simple(value: Int): Int {
+    if (value > 0) {
+        return 1
+    }
+    return 0
+}
+
+fun nested(items: List<Item>): Int {
+    for (item in items) {
+        if (item.active) {
+            while (item.next != null) {
+                item.advance()
+            }
+        } else if (item.pending) {
+            return if (item.a && item.b || item.c) 1 else 0
+        } else {
+            continue
+        }
+    }
+    return 0
+}
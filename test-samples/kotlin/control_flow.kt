@@ -0,0 +1,65 @@
+fun afterReturn(value: Int): Int {
+    return value * 2
+    println("never printed")
+}
+
+fun afterThrow() {
+    throw IllegalStateException("boom")
+    cleanup()
+}
+
+fun loopJumps(items: List<Item>) {
+    for (item in items) {
+        if (item.skip) {
+            continue
+            item.visited = true
+        }
+        break
+        item.done = true
+    }
+}
+
+fun constantBranch() {
+    if (false) {
+        start()
+    }
+    while (false) {
+        stop()
+    }
+}
+
+fun spin() {
+    while (true) {
+        tick()
+    }
+}
+
+fun poll() {
+    outer@ while (true) {
+        for (item in items) {
+            if (item.last) {
+                break@outer
+            }
+        }
+    }
+    done()
+}
+
+fun describe(value: Int): String {
+    when (value) {
+        0 -> return "zero"
+        else -> return "other"
+    }
+    println("unreachable")
+}
+
+fun guarded(): Int {
+    try {
+        return load()
+    } catch (e: Exception) {
+        report(e)
+    } finally {
+        release()
+    }
+    return 0
+}
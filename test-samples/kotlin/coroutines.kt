@@ -0,0 +1,58 @@
+package com.example.sync
+
+import java.io.File
+import kotlinx.coroutines.*
+
+class SyncRepository(private val scope: CoroutineScope) {
+    fun refresh() {
+        GlobalScope.launch { sync() }
+        val items = runBlocking { fetch() }
+    }
+
+    suspend fun sync() {
+        Thread.sleep(500)
+        val cached = File("cache.json").readText()
+        withContext(Dispatchers.IO) {
+            File("cache.json").writeText(cached)
+        }
+        delay(10)
+    }
+
+    suspend fun fetch(): List<String> {
+        try {
+            sync()
+        } catch (e: Exception) {
+            log(e)
+        }
+        try {
+            sync()
+        } catch (e: CancellationException) {
+            throw e
+        } catch (e: Exception) {
+            log(e)
+        }
+        try {
+            sync()
+        } catch (e: Throwable) {
+            throw e
+        }
+        return emptyList()
+    }
+
+    fun load() = scope.launch {
+        scope.async { fetch() }
+        val pending = async { fetch() }
+        val awaited = async { fetch() }
+        val parts = listOf(1, 2).map { async { fetch() } }
+        awaited.await()
+        parts.awaitAll()
+    }
+
+    fun log(e: Throwable) {
+        Thread.sleep(1)
+    }
+}
+
+fun main() = runBlocking {
+    SyncRepository(this).load()
+}
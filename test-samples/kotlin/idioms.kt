@@ -0,0 +1,54 @@
+package com.example.idioms
+
+sealed class Shape
+class Circle : Shape()
+class Square : Shape()
+
+enum class Color { RED, GREEN }
+
+fun label(name: String?, fallback: String): String {
+    val a = if (name != null) name else fallback
+    val b = if (name == null) { fallback } else { name }
+    val c = if (name != null) name.trim() else fallback
+    return a + b + c
+}
+
+fun log(message: String): Unit {
+    println(message)
+}
+
+fun noop(): Unit = Unit
+
+fun describe(shape: Shape, color: Color, code: Int) {
+    when (shape) {
+        is Circle -> println("circle")
+        is Square -> println("square")
+    }
+    when (color) {
+        Color.RED -> println("red")
+        Color.GREEN -> println("green")
+    }
+    when (code) {
+        1 -> println("one")
+        2 -> println("two")
+    }
+    when (code) {
+        1 -> println("one")
+        else -> println("other")
+    }
+}
+
+fun join(items: List<String>): String {
+    var result = ""
+    var count = 0
+    for (item in items) {
+        result += item
+        count += 1
+    }
+    items.forEach { result = result + it }
+    val builder = StringBuilder()
+    for (item in items) {
+        builder.append(item)
+    }
+    return result + builder
+}
@@ -0,0 +1,45 @@
+package com.example.nulls
+
+import java.io.File
+import javax.inject.Inject
+
+class Service(private val api: Api) {
+    @Inject lateinit var injected: Repository
+    lateinit var session: Session
+    lateinit var required: String
+
+    val home = System.getenv("HOME")
+    val typed: String? = System.getenv("USER")
+    private val hidden = System.getenv("SECRET")
+    fun property(key: String) = System.getProperty(key)
+    fun size() = File("/tmp").length().toInt()
+
+    fun close() {
+        if (::session.isInitialized) {
+            session.close()
+        }
+    }
+
+    fun render(user: User?, name: String?) {
+        val length = name!!.length
+        if (user != null) {
+            println(user!!.id)
+        }
+        if (name == null) {
+            println("none")
+        } else {
+            println(name!!)
+        }
+        user?.let { u ->
+            u.address?.let { address ->
+                address.city?.let { city ->
+                    println(city)
+                }
+            }
+        }
+    }
+}
+
+class MainActivity : AppCompatActivity() {
+    @Inject lateinit var presenter: Presenter
+}
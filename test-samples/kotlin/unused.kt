@@ -0,0 +1,49 @@
+package com.example.unused
+
+import java.io.File
+import java.util.UUID
+import kotlin.math.max as maximum
+import kotlin.math.min
+import androidx.compose.runtime.getValue
+import com.example.util.*
+
+/** Writes to a [File]. */
+class Repository(private val db: String, private val unusedDb: String, val name: String) {
+    private val cache = mutableMapOf<String, Int>()
+    private var counter = 0
+    private val viaOther = 1
+
+    private fun helper(x: Int, y: Int): Int = x + 1
+
+    private fun unusedHelper() = Unit
+
+    fun run(items: List<Int>, other: Repository) {
+        val id = UUID.randomUUID()
+        var total = 0
+        items.forEach { item -> total += item }
+        val shadowed = 1
+        items.forEach { shadowed -> println(shadowed) }
+        val written = 0
+        var assigned = 0
+        assigned = 2
+        val (first, second) = Pair(1, 2)
+        println(first)
+        cache["$id"] = helper(x = counter, y = 2)
+        println(other.viaOther + maximum(1, 2) + fromCompanion())
+        println("$db ${total}")
+        val identifier = 5
+        println("id: ${identifier}")
+    }
+
+    companion object {
+        private fun fromCompanion() = 1
+    }
+}
+
+data class Point(private val x: Int, private val y: Int)
+
+private fun topLevelUnused() {}
+
+private fun topLevelUsed(value: Int, _: String) = value
+
+fun caller() = topLevelUsed(1, "")
@@ -0,0 +1,74 @@
+;; Reports the first FIXME in a file. Lines are counted from the `\n` escapes in the JSON
+;; request, which is enough for sources without backslashes.
+(module
+  (memory (export "memory") 1)
+  (global $heap (mut i32) (i32.const 1024))
+
+  (data (i32.const 0) "[{\"line\":")
+  (data (i32.const 16) ",\"column\":1,\"message\":\"FIXME left in the code\",\"rule\":\"fixme\"}]")
+  (data (i32.const 128) "[]")
+
+  (func $alloc (export "alloc") (param $len i32) (result i32)
+    (local $ptr i32)
+    (local.set $ptr (global.get $heap))
+    (global.set $heap (i32.add (global.get $heap) (local.get $len)))
+    (block $done
+      (loop $grow
+        (br_if $done (i32.le_u (global.get $heap) (i32.mul (memory.size) (i32.const 65536))))
+        (if (i32.eq (memory.grow (i32.const 1)) (i32.const -1)) (then unreachable))
+        (br $grow)))
+    (local.get $ptr))
+
+  (func $copy (param $dst i32) (param $src i32) (param $len i32) (result i32)
+    (block $done
+      (loop $next
+        (br_if $done (i32.eqz (local.get $len)))
+        (i32.store8 (local.get $dst) (i32.load8_u (local.get $src)))
+        (local.set $dst (i32.add (local.get $dst) (i32.const 1)))
+        (local.set $src (i32.add (local.get $src) (i32.const 1)))
+        (local.set $len (i32.sub (local.get $len) (i32.const 1)))
+        (br $next)))
+    (local.get $dst))
+
+  ;; Write the decimal digits of $n at $dst, using 200..210 as scratch
+  (func $itoa (param $dst i32) (param $n i32) (result i32)
+    (local $end i32)
+    (local.set $end (i32.const 210))
+    (loop $digit
+      (local.set $end (i32.sub (local.get $end) (i32.const 1)))
+      (i32.store8 (local.get $end) (i32.add (i32.const 48) (i32.rem_u (local.get $n) (i32.const 10))))
+      (local.set $n (i32.div_u (local.get $n) (i32.const 10)))
+      (br_if $digit (local.get $n)))
+    (call $copy (local.get $dst) (local.get $end) (i32.sub (i32.const 210) (local.get $end))))
+
+  (func $is_fixme (param $at i32) (result i32)
+    (i32.and
+      (i32.and
+        (i32.and (i32.eq (i32.load8_u (local.get $at)) (i32.const 70))
+                 (i32.eq (i32.load8_u offset=1 (local.get $at)) (i32.const 73)))
+        (i32.and (i32.eq (i32.load8_u offset=2 (local.get $at)) (i32.const 88))
+                 (i32.eq (i32.load8_u offset=3 (local.get $at)) (i32.const 77))))
+      (i32.eq (i32.load8_u offset=4 (local.get $at)) (i32.const 69))))
+
+  (func (export "analyze") (param $ptr i32) (param $len i32) (result i64)
+    (local $i i32) (local $last i32) (local $line i32) (local $out i32) (local $end i32)
+    (local.set $i (local.get $ptr))
+    (local.set $last (i32.sub (i32.add (local.get $ptr) (local.get $len)) (i32.const 5)))
+    (local.set $line (i32.const 1))
+    (block $missing
+      (block $found
+        (loop $scan
+          (br_if $missing (i32.gt_s (local.get $i) (local.get $last)))
+          (br_if $found (call $is_fixme (local.get $i)))
+          (if (i32.and (i32.eq (i32.load8_u (local.get $i)) (i32.const 92))
+                       (i32.eq (i32.load8_u offset=1 (local.get $i)) (i32.const 110)))
+            (then (local.set $line (i32.add (local.get $line) (i32.const 1)))))
+          (local.set $i (i32.add (local.get $i) (i32.const 1)))
+          (br $scan)))
+      (local.set $out (call $alloc (i32.const 96)))
+      (local.set $end (call $copy (local.get $out) (i32.const 0) (i32.const 9)))
+      (local.set $end (call $itoa (local.get $end) (local.get $line)))
+      (local.set $end (call $copy (local.get $end) (i32.const 16) (i32.const 63)))
+      (return (i64.or (i64.shl (i64.extend_i32_u (local.get $out)) (i64.const 32))
+                      (i64.extend_i32_u (i32.sub (local.get $end) (local.get $out))))))
+    (i64.or (i64.shl (i64.const 128) (i64.const 32)) (i64.const 2))))
@@ -0,0 +1,6 @@
+;; Asks the host for a function, which plugins may not do
+(module
+  (import "env" "read_file" (func $read_file (param i32 i32) (result i32)))
+  (memory (export "memory") 1)
+  (func (export "alloc") (param i32) (result i32) (i32.const 1024))
+  (func (export "analyze") (param i32 i32) (result i64) (i64.const 2)))
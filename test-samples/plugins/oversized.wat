@@ -0,0 +1,6 @@
+;; Claims a 4 GiB answer in a single page of memory
+(module
+  (memory (export "memory") 1)
+  (func (export "alloc") (param i32) (result i32) (i32.const 1024))
+  (func (export "analyze") (param i32 i32) (result i64)
+    (i64.const 0xffffffff)))
@@ -0,0 +1,7 @@
+;; Never returns, so only the fuel limit stops it
+(module
+  (memory (export "memory") 1)
+  (func (export "alloc") (param i32) (result i32) (i32.const 1024))
+  (func (export "analyze") (param i32 i32) (result i64)
+    (loop $forever (br $forever))
+    unreachable))
@@ -0,0 +1,5 @@
+fun main() {
+    val total = 1
+    // FIXME: handle overflow
+    println(total)
+}
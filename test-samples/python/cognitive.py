@@ -0,0 +1,22 @@
+def simple(value):
+    if value:
+        return 1
+    return 0
+
+
+def nested(items):
+    for item in items:
+        if item.active:
+            while item.next:
+                item = item.next
+        elif item.pending:
+            return item.a and item.b or item.c
+        else:
+            continue
+    return None
+
+
+def factorial(n):
+    if n <= 1:
+        return 1
+    return n * factorial(n - 1) if n > 0 else 0
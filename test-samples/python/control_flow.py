@@ -0,0 +1,91 @@
+def after_return(value):
+    return value * 2
+    print("never printed")
+
+
+def after_raise():
+    raise ValueError("boom")
+    cleanup()
+
+
+def loop_jumps(items):
+    for item in items:
+        if item.skip:
+            continue
+            item.visited = True
+        break
+        item.done = True
+
+
+def constant_branch():
+    if False:
+        start()
+    elif True:
+        middle()
+    while 0:
+        stop()
+
+
+def inconsistent(flag):
+    if flag:
+        return 42
+
+
+def consistent(flag):
+    if flag:
+        return 1
+    elif flag is None:
+        return 2
+    else:
+        return 0
+
+
+def mixed(flag):
+    if flag:
+        return "yes"
+    return
+
+
+def spin():
+    while True:
+        tick()
+
+
+def poll():
+    while True:
+        if ready():
+            break
+
+
+def ids():
+    current = 0
+    while True:
+        yield current
+        current += 1
+
+
+def guarded():
+    try:
+        return load()
+    except OSError:
+        report()
+    finally:
+        release()
+    return None
+
+
+def matcher(command):
+    match command:
+        case "start":
+            return 1
+        case _:
+            return 0
+    print("unreachable")
+
+
+def cleaned_up():
+    try:
+        return load()
+    finally:
+        release()
+    print("unreachable")
@@ -0,0 +1,89 @@
+import asyncio
+import json
+import os.path
+from typing import List, Optional
+from collections import OrderedDict as Ordered
+
+
+def append_item(item, items=[]):
+    items.append(item)
+    return items
+
+
+def make_config(name, options=dict(), tags: set = set()):
+    return {"name": name, "options": options, "tags": tags}
+
+
+def safe_default(value, items=None):
+    return value if items is None else items
+
+
+def load(path):
+    try:
+        return open(path).read()
+    except:
+        return None
+
+
+def ignore_errors(action):
+    try:
+        action()
+    except Exception:
+        pass
+    try:
+        action()
+    except ValueError:
+        pass
+
+
+def compare(value, count):
+    if value == None:
+        return 0
+    if count is 1 or value is not "":
+        return 1
+    return 2
+
+
+def shadow(items):
+    list = [item for item in items]
+    id = len(list)
+    return id
+
+
+def unused_locals(path: Optional[str]) -> List[str]:
+    result = []
+    total = 0
+    _ignored = 1
+    count = 0
+    count += 1
+    for line in os.path.split(path):
+        result.append(line)
+    return result
+
+
+def make_handlers(names):
+    handlers = []
+    for name in names:
+        handlers.append(lambda: print(name))
+    callbacks = [lambda: name for name in names]
+    bound = [lambda name=name: name for name in names]
+    return handlers, callbacks, bound
+
+
+def cleanup(action):
+    try:
+        return action()
+    finally:
+        return None
+
+
+async def fetch(url):
+    await asyncio.sleep(1)
+    return url
+
+
+async def run(urls):
+    fetch(urls[0])
+    asyncio.sleep(1)
+    await fetch(urls[1])
+    return [await fetch(url) for url in urls]
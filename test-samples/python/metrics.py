@@ -0,0 +1,14 @@
+"""Order helpers"""
+
+
+def total(items, tax=0.1):
+    # sum every positive line
+    result = 0
+    for item in items:
+        if item.price > 0 and item.qty > 0:
+            result += item.price * item.qty
+    return result * (1 + tax)
+
+
+def label(order):
+    return "paid" if order.paid else "open"
@@ -0,0 +1,61 @@
+import hashlib
+import os
+import pickle
+import random
+import subprocess as sp
+import yaml
+from subprocess import run
+
+import requests
+
+
+def run_code(expression):
+    return eval(expression)
+
+
+def shell(host, path):
+    sp.call("ls -l", shell=True)
+    run(f"ping {host}", shell=True)
+    sp.run(["ls", path])
+    os.system("rm -rf " + path)
+
+
+def load(blob, text):
+    data = pickle.loads(blob)
+    config = yaml.load(text)
+    safe = yaml.load(text, Loader=yaml.SafeLoader)
+    return data, config, safe
+
+
+def find_user(cursor, name, user_id):
+    cursor.execute(f"SELECT * FROM users WHERE name = '{name}'")
+    cursor.execute("SELECT * FROM users WHERE id = %s" % user_id)
+    query = "DELETE FROM users WHERE id = {}".format(user_id)
+    cursor.execute(query)
+    cursor.execute("SELECT * FROM users WHERE id = %s", (user_id,))
+
+
+def fetch(url):
+    return requests.get(url, verify=False)
+
+
+def fingerprint(data):
+    legacy = hashlib.md5(data).hexdigest()
+    checksum = hashlib.sha1(data, usedforsecurity=False).hexdigest()
+    strong = hashlib.sha256(data).hexdigest()
+    return legacy, checksum, strong
+
+
+def new_reset_token():
+    return "".join(random.choice("abcdef0123456789") for _ in range(32))
+
+
+def shuffle_deck(deck):
+    position = random.randint(0, len(deck))
+    return deck[position]
+
+
+def delete_account(user):
+    assert user.is_admin, "admin only"
+    assert len(user.name) > 0
+    user.delete()
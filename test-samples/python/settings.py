@@ -0,0 +1,9 @@
+import os
+
+DEBUG = True
+ALLOWED_HOSTS = ["example.com"]
+
+
+def debug_enabled():
+    DEBUG = True
+    return DEBUG
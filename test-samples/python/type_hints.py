@@ -0,0 +1,35 @@
+from typing import Optional
+
+
+def annotated(name: str, count: int = 1, *args: str, **kwargs: int) -> str:
+    return name * count
+
+
+def no_return(name: str):
+    return name
+
+
+def partly(name, count: int = 1, flag=False) -> None:
+    pass
+
+
+def _private(value):
+    return value
+
+
+class Service:
+    def __init__(self, url: str):
+        self.url = url
+
+    def fetch(self, path: str) -> Optional[str]:
+        def helper(part):
+            return part
+        return helper(path)
+
+    @classmethod
+    def create(cls, *args, **kwargs):
+        return cls(*args)
+
+    @staticmethod
+    def ping(host: str, *, timeout: float = 1.0, port: int) -> bool:
+        return True
@@ -0,0 +1,8 @@
+import os
+import subprocess
+
+
+def run(cmd):
+    os.system(cmd)
+    subprocess.run([cmd])
+    return os.popen(cmd).read()
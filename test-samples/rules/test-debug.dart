@@ -0,0 +1,5 @@
+void main() {
+  debugPrint('starting');
+  print('running');
+  debugPrintStack();
+}
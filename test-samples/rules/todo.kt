@@ -0,0 +1,7 @@
+class Repository {
+    fun load(): String = TODO()
+
+    fun save(value: String) {
+        TODO("persist $value")
+    }
+}
@@ -0,0 +1,17 @@
+//! Helpers shared by the integration tests; each test crate uses only some of them
+#![allow(dead_code)]
+
+use js_ast_analyzer::{Issue, JsParser};
+use std::path::Path;
+
+/// Lines of the issues reported under `rule`, in report order
+pub fn rule_lines(issues: &[Issue], rule: &str) -> Vec<usize> {
+    issues.iter().filter(|issue| issue.rule == rule).map(|issue| issue.line).collect()
+}
+
+/// Issues the default JavaScript/TypeScript analyzers report for a fixture
+pub fn js_issues(path: &str) -> Vec<Issue> {
+    let result = JsParser::new().analyze_file(Path::new(path));
+    assert!(result.is_ok(), "Failed to parse {}", path);
+    result.unwrap().issues
+}
@@ -0,0 +1,111 @@
+use js_ast_analyzer::clones::CloneDetector;
+use js_ast_analyzer::config::ClonesConfig;
+use js_ast_analyzer::metrics::halstead;
+use js_ast_analyzer::types::Category;
+use js_ast_analyzer::{Config, JsParser, PythonParser};
+use std::fs;
+use std::path::PathBuf;
+
+fn detector(min_tokens: usize) -> CloneDetector {
+    CloneDetector::with_config(&ClonesConfig {
+        enabled: true,
+        min_tokens,
+    })
+}
+
+fn sources(paths: &[&str]) -> Vec<(String, String)> {
+    paths
+        .iter()
+        .map(|path| (path.to_string(), fs::read_to_string(path).unwrap()))
+        .collect()
+}
+
+#[test]
+fn test_js_clone_across_files() {
+    let sources = sources(&[
+        "test-samples/clones/javascript/orders.js",
+        "test-samples/clones/javascript/invoices.js",
+    ]);
+    let (groups, summary) = detector(50).find_clones(&sources, &halstead::JAVASCRIPT);
+
+    assert_eq!(groups.len(), 1, "Renamed identifiers should still match");
+    let lines: Vec<(usize, usize)> = groups[0]
+        .locations
+        .iter()
+        .map(|location| (location.start_line, location.end_line))
+        .collect();
+    assert_eq!(lines, vec![(3, 16), (3, 16)], "Imports are not part of the clone");
+
+    assert_eq!(summary.clone_groups, 1);
+    assert_eq!(summary.duplicated_lines, 28);
+    assert_eq!(summary.total_lines, 33);
+    assert!((summary.percentage - 84.85).abs() < 0.01, "{}", summary.percentage);
+}
+
+#[test]
+fn test_clone_shorter_than_threshold() {
+    let sources = sources(&[
+        "test-samples/clones/javascript/orders.js",
+        "test-samples/clones/javascript/invoices.js",
+    ]);
+    let (groups, summary) = detector(200).find_clones(&sources, &halstead::JAVASCRIPT);
+    assert!(groups.is_empty());
+    assert_eq!(summary.percentage, 0.0);
+}
+
+#[test]
+fn test_python_clone_within_file() {
+    let sources = sources(&["test-samples/clones/python/report.py"]);
+    let (groups, _) = detector(30).find_clones(&sources, &halstead::PYTHON);
+
+    assert_eq!(groups.len(), 1);
+    let lines: Vec<(usize, usize)> = groups[0]
+        .locations
+        .iter()
+        .map(|location| (location.start_line, location.end_line))
+        .collect();
+    assert_eq!(lines, vec![(1, 8), (11, 18)]);
+}
+
+#[test]
+fn test_directory_issues_and_summary() {
+    let parser = JsParser::new();
+    let result = parser
+        .analyze_directory(&PathBuf::from("test-samples/clones/javascript"))
+        .unwrap();
+
+    let clones: Vec<_> = result
+        .files
+        .iter()
+        .flat_map(|file| &file.issues)
+        .filter(|issue| issue.rule == "duplicate-code")
+        .collect();
+    assert_eq!(clones.len(), 2, "One issue per copy");
+    assert!(clones.iter().all(|issue| issue.category == Category::Maintainability));
+    assert!(clones.iter().all(|issue| issue.line == 3 && issue.end_line == Some(16)));
+    assert!(
+        clones.iter().any(|issue| issue.message.contains("also appear at test-samples/clones/javascript/invoices.js:3-16")),
+        "{:?}",
+        clones
+    );
+
+    let duplication = result.duplication.expect("Duplication summary should be set");
+    assert_eq!(duplication.clone_groups, 1);
+}
+
+#[test]
+fn test_disabled_in_config() {
+    let mut config = Config::default();
+    config.clones.enabled = false;
+    let parser = PythonParser::with_config(&config).unwrap();
+    let result = parser
+        .analyze_directory(&PathBuf::from("test-samples/clones/python"))
+        .unwrap();
+
+    assert!(result.duplication.is_none());
+    assert!(result
+        .files
+        .iter()
+        .flat_map(|file| &file.issues)
+        .all(|issue| issue.rule != "duplicate-code"));
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use js_ast_analyzer::{Config, DartParser, Issue, JsParser, KotlinParser, PythonParser};
+use std::path::PathBuf;
+use common::rule_lines;
+
+fn config(threshold: usize) -> Config {
+    let mut config = Config::default();
+    config.complexity.cognitive_threshold = threshold;
+    config
+}
+
+fn cognitive(issues: &[Issue]) -> Vec<&Issue> {
+    issues.iter().filter(|issue| issue.rule == "cognitive-complexity").collect()
+}
+
+fn js_issues(threshold: usize) -> Vec<Issue> {
+    let parser = JsParser::with_config(&config(threshold));
+    let result = parser.analyze_file(&PathBuf::from("test-samples/javascript/test-cognitive.js"));
+    assert!(result.is_ok(), "Failed to parse test-cognitive.js");
+    result.unwrap().issues
+}
+
+#[test]
+fn test_js_every_function_like_node() {
+    assert_eq!(
+        rule_lines(&js_issues(5), "cognitive-complexity"),
+        vec![9, 24, 43],
+        "function declaration, arrow function and class method; nested arrows are scored on their own"
+    );
+}
+
+#[test]
+fn test_js_default_threshold() {
+    assert!(rule_lines(&js_issues(15), "cognitive-complexity").is_empty());
+}
+
+#[test]
+fn test_js_message_lists_contributors() {
+    let issues = js_issues(5);
+    let nested = cognitive(&issues)[0];
+    assert!(nested.message.contains("'nested'"), "{}", nested.message);
+    assert!(nested.message.contains("cognitive complexity 10"), "{}", nested.message);
+    assert!(nested.message.contains("while +3 (baris 12)"), "{}", nested.message);
+}
+
+#[test]
+fn test_python_cognitive_complexity() {
+    let parser = PythonParser::with_config(&config(2)).unwrap();
+    let analysis = parser.analyze_file(&PathBuf::from("test-samples/python/cognitive.py")).unwrap();
+    assert_eq!(rule_lines(&analysis.issues, "cognitive-complexity"), vec![7, 19], "recursion pushes factorial over the threshold");
+}
+
+#[test]
+fn test_kotlin_cognitive_complexity() {
+    let parser = KotlinParser::with_config(&config(5)).unwrap();
+    let analysis = parser.analyze_file(&PathBuf::from("test-samples/kotlin/cognitive.kt")).unwrap();
+    assert_eq!(rule_lines(&analysis.issues, "cognitive-complexity"), vec![8]);
+}
+
+#[test]
+fn test_dart_cognitive_complexity() {
+    let parser = DartParser::with_config(&config(5)).unwrap();
+    let analysis = parser.analyze_file(&PathBuf::from("test-samples/dart/test-cognitive.dart")).unwrap();
+    assert_eq!(rule_lines(&analysis.issues, "cognitive-complexity"), vec![8]);
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use js_ast_analyzer::{DartParser, Issue, JsParser, KotlinParser, PythonParser};
+use std::path::PathBuf;
+use common::rule_lines;
+
+fn js_issues() -> Vec<Issue> {
+    let result = JsParser::new().analyze_file(&PathBuf::from("test-samples/javascript/test-control-flow.js"));
+    assert!(result.is_ok(), "Failed to parse test-control-flow.js");
+    result.unwrap().issues
+}
+
+fn python_issues() -> Vec<Issue> {
+    let result = PythonParser::new().unwrap().analyze_file(&PathBuf::from("test-samples/python/control_flow.py"));
+    assert!(result.is_ok(), "Failed to parse control_flow.py");
+    result.unwrap().issues
+}
+
+fn kotlin_issues() -> Vec<Issue> {
+    let result = KotlinParser::new().unwrap().analyze_file(&PathBuf::from("test-samples/kotlin/control_flow.kt"));
+    assert!(result.is_ok(), "Failed to parse control_flow.kt");
+    result.unwrap().issues
+}
+
+fn dart_issues() -> Vec<Issue> {
+    let result = DartParser::new().unwrap().analyze_file(&PathBuf::from("test-samples/dart/test-control-flow.dart"));
+    assert!(result.is_ok(), "Failed to parse test-control-flow.dart");
+    result.unwrap().issues
+}
+
+#[test]
+fn test_js_unreachable_code() {
+    assert_eq!(
+        rule_lines(&js_issues(), "unreachable-code"),
+        vec![3, 9, 16, 19, 137],
+        "First statement after return, throw, continue and break; hoisted functions and code after try/catch/finally are fine, after try/finally that returns is not"
+    );
+}
+
+#[test]
+fn test_js_constant_condition() {
+    let issues = js_issues();
+    assert_eq!(rule_lines(&issues, "constant-condition"), vec![31, 34], "do/while (false) is an idiom");
+    let branch = issues.iter().find(|issue| issue.rule == "constant-condition").unwrap();
+    assert!(branch.message.contains("always true"), "{}", branch.message);
+}
+
+#[test]
+fn test_js_switch_fallthrough() {
+    assert_eq!(
+        rule_lines(&js_issues(), "switch-fallthrough"),
+        vec![44],
+        "Commented fallthrough, empty cases and cases ending in break are fine"
+    );
+}
+
+#[test]
+fn test_js_inconsistent_return() {
+    let issues = js_issues();
+    assert_eq!(rule_lines(&issues, "inconsistent-return"), vec![61, 74]);
+    let implicit = issues.iter().find(|issue| issue.rule == "inconsistent-return").unwrap();
+    assert!(implicit.message.contains("'inconsistent'"), "{}", implicit.message);
+    assert!(implicit.message.contains("end of the function (line 65)"), "{}", implicit.message);
+}
+
+#[test]
+fn test_js_infinite_loop() {
+    assert_eq!(
+        rule_lines(&js_issues(), "infinite-loop"),
+        vec![82],
+        "Loops left by break, labeled break or return, and generator loops are fine"
+    );
+}
+
+#[test]
+fn test_python_control_flow() {
+    let issues = python_issues();
+    assert_eq!(rule_lines(&issues, "unreachable-code"), vec![3, 8, 15, 17, 83, 91], "Code after an exhaustive match or a returning try/finally is dead too");
+    assert_eq!(rule_lines(&issues, "constant-condition"), vec![21, 23, 25], "if, elif and while conditions");
+    assert_eq!(rule_lines(&issues, "inconsistent-return"), vec![29, 43]);
+    assert_eq!(rule_lines(&issues, "infinite-loop"), vec![50], "Generators may loop forever");
+}
+
+#[test]
+fn test_kotlin_control_flow() {
+    let issues = kotlin_issues();
+    assert_eq!(rule_lines(&issues, "unreachable-code"), vec![3, 8, 15, 18, 53], "Code after a when with else is dead too");
+    assert_eq!(rule_lines(&issues, "constant-condition"), vec![23, 26]);
+    assert_eq!(rule_lines(&issues, "infinite-loop"), vec![32], "break@outer leaves the labeled loop");
+    assert!(rule_lines(&issues, "inconsistent-return").is_empty(), "The Kotlin compiler checks returns");
+}
+
+#[test]
+fn test_dart_control_flow() {
+    let issues = dart_issues();
+    assert_eq!(rule_lines(&issues, "unreachable-code"), vec![3, 8, 15, 18, 75, 95], "Code after a switch with default or a returning try/finally is dead too");
+    assert_eq!(rule_lines(&issues, "constant-condition"), vec![23, 26]);
+    assert_eq!(rule_lines(&issues, "inconsistent-return"), vec![31]);
+    assert_eq!(rule_lines(&issues, "infinite-loop"), vec![47], "for (;;) with a break and sync* generators are fine");
+}
@@ -3,7 +3,7 @@ use std::path::PathBuf;
 
 #[test]
 fn test_dart_complexity_simple_function() {
-    let parser = DartParser::new();
+    let parser = DartParser::new().unwrap();
     let test_file = PathBuf::from("test-samples/dart/test-complexity.dart");
     
     let result = parser.analyze_file(&test_file);
@@ -17,7 +17,7 @@ fn test_dart_complexity_simple_function() {
 
 #[test]
 fn test_dart_complexity_nested_conditions() {
-    let parser = DartParser::new();
+    let parser = DartParser::new().unwrap();
     let test_file = PathBuf::from("test-samples/dart/test-complexity.dart");
     
     let result = parser.analyze_file(&test_file);
@@ -35,7 +35,7 @@ fn test_dart_complexity_nested_conditions() {
 
 #[test]
 fn test_dart_complexity_too_many_parameters() {
-    let parser = DartParser::new();
+    let parser = DartParser::new().unwrap();
     let test_file = PathBuf::from("test-samples/dart/test-complexity.dart");
     
     let result = parser.analyze_file(&test_file);
@@ -53,7 +53,7 @@ fn test_dart_complexity_too_many_parameters() {
 
 #[test]
 fn test_dart_complexity_summary() {
-    let parser = DartParser::new();
+    let parser = DartParser::new().unwrap();
     let test_file = PathBuf::from("test-samples/dart/test-complexity.dart");
     
     let result = parser.analyze_file(&test_file);
@@ -0,0 +1,73 @@
+mod common;
+
+use js_ast_analyzer::config::{Config, DartConfig};
+use js_ast_analyzer::{DartParser, Issue};
+use std::path::PathBuf;
+use common::rule_lines;
+
+const SAMPLE: &str = "test-samples/dart/test-flutter.dart";
+
+fn flutter_issues(parser: DartParser) -> Vec<Issue> {
+    let result = parser.analyze_file(&PathBuf::from(SAMPLE));
+    assert!(result.is_ok(), "Failed to parse {}", SAMPLE);
+    result.unwrap().issues
+}
+
+#[test]
+fn test_setstate_after_await() {
+    let issues = flutter_issues(DartParser::new().unwrap());
+    assert_eq!(
+        rule_lines(&issues, "flutter-setstate-after-await"),
+        vec![21],
+        "Calls before the await or behind a mounted check are fine"
+    );
+}
+
+#[test]
+fn test_context_across_async_gap() {
+    let issues = flutter_issues(DartParser::new().unwrap());
+    assert_eq!(
+        rule_lines(&issues, "flutter-context-across-async-gap"),
+        vec![22],
+        "Uses guarded by context.mounted are fine"
+    );
+}
+
+#[test]
+fn test_prefer_const() {
+    let issues = flutter_issues(DartParser::new().unwrap());
+    assert_eq!(
+        rule_lines(&issues, "flutter-prefer-const"),
+        vec![53, 55],
+        "Only the outermost constant constructor is reported, and not when it uses state"
+    );
+}
+
+#[test]
+fn test_undisposed_controller() {
+    let issues = flutter_issues(DartParser::new().unwrap());
+    assert_eq!(rule_lines(&issues, "flutter-undisposed-controller"), vec![13, 14]);
+    assert!(issues.iter().any(|issue| issue.message.contains("'emailController'")));
+}
+
+#[test]
+fn test_print_in_build() {
+    let issues = flutter_issues(DartParser::new().unwrap());
+    assert_eq!(rule_lines(&issues, "flutter-print-in-build"), vec![50]);
+}
+
+#[test]
+fn test_large_build() {
+    assert!(rule_lines(&flutter_issues(DartParser::new().unwrap()), "flutter-large-build").is_empty());
+
+    let config = Config {
+        dart: DartConfig {
+            max_build_lines: 10,
+            ..DartConfig::default()
+        },
+        ..Config::default()
+    };
+    let issues = flutter_issues(DartParser::with_config(&config).unwrap());
+    assert_eq!(rule_lines(&issues, "flutter-large-build"), vec![49]);
+    assert!(issues.iter().any(|issue| issue.message.contains("build() has 12 lines")));
+}
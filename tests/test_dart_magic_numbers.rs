@@ -3,7 +3,7 @@ use std::path::PathBuf;
 
 #[test]
 fn test_dart_magic_numbers_detection() {
-    let parser = DartParser::new();
+    let parser = DartParser::new().unwrap();
     let test_file = PathBuf::from("test-samples/dart/test-magic-numbers.dart");
     
     let result = parser.analyze_file(&test_file);
@@ -21,7 +21,7 @@ fn test_dart_magic_numbers_detection() {
 
 #[test]
 fn test_dart_magic_numbers_allowed_values() {
-    let parser = DartParser::new();
+    let parser = DartParser::new().unwrap();
     let test_file = PathBuf::from("test-samples/dart/test-magic-numbers.dart");
     
     let result = parser.analyze_file(&test_file);
@@ -39,7 +39,7 @@ fn test_dart_magic_numbers_allowed_values() {
 
 #[test]
 fn test_dart_hardcoded_strings() {
-    let parser = DartParser::new();
+    let parser = DartParser::new().unwrap();
     let test_file = PathBuf::from("test-samples/dart/test-magic-numbers.dart");
     
     let result = parser.analyze_file(&test_file);
@@ -3,7 +3,7 @@ use std::path::PathBuf;
 
 #[test]
 fn test_dart_naming_generic_names() {
-    let parser = DartParser::new();
+    let parser = DartParser::new().unwrap();
     let test_file = PathBuf::from("test-samples/dart/test-naming.dart");
     
     let result = parser.analyze_file(&test_file);
@@ -21,7 +21,7 @@ fn test_dart_naming_generic_names() {
 
 #[test]
 fn test_dart_naming_short_names() {
-    let parser = DartParser::new();
+    let parser = DartParser::new().unwrap();
     let test_file = PathBuf::from("test-samples/dart/test-naming.dart");
     
     let result = parser.analyze_file(&test_file);
@@ -39,7 +39,7 @@ fn test_dart_naming_short_names() {
 
 #[test]
 fn test_dart_naming_boolean_prefix() {
-    let parser = DartParser::new();
+    let parser = DartParser::new().unwrap();
     let test_file = PathBuf::from("test-samples/dart/test-naming.dart");
     
     let result = parser.analyze_file(&test_file);
@@ -57,7 +57,7 @@ fn test_dart_naming_boolean_prefix() {
 
 #[test]
 fn test_dart_naming_summary() {
-    let parser = DartParser::new();
+    let parser = DartParser::new().unwrap();
     let test_file = PathBuf::from("test-samples/dart/test-naming.dart");
     
     let result = parser.analyze_file(&test_file);
@@ -1,9 +1,12 @@
-use js_ast_analyzer::DartParser;
+mod common;
+
+use js_ast_analyzer::{DartParser, Issue};
 use std::path::PathBuf;
+use common::rule_lines;
 
 #[test]
 fn test_dart_null_safety_property_access() {
-    let parser = DartParser::new();
+    let parser = DartParser::new().unwrap();
     let test_file = PathBuf::from("test-samples/dart/test-null-safety.dart");
     
     let result = parser.analyze_file(&test_file);
@@ -21,7 +24,7 @@ fn test_dart_null_safety_property_access() {
 
 #[test]
 fn test_dart_null_safety_array_access() {
-    let parser = DartParser::new();
+    let parser = DartParser::new().unwrap();
     let test_file = PathBuf::from("test-samples/dart/test-null-safety.dart");
     
     let result = parser.analyze_file(&test_file);
@@ -39,7 +42,7 @@ fn test_dart_null_safety_array_access() {
 
 #[test]
 fn test_dart_null_safety_summary() {
-    let parser = DartParser::new();
+    let parser = DartParser::new().unwrap();
     let test_file = PathBuf::from("test-samples/dart/test-null-safety.dart");
     
     let result = parser.analyze_file(&test_file);
@@ -51,3 +54,56 @@ fn test_dart_null_safety_summary() {
     let total = analysis.summary.error + analysis.summary.warning + analysis.summary.suggestion;
     assert_eq!(total, analysis.issues.len(), "Summary should match issue count");
 }
+
+/// Issues in line order; the Dart checks run one after another, not in a single pass over the file
+fn dart_issues(file: &str) -> Vec<Issue> {
+    let mut issues = DartParser::new().unwrap().analyze_file(&PathBuf::from(file)).unwrap().issues;
+    issues.sort_by_key(|issue| issue.line);
+    issues
+}
+
+#[test]
+fn test_dart_null_safety_uses_declared_types() {
+    assert_eq!(
+        rule_lines(&dart_issues("test-samples/dart/test-null-safety.dart"), "null-safety"),
+        vec![6, 14, 18, 48],
+        "Promoted and null-aware accesses are fine"
+    );
+    assert_eq!(
+        rule_lines(&dart_issues("test-samples/dart/test-null-safety-scopes.dart"), "null-safety"),
+        vec![41],
+        "Shadowed names, `?` inside type arguments and early returns are not unsafe"
+    );
+}
+
+#[test]
+fn test_dart_bang_operator() {
+    assert_eq!(rule_lines(&dart_issues("test-samples/dart/test-null-safety-scopes.dart"), "dart-bang-operator"), vec![33, 44]);
+}
+
+#[test]
+fn test_dart_late_uninitialized() {
+    assert_eq!(
+        rule_lines(&dart_issues("test-samples/dart/test-null-safety-scopes.dart"), "dart-late-uninitialized"),
+        vec![4],
+        "Fields set by every constructor or with an initializer are fine"
+    );
+}
+
+#[test]
+fn test_dart_dynamic() {
+    assert_eq!(
+        rule_lines(&dart_issues("test-samples/dart/test-null-safety-scopes.dart"), "dart-dynamic"),
+        vec![37],
+        "Map<String, dynamic> is the JSON idiom"
+    );
+}
+
+#[test]
+fn test_dart_nullable_cast() {
+    assert_eq!(
+        rule_lines(&dart_issues("test-samples/dart/test-null-safety-scopes.dart"), "dart-nullable-cast"),
+        vec![38],
+        "Casts to a nullable type or of non-null values are fine"
+    );
+}
@@ -3,7 +3,7 @@ use std::path::PathBuf;
 
 #[test]
 fn test_dart_unused_variables() {
-    let parser = DartParser::new();
+    let parser = DartParser::new().unwrap();
     let test_file = PathBuf::from("test-samples/dart/test-unused.dart");
     
     let result = parser.analyze_file(&test_file);
@@ -21,7 +21,7 @@ fn test_dart_unused_variables() {
 
 #[test]
 fn test_dart_unused_summary() {
-    let parser = DartParser::new();
+    let parser = DartParser::new().unwrap();
     let test_file = PathBuf::from("test-samples/dart/test-unused.dart");
     
     let result = parser.analyze_file(&test_file);
@@ -0,0 +1,24 @@
+use js_ast_analyzer::languages::grammar::Grammar;
+use js_ast_analyzer::AnalyzerError;
+use std::path::Path;
+
+#[test]
+fn test_bad_query_is_reported_at_construction() {
+    match Grammar::new("Kotlin", tree_sitter_kotlin::language(), "(no_such_node) @capture") {
+        Err(AnalyzerError::GrammarError { language, message }) => {
+            assert_eq!(language, "Kotlin");
+            assert!(!message.is_empty());
+        }
+        Err(other) => panic!("Expected a grammar error, got {other}"),
+        Ok(_) => panic!("An unknown node kind should not compile"),
+    }
+}
+
+#[test]
+fn test_grammar_parses_repeatedly() {
+    let grammar = Grammar::new("Python", tree_sitter_python::language(), "(identifier) @name").unwrap();
+    for code in ["x = 1\n", "def f():\n    return 2\n"] {
+        let tree = grammar.parse(code, Path::new("sample.py")).unwrap();
+        assert_eq!(tree.root_node().kind(), "module");
+    }
+}
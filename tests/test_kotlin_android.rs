@@ -0,0 +1,152 @@
+mod common;
+
+use js_ast_analyzer::config::Config;
+use js_ast_analyzer::types::Category;
+use js_ast_analyzer::{Issue, KotlinParser};
+use std::fs;
+use std::path::PathBuf;
+use common::rule_lines;
+
+const ACTIVITY: &str = "test-samples/kotlin/android/MainActivity.kt";
+const MANIFEST: &str = "test-samples/kotlin/android/AndroidManifest.xml";
+
+fn android_parser() -> KotlinParser {
+    let config = Config {
+        profiles: vec!["android".to_string()],
+        ..Config::default()
+    };
+    KotlinParser::with_config(&config).unwrap()
+}
+
+fn activity_issues() -> Vec<Issue> {
+    let result = android_parser().analyze_file(&PathBuf::from(ACTIVITY));
+    assert!(result.is_ok(), "Failed to parse {}", ACTIVITY);
+    result.unwrap().issues
+}
+
+#[test]
+fn test_rules_are_off_without_profile() {
+    let issues = KotlinParser::new().unwrap().analyze_file(&PathBuf::from(ACTIVITY)).unwrap().issues;
+    assert!(issues.iter().all(|issue| !issue.rule.starts_with("android-")));
+}
+
+#[test]
+fn test_debug_log() {
+    let issues = activity_issues();
+    assert_eq!(
+        rule_lines(&issues, "android-debug-log"),
+        vec![22],
+        "Logs behind BuildConfig.DEBUG and error logs are kept"
+    );
+}
+
+#[test]
+fn test_hardcoded_text() {
+    let issues = activity_issues();
+    assert_eq!(
+        rule_lines(&issues, "android-hardcoded-text"),
+        vec![29, 32],
+        "R.string resources and templates are not hard-coded text"
+    );
+}
+
+#[test]
+fn test_static_context() {
+    let issues = activity_issues();
+    assert_eq!(
+        rule_lines(&issues, "android-static-context"),
+        vec![11, 15, 16],
+        "WeakReference holders and plain constants are fine"
+    );
+}
+
+#[test]
+fn test_webview_javascript_and_world_readable() {
+    let issues = activity_issues();
+    assert_eq!(rule_lines(&issues, "android-webview-javascript"), vec![36, 37]);
+    assert_eq!(rule_lines(&issues, "android-world-readable"), vec![40], "The import is not a use");
+    assert!(issues
+        .iter()
+        .filter(|issue| issue.rule == "android-world-readable")
+        .all(|issue| issue.category == Category::Security));
+}
+
+#[test]
+fn test_manifest_allow_backup() {
+    let issues = android_parser().analyze_file(&PathBuf::from(MANIFEST)).unwrap().issues;
+    assert_eq!(rule_lines(&issues, "android-allow-backup"), vec![6]);
+
+    let result = android_parser().analyze_directory(&PathBuf::from("test-samples/kotlin/android")).unwrap();
+    assert!(result.files.iter().any(|file| file.file_path.ends_with("AndroidManifest.xml")));
+}
+
+#[test]
+fn test_manifests_are_not_clones() {
+    let dir = std::env::temp_dir().join(format!("js-ast-analyzer-android-modules-{}", std::process::id()));
+    let activities: String = (0..10)
+        .map(|index| format!("        <activity android:name=\".Screen{}\" android:exported=\"false\" />\n", index))
+        .collect();
+    let manifest = format!(
+        "<manifest xmlns:android=\"http://schemas.android.com/apk/res/android\">\n    <application android:allowBackup=\"false\">\n{}    </application>\n</manifest>\n",
+        activities
+    );
+    for module in ["app", "lib"] {
+        fs::create_dir_all(dir.join(module)).unwrap();
+        fs::write(dir.join(module).join("AndroidManifest.xml"), &manifest).unwrap();
+    }
+
+    let result = android_parser().analyze_directory(&dir).unwrap();
+    fs::remove_dir_all(&dir).ok();
+    assert_eq!(result.files.len(), 2);
+    assert!(result.files.iter().all(|file| rule_lines(&file.issues, "duplicate-code").is_empty()));
+    assert_eq!(result.duplication.unwrap().percentage, 0.0, "Identical manifests are XML, not duplicated Kotlin");
+}
+
+fn manifest_issues(name: &str, application: &str) -> Vec<Issue> {
+    let dir = std::env::temp_dir().join(format!("js-ast-analyzer-android-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("AndroidManifest.xml");
+    let manifest = format!(
+        "<manifest xmlns:android=\"http://schemas.android.com/apk/res/android\"\n    xmlns:tools=\"http://schemas.android.com/tools\">\n{}\n</manifest>\n",
+        application
+    );
+    fs::write(&path, manifest).unwrap();
+
+    let issues = android_parser().analyze_file(&path).unwrap().issues;
+    fs::remove_dir_all(&dir).ok();
+    issues
+}
+
+#[test]
+fn test_manifest_without_allow_backup() {
+    let issues = manifest_issues("default", "    <application android:label=\"App\">\n    </application>");
+    assert_eq!(rule_lines(&issues, "android-allow-backup"), vec![3], "allowBackup defaults to true");
+
+    let issues = manifest_issues(
+        "mentioned",
+        "    <!-- android:allowBackup is set by the library -->\n    <application tools:replace=\"android:allowBackup\">\n    </application>",
+    );
+    assert_eq!(rule_lines(&issues, "android-allow-backup"), vec![4], "Comments and tools:replace don't set the attribute");
+}
+
+#[test]
+fn test_manifest_allow_backup_with_spaces() {
+    let issues = manifest_issues("spaces", "    <application\n        android:allowBackup = \"true\">\n    </application>");
+    assert_eq!(rule_lines(&issues, "android-allow-backup"), vec![4]);
+
+    let issues = manifest_issues("false", "    <application android:allowBackup = 'false'>\n    </application>");
+    assert!(rule_lines(&issues, "android-allow-backup").is_empty());
+}
+
+#[test]
+fn test_unknown_profile_is_rejected() {
+    let dir = std::env::temp_dir().join(format!("js-ast-analyzer-profiles-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("config.json"), r#"{ "profiles": ["android"] }"#).unwrap();
+    assert!(Config::load(&dir.join("config.json")).unwrap().has_profile("android"));
+
+    fs::write(dir.join("config.json"), r#"{ "profiles": ["ios"] }"#).unwrap();
+    let error = Config::load(&dir.join("config.json"));
+    fs::remove_dir_all(&dir).ok();
+    assert!(error.is_err());
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use js_ast_analyzer::types::Category;
+use js_ast_analyzer::{Issue, KotlinParser};
+use std::path::PathBuf;
+use common::rule_lines;
+
+fn coroutine_issues() -> Vec<Issue> {
+    let path = "test-samples/kotlin/coroutines.kt";
+    let result = KotlinParser::new().unwrap().analyze_file(&PathBuf::from(path));
+    assert!(result.is_ok(), "Failed to parse {}", path);
+    result.unwrap().issues
+}
+
+#[test]
+fn test_global_scope() {
+    let issues = coroutine_issues();
+    assert_eq!(rule_lines(&issues, "kotlin-global-scope"), vec![8]);
+}
+
+#[test]
+fn test_run_blocking() {
+    let issues = coroutine_issues();
+    assert_eq!(rule_lines(&issues, "kotlin-run-blocking"), vec![9], "runBlocking in main is allowed");
+}
+
+#[test]
+fn test_thread_sleep() {
+    let issues = coroutine_issues();
+    assert_eq!(rule_lines(&issues, "kotlin-thread-sleep"), vec![13], "Only sleeps in suspend code are reported");
+}
+
+#[test]
+fn test_blocking_io() {
+    let issues = coroutine_issues();
+    assert_eq!(rule_lines(&issues, "kotlin-blocking-io"), vec![14], "IO inside withContext(Dispatchers.IO) is fine");
+    assert!(issues
+        .iter()
+        .filter(|issue| issue.rule == "kotlin-blocking-io")
+        .all(|issue| issue.category == Category::Performance));
+}
+
+#[test]
+fn test_swallowed_cancellation() {
+    let issues = coroutine_issues();
+    assert_eq!(
+        rule_lines(&issues, "kotlin-swallowed-cancellation"),
+        vec![24],
+        "Catching CancellationException first or rethrowing keeps cancellation working"
+    );
+}
+
+#[test]
+fn test_unawaited_async() {
+    let issues = coroutine_issues();
+    assert_eq!(
+        rule_lines(&issues, "kotlin-unawaited-async"),
+        vec![43, 44],
+        "Awaited, collected and returned Deferreds are fine"
+    );
+}
+
+#[test]
+fn test_test_sources_may_run_blocking() {
+    let dir = std::env::temp_dir().join(format!("js-ast-analyzer-coroutines-{}", std::process::id()));
+    let test_dir = dir.join("src").join("test");
+    std::fs::create_dir_all(&test_dir).unwrap();
+    let path = test_dir.join("Sync.kt");
+    std::fs::copy("test-samples/kotlin/coroutines.kt", &path).unwrap();
+
+    let issues = KotlinParser::new().unwrap().analyze_file(&path).unwrap().issues;
+    std::fs::remove_dir_all(&dir).ok();
+    assert!(rule_lines(&issues, "kotlin-run-blocking").is_empty());
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use js_ast_analyzer::types::Category;
+use js_ast_analyzer::{Issue, KotlinParser};
+use std::path::PathBuf;
+use common::rule_lines;
+
+fn idiom_issues() -> Vec<Issue> {
+    let path = "test-samples/kotlin/idioms.kt";
+    let result = KotlinParser::new().unwrap().analyze_file(&PathBuf::from(path));
+    assert!(result.is_ok(), "Failed to parse {}", path);
+    result.unwrap().issues
+}
+
+#[test]
+fn test_prefer_elvis() {
+    let issues = idiom_issues();
+    assert_eq!(
+        rule_lines(&issues, "kotlin-prefer-elvis"),
+        vec![10, 11],
+        "Only branches that return the checked value itself are an elvis"
+    );
+    assert!(issues.iter().any(|issue| issue.message.contains("`name ?: fallback`")));
+}
+
+#[test]
+fn test_redundant_unit() {
+    let issues = idiom_issues();
+    assert_eq!(rule_lines(&issues, "kotlin-redundant-unit"), vec![16], "Expression bodies may spell out Unit");
+}
+
+#[test]
+fn test_when_without_else() {
+    let issues = idiom_issues();
+    assert_eq!(
+        rule_lines(&issues, "kotlin-when-else"),
+        vec![31],
+        "Sealed classes and enums declared in the file are exhaustive"
+    );
+}
+
+#[test]
+fn test_string_concat_in_loop() {
+    let issues = idiom_issues();
+    assert_eq!(rule_lines(&issues, "kotlin-string-concat-in-loop"), vec![45, 48]);
+    assert!(issues
+        .iter()
+        .filter(|issue| issue.rule == "kotlin-string-concat-in-loop")
+        .all(|issue| issue.category == Category::Performance));
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use js_ast_analyzer::config::KotlinConfig;
+use js_ast_analyzer::{Config, Issue, KotlinParser};
+use std::path::PathBuf;
+use common::rule_lines;
+
+const SAMPLE: &str = "test-samples/kotlin/null_safety.kt";
+
+fn issues_with(parser: KotlinParser) -> Vec<Issue> {
+    let result = parser.analyze_file(&PathBuf::from(SAMPLE));
+    assert!(result.is_ok(), "Failed to parse {}", SAMPLE);
+    result.unwrap().issues
+}
+
+#[test]
+fn test_not_null_assertions() {
+    let issues = issues_with(KotlinParser::new().unwrap());
+    assert_eq!(rule_lines(&issues, "kotlin-not-null-assertion"), vec![24]);
+    assert_eq!(
+        rule_lines(&issues, "kotlin-smart-cast"),
+        vec![26, 31],
+        "!! after a null check, in the then or else branch, is redundant"
+    );
+}
+
+#[test]
+fn test_lateinit() {
+    let issues = issues_with(KotlinParser::new().unwrap());
+    assert_eq!(
+        rule_lines(&issues, "kotlin-lateinit-injection"),
+        vec![7],
+        "Framework classes like activities can't use constructor injection"
+    );
+    assert_eq!(rule_lines(&issues, "kotlin-lateinit-optional"), vec![8]);
+}
+
+#[test]
+fn test_platform_types() {
+    let issues = issues_with(KotlinParser::new().unwrap());
+    assert_eq!(
+        rule_lines(&issues, "kotlin-platform-type"),
+        vec![11, 14],
+        "Typed, private and converted declarations don't leak platform types"
+    );
+}
+
+#[test]
+fn test_nested_let() {
+    let issues = issues_with(KotlinParser::new().unwrap());
+    assert_eq!(rule_lines(&issues, "kotlin-nested-let"), vec![35]);
+
+    let config = Config {
+        kotlin: KotlinConfig { max_let_depth: 3 },
+        ..Config::default()
+    };
+    let issues = issues_with(KotlinParser::with_config(&config).unwrap());
+    assert!(rule_lines(&issues, "kotlin-nested-let").is_empty(), "The depth threshold is configurable");
+}
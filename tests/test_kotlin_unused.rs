@@ -0,0 +1,67 @@
+mod common;
+
+use js_ast_analyzer::{Issue, KotlinParser};
+use std::path::PathBuf;
+use common::rule_lines;
+
+fn unused_issues() -> Vec<Issue> {
+    let path = "test-samples/kotlin/unused.kt";
+    let result = KotlinParser::new().unwrap().analyze_file(&PathBuf::from(path));
+    assert!(result.is_ok(), "Failed to parse {}", path);
+    result.unwrap().issues
+}
+
+fn names(issues: &[Issue], rule: &str) -> Vec<(usize, String)> {
+    issues
+        .iter()
+        .filter(|issue| issue.rule == rule)
+        .map(|issue| (issue.line, issue.code_snippet.clone().unwrap_or_default()))
+        .collect()
+}
+
+#[test]
+fn test_unused_locals() {
+    let issues = unused_issues();
+    assert_eq!(
+        names(&issues, "unused-variable"),
+        vec![
+            (24, "shadowed".to_string()),
+            (26, "written".to_string()),
+            (27, "assigned".to_string()),
+            (29, "second".to_string()),
+        ],
+        "A lambda parameter shadows the outer variable; `id` inside `identifier` does not count as a use"
+    );
+}
+
+#[test]
+fn test_unused_private_members() {
+    let issues = unused_issues();
+    assert_eq!(names(&issues, "unused-private-property"), vec![(11, "unusedDb".to_string())]);
+    assert_eq!(
+        names(&issues, "unused-private-function"),
+        vec![(18, "unusedHelper".to_string()), (45, "topLevelUnused".to_string())],
+        "Companion functions called from the class are used"
+    );
+}
+
+#[test]
+fn test_unused_parameters() {
+    let issues = unused_issues();
+    assert_eq!(
+        names(&issues, "unused-parameter"),
+        vec![(16, "y".to_string())],
+        "Named arguments are not reads, and `_` is never reported"
+    );
+}
+
+#[test]
+fn test_unused_imports() {
+    let issues = unused_issues();
+    let imports = rule_lines(&issues, "unused-import");
+    assert_eq!(
+        imports,
+        vec![6],
+        "KDoc links, aliases, delegate operators and wildcard imports count as used"
+    );
+}
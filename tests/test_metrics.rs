@@ -0,0 +1,114 @@
+use js_ast_analyzer::metrics::{FileMetrics, MetricsReport};
+use js_ast_analyzer::output::metrics::MetricsFormatter;
+use js_ast_analyzer::types::MetricsFormat;
+use js_ast_analyzer::{JsParser, PythonParser};
+use std::path::{Path, PathBuf};
+
+fn js_metrics() -> FileMetrics {
+    let result = JsParser::new().metrics_file(&PathBuf::from("test-samples/javascript/test-metrics.js"));
+    assert!(result.is_ok(), "Failed to parse test-metrics.js");
+    result.unwrap()
+}
+
+fn python_metrics() -> FileMetrics {
+    let result = PythonParser::new().unwrap().metrics_file(&PathBuf::from("test-samples/python/metrics.py"));
+    assert!(result.is_ok(), "Failed to parse metrics.py");
+    result.unwrap()
+}
+
+#[test]
+fn test_js_function_metrics() {
+    let file = js_metrics();
+    let names: Vec<&str> = file.functions.iter().map(|function| function.name.as_str()).collect();
+    assert_eq!(names, vec!["total", "label", "add"]);
+    assert_eq!(file.functions.iter().map(|function| function.line).collect::<Vec<_>>(), vec![3, 13, 16]);
+    assert_eq!(file.functions.iter().map(|function| function.params).collect::<Vec<_>>(), vec![2, 1, 3]);
+    assert_eq!(file.functions.iter().map(|function| function.cyclomatic).collect::<Vec<_>>(), vec![4, 2, 1]);
+
+    let total = &file.functions[0];
+    assert_eq!(total.cognitive, 4);
+    assert_eq!(total.max_nesting, 2);
+    assert_eq!(total.loc.logical, 5);
+    assert_eq!(total.loc.physical, 9);
+    assert!(total.halstead_volume > 0.0);
+    assert!(total.maintainability_index > 0.0 && total.maintainability_index <= 100.0);
+}
+
+#[test]
+fn test_js_file_totals() {
+    let file = js_metrics();
+    assert_eq!(file.cyclomatic, 7);
+    assert_eq!(file.max_nesting, 2);
+    assert_eq!(file.loc.physical, 19);
+    assert_eq!(file.loc.logical, 10);
+    assert_eq!(file.loc.comment, 1);
+    assert_eq!(file.loc.blank, 3);
+}
+
+#[test]
+fn test_python_function_metrics() {
+    let file = python_metrics();
+    let names: Vec<&str> = file.functions.iter().map(|function| function.name.as_str()).collect();
+    assert_eq!(names, vec!["total", "label"]);
+    assert_eq!(file.functions.iter().map(|function| function.line).collect::<Vec<_>>(), vec![4, 13]);
+    assert_eq!(file.functions.iter().map(|function| function.params).collect::<Vec<_>>(), vec![2, 1]);
+    assert_eq!(file.functions.iter().map(|function| function.cyclomatic).collect::<Vec<_>>(), vec![4, 2]);
+
+    let total = &file.functions[0];
+    assert_eq!(total.cognitive, 4);
+    assert_eq!(total.max_nesting, 2);
+    assert_eq!(total.loc.logical, 5);
+    assert_eq!(total.loc.comment, 1);
+}
+
+#[test]
+fn test_python_lambdas_count_once() {
+    let result = PythonParser::new().unwrap().metrics_file(&PathBuf::from("test-samples/python/correctness.py"));
+    let file = result.unwrap();
+    let lines: Vec<usize> = file.functions.iter().filter(|function| (64..=70).contains(&function.line)).map(|function| function.line).collect();
+    assert_eq!(lines, vec![64, 67, 68, 69], "The `lambda` keyword token is not a function of its own");
+}
+
+#[test]
+fn test_python_file_loc() {
+    let file = python_metrics();
+    assert_eq!(file.loc.physical, 14);
+    assert_eq!(file.loc.logical, 9);
+    assert_eq!(file.loc.comment, 1);
+    assert_eq!(file.loc.blank, 4);
+}
+
+#[test]
+fn test_directory_rollup() {
+    let root = Path::new("test-samples/python");
+    let files = PythonParser::new().unwrap().metrics_directory(root).unwrap();
+    let file_count = files.len();
+    let function_count: usize = files.iter().map(|file| file.functions.len()).sum();
+
+    let report = MetricsReport::new(root, files);
+    assert_eq!(report.directories.len(), 1);
+    assert_eq!(report.directories[0].path, "test-samples/python");
+    assert_eq!(report.directories[0].files, file_count);
+    assert_eq!(report.directories[0].functions, function_count);
+}
+
+#[test]
+fn test_worst_functions() {
+    let report = MetricsReport::new(Path::new("test-samples/javascript"), vec![js_metrics()]);
+    let worst = report.worst_functions(2);
+    assert_eq!(worst.len(), 2);
+    assert_eq!(worst[0].1.name, "total");
+    assert_eq!(worst[1].1.name, "label");
+}
+
+#[test]
+fn test_csv_output() {
+    let report = MetricsReport::new(Path::new("test-samples/javascript"), vec![js_metrics()]);
+    let csv = MetricsFormatter::format(&report, MetricsFormat::Csv, 10);
+    let rows: Vec<&str> = csv.lines().collect();
+    assert!(rows[0].starts_with("kind,path,name,line,"));
+    assert!(rows[1].starts_with("directory,test-samples/javascript,"));
+    assert!(rows[2].starts_with("file,test-samples/javascript/test-metrics.js,"));
+    assert!(rows[3].starts_with("function,test-samples/javascript/test-metrics.js,total,3,9,5,0,0,2,4,4,2,"));
+    assert_eq!(rows.len(), 6);
+}
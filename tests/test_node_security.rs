@@ -0,0 +1,55 @@
+mod common;
+
+use common::{js_issues, rule_lines};
+
+const FIXTURE: &str = "test-samples/javascript/test-node-security.js";
+
+#[test]
+fn test_exec_with_non_literal_command() {
+    let lines = rule_lines(&js_issues(FIXTURE), "no-exec-non-literal");
+    assert_eq!(lines, vec![60], "Only the concatenated command should be flagged, request input is left to taint tracking");
+    assert_eq!(rule_lines(&js_issues(FIXTURE), "taint-command-injection"), vec![13]);
+}
+
+#[test]
+fn test_fs_path_from_request() {
+    assert_eq!(rule_lines(&js_issues(FIXTURE), "taint-path-traversal"), vec![20], "Destructured request value should be tracked into fs.readFile()");
+    assert!(rule_lines(&js_issues(FIXTURE), "no-fs-request-path").is_empty(), "The taint flow should not be reported twice");
+}
+
+#[test]
+fn test_sql_concatenation() {
+    let lines = rule_lines(&js_issues(FIXTURE), "no-sql-concat");
+    assert_eq!(lines, vec![61], "Concatenated SQL should be flagged, request input is left to taint tracking");
+    assert_eq!(rule_lines(&js_issues(FIXTURE), "taint-sql-injection"), vec![26, 28], "Parameterized query should not be flagged");
+}
+
+#[test]
+fn test_regexp_from_user_input() {
+    let lines = rule_lines(&js_issues(FIXTURE), "no-regexp-user-input");
+    assert_eq!(lines, vec![35, 66, 67, 69, 70], "Loop headers, template literals and array elements are checked");
+}
+
+#[test]
+fn test_weak_hash() {
+    let lines = rule_lines(&js_issues(FIXTURE), "no-weak-hash");
+    assert_eq!(lines, vec![40, 41], "md5 and sha1 should be flagged, sha256 should not");
+}
+
+#[test]
+fn test_insecure_random_for_tokens() {
+    let lines = rule_lines(&js_issues(FIXTURE), "no-insecure-random");
+    assert_eq!(lines, vec![45], "Only token-like names should be flagged");
+}
+
+#[test]
+fn test_tls_verification_disabled() {
+    let lines = rule_lines(&js_issues(FIXTURE), "no-tls-verify-disabled");
+    assert_eq!(lines.len(), 2, "Should flag rejectUnauthorized: false and NODE_TLS_REJECT_UNAUTHORIZED");
+}
+
+#[test]
+fn test_open_redirect() {
+    let lines = rule_lines(&js_issues(FIXTURE), "no-open-redirect");
+    assert_eq!(lines, vec![54, 68], "Only the redirect to request input should be flagged, also inside do-while");
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use js_ast_analyzer::languages::grammar::Grammar;
+use js_ast_analyzer::plugins;
+use js_ast_analyzer::{AnalyzerError, Config, JsParser, KotlinParser, PythonParser};
+use std::fs;
+use std::path::{Path, PathBuf};
+use common::rule_lines;
+
+/// Compile the WAT plugins into a scratch directory next to a config that uses them
+fn config_dir(name: &str, plugins: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("js-ast-analyzer-plugins-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    for plugin in ["fixme", "spin", "imports", "oversized"] {
+        let wasm = wat::parse_file(format!("test-samples/plugins/{}.wat", plugin)).unwrap();
+        fs::write(dir.join(format!("{}.wasm", plugin)), wasm).unwrap();
+    }
+    fs::write(dir.join("config.json"), format!(r#"{{"plugins": {}}}"#, plugins)).unwrap();
+    dir
+}
+
+fn load(name: &str, plugins: &str) -> std::result::Result<Config, AnalyzerError> {
+    let dir = config_dir(name, plugins);
+    let config = Config::load(&dir.join("config.json"));
+    fs::remove_dir_all(&dir).ok();
+    config
+}
+
+#[test]
+fn test_plugin_reports_issues() {
+    let config = load("fixme", r#"[{"path": "fixme.wasm", "languages": ["kotlin"], "ast": false}]"#).unwrap();
+    let analysis = KotlinParser::with_config(&config).unwrap()
+        .analyze_file(&PathBuf::from("test-samples/plugins/todo.kt"))
+        .unwrap();
+
+    assert_eq!(rule_lines(&analysis.issues, "fixme"), vec![3]);
+    let issue = analysis.issues.iter().find(|issue| issue.rule == "fixme").unwrap();
+    assert_eq!(issue.message, "FIXME left in the code");
+    assert_eq!(issue.file_path, "test-samples/plugins/todo.kt");
+}
+
+#[test]
+fn test_plugin_only_runs_on_its_languages() {
+    let config = load("languages", r#"[{"path": "fixme.wasm", "languages": ["kotlin"]}]"#).unwrap();
+    let analysis = PythonParser::with_config(&config).unwrap()
+        .analyze_file(&PathBuf::from("test-samples/python/basics.py"))
+        .unwrap();
+
+    assert!(analysis.issues.iter().all(|issue| issue.rule != "fixme" && issue.rule != "plugin-error"));
+}
+
+#[test]
+fn test_plugin_out_of_fuel_is_reported() {
+    let config = load("fuel", r#"[{"path": "spin.wasm", "fuel": 10000}]"#).unwrap();
+    let analysis = JsParser::with_config(&config)
+        .analyze_file(&PathBuf::from("test-samples/javascript/sample.js"))
+        .unwrap();
+
+    assert_eq!(rule_lines(&analysis.issues, "plugin-error"), vec![1]);
+    let issue = analysis.issues.iter().find(|issue| issue.rule == "plugin-error").unwrap();
+    assert!(issue.message.starts_with("Plugin 'spin' failed"), "{}", issue.message);
+}
+
+#[test]
+fn test_plugin_answer_outside_memory_is_reported() {
+    let config = load("oversized", r#"[{"path": "oversized.wasm"}]"#).unwrap();
+    let analysis = JsParser::with_config(&config)
+        .analyze_file(&PathBuf::from("test-samples/javascript/sample.js"))
+        .unwrap();
+
+    let issue = analysis.issues.iter().find(|issue| issue.rule == "plugin-error").unwrap();
+    assert!(issue.message.contains("outside the plugin's memory"), "{}", issue.message);
+}
+
+#[test]
+fn test_invalid_plugins_fail_to_load() {
+    let error = load("imports", r#"[{"path": "imports.wasm"}]"#).unwrap_err();
+    assert!(matches!(error, AnalyzerError::PluginError { .. }), "{}", error);
+    assert!(error.to_string().contains("env.read_file"), "{}", error);
+
+    let error = load("missing", r#"[{"path": "missing.wasm"}]"#).unwrap_err();
+    assert!(matches!(error, AnalyzerError::PluginError { .. }), "{}", error);
+
+    let error = load("language", r#"[{"path": "fixme.wasm", "languages": ["cobol"]}]"#).unwrap_err();
+    assert!(error.to_string().contains("unknown language 'cobol'"), "{}", error);
+}
+
+#[test]
+fn test_tree_sitter_ast() {
+    let grammar = Grammar::new("Python", tree_sitter_python::language(), "").unwrap();
+    let code = "x = 1\n";
+    let tree = grammar.parse(code, Path::new("sample.py")).unwrap();
+    let ast = plugins::tree_sitter_ast(tree.root_node(), code);
+
+    assert_eq!(ast["type"], "module");
+    let assignment = &ast["children"][0]["children"][0];
+    assert_eq!(assignment["type"], "assignment");
+    assert_eq!(assignment["children"][0]["text"], "x");
+    assert_eq!(assignment["children"][1]["start"]["column"], 5);
+}
+
+#[test]
+fn test_javascript_ast() {
+    let allocator = oxc_allocator::Allocator::default();
+    let code = "foo(1);\nbar;\n";
+    let program = oxc_parser::Parser::new(&allocator, code, oxc_span::SourceType::default()).parse().program;
+    let ast = plugins::javascript_ast(&program, code);
+
+    assert_eq!(ast["type"], "Program");
+    let call = &ast["children"][0]["children"][0];
+    assert_eq!(call["type"], "CallExpression");
+    assert_eq!(call["children"][0]["type"], "IdentifierReference");
+    assert_eq!(call["children"][0]["text"], "foo");
+    assert_eq!(ast["children"][1]["start"]["line"], 2);
+}
@@ -0,0 +1,43 @@
+mod common;
+
+use common::{js_issues, rule_lines};
+
+const FIXTURE: &str = "test-samples/javascript/test-promises.ts";
+
+#[test]
+fn test_floating_promises() {
+    assert_eq!(
+        rule_lines(&js_issues(FIXTURE), "no-floating-promises"),
+        vec![16, 17, 18, 19, 68, 69],
+        "async function, Promise<T> return type, axios, Promise-typed parameter and fs/promises imports; not void, .catch(), or string return type"
+    );
+}
+
+#[test]
+fn test_then_without_catch() {
+    assert_eq!(rule_lines(&js_issues(FIXTURE), "promise-then-without-catch"), vec![24], ".then(onOk, onError) handles rejection");
+}
+
+#[test]
+fn test_await_in_loop() {
+    assert_eq!(rule_lines(&js_issues(FIXTURE), "no-await-in-loop"), vec![28], "Promise.all with map should not be flagged");
+}
+
+#[test]
+fn test_async_without_await() {
+    assert_eq!(rule_lines(&js_issues(FIXTURE), "require-await"), vec![33, 44]);
+}
+
+#[test]
+fn test_async_promise_executor() {
+    assert_eq!(rule_lines(&js_issues(FIXTURE), "no-async-promise-executor"), vec![38]);
+}
+
+#[test]
+fn test_return_await() {
+    assert_eq!(
+        rule_lines(&js_issues(FIXTURE), "return-await"),
+        vec![46, 53],
+        "Missing await inside try and redundant await outside try; 'return await' inside try is correct"
+    );
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use js_ast_analyzer::{Issue, PythonParser, Severity};
+use std::path::PathBuf;
+use common::rule_lines;
+
+fn correctness_issues() -> Vec<Issue> {
+    let path = "test-samples/python/correctness.py";
+    let result = PythonParser::new().unwrap().analyze_file(&PathBuf::from(path));
+    assert!(result.is_ok(), "Failed to parse {}", path);
+    result.unwrap().issues
+}
+
+#[test]
+fn test_mutable_defaults() {
+    let issues = correctness_issues();
+    assert_eq!(
+        rule_lines(&issues, "python-mutable-default"),
+        vec![8, 13, 13],
+        "Literals and constructor calls are flagged, None is not"
+    );
+}
+
+#[test]
+fn test_except_clauses() {
+    let issues = correctness_issues();
+    assert_eq!(rule_lines(&issues, "python-bare-except"), vec![24]);
+    assert_eq!(
+        rule_lines(&issues, "python-swallowed-exception"),
+        vec![31],
+        "Only broad exceptions that are silently passed are reported"
+    );
+}
+
+#[test]
+fn test_comparisons() {
+    let issues = correctness_issues();
+    assert_eq!(rule_lines(&issues, "python-none-comparison"), vec![40]);
+    assert_eq!(rule_lines(&issues, "python-is-literal"), vec![42, 42]);
+    assert!(issues
+        .iter()
+        .filter(|issue| issue.rule == "python-is-literal")
+        .all(|issue| issue.severity == Severity::Error));
+}
+
+#[test]
+fn test_shadowed_builtins() {
+    let issues = correctness_issues();
+    assert_eq!(rule_lines(&issues, "python-shadowed-builtin"), vec![48, 49]);
+}
+
+#[test]
+fn test_unused_imports() {
+    let issues = correctness_issues();
+    assert_eq!(
+        rule_lines(&issues, "python-unused-import"),
+        vec![2, 5],
+        "Imports used in annotations, calls and attribute chains count as used"
+    );
+}
+
+#[test]
+fn test_unused_variables() {
+    let issues = correctness_issues();
+    assert_eq!(
+        rule_lines(&issues, "python-unused-variable"),
+        vec![55],
+        "Underscore names, augmented assignments and loop targets are not reported"
+    );
+}
+
+#[test]
+fn test_loop_closures() {
+    let issues = correctness_issues();
+    assert_eq!(
+        rule_lines(&issues, "python-loop-closure"),
+        vec![67, 68],
+        "Binding the loop variable as a default argument is fine"
+    );
+}
+
+#[test]
+fn test_return_in_finally() {
+    let issues = correctness_issues();
+    assert_eq!(rule_lines(&issues, "python-finally-return"), vec![77]);
+}
+
+#[test]
+fn test_missing_await() {
+    let issues = correctness_issues();
+    assert_eq!(
+        rule_lines(&issues, "python-missing-await"),
+        vec![86, 87],
+        "Awaited calls, including in comprehensions, are fine"
+    );
+}
@@ -3,7 +3,7 @@ use std::path::PathBuf;
 
 #[test]
 fn test_python_naming() {
-    let parser = PythonParser::new();
+    let parser = PythonParser::new().unwrap();
     let test_file = PathBuf::from("test-samples/python/naming.py");
     
     let result = parser.analyze_file(&test_file);
@@ -27,7 +27,7 @@ fn test_python_naming() {
 
 #[test]
 fn test_python_complexity() {
-    let parser = PythonParser::new();
+    let parser = PythonParser::new().unwrap();
     let test_file = PathBuf::from("test-samples/python/complexity.py");
     
     let result = parser.analyze_file(&test_file);
@@ -44,7 +44,7 @@ fn test_python_complexity() {
 
 #[test]
 fn test_python_parameters() {
-    let parser = PythonParser::new();
+    let parser = PythonParser::new().unwrap();
     let test_file = PathBuf::from("test-samples/python/naming.py"); // reused for params
     let result = parser.analyze_file(&test_file);
     assert!(result.is_ok());
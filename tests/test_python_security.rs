@@ -0,0 +1,101 @@
+mod common;
+
+use js_ast_analyzer::types::Category;
+use js_ast_analyzer::{Issue, PythonParser, Severity};
+use std::path::PathBuf;
+use common::rule_lines;
+
+const SECURITY_RULES: &[&str] = &[
+    "python-eval",
+    "python-shell-injection",
+    "python-unsafe-deserialization",
+    "python-sql-injection",
+    "python-insecure-request",
+    "python-weak-hash",
+    "python-insecure-random",
+    "python-assert-auth",
+    "python-debug-enabled",
+];
+
+fn issues(path: &str) -> Vec<Issue> {
+    let result = PythonParser::new().unwrap().analyze_file(&PathBuf::from(path));
+    assert!(result.is_ok(), "Failed to parse {}", path);
+    result.unwrap().issues
+}
+
+fn security_issues() -> Vec<Issue> {
+    issues("test-samples/python/security.py")
+}
+
+#[test]
+fn test_eval() {
+    let issues = security_issues();
+    assert_eq!(rule_lines(&issues, "python-eval"), vec![13]);
+    assert!(issues
+        .iter()
+        .filter(|issue| SECURITY_RULES.contains(&issue.rule.as_str()))
+        .all(|issue| issue.category == Category::Security));
+}
+
+#[test]
+fn test_shell_commands() {
+    let issues = security_issues();
+    let shell: Vec<(usize, Severity)> = issues
+        .iter()
+        .filter(|issue| issue.rule == "python-shell-injection")
+        .map(|issue| (issue.line, issue.severity))
+        .collect();
+    assert_eq!(
+        shell,
+        vec![(17, Severity::Warning), (18, Severity::Error), (20, Severity::Error)],
+        "Constant commands are warnings, built ones errors; argument lists without shell=True are fine"
+    );
+    assert!(issues.iter().any(|issue| issue.message.starts_with("subprocess.run()")), "`from subprocess import run` is resolved");
+}
+
+#[test]
+fn test_unsafe_deserialization() {
+    assert_eq!(
+        rule_lines(&security_issues(), "python-unsafe-deserialization"),
+        vec![24, 25],
+        "yaml.load with SafeLoader is fine"
+    );
+}
+
+#[test]
+fn test_sql_injection() {
+    assert_eq!(
+        rule_lines(&security_issues(), "python-sql-injection"),
+        vec![31, 32, 34],
+        "f-string, % formatting and a .format() query in a variable; parameters are fine"
+    );
+}
+
+#[test]
+fn test_insecure_request() {
+    assert_eq!(rule_lines(&security_issues(), "python-insecure-request"), vec![39]);
+}
+
+#[test]
+fn test_weak_hash() {
+    assert_eq!(rule_lines(&security_issues(), "python-weak-hash"), vec![43], "usedforsecurity=False opts out");
+}
+
+#[test]
+fn test_insecure_random() {
+    let issues = security_issues();
+    assert_eq!(rule_lines(&issues, "python-insecure-random"), vec![50], "Only values that end up in secrets");
+    assert!(issues.iter().any(|issue| issue.message.contains("'new_reset_token'")));
+}
+
+#[test]
+fn test_assert_auth() {
+    assert_eq!(rule_lines(&security_issues(), "python-assert-auth"), vec![59]);
+}
+
+#[test]
+fn test_debug_in_settings() {
+    let settings = issues("test-samples/python/settings.py");
+    assert_eq!(rule_lines(&settings, "python-debug-enabled"), vec![3], "Only module-level DEBUG");
+    assert!(rule_lines(&security_issues(), "python-debug-enabled").is_empty());
+}
@@ -0,0 +1,83 @@
+use js_ast_analyzer::languages::python::type_hints::{TypeCoverage, TypeCoverageReport};
+use js_ast_analyzer::output::type_coverage::TypeCoverageFormatter;
+use js_ast_analyzer::types::MetricsFormat;
+use js_ast_analyzer::{Issue, PythonParser, Severity};
+use std::path::{Path, PathBuf};
+
+const SAMPLE: &str = "test-samples/python/type_hints.py";
+
+fn type_hint_issues() -> Vec<Issue> {
+    let result = PythonParser::new().unwrap().analyze_file(&PathBuf::from(SAMPLE));
+    assert!(result.is_ok(), "Failed to parse {}", SAMPLE);
+    result
+        .unwrap()
+        .issues
+        .into_iter()
+        .filter(|issue| issue.rule == "python-missing-type-hints")
+        .collect()
+}
+
+fn coverage(path: &str, functions: usize, annotated: usize) -> TypeCoverage {
+    TypeCoverage {
+        path: path.to_string(),
+        functions,
+        annotated,
+        coverage: if functions == 0 { 100.0 } else { annotated as f64 * 100.0 / functions as f64 },
+    }
+}
+
+#[test]
+fn test_missing_type_hints() {
+    let issues = type_hint_issues();
+    let lines: Vec<usize> = issues.iter().map(|issue| issue.line).collect();
+    assert_eq!(
+        lines,
+        vec![8, 12, 30],
+        "Private, nested, fully annotated functions and __init__ without a return type are not reported"
+    );
+    assert!(issues.iter().all(|issue| issue.severity == Severity::Suggestion));
+    assert!(issues[0].message.contains("the return type"));
+    assert!(issues[1].message.contains("parameter(s) name, flag"));
+    assert!(issues[2].message.contains("*args, **kwargs"), "cls is not required: {}", issues[2].message);
+}
+
+#[test]
+fn test_file_coverage() {
+    let file = PythonParser::new().unwrap().type_coverage_file(Path::new(SAMPLE)).unwrap();
+    assert_eq!(file.functions, 9);
+    assert_eq!(file.annotated, 4, "annotated, __init__, fetch and ping");
+    assert_eq!(file.coverage, 44.44);
+}
+
+#[test]
+fn test_package_rollup() {
+    let report = TypeCoverageReport::new(
+        Path::new("src"),
+        vec![
+            coverage("src/app/models.py", 4, 4),
+            coverage("src/app/views.py", 6, 2),
+            coverage("src/main.py", 2, 0),
+        ],
+    );
+
+    assert_eq!(report.total.functions, 12);
+    assert_eq!(report.total.annotated, 6);
+    assert_eq!(report.total.coverage, 50.0);
+
+    let packages: Vec<(&str, usize, f64)> = report
+        .packages
+        .iter()
+        .map(|package| (package.path.as_str(), package.functions, package.coverage))
+        .collect();
+    assert_eq!(packages, vec![("src", 12, 50.0), ("src/app", 10, 60.0)]);
+}
+
+#[test]
+fn test_csv_output() {
+    let report = TypeCoverageReport::new(Path::new("src"), vec![coverage("src/main.py", 2, 1)]);
+    let csv = TypeCoverageFormatter::format(&report, MetricsFormat::Csv);
+    let rows: Vec<&str> = csv.lines().collect();
+    assert_eq!(rows[0], "kind,path,functions,annotated,coverage");
+    assert_eq!(rows[1], "total,src,2,1,50");
+    assert_eq!(rows[3], "file,src/main.py,2,1,50");
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use js_ast_analyzer::config::RuleConfig;
+use js_ast_analyzer::languages::query_rules;
+use js_ast_analyzer::{AnalyzerError, Config, DartParser, KotlinParser, PythonParser, Severity};
+use std::path::{Path, PathBuf};
+use common::rule_lines;
+
+fn config() -> Config {
+    Config::load(Path::new("test-samples/rules/rules.json")).unwrap()
+}
+
+#[test]
+fn test_python_query_rule_with_predicates() {
+    let analysis = PythonParser::with_config(&config()).unwrap()
+        .analyze_file(&PathBuf::from("test-samples/rules/shell.py"))
+        .unwrap();
+
+    assert_eq!(rule_lines(&analysis.issues, "no-os-shell"), vec![6, 8], "subprocess.run is not reported");
+    let issue = analysis.issues.iter().find(|issue| issue.rule == "no-os-shell").unwrap();
+    assert_eq!(issue.message, "Use subprocess.run instead of os.system");
+    assert_eq!(issue.severity, Severity::Error);
+    assert_eq!(issue.code_snippet.as_deref(), Some("os.system(cmd)"));
+}
+
+#[test]
+fn test_kotlin_query_rule() {
+    let analysis = KotlinParser::with_config(&config()).unwrap()
+        .analyze_file(&PathBuf::from("test-samples/rules/todo.kt"))
+        .unwrap();
+
+    assert_eq!(rule_lines(&analysis.issues, "no-todo-call"), vec![2, 5]);
+    assert!(analysis.issues.iter().any(|issue| issue.message == "Implement this before release instead of calling TODO()"));
+}
+
+#[test]
+fn test_dart_query_rule() {
+    let analysis = DartParser::with_config(&config()).unwrap()
+        .analyze_file(&PathBuf::from("test-samples/rules/test-debug.dart"))
+        .unwrap();
+
+    assert_eq!(rule_lines(&analysis.issues, "no-debug-print"), vec![2, 4]);
+}
+
+#[test]
+fn test_rules_only_run_in_their_language() {
+    let analysis = PythonParser::with_config(&config()).unwrap()
+        .analyze_file(&PathBuf::from("test-samples/rules/shell.py"))
+        .unwrap();
+
+    assert!(rule_lines(&analysis.issues, "no-todo-call").is_empty());
+    assert!(rule_lines(&analysis.issues, "no-debug-print").is_empty());
+}
+
+#[test]
+fn test_rule_examples() {
+    for rule in &config().rules {
+        let outcomes = query_rules::test_examples(rule).unwrap();
+        assert_eq!(outcomes.len(), rule.invalid.len() + rule.valid.len());
+        assert!(outcomes.iter().all(|outcome| outcome.passed()), "{} fails its examples", rule.id);
+    }
+
+    let mut rule = config().rules.remove(1);
+    rule.valid.push("fun g(): Int = TODO()\n".to_string());
+    let failures: Vec<_> = query_rules::test_examples(&rule).unwrap().into_iter().filter(|outcome| !outcome.passed()).collect();
+    assert_eq!(failures.len(), 1);
+    assert!(!failures[0].invalid);
+    assert_eq!(failures[0].index, 1);
+}
+
+fn broken(query: &str, capture: &str) -> RuleConfig {
+    let mut rule = config().rules.remove(0);
+    rule.query = query.to_string();
+    rule.capture = capture.to_string();
+    rule
+}
+
+#[test]
+fn test_invalid_rules_fail_construction() {
+    for rule in [broken("(call", "call"), broken("(no_such_node) @call", "call"), broken("(call) @call", "missing")] {
+        let config = Config { rules: vec![rule], ..Config::default() };
+        match PythonParser::with_config(&config) {
+            Err(AnalyzerError::RuleError { rule, .. }) => assert_eq!(rule, "no-os-shell"),
+            Err(other) => panic!("Expected a rule error, got {other}"),
+            Ok(_) => panic!("A broken rule should not compile"),
+        }
+    }
+}
+
+#[test]
+fn test_unknown_rule_language_is_rejected() {
+    let dir = std::env::temp_dir().join(format!("js-ast-analyzer-rules-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("config.json");
+    std::fs::write(&path, r#"{"rules": [{"id": "x", "language": "cobol", "query": "(a) @a", "capture": "a", "message": "m"}]}"#).unwrap();
+
+    let error = Config::load(&path).unwrap_err();
+    std::fs::remove_dir_all(&dir).ok();
+    assert!(error.to_string().contains("unknown language 'cobol'"), "{}", error);
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use common::{js_issues, rule_lines};
+
+const FIXTURE: &str = "test-samples/javascript/test-react.jsx";
+
+#[test]
+fn test_dangerously_set_inner_html() {
+    assert_eq!(rule_lines(&js_issues(FIXTURE), "react-no-danger"), vec![24]);
+}
+
+#[test]
+fn test_javascript_url() {
+    assert_eq!(rule_lines(&js_issues(FIXTURE), "react-no-script-url"), vec![25]);
+}
+
+#[test]
+fn test_target_blank_without_rel() {
+    assert_eq!(rule_lines(&js_issues(FIXTURE), "react-no-target-blank"), vec![26], "Link with rel=\"noopener noreferrer\" should not be flagged");
+}
+
+#[test]
+fn test_missing_key_in_map() {
+    assert_eq!(rule_lines(&js_issues(FIXTURE), "react-jsx-key"), vec![44], "Only the element without a key should be flagged");
+}
+
+#[test]
+fn test_array_index_as_key() {
+    assert_eq!(rule_lines(&js_issues(FIXTURE), "react-no-array-index-key"), vec![45, 47], "Index and template built from index should be flagged, item.id should not");
+}
+
+#[test]
+fn test_rules_of_hooks() {
+    assert_eq!(rule_lines(&js_issues(FIXTURE), "rules-of-hooks"), vec![35, 39, 64], "Hooks in a loop, in a nested function, and after an early return");
+}
+
+#[test]
+fn test_effect_dependencies() {
+    assert_eq!(
+        rule_lines(&js_issues(FIXTURE), "react-hooks-exhaustive-deps"),
+        vec![9, 11, 17],
+        "Missing 'user', no array with setState, and unstable object dependency"
+    );
+}
+
+#[test]
+fn test_nested_component() {
+    assert_eq!(rule_lines(&js_issues(FIXTURE), "react-no-nested-components"), vec![21]);
+}
+
+#[test]
+fn test_class_components() {
+    let issues = js_issues("test-samples/javascript/test-react-class.jsx");
+
+    assert_eq!(rule_lines(&issues, "react-no-danger"), vec![4], "JSX returned from render() is checked");
+    assert_eq!(rule_lines(&issues, "react-no-script-url"), vec![5]);
+    assert_eq!(rule_lines(&issues, "react-no-target-blank"), vec![5, 13], "Class properties of a default export too");
+    assert_eq!(rule_lines(&issues, "react-jsx-key"), vec![6]);
+}
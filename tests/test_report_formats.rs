@@ -0,0 +1,157 @@
+use js_ast_analyzer::output::OutputFormatter;
+use js_ast_analyzer::types::{Category, CodeIssue, SeveritySummary};
+use js_ast_analyzer::{AnalysisResult, FileAnalysis, OutputFormat, Severity};
+
+fn issue(line: usize, severity: Severity, rule: &str, message: &str) -> CodeIssue {
+    CodeIssue {
+        file_path: "src/a&b.js".to_string(),
+        line,
+        column: 3,
+        end_line: None,
+        end_column: None,
+        message: message.to_string(),
+        severity,
+        category: Category::Security,
+        rule: rule.to_string(),
+        code_snippet: Some("if (a < b && c > \"d\") {}\u{1b}".to_string()),
+    }
+}
+
+fn file(path: &str, issues: Vec<CodeIssue>) -> FileAnalysis {
+    let mut summary = SeveritySummary::new();
+    for issue in &issues {
+        summary.add(issue.severity);
+    }
+    FileAnalysis { file_path: path.to_string(), issues, summary }
+}
+
+fn result() -> AnalysisResult {
+    let mut result = AnalysisResult::new();
+    result.add_file(file(
+        "src/a&b.js",
+        vec![
+            issue(4, Severity::Error, "no-eval", "Don't use <eval> & 'friends'"),
+            issue(9, Severity::Suggestion, "no-magic-numbers", "Magic number \"42\""),
+        ],
+    ));
+    result.add_file(file("src/clean.js", Vec::new()));
+    result
+}
+
+#[test]
+fn test_checkstyle() {
+    let xml = OutputFormatter::format(&result(), OutputFormat::Checkstyle);
+    let doc = roxmltree::Document::parse(&xml).expect("Checkstyle output must be well-formed XML");
+
+    let root = doc.root_element();
+    assert_eq!(root.tag_name().name(), "checkstyle");
+    let files: Vec<_> = root.children().filter(|n| n.has_tag_name("file")).collect();
+    assert_eq!(files.len(), 2);
+    assert_eq!(files[0].attribute("name"), Some("src/a&b.js"));
+
+    let errors: Vec<_> = files[0].children().filter(|n| n.has_tag_name("error")).collect();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].attribute("line"), Some("4"));
+    assert_eq!(errors[0].attribute("column"), Some("3"));
+    assert_eq!(errors[0].attribute("severity"), Some("error"));
+    assert_eq!(errors[0].attribute("source"), Some("no-eval"));
+    assert_eq!(errors[0].attribute("message"), Some("Don't use <eval> & 'friends'"));
+    assert_eq!(errors[1].attribute("severity"), Some("info"), "Suggestions map to Checkstyle's info");
+}
+
+#[test]
+fn test_junit() {
+    let xml = OutputFormatter::format(&result(), OutputFormat::Junit);
+    let doc = roxmltree::Document::parse(&xml).expect("JUnit output must be well-formed XML");
+
+    let root = doc.root_element();
+    assert_eq!(root.tag_name().name(), "testsuites");
+    assert_eq!(root.attribute("failures"), Some("2"));
+
+    let suites: Vec<_> = root.children().filter(|n| n.has_tag_name("testsuite")).collect();
+    assert_eq!(suites.len(), 2, "One testsuite per file");
+    assert_eq!(suites[1].attribute("tests"), Some("0"));
+
+    let cases: Vec<_> = suites[0].children().filter(|n| n.has_tag_name("testcase")).collect();
+    assert_eq!(cases.len(), 2, "One testcase per issue");
+    assert_eq!(cases[0].attribute("name"), Some("no-eval (4:3)"));
+    assert_eq!(cases[0].attribute("file"), Some("src/a&b.js"));
+    assert_eq!(cases[0].attribute("line"), Some("4"));
+
+    let failure = cases[0].children().find(|n| n.has_tag_name("failure")).unwrap();
+    assert_eq!(failure.attribute("type"), Some("error"));
+    assert_eq!(failure.attribute("message"), Some("Don't use <eval> & 'friends'"));
+    assert_eq!(
+        failure.text(),
+        Some("src/a&b.js:4:3 [error] no-eval\nDon't use <eval> & 'friends'\n> if (a < b && c > \"d\") {}"),
+        "Control characters are dropped"
+    );
+}
+
+fn ci_result() -> AnalysisResult {
+    let absolute = std::env::current_dir().unwrap().join("src/app.js").display().to_string();
+    let mut multiline = issue(7, Severity::Warning, "no-console", "Remove console.log: 100%\nfor real");
+    multiline.end_line = Some(8);
+    multiline.end_column = Some(12);
+
+    let mut magic = issue(20, Severity::Suggestion, "no-magic-numbers", "Magic number 42");
+    magic.category = Category::BestPractice;
+
+    let mut result = AnalysisResult::new();
+    result.add_file(file(
+        &absolute,
+        vec![
+            issue(4, Severity::Error, "no-eval", "Avoid eval"),
+            issue(12, Severity::Error, "no-eval", "Avoid eval"),
+            magic,
+        ],
+    ));
+    result.add_file(file("./lib/a,b.js", vec![multiline]));
+    result
+}
+
+#[test]
+fn test_gitlab_code_quality() {
+    let output = OutputFormatter::format(&ci_result(), OutputFormat::Gitlab);
+    let entries: Vec<serde_json::Value> = serde_json::from_str(&output).unwrap();
+    assert_eq!(entries.len(), 4);
+
+    assert_eq!(entries[0]["check_name"], "no-eval");
+    assert_eq!(entries[0]["description"], "Avoid eval");
+    assert_eq!(entries[0]["severity"], "major");
+    assert_eq!(entries[0]["location"]["path"], "src/app.js", "Paths are relative to the working directory");
+    assert_eq!(entries[0]["location"]["lines"]["begin"], 4);
+    assert_eq!(entries[2]["severity"], "info");
+    assert_eq!(entries[3]["severity"], "minor");
+    assert_eq!(entries[3]["location"]["path"], "lib/a,b.js");
+    assert_eq!(entries[3]["location"]["lines"]["end"], 8);
+    assert_eq!(entries[0]["categories"], serde_json::json!(["Security"]));
+    assert_eq!(entries[2]["categories"], serde_json::json!(["Style"]), "Code Climate category names");
+
+    let fingerprints: Vec<_> = entries.iter().map(|entry| entry["fingerprint"].as_str().unwrap().to_string()).collect();
+    assert!(fingerprints.iter().all(|fingerprint| fingerprint.len() == 32));
+    assert_ne!(fingerprints[0], fingerprints[1], "Identical issues in one file get distinct fingerprints");
+
+    let mut moved = ci_result();
+    for issue in &mut moved.files[0].issues {
+        issue.line += 10;
+        issue.message = format!("{} (see line {})", issue.message, issue.line);
+    }
+    let moved: Vec<serde_json::Value> = serde_json::from_str(&OutputFormatter::format(&moved, OutputFormat::Gitlab)).unwrap();
+    let moved: Vec<_> = moved.iter().map(|entry| entry["fingerprint"].as_str().unwrap().to_string()).collect();
+    assert_eq!(fingerprints, moved, "Fingerprints don't depend on line numbers, even in messages");
+}
+
+#[test]
+fn test_github_annotations() {
+    let output = OutputFormatter::format(&ci_result(), OutputFormat::Github);
+    let lines: Vec<_> = output.lines().collect();
+
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0], "::error file=src/app.js,line=4,col=3,title=no-eval::Avoid eval");
+    assert_eq!(lines[2], "::notice file=src/app.js,line=20,col=3,title=no-magic-numbers::Magic number 42");
+    assert_eq!(
+        lines[3],
+        "::warning file=lib/a%2Cb.js,line=7,col=3,endLine=8,endColumn=12,title=no-console::Remove console.log: 100%25%0Afor real"
+    );
+}
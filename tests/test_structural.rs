@@ -0,0 +1,83 @@
+mod common;
+
+use js_ast_analyzer::languages::javascript::structural::{Pattern, StructuralRule};
+use js_ast_analyzer::{AnalyzerError, Config, FileAnalysis, JsParser, Severity};
+use std::fs;
+use std::path::{Path, PathBuf};
+use common::rule_lines;
+
+fn analysis() -> FileAnalysis {
+    let config = Config::load(Path::new("test-samples/javascript/rules/config.json")).unwrap();
+    JsParser::with_config(&config)
+        .analyze_file(&PathBuf::from("test-samples/javascript/test-structural.js"))
+        .unwrap()
+}
+
+#[test]
+fn test_assignment_pattern_with_not_inside() {
+    let analysis = analysis();
+    assert_eq!(
+        rule_lines(&analysis.issues, "org-inner-html"),
+        vec![2, 3, 21],
+        "Computed access matches, textContent and code inside renderTrusted(...) do not"
+    );
+
+    let top_level = analysis.issues.iter().find(|issue| issue.rule == "org-inner-html" && issue.line == 21).unwrap();
+    assert_eq!(top_level.column, 1, "A match at the start of a line is in column 1 of that line");
+
+    let issue = analysis.issues.iter().find(|issue| issue.rule == "org-inner-html").unwrap();
+    assert_eq!(issue.message, "Assigning html to el.innerHTML can run injected scripts");
+    assert_eq!(issue.severity, Severity::Error);
+}
+
+#[test]
+fn test_object_subset_and_inside() {
+    let analysis = analysis();
+    assert_eq!(
+        rule_lines(&analysis.issues, "org-post-without-csrf"),
+        vec![11],
+        "Extra properties and quote style don't matter; GET and calls outside submit() are ignored"
+    );
+}
+
+#[test]
+fn test_repeated_metavariable_must_bind_same_code() {
+    assert_eq!(rule_lines(&analysis().issues, "org-same-operands"), vec![17]);
+}
+
+#[test]
+fn test_rules_load_from_directory() {
+    let rules = StructuralRule::load(Path::new("test-samples/javascript/rules")).unwrap();
+    let ids: Vec<_> = rules.iter().map(|rule| rule.id.as_str()).collect();
+    assert_eq!(ids, vec!["org-inner-html", "org-post-without-csrf", "org-same-operands"]);
+}
+
+#[test]
+fn test_ellipsis_and_spread() {
+    assert_eq!(Pattern::parse("foo(...)").unwrap(), Pattern::parse("foo( ... )").unwrap());
+    assert_ne!(Pattern::parse("foo(...args)").unwrap(), Pattern::parse("foo(...)").unwrap());
+    assert!(Pattern::parse("a; b").is_err(), "A pattern is a single expression");
+    assert!(Pattern::parse("foo(").is_err());
+}
+
+fn write_rules(name: &str, yaml: &str) -> (PathBuf, PathBuf) {
+    let dir = std::env::temp_dir().join(format!("js-ast-analyzer-structural-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("rules.yml"), yaml).unwrap();
+    fs::write(dir.join("config.json"), r#"{"javascript": {"rule_files": ["rules.yml"]}}"#).unwrap();
+    let config = dir.join("config.json");
+    (dir, config)
+}
+
+#[test]
+fn test_invalid_rule_files_fail_to_load() {
+    let (dir, config) = write_rules("pattern", "rules:\n  - id: broken\n    pattern: 'eval('\n    message: m\n");
+    let error = Config::load(&config).unwrap_err();
+    fs::remove_dir_all(&dir).ok();
+    assert!(matches!(error, AnalyzerError::RuleError { ref rule, .. } if rule == "broken"), "{}", error);
+
+    let (dir, config) = write_rules("yaml", "rules:\n  - id: missing-pattern\n    message: m\n");
+    let error = Config::load(&config).unwrap_err();
+    fs::remove_dir_all(&dir).ok();
+    assert!(matches!(error, AnalyzerError::ConfigError { .. }), "{}", error);
+}
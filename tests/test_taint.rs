@@ -0,0 +1,114 @@
+use js_ast_analyzer::{Config, JsParser};
+use std::path::PathBuf;
+
+fn taint_issues() -> Vec<js_ast_analyzer::Issue> {
+    let parser = JsParser::new();
+    let test_file = PathBuf::from("test-samples/javascript/test-taint.js");
+
+    let result = parser.analyze_file(&test_file);
+    assert!(result.is_ok(), "Failed to parse test-taint.js");
+
+    result
+        .unwrap()
+        .issues
+        .into_iter()
+        .filter(|issue| issue.rule.starts_with("taint-"))
+        .collect()
+}
+
+#[test]
+fn test_taint_source_to_inner_html() {
+    let issues = taint_issues();
+
+    let xss: Vec<_> = issues.iter()
+        .filter(|issue| issue.rule == "taint-xss" && issue.line == 9)
+        .collect();
+
+    assert_eq!(xss.len(), 1, "Should report location.hash flowing into innerHTML");
+    assert!(xss[0].message.contains("location.hash"), "Message should name the source");
+    assert!(xss[0].message.contains("html (baris 8)"), "Message should contain the propagation path");
+}
+
+#[test]
+fn test_taint_sanitized_and_static_values_are_ignored() {
+    let issues = taint_issues();
+
+    assert!(!issues.iter().any(|issue| issue.line == 15), "Sanitized value should not be reported");
+    assert!(!issues.iter().any(|issue| issue.line == 20), "Constant string should not be reported");
+}
+
+#[test]
+fn test_taint_server_side_sinks() {
+    let issues = taint_issues();
+
+    assert!(issues.iter().any(|issue| issue.rule == "taint-command-injection"), "Should detect req.query reaching exec()");
+    assert!(issues.iter().any(|issue| issue.rule == "taint-path-traversal"), "Should detect req.params reaching fs.readFileSync()");
+}
+
+#[test]
+fn test_taint_inter_procedural() {
+    let issues = taint_issues();
+
+    let sql: Vec<_> = issues.iter()
+        .filter(|issue| issue.rule == "taint-sql-injection")
+        .collect();
+
+    assert_eq!(sql.len(), 2, "Should follow req.body into findUser() and req.params into loadOrder()");
+    assert_eq!(sql[0].line, 35, "Should be reported at the call site");
+    assert!(sql[0].message.contains("findUser()"));
+}
+
+#[test]
+fn test_taint_helper_chains() {
+    let issues = taint_issues();
+
+    let chain = issues.iter()
+        .find(|issue| issue.rule == "taint-sql-injection" && issue.line == 83)
+        .expect("Should follow req.params through three levels of helpers");
+    assert!(chain.message.contains("loadOrder()"));
+    assert!(chain.message.contains("db.query()"));
+}
+
+#[test]
+fn test_taint_window_name_and_local_bindings() {
+    let issues = taint_issues();
+
+    assert!(issues.iter().any(|issue| issue.rule == "taint-xss" && issue.line == 60), "window.name is a source");
+    assert!(!issues.iter().any(|issue| issue.line == 66), "A local `location` is not the global one");
+}
+
+#[test]
+fn test_taint_replaces_single_node_rules() {
+    let analysis = JsParser::new().analyze_file(&PathBuf::from("test-samples/javascript/test-taint.js")).unwrap();
+    let rules_at = |line: usize| -> Vec<&str> {
+        analysis.issues.iter().filter(|issue| issue.line == line).map(|issue| issue.rule.as_str()).collect()
+    };
+
+    assert_eq!(rules_at(9), vec!["taint-xss"], "The flow replaces no-inner-html");
+    assert_eq!(rules_at(46), vec!["taint-code-injection"], "The flow replaces no-eval");
+    assert!(!rules_at(20).contains(&"no-inner-html"), "Constant string should not be reported");
+    assert_eq!(rules_at(66), vec!["no-inner-html"], "Unknown values are still reported without a flow");
+}
+
+#[test]
+fn test_taint_browser_sources() {
+    let issues = taint_issues();
+
+    assert!(issues.iter().any(|issue| issue.rule == "taint-code-injection" && issue.message.contains("postMessage")));
+    assert!(issues.iter().any(|issue| issue.rule == "taint-xss" && issue.message.contains("URLSearchParams")));
+}
+
+#[test]
+fn test_taint_configurable_sanitizers() {
+    let test_file = PathBuf::from("test-samples/javascript/test-taint.js");
+
+    let default_analysis = JsParser::new().analyze_file(&test_file).unwrap();
+    assert!(default_analysis.issues.iter().any(|issue| issue.rule == "taint-xss" && issue.line == 55),
+        "Unknown function should propagate taint");
+
+    let mut config = Config::default();
+    config.taint.sanitizers = vec!["cleanHtml".to_string()];
+    let configured_analysis = JsParser::with_config(&config).analyze_file(&test_file).unwrap();
+    assert!(!configured_analysis.issues.iter().any(|issue| issue.rule == "taint-xss" && issue.line == 55),
+        "Configured sanitizer should stop taint");
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use common::{js_issues, rule_lines};
+
+const FIXTURE: &str = "test-samples/javascript/test-typescript.ts";
+
+#[test]
+fn test_any_everywhere() {
+    assert_eq!(
+        rule_lines(&js_issues(FIXTURE), "no-any-type"),
+        vec![2, 3, 5, 9, 29],
+        "function type params, generic defaults, type arguments, class properties and `as any`"
+    );
+}
+
+#[test]
+fn test_return_type_only_for_exports() {
+    assert_eq!(
+        rule_lines(&js_issues(FIXTURE), "explicit-function-return-type"),
+        vec![13, 25],
+        "internal functions and annotated exports should not be flagged"
+    );
+}
+
+#[test]
+fn test_non_null_assertion() {
+    assert_eq!(rule_lines(&js_issues(FIXTURE), "no-non-null-assertion"), vec![30]);
+}
+
+#[test]
+fn test_ts_comment_without_reason() {
+    assert_eq!(rule_lines(&js_issues(FIXTURE), "ban-ts-comment"), vec![33], "@ts-expect-error with a reason is allowed");
+}
+
+#[test]
+fn test_unsafe_literal_cast() {
+    assert_eq!(
+        rule_lines(&js_issues(FIXTURE), "no-unsafe-literal-cast"),
+        vec![38, 39],
+        "casting to the same literal or widening to string is fine"
+    );
+}
+
+#[test]
+fn test_prefer_union_over_enum() {
+    assert_eq!(rule_lines(&js_issues(FIXTURE), "prefer-union-over-enum"), vec![43]);
+}
+
+#[test]
+fn test_empty_interface() {
+    assert_eq!(
+        rule_lines(&js_issues(FIXTURE), "no-empty-interface"),
+        vec![48, 49],
+        "an empty interface combining several types is allowed"
+    );
+}
+
+#[test]
+fn test_ban_types() {
+    assert_eq!(rule_lines(&js_issues(FIXTURE), "ban-types"), vec![52, 52]);
+}